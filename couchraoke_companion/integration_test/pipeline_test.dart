@@ -1,4 +1,7 @@
 import 'dart:async';
+import 'dart:convert';
+import 'dart:io';
+import 'dart:math';
 import 'dart:typed_data';
 
 import 'package:flutter/services.dart';
@@ -7,7 +10,9 @@ import 'package:integration_test/integration_test.dart';
 import 'package:wav/wav.dart';
 
 import 'package:couchraoke_companion/src/rust/api/frb_generated.dart';
+import 'package:couchraoke_companion/src/rust/api/pyin.dart';
 import 'package:couchraoke_companion/src/rust/api/pyin/api.dart';
+import 'package:couchraoke_companion/src/rust/api/scoring.dart';
 
 void main() {
   IntegrationTestWidgetsFlutterBinding.ensureInitialized();
@@ -35,43 +40,338 @@ void main() {
         sampleRateHz: wav.samplesPerSecond,
         updateIntervalMs: 30,
         windowSizeMs: 50,
+        referenceA4Hz: 440,
+        pcmFormat: PcmFormat.i16Le,
+        channels: 1,
+        channelMix: const ChannelMix.downmix(),
+        analysisSampleRateHz: null,
+        vadEnabled: false,
+        octaveCorrectionEnabled: false,
+        algorithm: PitchAlgorithm.pyin,
+        multiResolutionEnabled: false,
       );
 
       final analyzer = await AudioAnalyzer.newInstance(config: config);
 
-      final emittedNotes = <int>[];
-
       // 4. Stream Simulation
-      const chunkSize = 2048;
+      final emittedNotes = await _streamNotes(analyzer, pcmBytes);
+
+      // 5. Assertions
+      expect(emittedNotes, isNotEmpty,
+          reason: 'Pipeline failed: No events emitted');
+
+      final mode = _calculateMode(emittedNotes);
+      expect(mode, equals(expectedMidi), reason: 'Pitch mismatch');
+    });
+  });
 
-      final pending = <Future<Uint8List>>[];
-      for (var i = 0; i < pcmBytes.length; i += chunkSize) {
-        final end = (i + chunkSize < pcmBytes.length)
-            ? i + chunkSize
-            : pcmBytes.length;
-        final chunk = pcmBytes.sublist(i, end);
+  testWidgets('Pipeline Test: pick one channel of stereo input and resample',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      final wav = await _loadFixture(testFile);
+      final mono = wav.channels.first;
+      // The voice on the right channel, silence on the left.
+      final stereo = <double>[
+        for (final sample in mono) ...[0.0, sample],
+      ];
+
+      final analyzer = await AudioAnalyzer.newInstance(
+        config: PitchConfig(
+          sampleRateHz: wav.samplesPerSecond,
+          updateIntervalMs: 30,
+          windowSizeMs: 50,
+          referenceA4Hz: 440,
+          pcmFormat: PcmFormat.i16Le,
+          channels: 2,
+          channelMix: const ChannelMix.pick(1),
+          analysisSampleRateHz: 16000,
+          vadEnabled: false,
+          octaveCorrectionEnabled: false,
+          algorithm: PitchAlgorithm.pyin,
+          multiResolutionEnabled: false,
+        ),
+      );
+      final emittedNotes =
+          await _streamNotes(analyzer, _floatToPcm16(stereo));
+
+      expect(emittedNotes, isNotEmpty,
+          reason: 'Pipeline failed: No events emitted');
+      expect(_calculateMode(emittedNotes), equals(expectedMidi),
+          reason: 'Pitch mismatch');
+    });
+  });
+
+  testWidgets('Pipeline Test: voice activity detection reports speech',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      final wav = await _loadFixture(testFile);
+      final analyzer = await AudioAnalyzer.newInstance(
+        config: _monoConfig(wav.samplesPerSecond, vadEnabled: true),
+      );
+      final events = await analyzer.processChunkCollectEvents(
+        pcmBytes: _floatToPcm16(wav.channels.first),
+      );
 
-        pending.add(analyzer.processChunkCollect(pcm16LeBytes: chunk));
+      final voiced = events.where((event) => event.voiced).toList();
+      expect(voiced, isNotEmpty, reason: 'VAD gated out the whole tone');
+      for (final event in voiced) {
+        expect(event.speechProb, greaterThan(0.5));
+        expect(event.noiseFloorDbfs, lessThan(event.rmsDbfs));
       }
+    });
+  });
 
-      final collected = await Future.wait(pending)
-          .timeout(const Duration(seconds: 5), onTimeout: () => <Uint8List>[]);
-      for (final chunkNotes in collected) {
-        for (final note in chunkNotes) {
-          if (note != 255) emittedNotes.add(note);
-        }
+  testWidgets('Pipeline Test: octave correction keeps the sung octave',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      final wav = await _loadFixture(testFile);
+      final analyzer = await AudioAnalyzer.newInstance(
+        config: _monoConfig(
+          wav.samplesPerSecond,
+          octaveCorrectionEnabled: true,
+        ),
+      );
+      final emittedNotes = await _streamNotes(
+        analyzer,
+        _floatToPcm16(wav.channels.first),
+      );
+
+      expect(emittedNotes, isNotEmpty,
+          reason: 'Pipeline failed: No events emitted');
+      expect(_calculateMode(emittedNotes), equals(expectedMidi),
+          reason: 'Pitch mismatch');
+    });
+  });
+
+  testWidgets('Pipeline Test: every pitch algorithm finds the note',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      final wav = await _loadFixture(testFile);
+      final pcmBytes = _floatToPcm16(wav.channels.first);
+      for (final algorithm in PitchAlgorithm.values) {
+        final analyzer = await AudioAnalyzer.newInstance(
+          config: _monoConfig(wav.samplesPerSecond, algorithm: algorithm),
+        );
+        final emittedNotes = await _streamNotes(analyzer, pcmBytes);
+
+        expect(emittedNotes, isNotEmpty, reason: '$algorithm emitted nothing');
+        expect(_calculateMode(emittedNotes), equals(expectedMidi),
+            reason: 'Pitch mismatch with $algorithm');
       }
+    });
+  });
+
+  testWidgets('Pipeline Test: finish flushes the tail of the recording',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      final wav = await _loadFixture(testFile);
+      final analyzer = await AudioAnalyzer.newInstance(
+        config: _monoConfig(wav.samplesPerSecond),
+      );
+      final live = await analyzer.processChunkCollectEvents(
+        pcmBytes: _floatToPcm16(wav.channels.first),
+      );
+      final tail = await analyzer.finishCollectEvents();
+
+      expect(tail, isNotEmpty, reason: 'The last partial frame was dropped');
+      expect(tail.every((event) => event.isFinal), isTrue);
+      expect(tail.last.timeSec, greaterThan(live.last.timeSec));
+    });
+  });
+
+  testWidgets('Pipeline Test: file analysis streams its progress',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      final fileData = await rootBundle.load('rust/fixtures/$testFile');
+      final dir = await Directory.systemTemp.createTemp('pipeline_test');
+      final file = File('${dir.path}/$testFile');
+      await file.writeAsBytes(fileData.buffer.asUint8List());
+
+      final updates = await analyzeFileStream(
+        path: file.path,
+        options: const FileAnalysisOptions(
+          analysisSampleRateHz: 16000,
+          updateIntervalMs: 10,
+          windowSizeMs: 43,
+          referenceA4Hz: 440,
+          startSec: 0,
+          endSec: null,
+        ),
+      ).toList();
+      await dir.delete(recursive: true);
+
+      final progress = updates.map((update) => update.processedSec).toList();
+      for (var i = 1; i < progress.length; i++) {
+        expect(progress[i], greaterThanOrEqualTo(progress[i - 1]));
+      }
+      expect(updates.length, greaterThan(1));
+      final contour = updates.last.contour;
+      expect(contour, isNotNull, reason: 'The last update carries the contour');
+      expect(updates.take(updates.length - 1).every((u) => u.contour == null),
+          isTrue);
+      expect(progress.last, closeTo(contour!.durationSec, 0.1));
+      final notes = contour.events
+          .where((event) => event.midiFractional != null)
+          .map((event) => event.midiFractional!.round())
+          .toList();
+      expect(_calculateMode(notes), equals(expectedMidi));
+    });
+  });
+
+  testWidgets('Pipeline Test: multi-resolution windows track a high note',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      final wav = await _loadFixture('B4_493Hz.wav');
+      final analyzer = await AudioAnalyzer.newInstance(
+        config: _monoConfig(
+          wav.samplesPerSecond,
+          multiResolutionEnabled: true,
+        ),
+      );
+      final emittedNotes = await _streamNotes(
+        analyzer,
+        _floatToPcm16(wav.channels.first),
+      );
 
-      // 5. Assertions
       expect(emittedNotes, isNotEmpty,
           reason: 'Pipeline failed: No events emitted');
+      expect(_calculateMode(emittedNotes), equals(71),
+          reason: 'Pitch mismatch');
+    });
+  });
 
-      final mode = _calculateMode(emittedNotes);
-      expect(mode, equals(expectedMidi), reason: 'Pitch mismatch');
+  testWidgets('Pipeline Test: a party scores each singer on the song clock',
+      (WidgetTester tester) async {
+    await tester.runAsync(() async {
+      // The song starts at 20 s on the party clock; each phone started its
+      // stream at its own time.
+      final party = await Party.newInstance(
+        chart: utf8.encode(_duetChart),
+        songStartSec: 20,
+      );
+      final phones = [
+        (name: 'Ann', track: 0, startSec: 19.6, notes: [220.0, 261.63]),
+        (name: 'Bob', track: 1, startSec: 18.9, notes: [329.63, 392.0]),
+      ];
+      for (final phone in phones) {
+        final singer = await party.addSinger(
+          config: _monoConfig(
+            _duetRate,
+            updateIntervalMs: 10,
+            windowSizeMs: 64,
+          ),
+          options: SingerOptions(
+            name: phone.name,
+            track: phone.track,
+            harmony: false,
+            difficulty: Difficulty.hard,
+            lineBonus: true,
+            streamStartSec: phone.startSec,
+          ),
+        );
+        final pcmBytes = _floatToPcm16(
+          _singDuetPart(phone.notes, songStartSec: 20 - phone.startSec),
+        );
+        for (var i = 0; i < pcmBytes.length; i += 4096) {
+          await party.processChunk(
+            singer: singer,
+            pcmBytes: pcmBytes.sublist(i, min(i + 4096, pcmBytes.length)),
+          );
+        }
+      }
+      await party.finish();
+
+      final results = await party.results();
+      expect(
+        results.map((result) => '${result.name}: ${result.score}').toList(),
+        equals(['Ann: 10000', 'Bob: 10000']),
+      );
     });
   });
 }
 
+const _duetRate = 16000;
+
+/// Two lines of two half-second notes, one per singer.
+const _duetChart = '#TITLE:T\n#ARTIST:A\n#BPM:240\n#P1:Ann\n#P2:Bob\n'
+    'P1\n: 0 8 -3 la\n: 8 8 0 do\nP2\n: 0 8 4 mi\n: 8 8 7 so\nE\n';
+
+/// One phone's stream of a part of [_duetChart], in tune, with the song
+/// heard [songStartSec] into the stream.
+List<double> _singDuetPart(
+  List<double> frequencies, {
+  required double songStartSec,
+}) {
+  final length = ((songStartSec + 1.5) * _duetRate).floor();
+  final samples = List<double>.filled(length, 0);
+  for (var i = 0; i < frequencies.length; i++) {
+    final from = ((i * 0.5 + songStartSec) * _duetRate).floor();
+    for (var n = 0; n < _duetRate ~/ 2; n++) {
+      samples[from + n] = 0.5 * sin(2 * pi * frequencies[i] * n / _duetRate);
+    }
+  }
+  return samples;
+}
+
+Future<Wav> _loadFixture(String name) async {
+  final ByteData fileData = await rootBundle.load('rust/fixtures/$name');
+  return Wav.read(fileData.buffer.asUint8List());
+}
+
+/// Mono 16-bit capture analysed at [sampleRateHz], by default with the
+/// hop and window of the first test.
+PitchConfig _monoConfig(
+  int sampleRateHz, {
+  int updateIntervalMs = 30,
+  int windowSizeMs = 50,
+  bool vadEnabled = false,
+  bool octaveCorrectionEnabled = false,
+  PitchAlgorithm algorithm = PitchAlgorithm.pyin,
+  bool multiResolutionEnabled = false,
+}) => PitchConfig(
+  sampleRateHz: sampleRateHz,
+  updateIntervalMs: updateIntervalMs,
+  windowSizeMs: windowSizeMs,
+  referenceA4Hz: 440,
+  pcmFormat: PcmFormat.i16Le,
+  channels: 1,
+  channelMix: const ChannelMix.downmix(),
+  analysisSampleRateHz: null,
+  vadEnabled: vadEnabled,
+  octaveCorrectionEnabled: octaveCorrectionEnabled,
+  algorithm: algorithm,
+  multiResolutionEnabled: multiResolutionEnabled,
+);
+
+/// Feeds [pcmBytes] to [analyzer] in mic-sized chunks and returns the voiced
+/// MIDI notes it reports.
+Future<List<int>> _streamNotes(
+  AudioAnalyzer analyzer,
+  Uint8List pcmBytes,
+) async {
+  const chunkSize = 2048;
+
+  final pending = <Future<Uint8List>>[];
+  for (var i = 0; i < pcmBytes.length; i += chunkSize) {
+    final end = (i + chunkSize < pcmBytes.length)
+        ? i + chunkSize
+        : pcmBytes.length;
+    final chunk = pcmBytes.sublist(i, end);
+
+    pending.add(analyzer.processChunkCollect(pcmBytes: chunk));
+  }
+
+  final collected = await Future.wait(pending)
+      .timeout(const Duration(seconds: 5), onTimeout: () => <Uint8List>[]);
+  final emittedNotes = <int>[];
+  for (final chunkNotes in collected) {
+    for (final note in chunkNotes) {
+      if (note != 255) emittedNotes.add(note);
+    }
+  }
+  return emittedNotes;
+}
+
 Uint8List _floatToPcm16(List<double> floats) {
   final buffer = ByteData(floats.length * 2);
   for (var i = 0; i < floats.length; i++) {
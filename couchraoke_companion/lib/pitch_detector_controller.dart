@@ -1,9 +1,13 @@
 import 'dart:async';
+import 'dart:io';
+import 'dart:typed_data';
 import 'package:flutter/widgets.dart';
 import 'package:permission_handler/permission_handler.dart';
 import 'package:record/record.dart';
 import 'src/rust/api/frb_generated.dart';
+import 'src/rust/api/pyin.dart';
 import 'src/rust/api/pyin/api.dart';
+import 'src/rust/api/scoring.dart';
 
 class PitchDetectorController with WidgetsBindingObserver {
   final AudioRecorder _recorder = AudioRecorder();
@@ -16,29 +20,74 @@ class PitchDetectorController with WidgetsBindingObserver {
   // Cache config to restart recorder on resume
   PitchConfig? _lastConfig;
 
-  Stream<int>? _noteStream;
+  // Where the song sits on the stream clock, moved on when the mic was off
+  // while the song played on.
+  double? _songStartSec;
+  double? _backingTrackDelaySec;
+  DateTime? _micStoppedAt;
+
+  Stream<PitchEvent>? _events;
+  Stream<ScoreEvent>? _scoreEvents;
   StreamSubscription<List<int>>? _micSubscription;
 
-  PitchDetectorController() {
+  /// Where the analyzer state is saved when the app goes to the background,
+  /// so a session survives the OS killing the app; see [restoreSnapshot].
+  final File? snapshotFile;
+
+  PitchDetectorController({this.snapshotFile}) {
     WidgetsBinding.instance.addObserver(this);
   }
 
-  Stream<int>? get noteStream => _noteStream;
+  /// Structured pitch events for every analysed frame that passes the
+  /// stream options given to [start]. An event the decoder may still revise
+  /// is followed by its final update once the frame is committed.
+  Stream<PitchEvent>? get events => _events;
+
+  /// Nearest MIDI note of each voiced event.
+  Stream<int>? get noteStream => _events
+      ?.map((event) => event.midiFractional)
+      .where((midi) => midi != null)
+      .map((midi) => midi!.round());
+
+  /// Feedback on each finished note and line of the song given to
+  /// [startScoring].
+  Stream<ScoreEvent>? get scoreEvents => _scoreEvents;
 
   @override
   void didChangeAppLifecycleState(AppLifecycleState state) {
     if (state == AppLifecycleState.paused || state == AppLifecycleState.inactive) {
+      if (_micSubscription != null) _micStoppedAt = DateTime.now();
       _micSubscription?.cancel();
       _micSubscription = null;
       _recorder.stop();
+      // `inactive` comes first on the way to the background; saving on
+      // `paused` alone keeps two writes of the snapshot from racing.
+      if (state == AppLifecycleState.paused) unawaited(_saveSnapshot());
     } else if (state == AppLifecycleState.resumed) {
       if (_isRecordingIntent && _lastConfig != null) {
-        _startMicrophone(_lastConfig!);
+        unawaited(_resume(_lastConfig!));
       }
     }
   }
 
-  Future<void> start(PitchConfig config) async {
+  Future<void> start(
+    PitchConfig config, {
+    PitchStreamOptions options = const PitchStreamOptions(
+      onlyOnChange: true,
+      minChangeCents: 20,
+      maxEventsPerSec: 30,
+      finalOnly: false,
+    ),
+  }) async {
+    // The recorder streams 16-bit little-endian PCM.
+    if (config.pcmFormat != PcmFormat.i16Le) {
+      throw ArgumentError.value(
+        config.pcmFormat,
+        'config.pcmFormat',
+        'microphone input is 16-bit PCM',
+      );
+    }
+
     final status = await Permission.microphone.request();
     if (status != PermissionStatus.granted) {
       throw Exception('Microphone permission denied');
@@ -46,7 +95,7 @@ class PitchDetectorController with WidgetsBindingObserver {
 
     await RustLib.init();
     _analyzer = await AudioAnalyzer.newInstance(config: config);
-    _noteStream = _analyzer!.createStream();
+    _events = _analyzer!.createStream(options: options).asBroadcastStream();
 
     _lastConfig = config;
     _isRecordingIntent = true;
@@ -54,6 +103,134 @@ class PitchDetectorController with WidgetsBindingObserver {
     await _startMicrophone(config);
   }
 
+  AudioAnalyzer get _running =>
+      _analyzer ?? (throw StateError('PitchDetectorController is not started'));
+
+  /// Cancel the bleed of the song playing at [path] from the mic.
+  /// [delaySec] is the stream time at which the song reaches the mic.
+  Future<void> setBackingTrack(String path, {double delaySec = 0}) async {
+    await _running.setBackingTrackFile(
+      path: path,
+      config: BleedConfig(delaySec: delaySec, filterMs: 20, stepSize: 0.05),
+    );
+    _backingTrackDelaySec = delaySec;
+  }
+
+  /// Re-align the backing track after a seek or a latency estimate.
+  Future<void> setBackingTrackDelay(double delaySec) async {
+    await _running.setBackingTrackDelay(delaySec: delaySec);
+    _backingTrackDelaySec = delaySec;
+  }
+
+  Future<void> clearBackingTrack() async {
+    await _running.clearBackingTrack();
+    _backingTrackDelaySec = null;
+  }
+
+  /// Bias the tracker toward the melody of the song being sung.
+  /// [songStartSec] is the stream time at which the song's audio started.
+  Future<void> setTargetNotes(
+    List<TargetNote> notes, {
+    double songStartSec = 0,
+  }) async {
+    await _running.setTargetNotes(
+      notes: notes,
+      config: ScorePriorConfig(
+        songStartSec: songStartSec,
+        strength: 0.6,
+        sigmaCents: 150,
+        octaveWeight: 0.5,
+      ),
+    );
+    _songStartSec = songStartSec;
+  }
+
+  Future<void> clearTargetNotes() => _running.clearTargetNotes();
+
+  /// Score the singer against [chart], the bytes of an UltraStar .txt file,
+  /// replacing any song in progress. Events arrive on [scoreEvents].
+  void startScoring(List<int> chart, {required ScoreStreamOptions options}) {
+    _scoreEvents = _running
+        .createScoreStream(chart: chart, options: options)
+        .asBroadcastStream();
+    _songStartSec = options.songStartSec;
+  }
+
+  /// Score the notes still open once the song is over, streaming their
+  /// events to [scoreEvents].
+  Future<void> finishScoring() => _running.finishScoring();
+
+  /// Input levels of the most recent mic chunk, for a level bar and a
+  /// clipping warning; `null` before the first chunk.
+  Future<LevelReading?> latestLevel() => _running.latestLevel();
+
+  /// Continue the session saved in [snapshotFile] before the app was killed.
+  /// Call it after [start], once the same backing track, target notes and
+  /// [startScoring] song as before are set, placed as before; they are then
+  /// moved on by the time the app was away. Returns false, leaving a fresh
+  /// session, when there is no snapshot or it does not match the current
+  /// setup.
+  Future<bool> restoreSnapshot() async {
+    final file = snapshotFile;
+    if (file == null || !await file.exists()) return false;
+    final saved = await file.readAsBytes();
+    if (saved.length >= 8) {
+      final stoppedAt = DateTime.fromMicrosecondsSinceEpoch(
+        ByteData.sublistView(saved, 0, 8).getInt64(0, Endian.little),
+      );
+      try {
+        await _running.restore(blob: saved.sublist(8));
+        await _catchUpWithSong(stoppedAt);
+        return true;
+      } on PyinError {
+        // Fall through to a fresh session.
+      }
+    }
+    await file.delete();
+    return false;
+  }
+
+  /// Save the analyzer state after the time the mic stopped, which lets a
+  /// restore catch up with the song.
+  Future<void> _saveSnapshot() async {
+    final file = snapshotFile;
+    final analyzer = _analyzer;
+    if (file == null || analyzer == null) return;
+    final stoppedAt = _micStoppedAt ?? DateTime.now();
+    final header = ByteData(8)
+      ..setInt64(0, stoppedAt.microsecondsSinceEpoch, Endian.little);
+    final blob = await analyzer.snapshot();
+    await file.writeAsBytes(
+      [...header.buffer.asUint8List(), ...blob],
+      flush: true,
+    );
+  }
+
+  Future<void> _resume(PitchConfig config) async {
+    final stoppedAt = _micStoppedAt;
+    _micStoppedAt = null;
+    if (stoppedAt != null && _analyzer != null) {
+      await _catchUpWithSong(stoppedAt);
+    }
+    await _startMicrophone(config);
+  }
+
+  /// The song played on while the mic was off since [stoppedAt], but the
+  /// stream clock did not: move the song that much earlier on the clock.
+  Future<void> _catchUpWithSong(DateTime stoppedAt) async {
+    final gapSec = DateTime.now().difference(stoppedAt).inMicroseconds / 1e6;
+    final songStartSec = _songStartSec;
+    if (songStartSec != null) {
+      _songStartSec = songStartSec - gapSec;
+      await _running.setSongStart(songStartSec: _songStartSec!);
+    }
+    final delaySec = _backingTrackDelaySec;
+    if (delaySec != null) {
+      _backingTrackDelaySec = delaySec - gapSec;
+      await _running.setBackingTrackDelay(delaySec: _backingTrackDelaySec!);
+    }
+  }
+
   Future<void> _startMicrophone(PitchConfig config) async {
     if (await _recorder.isRecording()) return;
 
@@ -61,13 +238,13 @@ class PitchDetectorController with WidgetsBindingObserver {
       RecordConfig(
         encoder: AudioEncoder.pcm16bits,
         sampleRate: config.sampleRateHz,
-        numChannels: 1,
+        numChannels: config.channels,
       ),
     );
 
     _micSubscription?.cancel();
     _micSubscription = stream.listen((data) {
-      _analyzer?.processChunk(pcm16LeBytes: data);
+      _analyzer?.processChunk(pcmBytes: data);
     });
   }
 
@@ -76,9 +253,18 @@ class PitchDetectorController with WidgetsBindingObserver {
     _micSubscription?.cancel();
     _micSubscription = null;
     await _recorder.stop();
+    // Stream the tail of the last note before dropping the analyzer.
+    await _analyzer?.finish();
     _analyzer = null;
+    // The session is over; don't resume it on the next start.
+    final file = snapshotFile;
+    if (file != null && await file.exists()) await file.delete();
     _lastConfig = null;
-    _noteStream = null;
+    _songStartSec = null;
+    _backingTrackDelaySec = null;
+    _micStoppedAt = null;
+    _events = null;
+    _scoreEvents = null;
   }
 
   void dispose() {
@@ -12,6 +12,7 @@ import 'frb_generated.io.dart'
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 import 'pyin.dart';
 import 'pyin/api.dart';
+import 'scoring.dart';
 
 /// Main entrypoint of the Rust API
 class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
@@ -66,7 +67,7 @@ class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
   String get codegenVersion => '2.11.1';
 
   @override
-  int get rustContentHash => 66788347;
+  int get rustContentHash => 1649513208;
 
   static const kDefaultExternalLibraryLoaderConfig =
       ExternalLibraryLoaderConfig(
@@ -77,7 +78,40 @@ class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
 }
 
 abstract class RustLibApi extends BaseApi {
-  Stream<int> cratePyinApiAudioAnalyzerCreateStream({
+  Future<void> cratePyinApiAudioAnalyzerClearBackingTrack({
+    required AudioAnalyzer that,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerClearTargetNotes({
+    required AudioAnalyzer that,
+  });
+
+  Stream<ScoreEvent> cratePyinApiAudioAnalyzerCreateScoreStream({
+    required AudioAnalyzer that,
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  });
+
+  Stream<PitchEvent> cratePyinApiAudioAnalyzerCreateStream({
+    required AudioAnalyzer that,
+    required PitchStreamOptions options,
+  });
+
+  Future<List<ScoreEvent>> cratePyinApiAudioAnalyzerDrainScoreEvents({
+    required AudioAnalyzer that,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerFinish({required AudioAnalyzer that});
+
+  Future<List<PitchEvent>> cratePyinApiAudioAnalyzerFinishCollectEvents({
+    required AudioAnalyzer that,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerFinishScoring({
+    required AudioAnalyzer that,
+  });
+
+  Future<LevelReading?> cratePyinApiAudioAnalyzerLatestLevel({
     required AudioAnalyzer that,
   });
 
@@ -87,12 +121,100 @@ abstract class RustLibApi extends BaseApi {
 
   Future<void> cratePyinApiAudioAnalyzerProcessChunk({
     required AudioAnalyzer that,
-    required List<int> pcm16LeBytes,
+    required List<int> pcmBytes,
   });
 
   Future<Uint8List> cratePyinApiAudioAnalyzerProcessChunkCollect({
     required AudioAnalyzer that,
-    required List<int> pcm16LeBytes,
+    required List<int> pcmBytes,
+  });
+
+  Future<List<PitchEvent>> cratePyinApiAudioAnalyzerProcessChunkCollectEvents({
+    required AudioAnalyzer that,
+    required List<int> pcmBytes,
+  });
+
+  Future<Float32List> cratePyinApiAudioAnalyzerProcessChunkCollectFractional({
+    required AudioAnalyzer that,
+    required List<int> pcmBytes,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerRestore({
+    required AudioAnalyzer that,
+    required List<int> blob,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerSetBackingTrackDelay({
+    required AudioAnalyzer that,
+    required double delaySec,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerSetBackingTrackFile({
+    required AudioAnalyzer that,
+    required String path,
+    required BleedConfig config,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerSetSongStart({
+    required AudioAnalyzer that,
+    required double songStartSec,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerSetStreamOptions({
+    required AudioAnalyzer that,
+    required PitchStreamOptions options,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerSetTargetNotes({
+    required AudioAnalyzer that,
+    required List<TargetNote> notes,
+    required ScorePriorConfig config,
+  });
+
+  Future<Uint8List> cratePyinApiAudioAnalyzerSnapshot({
+    required AudioAnalyzer that,
+  });
+
+  Future<void> cratePyinApiAudioAnalyzerStartScoring({
+    required AudioAnalyzer that,
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  });
+
+  Future<int> cratePyinApiPartyAddSinger({
+    required Party that,
+    required PitchConfig config,
+    required SingerOptions options,
+  });
+
+  Future<List<ScoreEvent>> cratePyinApiPartyDrainScoreEvents({
+    required Party that,
+    required int singer,
+  });
+
+  Future<void> cratePyinApiPartyFinish({required Party that});
+
+  Future<Party> cratePyinApiPartyNew({
+    required List<int> chart,
+    required double songStartSec,
+  });
+
+  Future<void> cratePyinApiPartyProcessChunk({
+    required Party that,
+    required int singer,
+    required List<int> pcmBytes,
+  });
+
+  Future<List<SingerResult>> cratePyinApiPartyResults({required Party that});
+
+  Future<FileContour> cratePyinApiAnalyzeFile({
+    required String path,
+    required FileAnalysisOptions options,
+  });
+
+  Stream<FileAnalysisUpdate> cratePyinApiAnalyzeFileStream({
+    required String path,
+    required FileAnalysisOptions options,
   });
 
   Future<List<AudioFingerprint>> crateFingerprintGetBatchFingerprints({
@@ -121,6 +243,15 @@ abstract class RustLibApi extends BaseApi {
   CrossPlatformFinalizerArg
   get rust_arc_decrement_strong_count_AudioAnalyzerPtr;
 
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_Party;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_Party;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_PartyPtr;
+
   RustArcIncrementStrongCountFnType
   get rust_arc_increment_strong_count_PyinProcessor;
 
@@ -140,10 +271,128 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   });
 
   @override
-  Stream<int> cratePyinApiAudioAnalyzerCreateStream({
+  Future<void> cratePyinApiAudioAnalyzerClearBackingTrack({
+    required AudioAnalyzer that,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 1,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerClearBackingTrackConstMeta,
+        argValues: [that],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerClearBackingTrackConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_clear_backing_track",
+        argNames: ["that"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerClearTargetNotes({
+    required AudioAnalyzer that,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 2,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerClearTargetNotesConstMeta,
+        argValues: [that],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerClearTargetNotesConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_clear_target_notes",
+        argNames: ["that"],
+      );
+
+  @override
+  Stream<ScoreEvent> cratePyinApiAudioAnalyzerCreateScoreStream({
+    required AudioAnalyzer that,
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  }) {
+    final sink = RustStreamSink<ScoreEvent>();
+    unawaited(
+      handler.executeNormal(
+        NormalTask(
+          callFfi: (port_) {
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+              that,
+              serializer,
+            );
+            sse_encode_StreamSink_score_event_Sse(sink, serializer);
+            sse_encode_list_prim_u_8_loose(chart, serializer);
+            sse_encode_box_autoadd_score_stream_options(options, serializer);
+            pdeCallFfi(
+              generalizedFrbRustBinding,
+              serializer,
+              funcId: 3,
+              port: port_,
+            );
+          },
+          codec: SseCodec(
+            decodeSuccessData: sse_decode_unit,
+            decodeErrorData: sse_decode_pyin_error,
+          ),
+          constMeta: kCratePyinApiAudioAnalyzerCreateScoreStreamConstMeta,
+          argValues: [that, sink, chart, options],
+          apiImpl: this,
+        ),
+      ),
+    );
+    return sink.stream;
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerCreateScoreStreamConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_create_score_stream",
+        argNames: ["that", "sink", "chart", "options"],
+      );
+
+  @override
+  Stream<PitchEvent> cratePyinApiAudioAnalyzerCreateStream({
     required AudioAnalyzer that,
+    required PitchStreamOptions options,
   }) {
-    final sink = RustStreamSink<int>();
+    final sink = RustStreamSink<PitchEvent>();
     unawaited(
       handler.executeNormal(
         NormalTask(
@@ -153,11 +402,12 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
               that,
               serializer,
             );
-            sse_encode_StreamSink_u_8_Sse(sink, serializer);
+            sse_encode_StreamSink_pitch_event_Sse(sink, serializer);
+            sse_encode_box_autoadd_pitch_stream_options(options, serializer);
             pdeCallFfi(
               generalizedFrbRustBinding,
               serializer,
-              funcId: 1,
+              funcId: 4,
               port: port_,
             );
           },
@@ -166,7 +416,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
             decodeErrorData: null,
           ),
           constMeta: kCratePyinApiAudioAnalyzerCreateStreamConstMeta,
-          argValues: [that, sink],
+          argValues: [that, sink, options],
           apiImpl: this,
         ),
       ),
@@ -177,45 +427,47 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   TaskConstMeta get kCratePyinApiAudioAnalyzerCreateStreamConstMeta =>
       const TaskConstMeta(
         debugName: "AudioAnalyzer_create_stream",
-        argNames: ["that", "sink"],
+        argNames: ["that", "sink", "options"],
       );
 
   @override
-  Future<AudioAnalyzer> cratePyinApiAudioAnalyzerNew({
-    required PitchConfig config,
+  Future<List<ScoreEvent>> cratePyinApiAudioAnalyzerDrainScoreEvents({
+    required AudioAnalyzer that,
   }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_box_autoadd_pitch_config(config, serializer);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 2,
+            funcId: 5,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData:
-              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer,
-          decodeErrorData: sse_decode_pyin_error,
+          decodeSuccessData: sse_decode_list_score_event,
+          decodeErrorData: null,
         ),
-        constMeta: kCratePyinApiAudioAnalyzerNewConstMeta,
-        argValues: [config],
+        constMeta: kCratePyinApiAudioAnalyzerDrainScoreEventsConstMeta,
+        argValues: [that],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCratePyinApiAudioAnalyzerNewConstMeta =>
-      const TaskConstMeta(debugName: "AudioAnalyzer_new", argNames: ["config"]);
+  TaskConstMeta get kCratePyinApiAudioAnalyzerDrainScoreEventsConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_drain_score_events",
+        argNames: ["that"],
+      );
 
   @override
-  Future<void> cratePyinApiAudioAnalyzerProcessChunk({
-    required AudioAnalyzer that,
-    required List<int> pcm16LeBytes,
-  }) {
+  Future<void> cratePyinApiAudioAnalyzerFinish({required AudioAnalyzer that}) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
@@ -224,11 +476,10 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
             that,
             serializer,
           );
-          sse_encode_list_prim_u_8_loose(pcm16LeBytes, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 3,
+            funcId: 6,
             port: port_,
           );
         },
@@ -236,23 +487,22 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           decodeSuccessData: sse_decode_unit,
           decodeErrorData: sse_decode_pyin_error,
         ),
-        constMeta: kCratePyinApiAudioAnalyzerProcessChunkConstMeta,
-        argValues: [that, pcm16LeBytes],
+        constMeta: kCratePyinApiAudioAnalyzerFinishConstMeta,
+        argValues: [that],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCratePyinApiAudioAnalyzerProcessChunkConstMeta =>
+  TaskConstMeta get kCratePyinApiAudioAnalyzerFinishConstMeta =>
       const TaskConstMeta(
-        debugName: "AudioAnalyzer_process_chunk",
-        argNames: ["that", "pcm16LeBytes"],
+        debugName: "AudioAnalyzer_finish",
+        argNames: ["that"],
       );
 
   @override
-  Future<Uint8List> cratePyinApiAudioAnalyzerProcessChunkCollect({
+  Future<List<PitchEvent>> cratePyinApiAudioAnalyzerFinishCollectEvents({
     required AudioAnalyzer that,
-    required List<int> pcm16LeBytes,
   }) {
     return handler.executeNormal(
       NormalTask(
@@ -262,780 +512,3216 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
             that,
             serializer,
           );
-          sse_encode_list_prim_u_8_loose(pcm16LeBytes, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 4,
+            funcId: 7,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_list_prim_u_8_strict,
+          decodeSuccessData: sse_decode_list_pitch_event,
           decodeErrorData: sse_decode_pyin_error,
         ),
-        constMeta: kCratePyinApiAudioAnalyzerProcessChunkCollectConstMeta,
-        argValues: [that, pcm16LeBytes],
+        constMeta: kCratePyinApiAudioAnalyzerFinishCollectEventsConstMeta,
+        argValues: [that],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCratePyinApiAudioAnalyzerProcessChunkCollectConstMeta =>
+  TaskConstMeta get kCratePyinApiAudioAnalyzerFinishCollectEventsConstMeta =>
       const TaskConstMeta(
-        debugName: "AudioAnalyzer_process_chunk_collect",
-        argNames: ["that", "pcm16LeBytes"],
+        debugName: "AudioAnalyzer_finish_collect_events",
+        argNames: ["that"],
       );
 
   @override
-  Future<List<AudioFingerprint>> crateFingerprintGetBatchFingerprints({
-    required List<String> paths,
+  Future<void> cratePyinApiAudioAnalyzerFinishScoring({
+    required AudioAnalyzer that,
   }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_list_String(paths, serializer);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 5,
+            funcId: 8,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_list_audio_fingerprint,
-          decodeErrorData: null,
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
         ),
-        constMeta: kCrateFingerprintGetBatchFingerprintsConstMeta,
-        argValues: [paths],
+        constMeta: kCratePyinApiAudioAnalyzerFinishScoringConstMeta,
+        argValues: [that],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateFingerprintGetBatchFingerprintsConstMeta =>
+  TaskConstMeta get kCratePyinApiAudioAnalyzerFinishScoringConstMeta =>
       const TaskConstMeta(
-        debugName: "get_batch_fingerprints",
-        argNames: ["paths"],
+        debugName: "AudioAnalyzer_finish_scoring",
+        argNames: ["that"],
       );
 
   @override
-  Future<void> cratePyinApiInitLogging() {
+  Future<LevelReading?> cratePyinApiAudioAnalyzerLatestLevel({
+    required AudioAnalyzer that,
+  }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 6,
+            funcId: 9,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_unit,
+          decodeSuccessData: sse_decode_opt_box_autoadd_level_reading,
           decodeErrorData: null,
         ),
-        constMeta: kCratePyinApiInitLoggingConstMeta,
-        argValues: [],
+        constMeta: kCratePyinApiAudioAnalyzerLatestLevelConstMeta,
+        argValues: [that],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCratePyinApiInitLoggingConstMeta =>
-      const TaskConstMeta(debugName: "init_logging", argNames: []);
+  TaskConstMeta get kCratePyinApiAudioAnalyzerLatestLevelConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_latest_level",
+        argNames: ["that"],
+      );
 
   @override
-  Future<PyinProcessor> cratePyinApiNewProcessor({
-    required int sampleRateHz,
-    required int windowMs,
-    required int hopMs,
+  Future<AudioAnalyzer> cratePyinApiAudioAnalyzerNew({
+    required PitchConfig config,
   }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_u_32(sampleRateHz, serializer);
-          sse_encode_u_32(windowMs, serializer);
-          sse_encode_u_32(hopMs, serializer);
+          sse_encode_box_autoadd_pitch_config(config, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 7,
+            funcId: 10,
             port: port_,
           );
         },
         codec: SseCodec(
           decodeSuccessData:
-              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor,
-          decodeErrorData: null,
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer,
+          decodeErrorData: sse_decode_pyin_error,
         ),
-        constMeta: kCratePyinApiNewProcessorConstMeta,
-        argValues: [sampleRateHz, windowMs, hopMs],
+        constMeta: kCratePyinApiAudioAnalyzerNewConstMeta,
+        argValues: [config],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCratePyinApiNewProcessorConstMeta => const TaskConstMeta(
-    debugName: "new_processor",
-    argNames: ["sampleRateHz", "windowMs", "hopMs"],
-  );
+  TaskConstMeta get kCratePyinApiAudioAnalyzerNewConstMeta =>
+      const TaskConstMeta(debugName: "AudioAnalyzer_new", argNames: ["config"]);
 
   @override
-  Future<int> cratePyinApiPushAndGetMidi({
-    required PyinProcessor proc,
-    required List<int> pcm16LeBytes,
+  Future<void> cratePyinApiAudioAnalyzerProcessChunk({
+    required AudioAnalyzer that,
+    required List<int> pcmBytes,
   }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-            proc,
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
             serializer,
           );
-          sse_encode_list_prim_u_8_loose(pcm16LeBytes, serializer);
+          sse_encode_list_prim_u_8_loose(pcmBytes, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 8,
+            funcId: 11,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_u_16,
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerProcessChunkConstMeta,
+        argValues: [that, pcmBytes],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerProcessChunkConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_process_chunk",
+        argNames: ["that", "pcmBytes"],
+      );
+
+  @override
+  Future<Uint8List> cratePyinApiAudioAnalyzerProcessChunkCollect({
+    required AudioAnalyzer that,
+    required List<int> pcmBytes,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_list_prim_u_8_loose(pcmBytes, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 12,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_prim_u_8_strict,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerProcessChunkCollectConstMeta,
+        argValues: [that, pcmBytes],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerProcessChunkCollectConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_process_chunk_collect",
+        argNames: ["that", "pcmBytes"],
+      );
+
+  @override
+  Future<List<PitchEvent>> cratePyinApiAudioAnalyzerProcessChunkCollectEvents({
+    required AudioAnalyzer that,
+    required List<int> pcmBytes,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_list_prim_u_8_loose(pcmBytes, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 13,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_pitch_event,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerProcessChunkCollectEventsConstMeta,
+        argValues: [that, pcmBytes],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta
+  get kCratePyinApiAudioAnalyzerProcessChunkCollectEventsConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_process_chunk_collect_events",
+        argNames: ["that", "pcmBytes"],
+      );
+
+  @override
+  Future<Float32List> cratePyinApiAudioAnalyzerProcessChunkCollectFractional({
+    required AudioAnalyzer that,
+    required List<int> pcmBytes,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_list_prim_u_8_loose(pcmBytes, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 14,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_prim_f_32_strict,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta:
+            kCratePyinApiAudioAnalyzerProcessChunkCollectFractionalConstMeta,
+        argValues: [that, pcmBytes],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta
+  get kCratePyinApiAudioAnalyzerProcessChunkCollectFractionalConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_process_chunk_collect_fractional",
+        argNames: ["that", "pcmBytes"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerRestore({
+    required AudioAnalyzer that,
+    required List<int> blob,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_list_prim_u_8_loose(blob, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 15,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerRestoreConstMeta,
+        argValues: [that, blob],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerRestoreConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_restore",
+        argNames: ["that", "blob"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerSetBackingTrackDelay({
+    required AudioAnalyzer that,
+    required double delaySec,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_f_64(delaySec, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 16,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
           decodeErrorData: null,
         ),
-        constMeta: kCratePyinApiPushAndGetMidiConstMeta,
-        argValues: [proc, pcm16LeBytes],
+        constMeta: kCratePyinApiAudioAnalyzerSetBackingTrackDelayConstMeta,
+        argValues: [that, delaySec],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerSetBackingTrackDelayConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_set_backing_track_delay",
+        argNames: ["that", "delaySec"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerSetBackingTrackFile({
+    required AudioAnalyzer that,
+    required String path,
+    required BleedConfig config,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_String(path, serializer);
+          sse_encode_box_autoadd_bleed_config(config, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 17,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerSetBackingTrackFileConstMeta,
+        argValues: [that, path, config],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerSetBackingTrackFileConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_set_backing_track_file",
+        argNames: ["that", "path", "config"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerSetSongStart({
+    required AudioAnalyzer that,
+    required double songStartSec,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_f_64(songStartSec, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 18,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerSetSongStartConstMeta,
+        argValues: [that, songStartSec],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerSetSongStartConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_set_song_start",
+        argNames: ["that", "songStartSec"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerSetStreamOptions({
+    required AudioAnalyzer that,
+    required PitchStreamOptions options,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_box_autoadd_pitch_stream_options(options, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 19,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerSetStreamOptionsConstMeta,
+        argValues: [that, options],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCratePyinApiPushAndGetMidiConstMeta => const TaskConstMeta(
-    debugName: "push_and_get_midi",
-    argNames: ["proc", "pcm16LeBytes"],
-  );
+  TaskConstMeta get kCratePyinApiAudioAnalyzerSetStreamOptionsConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_set_stream_options",
+        argNames: ["that", "options"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerSetTargetNotes({
+    required AudioAnalyzer that,
+    required List<TargetNote> notes,
+    required ScorePriorConfig config,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_list_target_note(notes, serializer);
+          sse_encode_box_autoadd_score_prior_config(config, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 20,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerSetTargetNotesConstMeta,
+        argValues: [that, notes, config],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerSetTargetNotesConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_set_target_notes",
+        argNames: ["that", "notes", "config"],
+      );
+
+  @override
+  Future<Uint8List> cratePyinApiAudioAnalyzerSnapshot({
+    required AudioAnalyzer that,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 21,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_prim_u_8_strict,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerSnapshotConstMeta,
+        argValues: [that],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerSnapshotConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_snapshot",
+        argNames: ["that"],
+      );
+
+  @override
+  Future<void> cratePyinApiAudioAnalyzerStartScoring({
+    required AudioAnalyzer that,
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+            that,
+            serializer,
+          );
+          sse_encode_list_prim_u_8_loose(chart, serializer);
+          sse_encode_box_autoadd_score_stream_options(options, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 22,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAudioAnalyzerStartScoringConstMeta,
+        argValues: [that, chart, options],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAudioAnalyzerStartScoringConstMeta =>
+      const TaskConstMeta(
+        debugName: "AudioAnalyzer_start_scoring",
+        argNames: ["that", "chart", "options"],
+      );
+
+  @override
+  Future<int> cratePyinApiPartyAddSinger({
+    required Party that,
+    required PitchConfig config,
+    required SingerOptions options,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+            that,
+            serializer,
+          );
+          sse_encode_box_autoadd_pitch_config(config, serializer);
+          sse_encode_box_autoadd_singer_options(options, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 23,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_u_32,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiPartyAddSingerConstMeta,
+        argValues: [that, config, options],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiPartyAddSingerConstMeta => const TaskConstMeta(
+    debugName: "Party_add_singer",
+    argNames: ["that", "config", "options"],
+  );
+
+  @override
+  Future<List<ScoreEvent>> cratePyinApiPartyDrainScoreEvents({
+    required Party that,
+    required int singer,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+            that,
+            serializer,
+          );
+          sse_encode_u_32(singer, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 24,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_score_event,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiPartyDrainScoreEventsConstMeta,
+        argValues: [that, singer],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiPartyDrainScoreEventsConstMeta =>
+      const TaskConstMeta(
+        debugName: "Party_drain_score_events",
+        argNames: ["that", "singer"],
+      );
+
+  @override
+  Future<void> cratePyinApiPartyFinish({required Party that}) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+            that,
+            serializer,
+          );
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 25,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiPartyFinishConstMeta,
+        argValues: [that],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiPartyFinishConstMeta =>
+      const TaskConstMeta(debugName: "Party_finish", argNames: ["that"]);
+
+  @override
+  Future<Party> cratePyinApiPartyNew({
+    required List<int> chart,
+    required double songStartSec,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_list_prim_u_8_loose(chart, serializer);
+          sse_encode_f_64(songStartSec, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 26,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiPartyNewConstMeta,
+        argValues: [chart, songStartSec],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiPartyNewConstMeta => const TaskConstMeta(
+    debugName: "Party_new",
+    argNames: ["chart", "songStartSec"],
+  );
+
+  @override
+  Future<void> cratePyinApiPartyProcessChunk({
+    required Party that,
+    required int singer,
+    required List<int> pcmBytes,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+            that,
+            serializer,
+          );
+          sse_encode_u_32(singer, serializer);
+          sse_encode_list_prim_u_8_loose(pcmBytes, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 27,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiPartyProcessChunkConstMeta,
+        argValues: [that, singer, pcmBytes],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiPartyProcessChunkConstMeta =>
+      const TaskConstMeta(
+        debugName: "Party_process_chunk",
+        argNames: ["that", "singer", "pcmBytes"],
+      );
+
+  @override
+  Future<List<SingerResult>> cratePyinApiPartyResults({required Party that}) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+            that,
+            serializer,
+          );
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 28,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_singer_result,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiPartyResultsConstMeta,
+        argValues: [that],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiPartyResultsConstMeta =>
+      const TaskConstMeta(debugName: "Party_results", argNames: ["that"]);
+
+  @override
+  Future<FileContour> cratePyinApiAnalyzeFile({
+    required String path,
+    required FileAnalysisOptions options,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(path, serializer);
+          sse_encode_box_autoadd_file_analysis_options(options, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 29,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_file_contour,
+          decodeErrorData: sse_decode_pyin_error,
+        ),
+        constMeta: kCratePyinApiAnalyzeFileConstMeta,
+        argValues: [path, options],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiAnalyzeFileConstMeta => const TaskConstMeta(
+    debugName: "analyze_file",
+    argNames: ["path", "options"],
+  );
+
+  @override
+  Stream<FileAnalysisUpdate> cratePyinApiAnalyzeFileStream({
+    required String path,
+    required FileAnalysisOptions options,
+  }) {
+    final sink = RustStreamSink<FileAnalysisUpdate>();
+    unawaited(
+      handler.executeNormal(
+        NormalTask(
+          callFfi: (port_) {
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            sse_encode_String(path, serializer);
+            sse_encode_box_autoadd_file_analysis_options(options, serializer);
+            sse_encode_StreamSink_file_analysis_update_Sse(sink, serializer);
+            pdeCallFfi(
+              generalizedFrbRustBinding,
+              serializer,
+              funcId: 30,
+              port: port_,
+            );
+          },
+          codec: SseCodec(
+            decodeSuccessData: sse_decode_unit,
+            decodeErrorData: sse_decode_pyin_error,
+          ),
+          constMeta: kCratePyinApiAnalyzeFileStreamConstMeta,
+          argValues: [path, options, sink],
+          apiImpl: this,
+        ),
+      ),
+    );
+    return sink.stream;
+  }
+
+  TaskConstMeta get kCratePyinApiAnalyzeFileStreamConstMeta =>
+      const TaskConstMeta(
+        debugName: "analyze_file_stream",
+        argNames: ["path", "options", "sink"],
+      );
+
+  @override
+  Future<List<AudioFingerprint>> crateFingerprintGetBatchFingerprints({
+    required List<String> paths,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_list_String(paths, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 31,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_audio_fingerprint,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateFingerprintGetBatchFingerprintsConstMeta,
+        argValues: [paths],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateFingerprintGetBatchFingerprintsConstMeta =>
+      const TaskConstMeta(
+        debugName: "get_batch_fingerprints",
+        argNames: ["paths"],
+      );
+
+  @override
+  Future<void> cratePyinApiInitLogging() {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 32,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiInitLoggingConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiInitLoggingConstMeta =>
+      const TaskConstMeta(debugName: "init_logging", argNames: []);
+
+  @override
+  Future<PyinProcessor> cratePyinApiNewProcessor({
+    required int sampleRateHz,
+    required int windowMs,
+    required int hopMs,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_u_32(sampleRateHz, serializer);
+          sse_encode_u_32(windowMs, serializer);
+          sse_encode_u_32(hopMs, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 33,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiNewProcessorConstMeta,
+        argValues: [sampleRateHz, windowMs, hopMs],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiNewProcessorConstMeta => const TaskConstMeta(
+    debugName: "new_processor",
+    argNames: ["sampleRateHz", "windowMs", "hopMs"],
+  );
+
+  @override
+  Future<int> cratePyinApiPushAndGetMidi({
+    required PyinProcessor proc,
+    required List<int> pcm16LeBytes,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+            proc,
+            serializer,
+          );
+          sse_encode_list_prim_u_8_loose(pcm16LeBytes, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 34,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_u_16,
+          decodeErrorData: null,
+        ),
+        constMeta: kCratePyinApiPushAndGetMidiConstMeta,
+        argValues: [proc, pcm16LeBytes],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCratePyinApiPushAndGetMidiConstMeta => const TaskConstMeta(
+    debugName: "push_and_get_midi",
+    argNames: ["proc", "pcm16LeBytes"],
+  );
+
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_AudioAnalyzer => wire
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_AudioAnalyzer => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer;
+
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_Party => wire
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_Party => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty;
+
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_PyinProcessor => wire
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_PyinProcessor => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor;
+
+  @protected
+  AnyhowException dco_decode_AnyhowException(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return AnyhowException(raw as String);
+  }
+
+  @protected
+  AudioAnalyzer
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  Party
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PartyImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  PyinProcessor
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PyinProcessorImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  AudioAnalyzer
+  dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  Party
+  dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PartyImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  PyinProcessor
+  dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PyinProcessorImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  AudioAnalyzer
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  Party
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PartyImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  AudioAnalyzer
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  Party
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PartyImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  PyinProcessor
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PyinProcessorImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  }
+
+  @protected
+  RustStreamSink<FileAnalysisUpdate>
+  dco_decode_StreamSink_file_analysis_update_Sse(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    throw UnimplementedError();
+  }
+
+  @protected
+  RustStreamSink<PitchEvent> dco_decode_StreamSink_pitch_event_Sse(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    throw UnimplementedError();
+  }
+
+  @protected
+  RustStreamSink<ScoreEvent> dco_decode_StreamSink_score_event_Sse(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    throw UnimplementedError();
+  }
+
+  @protected
+  String dco_decode_String(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as String;
+  }
+
+  @protected
+  AudioFingerprint dco_decode_audio_fingerprint(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return AudioFingerprint(
+      path: dco_decode_String(arr[0]),
+      fingerprint: dco_decode_String(arr[1]),
+      durationSecs: dco_decode_f_64(arr[2]),
+    );
+  }
+
+  @protected
+  BleedConfig dco_decode_bleed_config(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return BleedConfig(
+      delaySec: dco_decode_f_64(arr[0]),
+      filterMs: dco_decode_f_32(arr[1]),
+      stepSize: dco_decode_f_32(arr[2]),
+    );
+  }
+
+  @protected
+  bool dco_decode_bool(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as bool;
+  }
+
+  @protected
+  BleedConfig dco_decode_box_autoadd_bleed_config(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_bleed_config(raw);
+  }
+
+  @protected
+  double dco_decode_box_autoadd_f_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as double;
+  }
+
+  @protected
+  double dco_decode_box_autoadd_f_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as double;
+  }
+
+  @protected
+  FileAnalysisOptions dco_decode_box_autoadd_file_analysis_options(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_file_analysis_options(raw);
+  }
+
+  @protected
+  FileContour dco_decode_box_autoadd_file_contour(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_file_contour(raw);
+  }
+
+  @protected
+  LevelReading dco_decode_box_autoadd_level_reading(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_level_reading(raw);
+  }
+
+  @protected
+  PitchConfig dco_decode_box_autoadd_pitch_config(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_pitch_config(raw);
+  }
+
+  @protected
+  PitchStreamOptions dco_decode_box_autoadd_pitch_stream_options(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_pitch_stream_options(raw);
+  }
+
+  @protected
+  ScorePriorConfig dco_decode_box_autoadd_score_prior_config(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_score_prior_config(raw);
+  }
+
+  @protected
+  ScoreStreamOptions dco_decode_box_autoadd_score_stream_options(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_score_stream_options(raw);
+  }
+
+  @protected
+  SingerOptions dco_decode_box_autoadd_singer_options(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_singer_options(raw);
+  }
+
+  @protected
+  int dco_decode_box_autoadd_u_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as int;
+  }
+
+  @protected
+  ChannelMix dco_decode_channel_mix(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    switch (raw[0]) {
+      case 0:
+        return ChannelMix_Downmix();
+      case 1:
+        return ChannelMix_Pick(dco_decode_u_16(raw[1]));
+      default:
+        throw Exception("unreachable");
+    }
+  }
+
+  @protected
+  Difficulty dco_decode_difficulty(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return Difficulty.values[raw as int];
+  }
+
+  @protected
+  double dco_decode_f_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as double;
+  }
+
+  @protected
+  double dco_decode_f_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as double;
+  }
+
+  @protected
+  FileAnalysisOptions dco_decode_file_analysis_options(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 6)
+      throw Exception('unexpected arr length: expect 6 but see ${arr.length}');
+    return FileAnalysisOptions(
+      analysisSampleRateHz: dco_decode_u_32(arr[0]),
+      updateIntervalMs: dco_decode_u_32(arr[1]),
+      windowSizeMs: dco_decode_u_32(arr[2]),
+      referenceA4Hz: dco_decode_f_32(arr[3]),
+      startSec: dco_decode_f_64(arr[4]),
+      endSec: dco_decode_opt_box_autoadd_f_64(arr[5]),
+    );
+  }
+
+  @protected
+  FileAnalysisUpdate dco_decode_file_analysis_update(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return FileAnalysisUpdate(
+      processedSec: dco_decode_f_64(arr[0]),
+      totalSec: dco_decode_opt_box_autoadd_f_64(arr[1]),
+      contour: dco_decode_opt_box_autoadd_file_contour(arr[2]),
+    );
+  }
+
+  @protected
+  FileContour dco_decode_file_contour(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return FileContour(
+      sourceSampleRateHz: dco_decode_u_32(arr[0]),
+      durationSec: dco_decode_f_64(arr[1]),
+      events: dco_decode_list_pitch_event(arr[2]),
+    );
+  }
+
+  @protected
+  LevelReading dco_decode_level_reading(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 5)
+      throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+    return LevelReading(
+      rmsDbfs: dco_decode_f_32(arr[0]),
+      peakDbfs: dco_decode_f_32(arr[1]),
+      momentaryLufs: dco_decode_f_32(arr[2]),
+      shortTermLufs: dco_decode_f_32(arr[3]),
+      clippedSamples: dco_decode_u_32(arr[4]),
+    );
+  }
+
+  @protected
+  List<String> dco_decode_list_String(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_String).toList();
+  }
+
+  @protected
+  List<AudioFingerprint> dco_decode_list_audio_fingerprint(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_audio_fingerprint).toList();
+  }
+
+  @protected
+  List<PitchEvent> dco_decode_list_pitch_event(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_pitch_event).toList();
+  }
+
+  @protected
+  Float32List dco_decode_list_prim_f_32_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as Float32List;
+  }
+
+  @protected
+  List<int> dco_decode_list_prim_u_8_loose(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as List<int>;
+  }
+
+  @protected
+  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as Uint8List;
+  }
+
+  @protected
+  List<ScoreEvent> dco_decode_list_score_event(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_score_event).toList();
+  }
+
+  @protected
+  List<SingerResult> dco_decode_list_singer_result(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_singer_result).toList();
+  }
+
+  @protected
+  List<TargetNote> dco_decode_list_target_note(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_target_note).toList();
+  }
+
+  @protected
+  double? dco_decode_opt_box_autoadd_f_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_box_autoadd_f_32(raw);
+  }
+
+  @protected
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_box_autoadd_f_64(raw);
+  }
+
+  @protected
+  FileContour? dco_decode_opt_box_autoadd_file_contour(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_box_autoadd_file_contour(raw);
+  }
+
+  @protected
+  LevelReading? dco_decode_opt_box_autoadd_level_reading(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_box_autoadd_level_reading(raw);
+  }
+
+  @protected
+  int? dco_decode_opt_box_autoadd_u_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_box_autoadd_u_32(raw);
+  }
+
+  @protected
+  PcmFormat dco_decode_pcm_format(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PcmFormat.values[raw as int];
+  }
+
+  @protected
+  PitchAlgorithm dco_decode_pitch_algorithm(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return PitchAlgorithm.values[raw as int];
+  }
+
+  @protected
+  PitchConfig dco_decode_pitch_config(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 12)
+      throw Exception('unexpected arr length: expect 12 but see ${arr.length}');
+    return PitchConfig(
+      sampleRateHz: dco_decode_u_32(arr[0]),
+      updateIntervalMs: dco_decode_u_32(arr[1]),
+      windowSizeMs: dco_decode_u_32(arr[2]),
+      referenceA4Hz: dco_decode_f_32(arr[3]),
+      pcmFormat: dco_decode_pcm_format(arr[4]),
+      channels: dco_decode_u_16(arr[5]),
+      channelMix: dco_decode_channel_mix(arr[6]),
+      analysisSampleRateHz: dco_decode_opt_box_autoadd_u_32(arr[7]),
+      vadEnabled: dco_decode_bool(arr[8]),
+      octaveCorrectionEnabled: dco_decode_bool(arr[9]),
+      algorithm: dco_decode_pitch_algorithm(arr[10]),
+      multiResolutionEnabled: dco_decode_bool(arr[11]),
+    );
+  }
+
+  @protected
+  PitchEvent dco_decode_pitch_event(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 12)
+      throw Exception('unexpected arr length: expect 12 but see ${arr.length}');
+    return PitchEvent(
+      samplePosition: dco_decode_u_64(arr[0]),
+      timeSec: dco_decode_f_64(arr[1]),
+      f0Hz: dco_decode_opt_box_autoadd_f_32(arr[2]),
+      midiFractional: dco_decode_opt_box_autoadd_f_32(arr[3]),
+      confidence: dco_decode_f_32(arr[4]),
+      voiced: dco_decode_bool(arr[5]),
+      rmsDbfs: dco_decode_f_32(arr[6]),
+      peakDbfs: dco_decode_f_32(arr[7]),
+      loudnessLufs: dco_decode_f_32(arr[8]),
+      isFinal: dco_decode_bool(arr[9]),
+      speechProb: dco_decode_opt_box_autoadd_f_32(arr[10]),
+      noiseFloorDbfs: dco_decode_opt_box_autoadd_f_32(arr[11]),
+    );
+  }
+
+  @protected
+  PitchStreamOptions dco_decode_pitch_stream_options(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return PitchStreamOptions(
+      onlyOnChange: dco_decode_bool(arr[0]),
+      minChangeCents: dco_decode_f_32(arr[1]),
+      maxEventsPerSec: dco_decode_u_32(arr[2]),
+      finalOnly: dco_decode_bool(arr[3]),
+    );
+  }
+
+  @protected
+  PyinError dco_decode_pyin_error(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    switch (raw[0]) {
+      case 0:
+        return PyinError_InvalidConfig(dco_decode_String(raw[1]));
+      default:
+        throw Exception("unreachable");
+    }
+  }
+
+  @protected
+  ScoreEvent dco_decode_score_event(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 8)
+      throw Exception('unexpected arr length: expect 8 but see ${arr.length}');
+    return ScoreEvent(
+      kind: dco_decode_score_event_kind(arr[0]),
+      songTimeSec: dco_decode_f_64(arr[1]),
+      track: dco_decode_u_32(arr[2]),
+      line: dco_decode_u_32(arr[3]),
+      note: dco_decode_opt_box_autoadd_u_32(arr[4]),
+      points: dco_decode_f_64(arr[5]),
+      totalScore: dco_decode_u_32(arr[6]),
+      goldenStreak: dco_decode_u_32(arr[7]),
+    );
+  }
+
+  @protected
+  ScoreEventKind dco_decode_score_event_kind(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return ScoreEventKind.values[raw as int];
+  }
+
+  @protected
+  ScorePriorConfig dco_decode_score_prior_config(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return ScorePriorConfig(
+      songStartSec: dco_decode_f_64(arr[0]),
+      strength: dco_decode_f_32(arr[1]),
+      sigmaCents: dco_decode_f_32(arr[2]),
+      octaveWeight: dco_decode_f_32(arr[3]),
+    );
+  }
+
+  @protected
+  ScoreStreamOptions dco_decode_score_stream_options(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 5)
+      throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+    return ScoreStreamOptions(
+      track: dco_decode_u_32(arr[0]),
+      harmony: dco_decode_bool(arr[1]),
+      difficulty: dco_decode_difficulty(arr[2]),
+      lineBonus: dco_decode_bool(arr[3]),
+      songStartSec: dco_decode_f_64(arr[4]),
+    );
+  }
+
+  @protected
+  ScoreTotals dco_decode_score_totals(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return ScoreTotals(
+      notes: dco_decode_f_64(arr[0]),
+      golden: dco_decode_f_64(arr[1]),
+      lineBonus: dco_decode_f_64(arr[2]),
+    );
+  }
+
+  @protected
+  SingerOptions dco_decode_singer_options(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 6)
+      throw Exception('unexpected arr length: expect 6 but see ${arr.length}');
+    return SingerOptions(
+      name: dco_decode_String(arr[0]),
+      track: dco_decode_u_32(arr[1]),
+      harmony: dco_decode_bool(arr[2]),
+      difficulty: dco_decode_difficulty(arr[3]),
+      lineBonus: dco_decode_bool(arr[4]),
+      streamStartSec: dco_decode_f_64(arr[5]),
+    );
+  }
+
+  @protected
+  SingerResult dco_decode_singer_result(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return SingerResult(
+      name: dco_decode_String(arr[0]),
+      track: dco_decode_usize(arr[1]),
+      totals: dco_decode_score_totals(arr[2]),
+      score: dco_decode_u_32(arr[3]),
+    );
+  }
+
+  @protected
+  TargetNote dco_decode_target_note(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return TargetNote(
+      startSec: dco_decode_f_64(arr[0]),
+      endSec: dco_decode_f_64(arr[1]),
+      midi: dco_decode_f_32(arr[2]),
+    );
+  }
+
+  @protected
+  int dco_decode_u_16(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as int;
+  }
+
+  @protected
+  int dco_decode_u_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as int;
+  }
+
+  @protected
+  BigInt dco_decode_u_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dcoDecodeU64(raw);
+  }
+
+  @protected
+  int dco_decode_u_8(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as int;
+  }
+
+  @protected
+  void dco_decode_unit(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return;
+  }
+
+  @protected
+  BigInt dco_decode_usize(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dcoDecodeU64(raw);
+  }
+
+  @protected
+  AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_String(deserializer);
+    return AnyhowException(inner);
+  }
+
+  @protected
+  AudioAnalyzer
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  Party
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return PartyImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  PyinProcessor
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return PyinProcessorImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  AudioAnalyzer
+  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  Party
+  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return PartyImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  PyinProcessor
+  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return PyinProcessorImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  AudioAnalyzer
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  Party
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return PartyImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  AudioAnalyzer
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return AudioAnalyzerImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  Party
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return PartyImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  PyinProcessor
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return PyinProcessorImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  RustStreamSink<FileAnalysisUpdate>
+  sse_decode_StreamSink_file_analysis_update_Sse(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    throw UnimplementedError('Unreachable ()');
+  }
+
+  @protected
+  RustStreamSink<PitchEvent> sse_decode_StreamSink_pitch_event_Sse(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    throw UnimplementedError('Unreachable ()');
+  }
+
+  @protected
+  RustStreamSink<ScoreEvent> sse_decode_StreamSink_score_event_Sse(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    throw UnimplementedError('Unreachable ()');
+  }
+
+  @protected
+  String sse_decode_String(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_list_prim_u_8_strict(deserializer);
+    return utf8.decoder.convert(inner);
+  }
+
+  @protected
+  AudioFingerprint sse_decode_audio_fingerprint(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_path = sse_decode_String(deserializer);
+    var var_fingerprint = sse_decode_String(deserializer);
+    var var_durationSecs = sse_decode_f_64(deserializer);
+    return AudioFingerprint(
+      path: var_path,
+      fingerprint: var_fingerprint,
+      durationSecs: var_durationSecs,
+    );
+  }
+
+  @protected
+  BleedConfig sse_decode_bleed_config(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_delaySec = sse_decode_f_64(deserializer);
+    var var_filterMs = sse_decode_f_32(deserializer);
+    var var_stepSize = sse_decode_f_32(deserializer);
+    return BleedConfig(
+      delaySec: var_delaySec,
+      filterMs: var_filterMs,
+      stepSize: var_stepSize,
+    );
+  }
+
+  @protected
+  bool sse_decode_bool(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getUint8() != 0;
+  }
+
+  @protected
+  BleedConfig sse_decode_box_autoadd_bleed_config(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_bleed_config(deserializer));
+  }
+
+  @protected
+  double sse_decode_box_autoadd_f_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_f_32(deserializer));
+  }
+
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_f_64(deserializer));
+  }
+
+  @protected
+  FileAnalysisOptions sse_decode_box_autoadd_file_analysis_options(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_file_analysis_options(deserializer));
+  }
+
+  @protected
+  FileContour sse_decode_box_autoadd_file_contour(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_file_contour(deserializer));
+  }
+
+  @protected
+  LevelReading sse_decode_box_autoadd_level_reading(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_level_reading(deserializer));
+  }
+
+  @protected
+  PitchConfig sse_decode_box_autoadd_pitch_config(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_pitch_config(deserializer));
+  }
+
+  @protected
+  PitchStreamOptions sse_decode_box_autoadd_pitch_stream_options(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_pitch_stream_options(deserializer));
+  }
+
+  @protected
+  ScorePriorConfig sse_decode_box_autoadd_score_prior_config(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_score_prior_config(deserializer));
+  }
+
+  @protected
+  ScoreStreamOptions sse_decode_box_autoadd_score_stream_options(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_score_stream_options(deserializer));
+  }
+
+  @protected
+  SingerOptions sse_decode_box_autoadd_singer_options(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_singer_options(deserializer));
+  }
+
+  @protected
+  int sse_decode_box_autoadd_u_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_u_32(deserializer));
+  }
+
+  @protected
+  ChannelMix sse_decode_channel_mix(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var tag_ = sse_decode_i_32(deserializer);
+    switch (tag_) {
+      case 0:
+        return ChannelMix_Downmix();
+      case 1:
+        var var_field0 = sse_decode_u_16(deserializer);
+        return ChannelMix_Pick(var_field0);
+      default:
+        throw UnimplementedError('');
+    }
+  }
+
+  @protected
+  Difficulty sse_decode_difficulty(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return Difficulty.values[inner];
+  }
+
+  @protected
+  double sse_decode_f_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getFloat32();
+  }
+
+  @protected
+  double sse_decode_f_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getFloat64();
+  }
+
+  @protected
+  FileAnalysisOptions sse_decode_file_analysis_options(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_analysisSampleRateHz = sse_decode_u_32(deserializer);
+    var var_updateIntervalMs = sse_decode_u_32(deserializer);
+    var var_windowSizeMs = sse_decode_u_32(deserializer);
+    var var_referenceA4Hz = sse_decode_f_32(deserializer);
+    var var_startSec = sse_decode_f_64(deserializer);
+    var var_endSec = sse_decode_opt_box_autoadd_f_64(deserializer);
+    return FileAnalysisOptions(
+      analysisSampleRateHz: var_analysisSampleRateHz,
+      updateIntervalMs: var_updateIntervalMs,
+      windowSizeMs: var_windowSizeMs,
+      referenceA4Hz: var_referenceA4Hz,
+      startSec: var_startSec,
+      endSec: var_endSec,
+    );
+  }
+
+  @protected
+  FileAnalysisUpdate sse_decode_file_analysis_update(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_processedSec = sse_decode_f_64(deserializer);
+    var var_totalSec = sse_decode_opt_box_autoadd_f_64(deserializer);
+    var var_contour = sse_decode_opt_box_autoadd_file_contour(deserializer);
+    return FileAnalysisUpdate(
+      processedSec: var_processedSec,
+      totalSec: var_totalSec,
+      contour: var_contour,
+    );
+  }
+
+  @protected
+  FileContour sse_decode_file_contour(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_sourceSampleRateHz = sse_decode_u_32(deserializer);
+    var var_durationSec = sse_decode_f_64(deserializer);
+    var var_events = sse_decode_list_pitch_event(deserializer);
+    return FileContour(
+      sourceSampleRateHz: var_sourceSampleRateHz,
+      durationSec: var_durationSec,
+      events: var_events,
+    );
+  }
+
+  @protected
+  LevelReading sse_decode_level_reading(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_rmsDbfs = sse_decode_f_32(deserializer);
+    var var_peakDbfs = sse_decode_f_32(deserializer);
+    var var_momentaryLufs = sse_decode_f_32(deserializer);
+    var var_shortTermLufs = sse_decode_f_32(deserializer);
+    var var_clippedSamples = sse_decode_u_32(deserializer);
+    return LevelReading(
+      rmsDbfs: var_rmsDbfs,
+      peakDbfs: var_peakDbfs,
+      momentaryLufs: var_momentaryLufs,
+      shortTermLufs: var_shortTermLufs,
+      clippedSamples: var_clippedSamples,
+    );
+  }
+
+  @protected
+  List<String> sse_decode_list_String(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <String>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_String(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<AudioFingerprint> sse_decode_list_audio_fingerprint(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <AudioFingerprint>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_audio_fingerprint(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<PitchEvent> sse_decode_list_pitch_event(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <PitchEvent>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_pitch_event(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  Float32List sse_decode_list_prim_f_32_strict(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var len_ = sse_decode_i_32(deserializer);
+    return deserializer.buffer.getFloat32List(len_);
+  }
+
+  @protected
+  List<int> sse_decode_list_prim_u_8_loose(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var len_ = sse_decode_i_32(deserializer);
+    return deserializer.buffer.getUint8List(len_);
+  }
+
+  @protected
+  Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var len_ = sse_decode_i_32(deserializer);
+    return deserializer.buffer.getUint8List(len_);
+  }
+
+  @protected
+  List<ScoreEvent> sse_decode_list_score_event(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <ScoreEvent>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_score_event(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<SingerResult> sse_decode_list_singer_result(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <SingerResult>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_singer_result(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<TargetNote> sse_decode_list_target_note(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <TargetNote>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_target_note(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  double? sse_decode_opt_box_autoadd_f_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_f_32(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_f_64(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  FileContour? sse_decode_opt_box_autoadd_file_contour(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_file_contour(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  LevelReading? sse_decode_opt_box_autoadd_level_reading(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_level_reading(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_u_32(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  PcmFormat sse_decode_pcm_format(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return PcmFormat.values[inner];
+  }
+
+  @protected
+  PitchAlgorithm sse_decode_pitch_algorithm(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return PitchAlgorithm.values[inner];
+  }
+
+  @protected
+  PitchConfig sse_decode_pitch_config(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_sampleRateHz = sse_decode_u_32(deserializer);
+    var var_updateIntervalMs = sse_decode_u_32(deserializer);
+    var var_windowSizeMs = sse_decode_u_32(deserializer);
+    var var_referenceA4Hz = sse_decode_f_32(deserializer);
+    var var_pcmFormat = sse_decode_pcm_format(deserializer);
+    var var_channels = sse_decode_u_16(deserializer);
+    var var_channelMix = sse_decode_channel_mix(deserializer);
+    var var_analysisSampleRateHz = sse_decode_opt_box_autoadd_u_32(
+      deserializer,
+    );
+    var var_vadEnabled = sse_decode_bool(deserializer);
+    var var_octaveCorrectionEnabled = sse_decode_bool(deserializer);
+    var var_algorithm = sse_decode_pitch_algorithm(deserializer);
+    var var_multiResolutionEnabled = sse_decode_bool(deserializer);
+    return PitchConfig(
+      sampleRateHz: var_sampleRateHz,
+      updateIntervalMs: var_updateIntervalMs,
+      windowSizeMs: var_windowSizeMs,
+      referenceA4Hz: var_referenceA4Hz,
+      pcmFormat: var_pcmFormat,
+      channels: var_channels,
+      channelMix: var_channelMix,
+      analysisSampleRateHz: var_analysisSampleRateHz,
+      vadEnabled: var_vadEnabled,
+      octaveCorrectionEnabled: var_octaveCorrectionEnabled,
+      algorithm: var_algorithm,
+      multiResolutionEnabled: var_multiResolutionEnabled,
+    );
+  }
+
+  @protected
+  PitchEvent sse_decode_pitch_event(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_samplePosition = sse_decode_u_64(deserializer);
+    var var_timeSec = sse_decode_f_64(deserializer);
+    var var_f0Hz = sse_decode_opt_box_autoadd_f_32(deserializer);
+    var var_midiFractional = sse_decode_opt_box_autoadd_f_32(deserializer);
+    var var_confidence = sse_decode_f_32(deserializer);
+    var var_voiced = sse_decode_bool(deserializer);
+    var var_rmsDbfs = sse_decode_f_32(deserializer);
+    var var_peakDbfs = sse_decode_f_32(deserializer);
+    var var_loudnessLufs = sse_decode_f_32(deserializer);
+    var var_isFinal = sse_decode_bool(deserializer);
+    var var_speechProb = sse_decode_opt_box_autoadd_f_32(deserializer);
+    var var_noiseFloorDbfs = sse_decode_opt_box_autoadd_f_32(deserializer);
+    return PitchEvent(
+      samplePosition: var_samplePosition,
+      timeSec: var_timeSec,
+      f0Hz: var_f0Hz,
+      midiFractional: var_midiFractional,
+      confidence: var_confidence,
+      voiced: var_voiced,
+      rmsDbfs: var_rmsDbfs,
+      peakDbfs: var_peakDbfs,
+      loudnessLufs: var_loudnessLufs,
+      isFinal: var_isFinal,
+      speechProb: var_speechProb,
+      noiseFloorDbfs: var_noiseFloorDbfs,
+    );
+  }
 
-  RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_AudioAnalyzer => wire
-      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer;
+  @protected
+  PitchStreamOptions sse_decode_pitch_stream_options(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_onlyOnChange = sse_decode_bool(deserializer);
+    var var_minChangeCents = sse_decode_f_32(deserializer);
+    var var_maxEventsPerSec = sse_decode_u_32(deserializer);
+    var var_finalOnly = sse_decode_bool(deserializer);
+    return PitchStreamOptions(
+      onlyOnChange: var_onlyOnChange,
+      minChangeCents: var_minChangeCents,
+      maxEventsPerSec: var_maxEventsPerSec,
+      finalOnly: var_finalOnly,
+    );
+  }
 
-  RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_AudioAnalyzer => wire
-      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer;
+  @protected
+  PyinError sse_decode_pyin_error(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
 
-  RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_PyinProcessor => wire
-      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor;
+    var tag_ = sse_decode_i_32(deserializer);
+    switch (tag_) {
+      case 0:
+        var var_field0 = sse_decode_String(deserializer);
+        return PyinError_InvalidConfig(var_field0);
+      default:
+        throw UnimplementedError('');
+    }
+  }
 
-  RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_PyinProcessor => wire
-      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor;
+  @protected
+  ScoreEvent sse_decode_score_event(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_kind = sse_decode_score_event_kind(deserializer);
+    var var_songTimeSec = sse_decode_f_64(deserializer);
+    var var_track = sse_decode_u_32(deserializer);
+    var var_line = sse_decode_u_32(deserializer);
+    var var_note = sse_decode_opt_box_autoadd_u_32(deserializer);
+    var var_points = sse_decode_f_64(deserializer);
+    var var_totalScore = sse_decode_u_32(deserializer);
+    var var_goldenStreak = sse_decode_u_32(deserializer);
+    return ScoreEvent(
+      kind: var_kind,
+      songTimeSec: var_songTimeSec,
+      track: var_track,
+      line: var_line,
+      note: var_note,
+      points: var_points,
+      totalScore: var_totalScore,
+      goldenStreak: var_goldenStreak,
+    );
+  }
 
   @protected
-  AnyhowException dco_decode_AnyhowException(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return AnyhowException(raw as String);
+  ScoreEventKind sse_decode_score_event_kind(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return ScoreEventKind.values[inner];
   }
 
   @protected
-  AudioAnalyzer
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    dynamic raw,
-  ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return AudioAnalyzerImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  ScorePriorConfig sse_decode_score_prior_config(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_songStartSec = sse_decode_f_64(deserializer);
+    var var_strength = sse_decode_f_32(deserializer);
+    var var_sigmaCents = sse_decode_f_32(deserializer);
+    var var_octaveWeight = sse_decode_f_32(deserializer);
+    return ScorePriorConfig(
+      songStartSec: var_songStartSec,
+      strength: var_strength,
+      sigmaCents: var_sigmaCents,
+      octaveWeight: var_octaveWeight,
+    );
   }
 
   @protected
-  PyinProcessor
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    dynamic raw,
+  ScoreStreamOptions sse_decode_score_stream_options(
+    SseDeserializer deserializer,
   ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return PyinProcessorImpl.frbInternalDcoDecode(raw as List<dynamic>);
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_track = sse_decode_u_32(deserializer);
+    var var_harmony = sse_decode_bool(deserializer);
+    var var_difficulty = sse_decode_difficulty(deserializer);
+    var var_lineBonus = sse_decode_bool(deserializer);
+    var var_songStartSec = sse_decode_f_64(deserializer);
+    return ScoreStreamOptions(
+      track: var_track,
+      harmony: var_harmony,
+      difficulty: var_difficulty,
+      lineBonus: var_lineBonus,
+      songStartSec: var_songStartSec,
+    );
   }
 
   @protected
-  AudioAnalyzer
-  dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    dynamic raw,
-  ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return AudioAnalyzerImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  ScoreTotals sse_decode_score_totals(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_notes = sse_decode_f_64(deserializer);
+    var var_golden = sse_decode_f_64(deserializer);
+    var var_lineBonus = sse_decode_f_64(deserializer);
+    return ScoreTotals(
+      notes: var_notes,
+      golden: var_golden,
+      lineBonus: var_lineBonus,
+    );
   }
 
   @protected
-  PyinProcessor
-  dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    dynamic raw,
-  ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return PyinProcessorImpl.frbInternalDcoDecode(raw as List<dynamic>);
+  SingerOptions sse_decode_singer_options(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_name = sse_decode_String(deserializer);
+    var var_track = sse_decode_u_32(deserializer);
+    var var_harmony = sse_decode_bool(deserializer);
+    var var_difficulty = sse_decode_difficulty(deserializer);
+    var var_lineBonus = sse_decode_bool(deserializer);
+    var var_streamStartSec = sse_decode_f_64(deserializer);
+    return SingerOptions(
+      name: var_name,
+      track: var_track,
+      harmony: var_harmony,
+      difficulty: var_difficulty,
+      lineBonus: var_lineBonus,
+      streamStartSec: var_streamStartSec,
+    );
   }
 
   @protected
-  AudioAnalyzer
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    dynamic raw,
+  SingerResult sse_decode_singer_result(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_name = sse_decode_String(deserializer);
+    var var_track = sse_decode_usize(deserializer);
+    var var_totals = sse_decode_score_totals(deserializer);
+    var var_score = sse_decode_u_32(deserializer);
+    return SingerResult(
+      name: var_name,
+      track: var_track,
+      totals: var_totals,
+      score: var_score,
+    );
+  }
+
+  @protected
+  TargetNote sse_decode_target_note(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_startSec = sse_decode_f_64(deserializer);
+    var var_endSec = sse_decode_f_64(deserializer);
+    var var_midi = sse_decode_f_32(deserializer);
+    return TargetNote(
+      startSec: var_startSec,
+      endSec: var_endSec,
+      midi: var_midi,
+    );
+  }
+
+  @protected
+  int sse_decode_u_16(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getUint16();
+  }
+
+  @protected
+  int sse_decode_u_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getUint32();
+  }
+
+  @protected
+  BigInt sse_decode_u_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getBigUint64();
+  }
+
+  @protected
+  int sse_decode_u_8(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getUint8();
+  }
+
+  @protected
+  void sse_decode_unit(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+  }
+
+  @protected
+  BigInt sse_decode_usize(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getBigUint64();
+  }
+
+  @protected
+  int sse_decode_i_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getInt32();
+  }
+
+  @protected
+  void sse_encode_AnyhowException(
+    AnyhowException self,
+    SseSerializer serializer,
   ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return AudioAnalyzerImpl.frbInternalDcoDecode(raw as List<dynamic>);
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.message, serializer);
   }
 
   @protected
-  PyinProcessor
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    dynamic raw,
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    AudioAnalyzer self,
+    SseSerializer serializer,
   ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return PyinProcessorImpl.frbInternalDcoDecode(raw as List<dynamic>);
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as AudioAnalyzerImpl).frbInternalSseEncode(move: true),
+      serializer,
+    );
   }
 
   @protected
-  RustStreamSink<int> dco_decode_StreamSink_u_8_Sse(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    throw UnimplementedError();
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as PartyImpl).frbInternalSseEncode(move: true),
+      serializer,
+    );
   }
 
   @protected
-  String dco_decode_String(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as String;
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    PyinProcessor self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as PyinProcessorImpl).frbInternalSseEncode(move: true),
+      serializer,
+    );
   }
 
   @protected
-  AudioFingerprint dco_decode_audio_fingerprint(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    final arr = raw as List<dynamic>;
-    if (arr.length != 3)
-      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
-    return AudioFingerprint(
-      path: dco_decode_String(arr[0]),
-      fingerprint: dco_decode_String(arr[1]),
-      durationSecs: dco_decode_f_64(arr[2]),
+  void
+  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    AudioAnalyzer self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as AudioAnalyzerImpl).frbInternalSseEncode(move: false),
+      serializer,
     );
   }
 
   @protected
-  PitchConfig dco_decode_box_autoadd_pitch_config(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return dco_decode_pitch_config(raw);
+  void
+  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as PartyImpl).frbInternalSseEncode(move: false),
+      serializer,
+    );
   }
 
   @protected
-  double dco_decode_f_64(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as double;
+  void
+  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    PyinProcessor self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as PyinProcessorImpl).frbInternalSseEncode(move: false),
+      serializer,
+    );
   }
 
   @protected
-  List<String> dco_decode_list_String(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_String).toList();
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    AudioAnalyzer self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as AudioAnalyzerImpl).frbInternalSseEncode(move: false),
+      serializer,
+    );
   }
 
   @protected
-  List<AudioFingerprint> dco_decode_list_audio_fingerprint(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_audio_fingerprint).toList();
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as PartyImpl).frbInternalSseEncode(move: false),
+      serializer,
+    );
   }
 
   @protected
-  List<int> dco_decode_list_prim_u_8_loose(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as List<int>;
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    AudioAnalyzer self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as AudioAnalyzerImpl).frbInternalSseEncode(move: null),
+      serializer,
+    );
   }
 
   @protected
-  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as Uint8List;
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as PartyImpl).frbInternalSseEncode(move: null),
+      serializer,
+    );
   }
 
   @protected
-  PitchConfig dco_decode_pitch_config(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    final arr = raw as List<dynamic>;
-    if (arr.length != 3)
-      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
-    return PitchConfig(
-      sampleRateHz: dco_decode_u_32(arr[0]),
-      updateIntervalMs: dco_decode_u_32(arr[1]),
-      windowSizeMs: dco_decode_u_32(arr[2]),
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
+    PyinProcessor self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as PyinProcessorImpl).frbInternalSseEncode(move: null),
+      serializer,
     );
   }
 
   @protected
-  PyinError dco_decode_pyin_error(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    switch (raw[0]) {
-      case 0:
-        return PyinError_InvalidConfig(dco_decode_String(raw[1]));
-      default:
-        throw Exception("unreachable");
-    }
+  void sse_encode_StreamSink_file_analysis_update_Sse(
+    RustStreamSink<FileAnalysisUpdate> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(
+      self.setupAndSerialize(
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_file_analysis_update,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+      ),
+      serializer,
+    );
   }
 
   @protected
-  int dco_decode_u_16(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as int;
+  void sse_encode_StreamSink_pitch_event_Sse(
+    RustStreamSink<PitchEvent> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(
+      self.setupAndSerialize(
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_pitch_event,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+      ),
+      serializer,
+    );
   }
 
   @protected
-  int dco_decode_u_32(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as int;
+  void sse_encode_StreamSink_score_event_Sse(
+    RustStreamSink<ScoreEvent> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(
+      self.setupAndSerialize(
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_score_event,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+      ),
+      serializer,
+    );
   }
 
   @protected
-  int dco_decode_u_8(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as int;
+  void sse_encode_String(String self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_list_prim_u_8_strict(utf8.encoder.convert(self), serializer);
   }
 
   @protected
-  void dco_decode_unit(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return;
+  void sse_encode_audio_fingerprint(
+    AudioFingerprint self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.path, serializer);
+    sse_encode_String(self.fingerprint, serializer);
+    sse_encode_f_64(self.durationSecs, serializer);
   }
 
   @protected
-  BigInt dco_decode_usize(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return dcoDecodeU64(raw);
+  void sse_encode_bleed_config(BleedConfig self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_f_64(self.delaySec, serializer);
+    sse_encode_f_32(self.filterMs, serializer);
+    sse_encode_f_32(self.stepSize, serializer);
   }
 
   @protected
-  AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer) {
+  void sse_encode_bool(bool self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    var inner = sse_decode_String(deserializer);
-    return AnyhowException(inner);
+    serializer.buffer.putUint8(self ? 1 : 0);
   }
 
   @protected
-  AudioAnalyzer
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    SseDeserializer deserializer,
+  void sse_encode_box_autoadd_bleed_config(
+    BleedConfig self,
+    SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return AudioAnalyzerImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
+    sse_encode_bleed_config(self, serializer);
   }
 
   @protected
-  PyinProcessor
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    SseDeserializer deserializer,
-  ) {
+  void sse_encode_box_autoadd_f_32(double self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return PyinProcessorImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
+    sse_encode_f_32(self, serializer);
   }
 
   @protected
-  AudioAnalyzer
-  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    SseDeserializer deserializer,
-  ) {
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return AudioAnalyzerImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
+    sse_encode_f_64(self, serializer);
   }
 
   @protected
-  PyinProcessor
-  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    SseDeserializer deserializer,
+  void sse_encode_box_autoadd_file_analysis_options(
+    FileAnalysisOptions self,
+    SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return PyinProcessorImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
+    sse_encode_file_analysis_options(self, serializer);
   }
 
   @protected
-  AudioAnalyzer
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    SseDeserializer deserializer,
+  void sse_encode_box_autoadd_file_contour(
+    FileContour self,
+    SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return AudioAnalyzerImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
+    sse_encode_file_contour(self, serializer);
   }
 
   @protected
-  PyinProcessor
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    SseDeserializer deserializer,
+  void sse_encode_box_autoadd_level_reading(
+    LevelReading self,
+    SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return PyinProcessorImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
+    sse_encode_level_reading(self, serializer);
   }
 
   @protected
-  RustStreamSink<int> sse_decode_StreamSink_u_8_Sse(
-    SseDeserializer deserializer,
+  void sse_encode_box_autoadd_pitch_config(
+    PitchConfig self,
+    SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    throw UnimplementedError('Unreachable ()');
+    sse_encode_pitch_config(self, serializer);
   }
 
   @protected
-  String sse_decode_String(SseDeserializer deserializer) {
+  void sse_encode_box_autoadd_pitch_stream_options(
+    PitchStreamOptions self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    var inner = sse_decode_list_prim_u_8_strict(deserializer);
-    return utf8.decoder.convert(inner);
+    sse_encode_pitch_stream_options(self, serializer);
   }
 
   @protected
-  AudioFingerprint sse_decode_audio_fingerprint(SseDeserializer deserializer) {
+  void sse_encode_box_autoadd_score_prior_config(
+    ScorePriorConfig self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    var var_path = sse_decode_String(deserializer);
-    var var_fingerprint = sse_decode_String(deserializer);
-    var var_durationSecs = sse_decode_f_64(deserializer);
-    return AudioFingerprint(
-      path: var_path,
-      fingerprint: var_fingerprint,
-      durationSecs: var_durationSecs,
-    );
+    sse_encode_score_prior_config(self, serializer);
   }
 
   @protected
-  PitchConfig sse_decode_box_autoadd_pitch_config(
-    SseDeserializer deserializer,
+  void sse_encode_box_autoadd_score_stream_options(
+    ScoreStreamOptions self,
+    SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return (sse_decode_pitch_config(deserializer));
+    sse_encode_score_stream_options(self, serializer);
   }
 
   @protected
-  double sse_decode_f_64(SseDeserializer deserializer) {
+  void sse_encode_box_autoadd_singer_options(
+    SingerOptions self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getFloat64();
+    sse_encode_singer_options(self, serializer);
   }
 
   @protected
-  List<String> sse_decode_list_String(SseDeserializer deserializer) {
+  void sse_encode_box_autoadd_u_32(int self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-
-    var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <String>[];
-    for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(sse_decode_String(deserializer));
-    }
-    return ans_;
+    sse_encode_u_32(self, serializer);
   }
 
   @protected
-  List<AudioFingerprint> sse_decode_list_audio_fingerprint(
-    SseDeserializer deserializer,
-  ) {
+  void sse_encode_channel_mix(ChannelMix self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-
-    var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <AudioFingerprint>[];
-    for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(sse_decode_audio_fingerprint(deserializer));
+    switch (self) {
+      case ChannelMix_Downmix():
+        sse_encode_i_32(0, serializer);
+      case ChannelMix_Pick(field0: final field0):
+        sse_encode_i_32(1, serializer);
+        sse_encode_u_16(field0, serializer);
     }
-    return ans_;
-  }
-
-  @protected
-  List<int> sse_decode_list_prim_u_8_loose(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    var len_ = sse_decode_i_32(deserializer);
-    return deserializer.buffer.getUint8List(len_);
   }
 
   @protected
-  Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer) {
+  void sse_encode_difficulty(Difficulty self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    var len_ = sse_decode_i_32(deserializer);
-    return deserializer.buffer.getUint8List(len_);
+    sse_encode_i_32(self.index, serializer);
   }
 
   @protected
-  PitchConfig sse_decode_pitch_config(SseDeserializer deserializer) {
+  void sse_encode_f_32(double self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    var var_sampleRateHz = sse_decode_u_32(deserializer);
-    var var_updateIntervalMs = sse_decode_u_32(deserializer);
-    var var_windowSizeMs = sse_decode_u_32(deserializer);
-    return PitchConfig(
-      sampleRateHz: var_sampleRateHz,
-      updateIntervalMs: var_updateIntervalMs,
-      windowSizeMs: var_windowSizeMs,
-    );
+    serializer.buffer.putFloat32(self);
   }
 
   @protected
-  PyinError sse_decode_pyin_error(SseDeserializer deserializer) {
+  void sse_encode_f_64(double self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-
-    var tag_ = sse_decode_i_32(deserializer);
-    switch (tag_) {
-      case 0:
-        var var_field0 = sse_decode_String(deserializer);
-        return PyinError_InvalidConfig(var_field0);
-      default:
-        throw UnimplementedError('');
-    }
+    serializer.buffer.putFloat64(self);
   }
 
   @protected
-  int sse_decode_u_16(SseDeserializer deserializer) {
+  void sse_encode_file_analysis_options(
+    FileAnalysisOptions self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getUint16();
+    sse_encode_u_32(self.analysisSampleRateHz, serializer);
+    sse_encode_u_32(self.updateIntervalMs, serializer);
+    sse_encode_u_32(self.windowSizeMs, serializer);
+    sse_encode_f_32(self.referenceA4Hz, serializer);
+    sse_encode_f_64(self.startSec, serializer);
+    sse_encode_opt_box_autoadd_f_64(self.endSec, serializer);
   }
 
   @protected
-  int sse_decode_u_32(SseDeserializer deserializer) {
+  void sse_encode_file_analysis_update(
+    FileAnalysisUpdate self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getUint32();
+    sse_encode_f_64(self.processedSec, serializer);
+    sse_encode_opt_box_autoadd_f_64(self.totalSec, serializer);
+    sse_encode_opt_box_autoadd_file_contour(self.contour, serializer);
   }
 
   @protected
-  int sse_decode_u_8(SseDeserializer deserializer) {
+  void sse_encode_file_contour(FileContour self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getUint8();
+    sse_encode_u_32(self.sourceSampleRateHz, serializer);
+    sse_encode_f_64(self.durationSec, serializer);
+    sse_encode_list_pitch_event(self.events, serializer);
   }
 
   @protected
-  void sse_decode_unit(SseDeserializer deserializer) {
+  void sse_encode_level_reading(LevelReading self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_f_32(self.rmsDbfs, serializer);
+    sse_encode_f_32(self.peakDbfs, serializer);
+    sse_encode_f_32(self.momentaryLufs, serializer);
+    sse_encode_f_32(self.shortTermLufs, serializer);
+    sse_encode_u_32(self.clippedSamples, serializer);
   }
 
   @protected
-  BigInt sse_decode_usize(SseDeserializer deserializer) {
+  void sse_encode_list_String(List<String> self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getBigUint64();
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_String(item, serializer);
+    }
   }
 
   @protected
-  int sse_decode_i_32(SseDeserializer deserializer) {
+  void sse_encode_list_audio_fingerprint(
+    List<AudioFingerprint> self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getInt32();
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_audio_fingerprint(item, serializer);
+    }
   }
 
   @protected
-  bool sse_decode_bool(SseDeserializer deserializer) {
+  void sse_encode_list_pitch_event(
+    List<PitchEvent> self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getUint8() != 0;
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_pitch_event(item, serializer);
+    }
   }
 
   @protected
-  void sse_encode_AnyhowException(
-    AnyhowException self,
+  void sse_encode_list_prim_f_32_strict(
+    Float32List self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_String(self.message, serializer);
+    sse_encode_i_32(self.length, serializer);
+    serializer.buffer.putFloat32List(self);
   }
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    AudioAnalyzer self,
+  void sse_encode_list_prim_u_8_loose(
+    List<int> self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_usize(
-      (self as AudioAnalyzerImpl).frbInternalSseEncode(move: true),
-      serializer,
+    sse_encode_i_32(self.length, serializer);
+    serializer.buffer.putUint8List(
+      self is Uint8List ? self : Uint8List.fromList(self),
     );
   }
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    PyinProcessor self,
+  void sse_encode_list_prim_u_8_strict(
+    Uint8List self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_usize(
-      (self as PyinProcessorImpl).frbInternalSseEncode(move: true),
-      serializer,
-    );
+    sse_encode_i_32(self.length, serializer);
+    serializer.buffer.putUint8List(self);
   }
 
   @protected
-  void
-  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    AudioAnalyzer self,
+  void sse_encode_list_score_event(
+    List<ScoreEvent> self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_usize(
-      (self as AudioAnalyzerImpl).frbInternalSseEncode(move: false),
-      serializer,
-    );
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_score_event(item, serializer);
+    }
   }
 
   @protected
-  void
-  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    PyinProcessor self,
+  void sse_encode_list_singer_result(
+    List<SingerResult> self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_usize(
-      (self as PyinProcessorImpl).frbInternalSseEncode(move: false),
-      serializer,
-    );
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_singer_result(item, serializer);
+    }
   }
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
-    AudioAnalyzer self,
+  void sse_encode_list_target_note(
+    List<TargetNote> self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_usize(
-      (self as AudioAnalyzerImpl).frbInternalSseEncode(move: null),
-      serializer,
-    );
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_target_note(item, serializer);
+    }
   }
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
-    PyinProcessor self,
+  void sse_encode_opt_box_autoadd_f_32(double? self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_f_32(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_f_64(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_opt_box_autoadd_file_contour(
+    FileContour? self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_usize(
-      (self as PyinProcessorImpl).frbInternalSseEncode(move: null),
-      serializer,
-    );
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_file_contour(self, serializer);
+    }
   }
 
   @protected
-  void sse_encode_StreamSink_u_8_Sse(
-    RustStreamSink<int> self,
+  void sse_encode_opt_box_autoadd_level_reading(
+    LevelReading? self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_String(
-      self.setupAndSerialize(
-        codec: SseCodec(
-          decodeSuccessData: sse_decode_u_8,
-          decodeErrorData: sse_decode_AnyhowException,
-        ),
-      ),
-      serializer,
-    );
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_level_reading(self, serializer);
+    }
   }
 
   @protected
-  void sse_encode_String(String self, SseSerializer serializer) {
+  void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_list_prim_u_8_strict(utf8.encoder.convert(self), serializer);
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_u_32(self, serializer);
+    }
   }
 
   @protected
-  void sse_encode_audio_fingerprint(
-    AudioFingerprint self,
+  void sse_encode_pcm_format(PcmFormat self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.index, serializer);
+  }
+
+  @protected
+  void sse_encode_pitch_algorithm(
+    PitchAlgorithm self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_String(self.path, serializer);
-    sse_encode_String(self.fingerprint, serializer);
-    sse_encode_f_64(self.durationSecs, serializer);
+    sse_encode_i_32(self.index, serializer);
   }
 
   @protected
-  void sse_encode_box_autoadd_pitch_config(
-    PitchConfig self,
+  void sse_encode_pitch_config(PitchConfig self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_u_32(self.sampleRateHz, serializer);
+    sse_encode_u_32(self.updateIntervalMs, serializer);
+    sse_encode_u_32(self.windowSizeMs, serializer);
+    sse_encode_f_32(self.referenceA4Hz, serializer);
+    sse_encode_pcm_format(self.pcmFormat, serializer);
+    sse_encode_u_16(self.channels, serializer);
+    sse_encode_channel_mix(self.channelMix, serializer);
+    sse_encode_opt_box_autoadd_u_32(self.analysisSampleRateHz, serializer);
+    sse_encode_bool(self.vadEnabled, serializer);
+    sse_encode_bool(self.octaveCorrectionEnabled, serializer);
+    sse_encode_pitch_algorithm(self.algorithm, serializer);
+    sse_encode_bool(self.multiResolutionEnabled, serializer);
+  }
+
+  @protected
+  void sse_encode_pitch_event(PitchEvent self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_u_64(self.samplePosition, serializer);
+    sse_encode_f_64(self.timeSec, serializer);
+    sse_encode_opt_box_autoadd_f_32(self.f0Hz, serializer);
+    sse_encode_opt_box_autoadd_f_32(self.midiFractional, serializer);
+    sse_encode_f_32(self.confidence, serializer);
+    sse_encode_bool(self.voiced, serializer);
+    sse_encode_f_32(self.rmsDbfs, serializer);
+    sse_encode_f_32(self.peakDbfs, serializer);
+    sse_encode_f_32(self.loudnessLufs, serializer);
+    sse_encode_bool(self.isFinal, serializer);
+    sse_encode_opt_box_autoadd_f_32(self.speechProb, serializer);
+    sse_encode_opt_box_autoadd_f_32(self.noiseFloorDbfs, serializer);
+  }
+
+  @protected
+  void sse_encode_pitch_stream_options(
+    PitchStreamOptions self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_pitch_config(self, serializer);
+    sse_encode_bool(self.onlyOnChange, serializer);
+    sse_encode_f_32(self.minChangeCents, serializer);
+    sse_encode_u_32(self.maxEventsPerSec, serializer);
+    sse_encode_bool(self.finalOnly, serializer);
   }
 
   @protected
-  void sse_encode_f_64(double self, SseSerializer serializer) {
+  void sse_encode_pyin_error(PyinError self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putFloat64(self);
+    switch (self) {
+      case PyinError_InvalidConfig(field0: final field0):
+        sse_encode_i_32(0, serializer);
+        sse_encode_String(field0, serializer);
+    }
   }
 
   @protected
-  void sse_encode_list_String(List<String> self, SseSerializer serializer) {
+  void sse_encode_score_event(ScoreEvent self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    for (final item in self) {
-      sse_encode_String(item, serializer);
-    }
+    sse_encode_score_event_kind(self.kind, serializer);
+    sse_encode_f_64(self.songTimeSec, serializer);
+    sse_encode_u_32(self.track, serializer);
+    sse_encode_u_32(self.line, serializer);
+    sse_encode_opt_box_autoadd_u_32(self.note, serializer);
+    sse_encode_f_64(self.points, serializer);
+    sse_encode_u_32(self.totalScore, serializer);
+    sse_encode_u_32(self.goldenStreak, serializer);
   }
 
   @protected
-  void sse_encode_list_audio_fingerprint(
-    List<AudioFingerprint> self,
+  void sse_encode_score_event_kind(
+    ScoreEventKind self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    for (final item in self) {
-      sse_encode_audio_fingerprint(item, serializer);
-    }
+    sse_encode_i_32(self.index, serializer);
   }
 
   @protected
-  void sse_encode_list_prim_u_8_loose(
-    List<int> self,
+  void sse_encode_score_prior_config(
+    ScorePriorConfig self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    serializer.buffer.putUint8List(
-      self is Uint8List ? self : Uint8List.fromList(self),
-    );
+    sse_encode_f_64(self.songStartSec, serializer);
+    sse_encode_f_32(self.strength, serializer);
+    sse_encode_f_32(self.sigmaCents, serializer);
+    sse_encode_f_32(self.octaveWeight, serializer);
   }
 
   @protected
-  void sse_encode_list_prim_u_8_strict(
-    Uint8List self,
+  void sse_encode_score_stream_options(
+    ScoreStreamOptions self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    serializer.buffer.putUint8List(self);
+    sse_encode_u_32(self.track, serializer);
+    sse_encode_bool(self.harmony, serializer);
+    sse_encode_difficulty(self.difficulty, serializer);
+    sse_encode_bool(self.lineBonus, serializer);
+    sse_encode_f_64(self.songStartSec, serializer);
   }
 
   @protected
-  void sse_encode_pitch_config(PitchConfig self, SseSerializer serializer) {
+  void sse_encode_score_totals(ScoreTotals self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_u_32(self.sampleRateHz, serializer);
-    sse_encode_u_32(self.updateIntervalMs, serializer);
-    sse_encode_u_32(self.windowSizeMs, serializer);
+    sse_encode_f_64(self.notes, serializer);
+    sse_encode_f_64(self.golden, serializer);
+    sse_encode_f_64(self.lineBonus, serializer);
   }
 
   @protected
-  void sse_encode_pyin_error(PyinError self, SseSerializer serializer) {
+  void sse_encode_singer_options(SingerOptions self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    switch (self) {
-      case PyinError_InvalidConfig(field0: final field0):
-        sse_encode_i_32(0, serializer);
-        sse_encode_String(field0, serializer);
-    }
+    sse_encode_String(self.name, serializer);
+    sse_encode_u_32(self.track, serializer);
+    sse_encode_bool(self.harmony, serializer);
+    sse_encode_difficulty(self.difficulty, serializer);
+    sse_encode_bool(self.lineBonus, serializer);
+    sse_encode_f_64(self.streamStartSec, serializer);
+  }
+
+  @protected
+  void sse_encode_singer_result(SingerResult self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.name, serializer);
+    sse_encode_usize(self.track, serializer);
+    sse_encode_score_totals(self.totals, serializer);
+    sse_encode_u_32(self.score, serializer);
+  }
+
+  @protected
+  void sse_encode_target_note(TargetNote self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_f_64(self.startSec, serializer);
+    sse_encode_f_64(self.endSec, serializer);
+    sse_encode_f_32(self.midi, serializer);
   }
 
   @protected
@@ -1050,6 +3736,12 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     serializer.buffer.putUint32(self);
   }
 
+  @protected
+  void sse_encode_u_64(BigInt self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    serializer.buffer.putBigUint64(self);
+  }
+
   @protected
   void sse_encode_u_8(int self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -1072,12 +3764,6 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     // Codec=Sse (Serialization based), see doc to use other codecs
     serializer.buffer.putInt32(self);
   }
-
-  @protected
-  void sse_encode_bool(bool self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putUint8(self ? 1 : 0);
-  }
 }
 
 @sealed
@@ -1099,20 +3785,181 @@ class AudioAnalyzerImpl extends RustOpaque implements AudioAnalyzer {
         RustLib.instance.api.rust_arc_decrement_strong_count_AudioAnalyzerPtr,
   );
 
-  Stream<int> createStream() =>
-      RustLib.instance.api.cratePyinApiAudioAnalyzerCreateStream(that: this);
+  Future<void> clearBackingTrack() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerClearBackingTrack(
+        that: this,
+      );
+
+  Future<void> clearTargetNotes() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerClearTargetNotes(
+        that: this,
+      );
+
+  Stream<ScoreEvent> createScoreStream({
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  }) => RustLib.instance.api.cratePyinApiAudioAnalyzerCreateScoreStream(
+    that: this,
+    chart: chart,
+    options: options,
+  );
+
+  Stream<PitchEvent> createStream({required PitchStreamOptions options}) =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerCreateStream(
+        that: this,
+        options: options,
+      );
+
+  Future<List<ScoreEvent>> drainScoreEvents() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerDrainScoreEvents(
+        that: this,
+      );
+
+  Future<void> finish() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerFinish(that: this);
+
+  Future<List<PitchEvent>> finishCollectEvents() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerFinishCollectEvents(
+        that: this,
+      );
+
+  Future<void> finishScoring() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerFinishScoring(that: this);
 
-  Future<void> processChunk({required List<int> pcm16LeBytes}) =>
+  Future<LevelReading?> latestLevel() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerLatestLevel(that: this);
+
+  Future<void> processChunk({required List<int> pcmBytes}) =>
       RustLib.instance.api.cratePyinApiAudioAnalyzerProcessChunk(
         that: this,
-        pcm16LeBytes: pcm16LeBytes,
+        pcmBytes: pcmBytes,
       );
 
-  Future<Uint8List> processChunkCollect({required List<int> pcm16LeBytes}) =>
+  Future<Uint8List> processChunkCollect({required List<int> pcmBytes}) =>
       RustLib.instance.api.cratePyinApiAudioAnalyzerProcessChunkCollect(
         that: this,
-        pcm16LeBytes: pcm16LeBytes,
+        pcmBytes: pcmBytes,
+      );
+
+  Future<List<PitchEvent>> processChunkCollectEvents({
+    required List<int> pcmBytes,
+  }) => RustLib.instance.api.cratePyinApiAudioAnalyzerProcessChunkCollectEvents(
+    that: this,
+    pcmBytes: pcmBytes,
+  );
+
+  Future<Float32List> processChunkCollectFractional({
+    required List<int> pcmBytes,
+  }) =>
+      RustLib.instance.api
+          .cratePyinApiAudioAnalyzerProcessChunkCollectFractional(
+            that: this,
+            pcmBytes: pcmBytes,
+          );
+
+  Future<void> restore({required List<int> blob}) =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerRestore(
+        that: this,
+        blob: blob,
+      );
+
+  Future<void> setBackingTrackDelay({required double delaySec}) =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerSetBackingTrackDelay(
+        that: this,
+        delaySec: delaySec,
+      );
+
+  Future<void> setBackingTrackFile({
+    required String path,
+    required BleedConfig config,
+  }) => RustLib.instance.api.cratePyinApiAudioAnalyzerSetBackingTrackFile(
+    that: this,
+    path: path,
+    config: config,
+  );
+
+  Future<void> setSongStart({required double songStartSec}) =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerSetSongStart(
+        that: this,
+        songStartSec: songStartSec,
       );
+
+  Future<void> setStreamOptions({required PitchStreamOptions options}) =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerSetStreamOptions(
+        that: this,
+        options: options,
+      );
+
+  Future<void> setTargetNotes({
+    required List<TargetNote> notes,
+    required ScorePriorConfig config,
+  }) => RustLib.instance.api.cratePyinApiAudioAnalyzerSetTargetNotes(
+    that: this,
+    notes: notes,
+    config: config,
+  );
+
+  Future<Uint8List> snapshot() =>
+      RustLib.instance.api.cratePyinApiAudioAnalyzerSnapshot(that: this);
+
+  Future<void> startScoring({
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  }) => RustLib.instance.api.cratePyinApiAudioAnalyzerStartScoring(
+    that: this,
+    chart: chart,
+    options: options,
+  );
+}
+
+@sealed
+class PartyImpl extends RustOpaque implements Party {
+  // Not to be used by end users
+  PartyImpl.frbInternalDcoDecode(List<dynamic> wire)
+    : super.frbInternalDcoDecode(wire, _kStaticData);
+
+  // Not to be used by end users
+  PartyImpl.frbInternalSseDecode(BigInt ptr, int externalSizeOnNative)
+    : super.frbInternalSseDecode(ptr, externalSizeOnNative, _kStaticData);
+
+  static final _kStaticData = RustArcStaticData(
+    rustArcIncrementStrongCount:
+        RustLib.instance.api.rust_arc_increment_strong_count_Party,
+    rustArcDecrementStrongCount:
+        RustLib.instance.api.rust_arc_decrement_strong_count_Party,
+    rustArcDecrementStrongCountPtr:
+        RustLib.instance.api.rust_arc_decrement_strong_count_PartyPtr,
+  );
+
+  Future<int> addSinger({
+    required PitchConfig config,
+    required SingerOptions options,
+  }) => RustLib.instance.api.cratePyinApiPartyAddSinger(
+    that: this,
+    config: config,
+    options: options,
+  );
+
+  Future<List<ScoreEvent>> drainScoreEvents({required int singer}) =>
+      RustLib.instance.api.cratePyinApiPartyDrainScoreEvents(
+        that: this,
+        singer: singer,
+      );
+
+  Future<void> finish() =>
+      RustLib.instance.api.cratePyinApiPartyFinish(that: this);
+
+  Future<void> processChunk({
+    required int singer,
+    required List<int> pcmBytes,
+  }) => RustLib.instance.api.cratePyinApiPartyProcessChunk(
+    that: this,
+    singer: singer,
+    pcmBytes: pcmBytes,
+  );
+
+  Future<List<SingerResult>> results() =>
+      RustLib.instance.api.cratePyinApiPartyResults(that: this);
 }
 
 @sealed
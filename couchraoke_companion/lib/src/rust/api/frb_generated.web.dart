@@ -13,6 +13,7 @@ import 'frb_generated.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated_web.dart';
 import 'pyin.dart';
 import 'pyin/api.dart';
+import 'scoring.dart';
 
 abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   RustLibApiImplPlatform({
@@ -26,6 +27,10 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   get rust_arc_decrement_strong_count_AudioAnalyzerPtr => wire
       .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer;
 
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_PartyPtr => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty;
+
   CrossPlatformFinalizerArg
   get rust_arc_decrement_strong_count_PyinProcessorPtr => wire
       .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor;
@@ -39,6 +44,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
+  @protected
+  Party
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  );
+
   @protected
   PyinProcessor
   dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
@@ -51,18 +62,42 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
+  @protected
+  Party
+  dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  );
+
   @protected
   PyinProcessor
   dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
     dynamic raw,
   );
 
+  @protected
+  AudioAnalyzer
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    dynamic raw,
+  );
+
+  @protected
+  Party
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  );
+
   @protected
   AudioAnalyzer
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
     dynamic raw,
   );
 
+  @protected
+  Party
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    dynamic raw,
+  );
+
   @protected
   PyinProcessor
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
@@ -70,7 +105,16 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  RustStreamSink<int> dco_decode_StreamSink_u_8_Sse(dynamic raw);
+  RustStreamSink<FileAnalysisUpdate>
+  dco_decode_StreamSink_file_analysis_update_Sse(
+    dynamic raw,
+  );
+
+  @protected
+  RustStreamSink<PitchEvent> dco_decode_StreamSink_pitch_event_Sse(dynamic raw);
+
+  @protected
+  RustStreamSink<ScoreEvent> dco_decode_StreamSink_score_event_Sse(dynamic raw);
 
   @protected
   String dco_decode_String(dynamic raw);
@@ -78,36 +122,165 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   AudioFingerprint dco_decode_audio_fingerprint(dynamic raw);
 
+  @protected
+  BleedConfig dco_decode_bleed_config(dynamic raw);
+
+  @protected
+  bool dco_decode_bool(dynamic raw);
+
+  @protected
+  BleedConfig dco_decode_box_autoadd_bleed_config(dynamic raw);
+
+  @protected
+  double dco_decode_box_autoadd_f_32(dynamic raw);
+
+  @protected
+  double dco_decode_box_autoadd_f_64(dynamic raw);
+
+  @protected
+  FileAnalysisOptions dco_decode_box_autoadd_file_analysis_options(dynamic raw);
+
+  @protected
+  FileContour dco_decode_box_autoadd_file_contour(dynamic raw);
+
+  @protected
+  LevelReading dco_decode_box_autoadd_level_reading(dynamic raw);
+
   @protected
   PitchConfig dco_decode_box_autoadd_pitch_config(dynamic raw);
 
+  @protected
+  PitchStreamOptions dco_decode_box_autoadd_pitch_stream_options(dynamic raw);
+
+  @protected
+  ScorePriorConfig dco_decode_box_autoadd_score_prior_config(dynamic raw);
+
+  @protected
+  ScoreStreamOptions dco_decode_box_autoadd_score_stream_options(dynamic raw);
+
+  @protected
+  SingerOptions dco_decode_box_autoadd_singer_options(dynamic raw);
+
+  @protected
+  int dco_decode_box_autoadd_u_32(dynamic raw);
+
+  @protected
+  ChannelMix dco_decode_channel_mix(dynamic raw);
+
+  @protected
+  Difficulty dco_decode_difficulty(dynamic raw);
+
+  @protected
+  double dco_decode_f_32(dynamic raw);
+
   @protected
   double dco_decode_f_64(dynamic raw);
 
+  @protected
+  FileAnalysisOptions dco_decode_file_analysis_options(dynamic raw);
+
+  @protected
+  FileAnalysisUpdate dco_decode_file_analysis_update(dynamic raw);
+
+  @protected
+  FileContour dco_decode_file_contour(dynamic raw);
+
+  @protected
+  LevelReading dco_decode_level_reading(dynamic raw);
+
   @protected
   List<String> dco_decode_list_String(dynamic raw);
 
   @protected
   List<AudioFingerprint> dco_decode_list_audio_fingerprint(dynamic raw);
 
+  @protected
+  List<PitchEvent> dco_decode_list_pitch_event(dynamic raw);
+
+  @protected
+  Float32List dco_decode_list_prim_f_32_strict(dynamic raw);
+
   @protected
   List<int> dco_decode_list_prim_u_8_loose(dynamic raw);
 
   @protected
   Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
 
+  @protected
+  List<ScoreEvent> dco_decode_list_score_event(dynamic raw);
+
+  @protected
+  List<SingerResult> dco_decode_list_singer_result(dynamic raw);
+
+  @protected
+  List<TargetNote> dco_decode_list_target_note(dynamic raw);
+
+  @protected
+  double? dco_decode_opt_box_autoadd_f_32(dynamic raw);
+
+  @protected
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw);
+
+  @protected
+  FileContour? dco_decode_opt_box_autoadd_file_contour(dynamic raw);
+
+  @protected
+  LevelReading? dco_decode_opt_box_autoadd_level_reading(dynamic raw);
+
+  @protected
+  int? dco_decode_opt_box_autoadd_u_32(dynamic raw);
+
+  @protected
+  PcmFormat dco_decode_pcm_format(dynamic raw);
+
+  @protected
+  PitchAlgorithm dco_decode_pitch_algorithm(dynamic raw);
+
   @protected
   PitchConfig dco_decode_pitch_config(dynamic raw);
 
+  @protected
+  PitchEvent dco_decode_pitch_event(dynamic raw);
+
+  @protected
+  PitchStreamOptions dco_decode_pitch_stream_options(dynamic raw);
+
   @protected
   PyinError dco_decode_pyin_error(dynamic raw);
 
+  @protected
+  ScoreEvent dco_decode_score_event(dynamic raw);
+
+  @protected
+  ScoreEventKind dco_decode_score_event_kind(dynamic raw);
+
+  @protected
+  ScorePriorConfig dco_decode_score_prior_config(dynamic raw);
+
+  @protected
+  ScoreStreamOptions dco_decode_score_stream_options(dynamic raw);
+
+  @protected
+  ScoreTotals dco_decode_score_totals(dynamic raw);
+
+  @protected
+  SingerOptions dco_decode_singer_options(dynamic raw);
+
+  @protected
+  SingerResult dco_decode_singer_result(dynamic raw);
+
+  @protected
+  TargetNote dco_decode_target_note(dynamic raw);
+
   @protected
   int dco_decode_u_16(dynamic raw);
 
   @protected
   int dco_decode_u_32(dynamic raw);
 
+  @protected
+  BigInt dco_decode_u_64(dynamic raw);
+
   @protected
   int dco_decode_u_8(dynamic raw);
 
@@ -126,6 +299,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseDeserializer deserializer,
   );
 
+  @protected
+  Party
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  );
+
   @protected
   PyinProcessor
   sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
@@ -138,18 +317,42 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseDeserializer deserializer,
   );
 
+  @protected
+  Party
+  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  );
+
   @protected
   PyinProcessor
   sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
     SseDeserializer deserializer,
   );
 
+  @protected
+  AudioAnalyzer
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  Party
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  );
+
   @protected
   AudioAnalyzer
   sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
     SseDeserializer deserializer,
   );
 
+  @protected
+  Party
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    SseDeserializer deserializer,
+  );
+
   @protected
   PyinProcessor
   sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
@@ -157,7 +360,18 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  RustStreamSink<int> sse_decode_StreamSink_u_8_Sse(
+  RustStreamSink<FileAnalysisUpdate>
+  sse_decode_StreamSink_file_analysis_update_Sse(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RustStreamSink<PitchEvent> sse_decode_StreamSink_pitch_event_Sse(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RustStreamSink<ScoreEvent> sse_decode_StreamSink_score_event_Sse(
     SseDeserializer deserializer,
   );
 
@@ -167,12 +381,88 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   AudioFingerprint sse_decode_audio_fingerprint(SseDeserializer deserializer);
 
+  @protected
+  BleedConfig sse_decode_bleed_config(SseDeserializer deserializer);
+
+  @protected
+  bool sse_decode_bool(SseDeserializer deserializer);
+
+  @protected
+  BleedConfig sse_decode_box_autoadd_bleed_config(SseDeserializer deserializer);
+
+  @protected
+  double sse_decode_box_autoadd_f_32(SseDeserializer deserializer);
+
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  FileAnalysisOptions sse_decode_box_autoadd_file_analysis_options(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FileContour sse_decode_box_autoadd_file_contour(SseDeserializer deserializer);
+
+  @protected
+  LevelReading sse_decode_box_autoadd_level_reading(
+    SseDeserializer deserializer,
+  );
+
   @protected
   PitchConfig sse_decode_box_autoadd_pitch_config(SseDeserializer deserializer);
 
+  @protected
+  PitchStreamOptions sse_decode_box_autoadd_pitch_stream_options(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ScorePriorConfig sse_decode_box_autoadd_score_prior_config(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ScoreStreamOptions sse_decode_box_autoadd_score_stream_options(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SingerOptions sse_decode_box_autoadd_singer_options(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  int sse_decode_box_autoadd_u_32(SseDeserializer deserializer);
+
+  @protected
+  ChannelMix sse_decode_channel_mix(SseDeserializer deserializer);
+
+  @protected
+  Difficulty sse_decode_difficulty(SseDeserializer deserializer);
+
+  @protected
+  double sse_decode_f_32(SseDeserializer deserializer);
+
   @protected
   double sse_decode_f_64(SseDeserializer deserializer);
 
+  @protected
+  FileAnalysisOptions sse_decode_file_analysis_options(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FileAnalysisUpdate sse_decode_file_analysis_update(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FileContour sse_decode_file_contour(SseDeserializer deserializer);
+
+  @protected
+  LevelReading sse_decode_level_reading(SseDeserializer deserializer);
+
   @protected
   List<String> sse_decode_list_String(SseDeserializer deserializer);
 
@@ -181,24 +471,103 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseDeserializer deserializer,
   );
 
+  @protected
+  List<PitchEvent> sse_decode_list_pitch_event(SseDeserializer deserializer);
+
+  @protected
+  Float32List sse_decode_list_prim_f_32_strict(SseDeserializer deserializer);
+
   @protected
   List<int> sse_decode_list_prim_u_8_loose(SseDeserializer deserializer);
 
   @protected
   Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
 
+  @protected
+  List<ScoreEvent> sse_decode_list_score_event(SseDeserializer deserializer);
+
+  @protected
+  List<SingerResult> sse_decode_list_singer_result(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<TargetNote> sse_decode_list_target_note(SseDeserializer deserializer);
+
+  @protected
+  double? sse_decode_opt_box_autoadd_f_32(SseDeserializer deserializer);
+
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  FileContour? sse_decode_opt_box_autoadd_file_contour(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LevelReading? sse_decode_opt_box_autoadd_level_reading(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer);
+
+  @protected
+  PcmFormat sse_decode_pcm_format(SseDeserializer deserializer);
+
+  @protected
+  PitchAlgorithm sse_decode_pitch_algorithm(SseDeserializer deserializer);
+
   @protected
   PitchConfig sse_decode_pitch_config(SseDeserializer deserializer);
 
+  @protected
+  PitchEvent sse_decode_pitch_event(SseDeserializer deserializer);
+
+  @protected
+  PitchStreamOptions sse_decode_pitch_stream_options(
+    SseDeserializer deserializer,
+  );
+
   @protected
   PyinError sse_decode_pyin_error(SseDeserializer deserializer);
 
+  @protected
+  ScoreEvent sse_decode_score_event(SseDeserializer deserializer);
+
+  @protected
+  ScoreEventKind sse_decode_score_event_kind(SseDeserializer deserializer);
+
+  @protected
+  ScorePriorConfig sse_decode_score_prior_config(SseDeserializer deserializer);
+
+  @protected
+  ScoreStreamOptions sse_decode_score_stream_options(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ScoreTotals sse_decode_score_totals(SseDeserializer deserializer);
+
+  @protected
+  SingerOptions sse_decode_singer_options(SseDeserializer deserializer);
+
+  @protected
+  SingerResult sse_decode_singer_result(SseDeserializer deserializer);
+
+  @protected
+  TargetNote sse_decode_target_note(SseDeserializer deserializer);
+
   @protected
   int sse_decode_u_16(SseDeserializer deserializer);
 
   @protected
   int sse_decode_u_32(SseDeserializer deserializer);
 
+  @protected
+  BigInt sse_decode_u_64(SseDeserializer deserializer);
+
   @protected
   int sse_decode_u_8(SseDeserializer deserializer);
 
@@ -211,9 +580,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int sse_decode_i_32(SseDeserializer deserializer);
 
-  @protected
-  bool sse_decode_bool(SseDeserializer deserializer);
-
   @protected
   void sse_encode_AnyhowException(
     AnyhowException self,
@@ -227,6 +593,13 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  );
+
   @protected
   void
   sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
@@ -241,6 +614,13 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void
+  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  );
+
   @protected
   void
   sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
@@ -248,6 +628,20 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
+    AudioAnalyzer self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  );
+
   @protected
   void
   sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerAudioAnalyzer(
@@ -255,6 +649,13 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    Party self,
+    SseSerializer serializer,
+  );
+
   @protected
   void
   sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
@@ -263,8 +664,20 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  void sse_encode_StreamSink_u_8_Sse(
-    RustStreamSink<int> self,
+  void sse_encode_StreamSink_file_analysis_update_Sse(
+    RustStreamSink<FileAnalysisUpdate> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_StreamSink_pitch_event_Sse(
+    RustStreamSink<PitchEvent> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_StreamSink_score_event_Sse(
+    RustStreamSink<ScoreEvent> self,
     SseSerializer serializer,
   );
 
@@ -277,15 +690,105 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_bleed_config(BleedConfig self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_bool(bool self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_bleed_config(
+    BleedConfig self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_f_32(double self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_file_analysis_options(
+    FileAnalysisOptions self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_file_contour(
+    FileContour self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_level_reading(
+    LevelReading self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_box_autoadd_pitch_config(
     PitchConfig self,
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_box_autoadd_pitch_stream_options(
+    PitchStreamOptions self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_score_prior_config(
+    ScorePriorConfig self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_score_stream_options(
+    ScoreStreamOptions self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_singer_options(
+    SingerOptions self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_u_32(int self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_channel_mix(ChannelMix self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_difficulty(Difficulty self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_f_32(double self, SseSerializer serializer);
+
   @protected
   void sse_encode_f_64(double self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_file_analysis_options(
+    FileAnalysisOptions self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_file_analysis_update(
+    FileAnalysisUpdate self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_file_contour(FileContour self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_level_reading(LevelReading self, SseSerializer serializer);
+
   @protected
   void sse_encode_list_String(List<String> self, SseSerializer serializer);
 
@@ -295,6 +798,18 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_list_pitch_event(
+    List<PitchEvent> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_prim_f_32_strict(
+    Float32List self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_list_prim_u_8_loose(List<int> self, SseSerializer serializer);
 
@@ -304,18 +819,111 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_list_score_event(
+    List<ScoreEvent> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_singer_result(
+    List<SingerResult> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_target_note(
+    List<TargetNote> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_opt_box_autoadd_f_32(double? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_file_contour(
+    FileContour? self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_opt_box_autoadd_level_reading(
+    LevelReading? self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_pcm_format(PcmFormat self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_pitch_algorithm(
+    PitchAlgorithm self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_pitch_config(PitchConfig self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_pitch_event(PitchEvent self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_pitch_stream_options(
+    PitchStreamOptions self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_pyin_error(PyinError self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_score_event(ScoreEvent self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_score_event_kind(
+    ScoreEventKind self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_score_prior_config(
+    ScorePriorConfig self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_score_stream_options(
+    ScoreStreamOptions self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_score_totals(ScoreTotals self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_singer_options(SingerOptions self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_singer_result(SingerResult self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_target_note(TargetNote self, SseSerializer serializer);
+
   @protected
   void sse_encode_u_16(int self, SseSerializer serializer);
 
   @protected
   void sse_encode_u_32(int self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_u_64(BigInt self, SseSerializer serializer);
+
   @protected
   void sse_encode_u_8(int self, SseSerializer serializer);
 
@@ -327,9 +935,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
 
   @protected
   void sse_encode_i_32(int self, SseSerializer serializer);
-
-  @protected
-  void sse_encode_bool(bool self, SseSerializer serializer);
 }
 
 // Section: wire_class
@@ -353,6 +958,22 @@ class RustLibWire implements BaseWire {
         ptr,
       );
 
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+        ptr,
+      );
+
   void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
     int ptr,
@@ -386,6 +1007,16 @@ extension type RustLibWasmModule._(JSObject _) implements JSObject {
     int ptr,
   );
 
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+    int ptr,
+  );
+
   external void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
     int ptr,
@@ -8,6 +8,114 @@ import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 import 'package:freezed_annotation/freezed_annotation.dart' hide protected;
 part 'pyin.freezed.dart';
 
+class BleedConfig {
+  /// Stream time (the clock of `FrameEstimate::time_sec`) at which the start
+  /// of the reference reaches the mic, i.e. playback start plus output and
+  /// acoustic latency. May be negative if playback started before the stream.
+  final double delaySec;
+  /// Length of the adaptive filter. It must cover the room response and the
+  /// error of `delay_sec`; the first quarter sits before the estimated delay.
+  final double filterMs;
+  /// NLMS step size in (0, 2). Smaller values adapt more slowly but are less
+  /// disturbed by the singer's voice.
+  final double stepSize;
+
+  const BleedConfig({
+    required this.delaySec,
+    required this.filterMs,
+    required this.stepSize,
+  });
+
+  @override
+  int get hashCode => delaySec.hashCode ^ filterMs.hashCode ^ stepSize.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is BleedConfig &&
+          runtimeType == other.runtimeType &&
+          delaySec == other.delaySec &&
+          filterMs == other.filterMs &&
+          stepSize == other.stepSize;
+}
+
+/// How interleaved multichannel input is reduced to the mono analysis signal.
+@freezed
+sealed class ChannelMix with _$ChannelMix {
+  const ChannelMix._();
+
+  /// Average all channels.
+  const factory ChannelMix.downmix() = ChannelMix_Downmix;
+  /// Use a single channel (zero-based), e.g. the vocal mic on a USB interface.
+  const factory ChannelMix.pick(int field0) = ChannelMix_Pick;
+}
+
+/// Levels after one block of input.
+class LevelReading {
+  /// RMS level of the block.
+  final double rmsDbfs;
+  /// Largest absolute sample of the block.
+  final double peakDbfs;
+  final double momentaryLufs;
+  final double shortTermLufs;
+  /// Samples of the block at or above `LevelConfig::clip_threshold_dbfs`.
+  final int clippedSamples;
+
+  const LevelReading({
+    required this.rmsDbfs,
+    required this.peakDbfs,
+    required this.momentaryLufs,
+    required this.shortTermLufs,
+    required this.clippedSamples,
+  });
+
+  @override
+  int get hashCode =>
+      rmsDbfs.hashCode ^
+      peakDbfs.hashCode ^
+      momentaryLufs.hashCode ^
+      shortTermLufs.hashCode ^
+      clippedSamples.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LevelReading &&
+          runtimeType == other.runtimeType &&
+          rmsDbfs == other.rmsDbfs &&
+          peakDbfs == other.peakDbfs &&
+          momentaryLufs == other.momentaryLufs &&
+          shortTermLufs == other.shortTermLufs &&
+          clippedSamples == other.clippedSamples;
+}
+
+/// Sample encoding of incoming PCM bytes. Integer formats are signed; 24-bit
+/// samples are packed into three bytes.
+enum PcmFormat {
+  i16Le,
+  f32Le,
+  i16Be,
+  i24Le,
+  i24Be,
+  i32Le,
+  i32Be,
+  f32Be,
+  ;
+}
+
+enum PitchAlgorithm {
+  /// Probabilistic YIN with HMM smoothing: the most robust and the most
+  /// expensive.
+  pyin,
+  /// YIN with a single absolute threshold.
+  yin,
+  /// McLeod pitch method over the normalised square difference function.
+  mpm,
+  /// Harmonic product spectrum.
+  hps,
+  ;
+}
+
 @freezed
 sealed class PyinError with _$PyinError implements FrbException {
   const PyinError._();
@@ -15,3 +123,70 @@ sealed class PyinError with _$PyinError implements FrbException {
   const factory PyinError.invalidConfig(String field0) =
       PyinError_InvalidConfig;
 }
+
+class ScorePriorConfig {
+  /// Stream time (the clock of `FrameEstimate::time_sec`) at which song time 0
+  /// is heard, i.e. when playback of the song's audio started relative to
+  /// the first sample pushed.
+  final double songStartSec;
+  /// How far the prior may pull, in [0, 1). Bins away from the target and its
+  /// octaves keep `1 - strength` of their weight.
+  final double strength;
+  /// Width of the boost around the target, in cents.
+  final double sigmaCents;
+  /// Boost of the octaves above and below the target relative to the target
+  /// itself, in [0, 1].
+  final double octaveWeight;
+
+  const ScorePriorConfig({
+    required this.songStartSec,
+    required this.strength,
+    required this.sigmaCents,
+    required this.octaveWeight,
+  });
+
+  @override
+  int get hashCode =>
+      songStartSec.hashCode ^
+      strength.hashCode ^
+      sigmaCents.hashCode ^
+      octaveWeight.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ScorePriorConfig &&
+          runtimeType == other.runtimeType &&
+          songStartSec == other.songStartSec &&
+          strength == other.strength &&
+          sigmaCents == other.sigmaCents &&
+          octaveWeight == other.octaveWeight;
+}
+
+/// One note of the expected melody, in song time: seconds from the start of
+/// the song's audio, not of the stream.
+class TargetNote {
+  final double startSec;
+  final double endSec;
+  /// Expected pitch as (fractional) MIDI note relative to
+  /// `PyinConfig::reference_a4_hz`.
+  final double midi;
+
+  const TargetNote({
+    required this.startSec,
+    required this.endSec,
+    required this.midi,
+  });
+
+  @override
+  int get hashCode => startSec.hashCode ^ endSec.hashCode ^ midi.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is TargetNote &&
+          runtimeType == other.runtimeType &&
+          startSec == other.startSec &&
+          endSec == other.endSec &&
+          midi == other.midi;
+}
@@ -15,6 +15,318 @@ final _privateConstructorUsedError = UnsupportedError(
   'It seems like you constructed your class using `MyClass._()`. This constructor is only meant to be used by freezed and you are not supposed to need it nor use it.\nPlease check the documentation here for more information: https://github.com/rrousselGit/freezed#adding-getters-and-methods-to-our-models',
 );
 
+/// @nodoc
+mixin _$ChannelMix {
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function() downmix,
+    required TResult Function(int field0) pick,
+  }) => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function()? downmix,
+    TResult? Function(int field0)? pick,
+  }) => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function()? downmix,
+    TResult Function(int field0)? pick,
+    required TResult orElse(),
+  }) => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(ChannelMix_Downmix value) downmix,
+    required TResult Function(ChannelMix_Pick value) pick,
+  }) => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(ChannelMix_Downmix value)? downmix,
+    TResult? Function(ChannelMix_Pick value)? pick,
+  }) => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(ChannelMix_Downmix value)? downmix,
+    TResult Function(ChannelMix_Pick value)? pick,
+    required TResult orElse(),
+  }) => throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+abstract class $ChannelMixCopyWith<$Res> {
+  factory $ChannelMixCopyWith(
+    ChannelMix value,
+    $Res Function(ChannelMix) then,
+  ) = _$ChannelMixCopyWithImpl<$Res, ChannelMix>;
+}
+
+/// @nodoc
+class _$ChannelMixCopyWithImpl<$Res, $Val extends ChannelMix>
+    implements $ChannelMixCopyWith<$Res> {
+  _$ChannelMixCopyWithImpl(this._value, this._then);
+
+  // ignore: unused_field
+  final $Val _value;
+  // ignore: unused_field
+  final $Res Function($Val) _then;
+
+  /// Create a copy of ChannelMix
+  /// with the given fields replaced by the non-null parameter values.
+}
+
+/// @nodoc
+abstract class _$$ChannelMix_DownmixImplCopyWith<$Res> {
+  factory _$$ChannelMix_DownmixImplCopyWith(
+    _$ChannelMix_DownmixImpl value,
+    $Res Function(_$ChannelMix_DownmixImpl) then,
+  ) = __$$ChannelMix_DownmixImplCopyWithImpl<$Res>;
+}
+
+/// @nodoc
+class __$$ChannelMix_DownmixImplCopyWithImpl<$Res>
+    extends _$ChannelMixCopyWithImpl<$Res, _$ChannelMix_DownmixImpl>
+    implements _$$ChannelMix_DownmixImplCopyWith<$Res> {
+  __$$ChannelMix_DownmixImplCopyWithImpl(
+    _$ChannelMix_DownmixImpl _value,
+    $Res Function(_$ChannelMix_DownmixImpl) _then,
+  ) : super(_value, _then);
+
+  /// Create a copy of ChannelMix
+  /// with the given fields replaced by the non-null parameter values.
+}
+
+/// @nodoc
+
+class _$ChannelMix_DownmixImpl extends ChannelMix_Downmix {
+  const _$ChannelMix_DownmixImpl() : super._();
+
+  @override
+  String toString() {
+    return 'ChannelMix.downmix()';
+  }
+
+  @override
+  bool operator ==(Object other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType && other is _$ChannelMix_DownmixImpl);
+  }
+
+  @override
+  int get hashCode => runtimeType.hashCode;
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function() downmix,
+    required TResult Function(int field0) pick,
+  }) {
+    return downmix();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function()? downmix,
+    TResult? Function(int field0)? pick,
+  }) {
+    return downmix?.call();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function()? downmix,
+    TResult Function(int field0)? pick,
+    required TResult orElse(),
+  }) {
+    if (downmix != null) {
+      return downmix();
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(ChannelMix_Downmix value) downmix,
+    required TResult Function(ChannelMix_Pick value) pick,
+  }) {
+    return downmix(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(ChannelMix_Downmix value)? downmix,
+    TResult? Function(ChannelMix_Pick value)? pick,
+  }) {
+    return downmix?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(ChannelMix_Downmix value)? downmix,
+    TResult Function(ChannelMix_Pick value)? pick,
+    required TResult orElse(),
+  }) {
+    if (downmix != null) {
+      return downmix(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class ChannelMix_Downmix extends ChannelMix {
+  const factory ChannelMix_Downmix() = _$ChannelMix_DownmixImpl;
+  const ChannelMix_Downmix._() : super._();
+}
+
+/// @nodoc
+abstract class _$$ChannelMix_PickImplCopyWith<$Res> {
+  factory _$$ChannelMix_PickImplCopyWith(
+    _$ChannelMix_PickImpl value,
+    $Res Function(_$ChannelMix_PickImpl) then,
+  ) = __$$ChannelMix_PickImplCopyWithImpl<$Res>;
+  @useResult
+  $Res call({int field0});
+}
+
+/// @nodoc
+class __$$ChannelMix_PickImplCopyWithImpl<$Res>
+    extends _$ChannelMixCopyWithImpl<$Res, _$ChannelMix_PickImpl>
+    implements _$$ChannelMix_PickImplCopyWith<$Res> {
+  __$$ChannelMix_PickImplCopyWithImpl(
+    _$ChannelMix_PickImpl _value,
+    $Res Function(_$ChannelMix_PickImpl) _then,
+  ) : super(_value, _then);
+
+  /// Create a copy of ChannelMix
+  /// with the given fields replaced by the non-null parameter values.
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({Object? field0 = null}) {
+    return _then(
+      _$ChannelMix_PickImpl(
+        null == field0
+            ? _value.field0
+            : field0 // ignore: cast_nullable_to_non_nullable
+                  as int,
+      ),
+    );
+  }
+}
+
+/// @nodoc
+
+class _$ChannelMix_PickImpl extends ChannelMix_Pick {
+  const _$ChannelMix_PickImpl(this.field0) : super._();
+
+  @override
+  final int field0;
+
+  @override
+  String toString() {
+    return 'ChannelMix.pick(field0: $field0)';
+  }
+
+  @override
+  bool operator ==(Object other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$ChannelMix_PickImpl &&
+            (identical(other.field0, field0) || other.field0 == field0));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, field0);
+
+  /// Create a copy of ChannelMix
+  /// with the given fields replaced by the non-null parameter values.
+  @JsonKey(includeFromJson: false, includeToJson: false)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$ChannelMix_PickImplCopyWith<_$ChannelMix_PickImpl> get copyWith =>
+      __$$ChannelMix_PickImplCopyWithImpl<_$ChannelMix_PickImpl>(
+        this,
+        _$identity,
+      );
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function() downmix,
+    required TResult Function(int field0) pick,
+  }) {
+    return pick(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function()? downmix,
+    TResult? Function(int field0)? pick,
+  }) {
+    return pick?.call(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function()? downmix,
+    TResult Function(int field0)? pick,
+    required TResult orElse(),
+  }) {
+    if (pick != null) {
+      return pick(field0);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(ChannelMix_Downmix value) downmix,
+    required TResult Function(ChannelMix_Pick value) pick,
+  }) {
+    return pick(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(ChannelMix_Downmix value)? downmix,
+    TResult? Function(ChannelMix_Pick value)? pick,
+  }) {
+    return pick?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(ChannelMix_Downmix value)? downmix,
+    TResult Function(ChannelMix_Pick value)? pick,
+    required TResult orElse(),
+  }) {
+    if (pick != null) {
+      return pick(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class ChannelMix_Pick extends ChannelMix {
+  const factory ChannelMix_Pick(final int field0) = _$ChannelMix_PickImpl;
+  const ChannelMix_Pick._() : super._();
+
+  int get field0;
+
+  /// Create a copy of ChannelMix
+  /// with the given fields replaced by the non-null parameter values.
+  @JsonKey(includeFromJson: false, includeToJson: false)
+  _$$ChannelMix_PickImplCopyWith<_$ChannelMix_PickImpl> get copyWith =>
+      throw _privateConstructorUsedError;
+}
+
 /// @nodoc
 mixin _$PyinError {
   String get field0 => throw _privateConstructorUsedError;
@@ -5,9 +5,33 @@
 
 import '../frb_generated.dart';
 import '../pyin.dart';
+import '../scoring.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
-// These functions are ignored because they are not marked as `pub`: `ms_to_samples`, `new`, `parse_pcm16le_bytes`, `parse_pcm16le_to_i16`, `process_pcm_bytes`
+// These functions are ignored because they are not marked as `pub`: `accept`, `accept_report`, `changed`, `events`, `file_contour`, `finish_pcm`, `from_frame`, `load_event`, `load_state`, `midi_or_unvoiced`, `ms_to_samples`, `new`, `parse_chart`, `process_pcm_bytes`, `push`, `pyin_mut`, `report`, `reset`, `sample_position`, `save_event`, `score_song`, `send_score_events`
+// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `FrameFeed`, `PitchEventFilter`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `default`
+
+/// Offline pitch contour of the audio file at `path`, e.g. to compare a
+/// singer against the song's own vocal track.
+Future<FileContour> analyzeFile({
+  required String path,
+  required FileAnalysisOptions options,
+}) => RustLib.instance.api.cratePyinApiAnalyzeFile(
+  path: path,
+  options: options,
+);
+
+/// Like `analyze_file`, streaming an update to `sink` as each packet of the
+/// file is decoded, for a progress bar over long files, then a last one
+/// carrying the contour.
+Stream<FileAnalysisUpdate> analyzeFileStream({
+  required String path,
+  required FileAnalysisOptions options,
+}) => RustLib.instance.api.cratePyinApiAnalyzeFileStream(
+  path: path,
+  options: options,
+);
 
 Future<void> initLogging() => RustLib.instance.api.cratePyinApiInitLogging();
 
@@ -30,35 +54,323 @@ Future<int> pushAndGetMidi({
 );
 
 // Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>>
+/// A pitch detection session fed PCM chunks from Dart.
+///
+/// Processing a chunk allocates, unlike `Pyin::push_bytes_into`: each frame
+/// becomes a `PitchEvent` for Dart. The allocation-free guarantee of the
+/// estimator stops at `Pyin`.
 abstract class AudioAnalyzer implements RustOpaqueInterface {
-  Stream<int> createStream();
+  Future<void> clearBackingTrack();
+
+  Future<void> clearTargetNotes();
+
+  /// Score further chunks against `chart`, an UltraStar .txt file, and push
+  /// a `ScoreEvent` to `sink` as each note and line finishes.
+  Stream<ScoreEvent> createScoreStream({
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  });
+
+  Stream<PitchEvent> createStream({required PitchStreamOptions options});
+
+  /// Take the score events not yet sent to a score stream.
+  Future<List<ScoreEvent>> drainScoreEvents();
+
+  /// End the stream once the recording is over: complete the last frame
+  /// with silence and stream every frame still held back by the pitch
+  /// decoder, final. The analyzer then starts a new stream, keeping its
+  /// backing track, target notes and scoring.
+  Future<void> finish();
+
+  /// Like `finish`, but returns the events instead of streaming them.
+  Future<List<PitchEvent>> finishCollectEvents();
+
+  /// Finish every note once the song is over, streaming the last events.
+  /// Ends the stream first (see `finish`), so the frames the pitch decoder
+  /// still holds back are scored too.
+  Future<void> finishScoring();
+
+  /// Input levels of the most recent chunk, for a mic level bar and
+  /// clipping warnings. `None` before the first chunk.
+  ///
+  /// `clipped_samples` counts the chunk's samples as captured, in every
+  /// channel that is mixed in, since averaging channels or resampling can
+  /// pull a clipped peak below the threshold.
+  Future<LevelReading?> latestLevel();
 
   // HINT: Make it `#[frb(sync)]` to let it become the default constructor of Dart class.
   static Future<AudioAnalyzer> newInstance({required PitchConfig config}) =>
       RustLib.instance.api.cratePyinApiAudioAnalyzerNew(config: config);
 
-  Future<void> processChunk({required List<int> pcm16LeBytes});
+  /// Process a chunk of PCM in the format given by `PitchConfig`.
+  Future<void> processChunk({required List<int> pcmBytes});
+
+  Future<Uint8List> processChunkCollect({required List<int> pcmBytes});
+
+  /// Process a chunk and return the `PitchEvent`s that pass the stream options
+  /// set by `create_stream` (all events if it was never called).
+  Future<List<PitchEvent>> processChunkCollectEvents({
+    required List<int> pcmBytes,
+  });
+
+  /// Like `process_chunk_collect`, but returns the unrounded MIDI pitch per hop
+  /// (relative to `PitchConfig::reference_a4_hz`), or 255.0 when unvoiced.
+  Future<Float32List> processChunkCollectFractional({
+    required List<int> pcmBytes,
+  });
+
+  /// Continue the session from a `snapshot`. On error the analyzer starts
+  /// over from an empty stream.
+  Future<void> restore({required List<int> blob});
+
+  Future<void> setBackingTrackDelay({required double delaySec});
+
+  /// Decode the backing track at `path` and cancel its bleed from the mic,
+  /// like `set_backing_track`.
+  Future<void> setBackingTrackFile({
+    required String path,
+    required BleedConfig config,
+  });
+
+  /// Move the song to stream time `song_start_sec` for scoring and the
+  /// target notes, e.g. after the stream paused while the song played on.
+  /// Move the backing track with `set_backing_track_delay`.
+  Future<void> setSongStart({required double songStartSec});
+
+  /// Select and throttle the events of further chunks.
+  Future<void> setStreamOptions({required PitchStreamOptions options});
 
-  Future<Uint8List> processChunkCollect({required List<int> pcm16LeBytes});
+  /// Bias tracking toward the song's melody; see `Pyin::set_score_prior`.
+  /// `notes` are in song time (`Song::target_notes`); `config.song_start_sec`
+  /// is the stream time, on the clock of `PitchEvent::time_sec`, at which the
+  /// song's audio started playing.
+  Future<void> setTargetNotes({
+    required List<TargetNote> notes,
+    required ScorePriorConfig config,
+  });
+
+  /// Serialise the session so far (decoder and estimator state, levels,
+  /// stream positions and the last streamed event) into a versioned blob,
+  /// e.g. before the app is suspended.
+  ///
+  /// Restore into an analyzer created with the same `PitchConfig`, with the
+  /// same backing track and target notes set and, if the stream is used,
+  /// after `create_stream`; see `Pyin::snapshot`. Scoring in progress is part
+  /// of the snapshot: start it with the same chart and options before
+  /// restoring.
+  Future<Uint8List> snapshot();
+
+  /// Score further chunks against `chart`, an UltraStar .txt file, replacing
+  /// any scoring in progress. Events wait in the session until
+  /// `drain_score_events` when there is no score stream.
+  ///
+  /// Frames are scored once the pitch decoder has committed them, so events
+  /// trail the audio by its lag.
+  Future<void> startScoring({
+    required List<int> chart,
+    required ScoreStreamOptions options,
+  });
+}
+
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>>
+/// The singers of one song, each with their own analyzer, scored against one
+/// song clock.
+///
+/// Times are on the party clock, which the host chooses, e.g. its monotonic
+/// clock: the song's audio starts playing at `song_start_sec` and each
+/// singer's stream starts at their `stream_start_sec`, which places every
+/// phone's stream on the song's timeline.
+abstract class Party implements RustOpaqueInterface {
+  /// Add a singer whose microphone captures PCM as described by `config`,
+  /// returning their index.
+  Future<int> addSinger({
+    required PitchConfig config,
+    required SingerOptions options,
+  });
+
+  /// Take the singer's score events not yet taken, in song time.
+  Future<List<ScoreEvent>> drainScoreEvents({required int singer});
+
+  /// Finish every singer once the song is over; see
+  /// `AudioAnalyzer::finish_scoring`. Returns the first error, after
+  /// finishing the others.
+  Future<void> finish();
+
+  /// A party singing `chart`, an UltraStar .txt file, whose audio starts
+  /// playing at `song_start_sec` on the party clock.
+  // HINT: Make it `#[frb(sync)]` to let it become the default constructor of Dart class.
+  static Future<Party> newInstance({
+    required List<int> chart,
+    required double songStartSec,
+  }) => RustLib.instance.api.cratePyinApiPartyNew(
+    chart: chart,
+    songStartSec: songStartSec,
+  );
+
+  /// Process a chunk of the singer's PCM.
+  Future<void> processChunk({required int singer, required List<int> pcmBytes});
+
+  /// Results of the singers, in the order they were added.
+  Future<List<SingerResult>> results();
 }
 
 // Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>>
 abstract class PyinProcessor implements RustOpaqueInterface {}
 
+/// Options of `analyze_file` and `analyze_file_stream`.
+class FileAnalysisOptions {
+  /// Rate the detector runs at; the file is resampled to it.
+  final int analysisSampleRateHz;
+  final int updateIntervalMs;
+  final int windowSizeMs;
+  final double referenceA4Hz;
+  /// Start of the analysed range, in seconds from the start of the file.
+  final double startSec;
+  /// End of the analysed range; `None` analyses to the end of the file.
+  final double? endSec;
+
+  const FileAnalysisOptions({
+    required this.analysisSampleRateHz,
+    required this.updateIntervalMs,
+    required this.windowSizeMs,
+    required this.referenceA4Hz,
+    required this.startSec,
+    required this.endSec,
+  });
+
+  @override
+  int get hashCode =>
+      analysisSampleRateHz.hashCode ^
+      updateIntervalMs.hashCode ^
+      windowSizeMs.hashCode ^
+      referenceA4Hz.hashCode ^
+      startSec.hashCode ^
+      endSec.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is FileAnalysisOptions &&
+          runtimeType == other.runtimeType &&
+          analysisSampleRateHz == other.analysisSampleRateHz &&
+          updateIntervalMs == other.updateIntervalMs &&
+          windowSizeMs == other.windowSizeMs &&
+          referenceA4Hz == other.referenceA4Hz &&
+          startSec == other.startSec &&
+          endSec == other.endSec;
+}
+
+/// Progress of `analyze_file_stream`.
+class FileAnalysisUpdate {
+  /// Seconds of the file decoded so far.
+  final double processedSec;
+  /// File duration, if the container reports it.
+  final double? totalSec;
+  /// The finished contour, on the last update only.
+  final FileContour? contour;
+
+  const FileAnalysisUpdate({
+    required this.processedSec,
+    required this.totalSec,
+    required this.contour,
+  });
+
+  @override
+  int get hashCode =>
+      processedSec.hashCode ^ totalSec.hashCode ^ contour.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is FileAnalysisUpdate &&
+          runtimeType == other.runtimeType &&
+          processedSec == other.processedSec &&
+          totalSec == other.totalSec &&
+          contour == other.contour;
+}
+
+/// Pitch contour of a decoded file, as delivered to Dart.
+class FileContour {
+  final int sourceSampleRateHz;
+  final double durationSec;
+  /// One final event per frame of the analysed range. `sample_position`
+  /// counts samples of the file at its own rate.
+  final List<PitchEvent> events;
+
+  const FileContour({
+    required this.sourceSampleRateHz,
+    required this.durationSec,
+    required this.events,
+  });
+
+  @override
+  int get hashCode =>
+      sourceSampleRateHz.hashCode ^ durationSec.hashCode ^ events.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is FileContour &&
+          runtimeType == other.runtimeType &&
+          sourceSampleRateHz == other.sourceSampleRateHz &&
+          durationSec == other.durationSec &&
+          events == other.events;
+}
+
 class PitchConfig {
+  /// Capture rate of the PCM handed to `process_chunk`.
   final int sampleRateHz;
   final int updateIntervalMs;
   final int windowSizeMs;
+  /// Frequency of A4 used for MIDI conversion, e.g. 440, 432 or 443 Hz.
+  final double referenceA4Hz;
+  final PcmFormat pcmFormat;
+  /// Number of interleaved channels in the PCM stream.
+  final int channels;
+  final ChannelMix channelMix;
+  /// Rate the detector runs at; the input is resampled when it differs from
+  /// `sample_rate_hz`. `None` analyses at the capture rate.
+  final int? analysisSampleRateHz;
+  /// Gate room noise and background chatter with the voice activity detector.
+  final bool vadEnabled;
+  /// Verify YIN candidates against the harmonic spectrum to fix octave errors.
+  final bool octaveCorrectionEnabled;
+  /// Pitch estimator to run. Bleed cancellation, target notes, VAD and
+  /// octave correction only apply to `PitchAlgorithm::Pyin`.
+  final PitchAlgorithm algorithm;
+  /// Track high notes over a quarter and half of the window, so they follow
+  /// the voice sooner. Only applies to `PitchAlgorithm::Pyin`.
+  final bool multiResolutionEnabled;
 
   const PitchConfig({
     required this.sampleRateHz,
     required this.updateIntervalMs,
     required this.windowSizeMs,
+    required this.referenceA4Hz,
+    required this.pcmFormat,
+    required this.channels,
+    required this.channelMix,
+    required this.analysisSampleRateHz,
+    required this.vadEnabled,
+    required this.octaveCorrectionEnabled,
+    required this.algorithm,
+    required this.multiResolutionEnabled,
   });
 
   @override
   int get hashCode =>
-      sampleRateHz.hashCode ^ updateIntervalMs.hashCode ^ windowSizeMs.hashCode;
+      sampleRateHz.hashCode ^
+      updateIntervalMs.hashCode ^
+      windowSizeMs.hashCode ^
+      referenceA4Hz.hashCode ^
+      pcmFormat.hashCode ^
+      channels.hashCode ^
+      channelMix.hashCode ^
+      analysisSampleRateHz.hashCode ^
+      vadEnabled.hashCode ^
+      octaveCorrectionEnabled.hashCode ^
+      algorithm.hashCode ^
+      multiResolutionEnabled.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -67,5 +379,208 @@ class PitchConfig {
           runtimeType == other.runtimeType &&
           sampleRateHz == other.sampleRateHz &&
           updateIntervalMs == other.updateIntervalMs &&
-          windowSizeMs == other.windowSizeMs;
+          windowSizeMs == other.windowSizeMs &&
+          referenceA4Hz == other.referenceA4Hz &&
+          pcmFormat == other.pcmFormat &&
+          channels == other.channels &&
+          channelMix == other.channelMix &&
+          analysisSampleRateHz == other.analysisSampleRateHz &&
+          vadEnabled == other.vadEnabled &&
+          octaveCorrectionEnabled == other.octaveCorrectionEnabled &&
+          algorithm == other.algorithm &&
+          multiResolutionEnabled == other.multiResolutionEnabled;
+}
+
+/// One pitch estimate as delivered to Dart.
+class PitchEvent {
+  /// Index of the first input sample of the analysis window.
+  final BigInt samplePosition;
+  final double timeSec;
+  final double? f0Hz;
+  /// Unrounded MIDI pitch relative to `PitchConfig::reference_a4_hz`.
+  final double? midiFractional;
+  final double confidence;
+  final bool voiced;
+  final double rmsDbfs;
+  final double peakDbfs;
+  /// Short-term loudness in LUFS.
+  final double loudnessLufs;
+  /// `false` while the pitch decoder may still revise this frame.
+  final bool isFinal;
+  /// Voice activity probability, when `PitchConfig::vad_enabled`.
+  final double? speechProb;
+  /// Estimated room noise level, when `PitchConfig::vad_enabled`.
+  final double? noiseFloorDbfs;
+
+  const PitchEvent({
+    required this.samplePosition,
+    required this.timeSec,
+    required this.f0Hz,
+    required this.midiFractional,
+    required this.confidence,
+    required this.voiced,
+    required this.rmsDbfs,
+    required this.peakDbfs,
+    required this.loudnessLufs,
+    required this.isFinal,
+    required this.speechProb,
+    required this.noiseFloorDbfs,
+  });
+
+  @override
+  int get hashCode =>
+      samplePosition.hashCode ^
+      timeSec.hashCode ^
+      f0Hz.hashCode ^
+      midiFractional.hashCode ^
+      confidence.hashCode ^
+      voiced.hashCode ^
+      rmsDbfs.hashCode ^
+      peakDbfs.hashCode ^
+      loudnessLufs.hashCode ^
+      isFinal.hashCode ^
+      speechProb.hashCode ^
+      noiseFloorDbfs.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is PitchEvent &&
+          runtimeType == other.runtimeType &&
+          samplePosition == other.samplePosition &&
+          timeSec == other.timeSec &&
+          f0Hz == other.f0Hz &&
+          midiFractional == other.midiFractional &&
+          confidence == other.confidence &&
+          voiced == other.voiced &&
+          rmsDbfs == other.rmsDbfs &&
+          peakDbfs == other.peakDbfs &&
+          loudnessLufs == other.loudnessLufs &&
+          isFinal == other.isFinal &&
+          speechProb == other.speechProb &&
+          noiseFloorDbfs == other.noiseFloorDbfs;
+}
+
+/// Throttling applied to the `PitchEvent` stream.
+class PitchStreamOptions {
+  /// Only emit when voicing flips or the pitch moves by `min_change_cents` or more.
+  final bool onlyOnChange;
+  final double minChangeCents;
+  /// Upper bound on events per second of audio; 0 disables the cap.
+  final int maxEventsPerSec;
+  /// Stream each frame once the pitch decoder has committed it, with
+  /// `is_final` set, rather than as soon as it is analysed. Final events
+  /// trail the live ones by the decoder's lag. Otherwise a live event not
+  /// yet final is followed by its final update, with the same
+  /// `sample_position`, once the decoder commits the frame.
+  final bool finalOnly;
+
+  const PitchStreamOptions({
+    required this.onlyOnChange,
+    required this.minChangeCents,
+    required this.maxEventsPerSec,
+    required this.finalOnly,
+  });
+
+  @override
+  int get hashCode =>
+      onlyOnChange.hashCode ^
+      minChangeCents.hashCode ^
+      maxEventsPerSec.hashCode ^
+      finalOnly.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is PitchStreamOptions &&
+          runtimeType == other.runtimeType &&
+          onlyOnChange == other.onlyOnChange &&
+          minChangeCents == other.minChangeCents &&
+          maxEventsPerSec == other.maxEventsPerSec &&
+          finalOnly == other.finalOnly;
+}
+
+/// Options of `AudioAnalyzer::create_score_stream`.
+class ScoreStreamOptions {
+  /// Part of the chart to score: 0, or 1 for the second singer of a duet.
+  final int track;
+  /// Score against every part and credit the best, for a singer free to
+  /// take either part of a duet; `track` is then ignored. Events of each
+  /// part are told apart by `ScoreEvent::track`.
+  final bool harmony;
+  final Difficulty difficulty;
+  final bool lineBonus;
+  /// Stream time (the clock of `PitchEvent::time_sec`) at which the song's
+  /// audio starts playing.
+  final double songStartSec;
+
+  const ScoreStreamOptions({
+    required this.track,
+    required this.harmony,
+    required this.difficulty,
+    required this.lineBonus,
+    required this.songStartSec,
+  });
+
+  @override
+  int get hashCode =>
+      track.hashCode ^
+      harmony.hashCode ^
+      difficulty.hashCode ^
+      lineBonus.hashCode ^
+      songStartSec.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ScoreStreamOptions &&
+          runtimeType == other.runtimeType &&
+          track == other.track &&
+          harmony == other.harmony &&
+          difficulty == other.difficulty &&
+          lineBonus == other.lineBonus &&
+          songStartSec == other.songStartSec;
+}
+
+/// Options of `Party::add_singer`.
+class SingerOptions {
+  final String name;
+  /// Part of the chart the singer is scored against; see
+  /// `ScoreStreamOptions`.
+  final int track;
+  final bool harmony;
+  final Difficulty difficulty;
+  final bool lineBonus;
+  /// Party clock time of the first sample of the singer's stream.
+  final double streamStartSec;
+
+  const SingerOptions({
+    required this.name,
+    required this.track,
+    required this.harmony,
+    required this.difficulty,
+    required this.lineBonus,
+    required this.streamStartSec,
+  });
+
+  @override
+  int get hashCode =>
+      name.hashCode ^
+      track.hashCode ^
+      harmony.hashCode ^
+      difficulty.hashCode ^
+      lineBonus.hashCode ^
+      streamStartSec.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SingerOptions &&
+          runtimeType == other.runtimeType &&
+          name == other.name &&
+          track == other.track &&
+          harmony == other.harmony &&
+          difficulty == other.difficulty &&
+          lineBonus == other.lineBonus &&
+          streamStartSec == other.streamStartSec;
 }
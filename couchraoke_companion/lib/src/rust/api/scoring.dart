@@ -0,0 +1,138 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+enum Difficulty {
+  easy,
+  medium,
+  hard,
+  ;
+}
+
+/// Live feedback on a finished note or line. Freestyle notes and lines
+/// without scored notes produce no events.
+class ScoreEvent {
+  final ScoreEventKind kind;
+  /// Song time at which the note or line ends, in seconds of the song audio.
+  final double songTimeSec;
+  /// Index of the scored part in the song's tracks.
+  final int track;
+  /// Index of the line in the track.
+  final int line;
+  /// Index of the note in the track, for note events.
+  final int? note;
+  /// Points of the note, or bonus of the line.
+  final double points;
+  /// Running score after this event, as `ScoreTotals::rounded`.
+  final int totalScore;
+  /// Golden notes hit in a row, up to and including this event.
+  final int goldenStreak;
+
+  const ScoreEvent({
+    required this.kind,
+    required this.songTimeSec,
+    required this.track,
+    required this.line,
+    required this.note,
+    required this.points,
+    required this.totalScore,
+    required this.goldenStreak,
+  });
+
+  @override
+  int get hashCode =>
+      kind.hashCode ^
+      songTimeSec.hashCode ^
+      track.hashCode ^
+      line.hashCode ^
+      note.hashCode ^
+      points.hashCode ^
+      totalScore.hashCode ^
+      goldenStreak.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ScoreEvent &&
+          runtimeType == other.runtimeType &&
+          kind == other.kind &&
+          songTimeSec == other.songTimeSec &&
+          track == other.track &&
+          line == other.line &&
+          note == other.note &&
+          points == other.points &&
+          totalScore == other.totalScore &&
+          goldenStreak == other.goldenStreak;
+}
+
+enum ScoreEventKind {
+  /// A note finished with at least half of its beats hit.
+  noteHit,
+  noteMissed,
+  lineFinished,
+  /// A line finished with every beat hit.
+  perfectLine,
+  ;
+}
+
+/// Points of a session so far.
+class ScoreTotals {
+  /// Points of normal and rap notes.
+  final double notes;
+  /// Points of golden notes.
+  final double golden;
+  final double lineBonus;
+
+  const ScoreTotals({
+    required this.notes,
+    required this.golden,
+    required this.lineBonus,
+  });
+
+  @override
+  int get hashCode => notes.hashCode ^ golden.hashCode ^ lineBonus.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ScoreTotals &&
+          runtimeType == other.runtimeType &&
+          notes == other.notes &&
+          golden == other.golden &&
+          lineBonus == other.lineBonus;
+}
+
+/// Final or running result of one singer.
+class SingerResult {
+  final String name;
+  /// Part the result was scored on; the best one in harmony mode.
+  final BigInt track;
+  final ScoreTotals totals;
+  /// `totals` as UltraStar Deluxe shows them.
+  final int score;
+
+  const SingerResult({
+    required this.name,
+    required this.track,
+    required this.totals,
+    required this.score,
+  });
+
+  @override
+  int get hashCode =>
+      name.hashCode ^ track.hashCode ^ totals.hashCode ^ score.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SingerResult &&
+          runtimeType == other.runtimeType &&
+          name == other.name &&
+          track == other.track &&
+          totals == other.totals &&
+          score == other.score;
+}
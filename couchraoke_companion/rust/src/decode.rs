@@ -0,0 +1,145 @@
+use std::ops::ControlFlow;
+
+use log::error;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{Decoder, DecoderOptions};
+use symphonia::core::conv::ConvertibleSample;
+use symphonia::core::errors::Error;
+use symphonia::core::formats::{FormatOptions, FormatReader};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use symphonia::core::sample::Sample;
+
+/// Stream parameters of the default track, known before the first packet.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StreamInfo {
+    pub sample_rate: u32,
+    pub channels: usize,
+    /// Total frames per channel, if the container reports it.
+    pub n_frames: Option<u64>,
+}
+
+/// Decoder for the default track of an audio file, probed through symphonia.
+pub(crate) struct FileDecoder {
+    path: String,
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+    info: StreamInfo,
+}
+
+impl FileDecoder {
+    pub fn open(path: &str) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+        let mut hint = Hint::new();
+        if let Some(ext) = std::path::Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+        {
+            hint.with_extension(ext);
+        }
+
+        let probed = symphonia::default::get_probe().format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )?;
+
+        let format = probed.format;
+        let (track_id, codec_params, info) = {
+            let track = format
+                .default_track()
+                .ok_or_else(|| anyhow::anyhow!("no default track found"))?;
+            let codec_params = track.codec_params.clone();
+            let sample_rate = codec_params
+                .sample_rate
+                .ok_or_else(|| anyhow::anyhow!("missing sample rate"))?;
+            let channels = codec_params
+                .channels
+                .ok_or_else(|| anyhow::anyhow!("missing channel information"))?
+                .count();
+            let info = StreamInfo {
+                sample_rate,
+                channels,
+                n_frames: codec_params.n_frames,
+            };
+            (track.id, codec_params, info)
+        };
+
+        let decoder =
+            symphonia::default::get_codecs().make(&codec_params, &DecoderOptions::default())?;
+
+        Ok(Self {
+            path: path.to_string(),
+            format,
+            decoder,
+            track_id,
+            info,
+        })
+    }
+
+    pub fn info(&self) -> StreamInfo {
+        self.info
+    }
+
+    /// Decode every packet, passing each one to `on_samples` as interleaved
+    /// samples. Undecodable packets are logged and skipped. Returns the number
+    /// of frames (samples per channel) decoded.
+    pub fn decode_all<S, F>(self, mut on_samples: F) -> anyhow::Result<u64>
+    where
+        S: Sample + ConvertibleSample,
+        F: FnMut(&[S]) -> anyhow::Result<()>,
+    {
+        self.decode_until(|samples| on_samples(samples).map(|()| ControlFlow::Continue(())))
+    }
+
+    /// Like `decode_all`, but stops after the packet for which `on_samples`
+    /// returns `ControlFlow::Break`.
+    pub fn decode_until<S, F>(mut self, mut on_samples: F) -> anyhow::Result<u64>
+    where
+        S: Sample + ConvertibleSample,
+        F: FnMut(&[S]) -> anyhow::Result<ControlFlow<()>>,
+    {
+        let mut total_frames: u64 = 0;
+
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(Error::IoError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    break
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            let decoded = match self.decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                Err(Error::DecodeError(err)) => {
+                    error!("Decode error in {}: {}", self.path, err);
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            let frame_count = decoded.frames() as u64;
+            let spec = *decoded.spec();
+            let mut sample_buffer = SampleBuffer::<S>::new(decoded.capacity() as u64, spec);
+            sample_buffer.copy_interleaved_ref(decoded);
+            let flow = on_samples(sample_buffer.samples())?;
+
+            total_frames += frame_count;
+            if flow.is_break() {
+                break;
+            }
+        }
+
+        Ok(total_frames)
+    }
+}
@@ -38,7 +38,7 @@ flutter_rust_bridge::frb_generated_boilerplate!(
     default_rust_auto_opaque = RustAutoOpaqueMoi,
 );
 pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_VERSION: &str = "2.11.1";
-pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = 66788347;
+pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = 1649513208;
 
 // Section: executor
 
@@ -46,7 +46,7 @@ flutter_rust_bridge::frb_generated_default_handler!();
 
 // Section: wire_funcs
 
-fn wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_clear_backing_track_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -54,7 +54,7 @@ fn wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "AudioAnalyzer_create_stream",
+            debug_name: "AudioAnalyzer_clear_backing_track",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -71,10 +71,6 @@ fn wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
             let api_that = <RustOpaqueMoi<
                 flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
             >>::sse_decode(&mut deserializer);
-            let api_sink =
-                <StreamSink<u8, flutter_rust_bridge::for_generated::SseCodec>>::sse_decode(
-                    &mut deserializer,
-                );
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
@@ -93,10 +89,7 @@ fn wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
                     }
                     let mut api_that_guard = api_that_guard.unwrap();
                     let output_ok = Result::<_, ()>::Ok({
-                        crate::pyin::api::AudioAnalyzer::create_stream(
-                            &mut *api_that_guard,
-                            api_sink,
-                        );
+                        crate::pyin::api::AudioAnalyzer::clear_backing_track(&mut *api_that_guard);
                     })?;
                     Ok(output_ok)
                 })())
@@ -104,7 +97,7 @@ fn wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
         },
     )
 }
-fn wire__crate__pyin__api__AudioAnalyzer_new_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_clear_target_notes_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -112,7 +105,7 @@ fn wire__crate__pyin__api__AudioAnalyzer_new_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "AudioAnalyzer_new",
+            debug_name: "AudioAnalyzer_clear_target_notes",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -126,18 +119,36 @@ fn wire__crate__pyin__api__AudioAnalyzer_new_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_config = <crate::pyin::api::PitchConfig>::sse_decode(&mut deserializer);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, crate::pyin::PyinError>((move || {
-                    let output_ok = crate::pyin::api::AudioAnalyzer::new(api_config)?;
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok({
+                        crate::pyin::api::AudioAnalyzer::clear_target_notes(&mut *api_that_guard);
+                    })?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_create_score_stream_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -145,7 +156,7 @@ fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "AudioAnalyzer_process_chunk",
+            debug_name: "AudioAnalyzer_create_score_stream",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -162,7 +173,12 @@ fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
             let api_that = <RustOpaqueMoi<
                 flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
             >>::sse_decode(&mut deserializer);
-            let api_pcm16le_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::scoring::ScoreEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            let api_chart = <Vec<u8>>::sse_decode(&mut deserializer);
+            let api_options = <crate::pyin::api::ScoreStreamOptions>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, crate::pyin::PyinError>((move || {
@@ -180,9 +196,11 @@ fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
                         }
                     }
                     let mut api_that_guard = api_that_guard.unwrap();
-                    let output_ok = crate::pyin::api::AudioAnalyzer::process_chunk(
+                    let output_ok = crate::pyin::api::AudioAnalyzer::create_score_stream(
                         &mut *api_that_guard,
-                        api_pcm16le_bytes,
+                        api_sink,
+                        api_chart,
+                        api_options,
                     )?;
                     Ok(output_ok)
                 })())
@@ -190,7 +208,7 @@ fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
         },
     )
 }
-fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -198,7 +216,7 @@ fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "AudioAnalyzer_process_chunk_collect",
+            debug_name: "AudioAnalyzer_create_stream",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -215,10 +233,14 @@ fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_impl(
             let api_that = <RustOpaqueMoi<
                 flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
             >>::sse_decode(&mut deserializer);
-            let api_pcm16le_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::pyin::api::PitchEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            let api_options = <crate::pyin::api::PitchStreamOptions>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                transform_result_sse::<_, ()>((move || {
                     let mut api_that_guard = None;
                     let decode_indices_ =
                         flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
@@ -233,17 +255,20 @@ fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_impl(
                         }
                     }
                     let mut api_that_guard = api_that_guard.unwrap();
-                    let output_ok = crate::pyin::api::AudioAnalyzer::process_chunk_collect(
-                        &mut *api_that_guard,
-                        &api_pcm16le_bytes,
-                    )?;
+                    let output_ok = Result::<_, ()>::Ok({
+                        crate::pyin::api::AudioAnalyzer::create_stream(
+                            &mut *api_that_guard,
+                            api_sink,
+                            api_options,
+                        );
+                    })?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__fingerprint__get_batch_fingerprints_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_drain_score_events_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -251,7 +276,7 @@ fn wire__crate__fingerprint__get_batch_fingerprints_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_batch_fingerprints",
+            debug_name: "AudioAnalyzer_drain_score_events",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -265,19 +290,36 @@ fn wire__crate__fingerprint__get_batch_fingerprints_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_paths = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok =
-                        Result::<_, ()>::Ok(crate::fingerprint::get_batch_fingerprints(api_paths))?;
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok(
+                        crate::pyin::api::AudioAnalyzer::drain_score_events(&mut *api_that_guard),
+                    )?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__pyin__api__init_logging_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_finish_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -285,7 +327,7 @@ fn wire__crate__pyin__api__init_logging_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "init_logging",
+            debug_name: "AudioAnalyzer_finish",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -299,19 +341,34 @@ fn wire__crate__pyin__api__init_logging_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok({
-                        crate::pyin::api::init_logging();
-                    })?;
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::finish(&mut *api_that_guard)?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__pyin__api__new_processor_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_finish_collect_events_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -319,7 +376,7 @@ fn wire__crate__pyin__api__new_processor_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "new_processor",
+            debug_name: "AudioAnalyzer_finish_collect_events",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -333,24 +390,36 @@ fn wire__crate__pyin__api__new_processor_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_sample_rate_hz = <u32>::sse_decode(&mut deserializer);
-            let api_window_ms = <u32>::sse_decode(&mut deserializer);
-            let api_hop_ms = <u32>::sse_decode(&mut deserializer);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::pyin::api::new_processor(
-                        api_sample_rate_hz,
-                        api_window_ms,
-                        api_hop_ms,
-                    ))?;
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::finish_collect_events(
+                        &mut *api_that_guard,
+                    )?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__pyin__api__push_and_get_midi_impl(
+fn wire__crate__pyin__api__AudioAnalyzer_finish_scoring_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -358,7 +427,7 @@ fn wire__crate__pyin__api__push_and_get_midi_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "push_and_get_midi",
+            debug_name: "AudioAnalyzer_finish_scoring",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -372,131 +441,1558 @@ fn wire__crate__pyin__api__push_and_get_midi_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_proc = <RustOpaqueMoi<
-                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>,
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok =
+                        crate::pyin::api::AudioAnalyzer::finish_scoring(&mut *api_that_guard)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_latest_level_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_latest_level",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
             >>::sse_decode(&mut deserializer);
-            let api_pcm16le_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let mut api_proc_guard = None;
+                    let mut api_that_guard = None;
                     let decode_indices_ =
                         flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
                             flutter_rust_bridge::for_generated::LockableOrderInfo::new(
-                                &api_proc, 0, true,
+                                &api_that, 0, false,
                             ),
                         ]);
                     for i in decode_indices_ {
                         match i {
-                            0 => api_proc_guard = Some(api_proc.lockable_decode_sync_ref_mut()),
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref()),
                             _ => unreachable!(),
                         }
                     }
-                    let mut api_proc_guard = api_proc_guard.unwrap();
-                    let output_ok = Result::<_, ()>::Ok(crate::pyin::api::push_and_get_midi(
-                        &mut *api_proc_guard,
-                        api_pcm16le_bytes,
-                    ))?;
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok(
+                        crate::pyin::api::AudioAnalyzer::latest_level(&*api_that_guard),
+                    )?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-
-// Section: related_funcs
-
-flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
-    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>
-);
-flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
-    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>
-);
-
-// Section: dart2rust
-
-impl SseDecode for flutter_rust_bridge::for_generated::anyhow::Error {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <String>::sse_decode(deserializer);
-        return flutter_rust_bridge::for_generated::anyhow::anyhow!("{}", inner);
-    }
+fn wire__crate__pyin__api__AudioAnalyzer_new_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_new",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_config = <crate::pyin::api::PitchConfig>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let output_ok = crate::pyin::api::AudioAnalyzer::new(api_config)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
 }
-
-impl SseDecode for AudioAnalyzer {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
-        >>::sse_decode(deserializer);
-        return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
-    }
+fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_process_chunk",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_pcm_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::process_chunk(
+                        &mut *api_that_guard,
+                        api_pcm_bytes,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_process_chunk_collect",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_pcm_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::process_chunk_collect(
+                        &mut *api_that_guard,
+                        &api_pcm_bytes,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_events_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_process_chunk_collect_events",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_pcm_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::process_chunk_collect_events(
+                        &mut *api_that_guard,
+                        &api_pcm_bytes,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_fractional_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_process_chunk_collect_fractional",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_pcm_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok =
+                        crate::pyin::api::AudioAnalyzer::process_chunk_collect_fractional(
+                            &mut *api_that_guard,
+                            &api_pcm_bytes,
+                        )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_restore_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_restore",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_blob = <Vec<u8>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok =
+                        crate::pyin::api::AudioAnalyzer::restore(&mut *api_that_guard, &api_blob)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_set_backing_track_delay_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_set_backing_track_delay",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_delay_sec = <f64>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok({
+                        crate::pyin::api::AudioAnalyzer::set_backing_track_delay(
+                            &mut *api_that_guard,
+                            api_delay_sec,
+                        );
+                    })?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_set_backing_track_file_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_set_backing_track_file",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            let api_config = <crate::pyin::BleedConfig>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::set_backing_track_file(
+                        &mut *api_that_guard,
+                        api_path,
+                        api_config,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_set_song_start_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_set_song_start",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_song_start_sec = <f64>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::set_song_start(
+                        &mut *api_that_guard,
+                        api_song_start_sec,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_set_stream_options_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_set_stream_options",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_options = <crate::pyin::api::PitchStreamOptions>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok({
+                        crate::pyin::api::AudioAnalyzer::set_stream_options(
+                            &mut *api_that_guard,
+                            api_options,
+                        );
+                    })?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_set_target_notes_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_set_target_notes",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_notes = <Vec<crate::pyin::TargetNote>>::sse_decode(&mut deserializer);
+            let api_config = <crate::pyin::ScorePriorConfig>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::set_target_notes(
+                        &mut *api_that_guard,
+                        api_notes,
+                        api_config,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_snapshot_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_snapshot",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, false,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok(
+                        crate::pyin::api::AudioAnalyzer::snapshot(&*api_that_guard),
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__AudioAnalyzer_start_scoring_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "AudioAnalyzer_start_scoring",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+            >>::sse_decode(&mut deserializer);
+            let api_chart = <Vec<u8>>::sse_decode(&mut deserializer);
+            let api_options = <crate::pyin::api::ScoreStreamOptions>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::AudioAnalyzer::start_scoring(
+                        &mut *api_that_guard,
+                        &api_chart,
+                        api_options,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__Party_add_singer_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "Party_add_singer",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>,
+            >>::sse_decode(&mut deserializer);
+            let api_config = <crate::pyin::api::PitchConfig>::sse_decode(&mut deserializer);
+            let api_options = <crate::pyin::api::SingerOptions>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::Party::add_singer(
+                        &mut *api_that_guard,
+                        api_config,
+                        api_options,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__Party_drain_score_events_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "Party_drain_score_events",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>,
+            >>::sse_decode(&mut deserializer);
+            let api_singer = <u32>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::Party::drain_score_events(
+                        &mut *api_that_guard,
+                        api_singer,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__Party_finish_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "Party_finish",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::Party::finish(&mut *api_that_guard)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__Party_new_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "Party_new",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_chart = <Vec<u8>>::sse_decode(&mut deserializer);
+            let api_song_start_sec = <f64>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let output_ok = crate::pyin::api::Party::new(api_chart, api_song_start_sec)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__Party_process_chunk_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "Party_process_chunk",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>,
+            >>::sse_decode(&mut deserializer);
+            let api_singer = <u32>::sse_decode(&mut deserializer);
+            let api_pcm_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok = crate::pyin::api::Party::process_chunk(
+                        &mut *api_that_guard,
+                        api_singer,
+                        api_pcm_bytes,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__Party_results_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "Party_results",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, false,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_that_guard = api_that_guard.unwrap();
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::pyin::api::Party::results(&*api_that_guard))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__analyze_file_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "analyze_file",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            let api_options =
+                <crate::pyin::api::FileAnalysisOptions>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let output_ok = crate::pyin::api::analyze_file(api_path, api_options)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__analyze_file_stream_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "analyze_file_stream",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            let api_options =
+                <crate::pyin::api::FileAnalysisOptions>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::pyin::api::FileAnalysisUpdate,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::pyin::PyinError>((move || {
+                    let output_ok =
+                        crate::pyin::api::analyze_file_stream(api_path, api_options, api_sink)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__fingerprint__get_batch_fingerprints_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_batch_fingerprints",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_paths = <Vec<String>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::fingerprint::get_batch_fingerprints(api_paths))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__init_logging_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "init_logging",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok({
+                        crate::pyin::api::init_logging();
+                    })?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__new_processor_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "new_processor",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_sample_rate_hz = <u32>::sse_decode(&mut deserializer);
+            let api_window_ms = <u32>::sse_decode(&mut deserializer);
+            let api_hop_ms = <u32>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::pyin::api::new_processor(
+                        api_sample_rate_hz,
+                        api_window_ms,
+                        api_hop_ms,
+                    ))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__pyin__api__push_and_get_midi_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "push_and_get_midi",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_proc = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>,
+            >>::sse_decode(&mut deserializer);
+            let api_pcm16le_bytes = <Vec<u8>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_proc_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_proc, 0, true,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_proc_guard = Some(api_proc.lockable_decode_sync_ref_mut()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let mut api_proc_guard = api_proc_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok(crate::pyin::api::push_and_get_midi(
+                        &mut *api_proc_guard,
+                        api_pcm16le_bytes,
+                    ))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+
+// Section: related_funcs
+
+flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
+    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>
+);
+flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
+    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>
+);
+flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
+    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>
+);
+
+// Section: dart2rust
+
+impl SseDecode for flutter_rust_bridge::for_generated::anyhow::Error {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return flutter_rust_bridge::for_generated::anyhow::anyhow!("{}", inner);
+    }
+}
+
+impl SseDecode for AudioAnalyzer {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <RustOpaqueMoi<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>,
+        >>::sse_decode(deserializer);
+        return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
+    }
+}
+
+impl SseDecode for Party {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <RustOpaqueMoi<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>,
+        >>::sse_decode(deserializer);
+        return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
+    }
 }
 
 impl SseDecode for PyinProcessor {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>,
-        >>::sse_decode(deserializer);
-        return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
+        let mut inner = <RustOpaqueMoi<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>,
+        >>::sse_decode(deserializer);
+        return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
+    }
+}
+
+impl SseDecode
+    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <usize>::sse_decode(deserializer);
+        return decode_rust_opaque_moi(inner);
+    }
+}
+
+impl SseDecode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <usize>::sse_decode(deserializer);
+        return decode_rust_opaque_moi(inner);
+    }
+}
+
+impl SseDecode
+    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <usize>::sse_decode(deserializer);
+        return decode_rust_opaque_moi(inner);
+    }
+}
+
+impl SseDecode
+    for StreamSink<
+        crate::pyin::api::FileAnalysisUpdate,
+        flutter_rust_bridge::for_generated::SseCodec,
+    >
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode
+    for StreamSink<crate::pyin::api::PitchEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode
+    for StreamSink<crate::scoring::ScoreEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode for String {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <Vec<u8>>::sse_decode(deserializer);
+        return String::from_utf8(inner).unwrap();
+    }
+}
+
+impl SseDecode for crate::fingerprint::AudioFingerprint {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_path = <String>::sse_decode(deserializer);
+        let mut var_fingerprint = <String>::sse_decode(deserializer);
+        let mut var_durationSecs = <f64>::sse_decode(deserializer);
+        return crate::fingerprint::AudioFingerprint {
+            path: var_path,
+            fingerprint: var_fingerprint,
+            duration_secs: var_durationSecs,
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::BleedConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_delaySec = <f64>::sse_decode(deserializer);
+        let mut var_filterMs = <f32>::sse_decode(deserializer);
+        let mut var_stepSize = <f32>::sse_decode(deserializer);
+        return crate::pyin::BleedConfig {
+            delay_sec: var_delaySec,
+            filter_ms: var_filterMs,
+            step_size: var_stepSize,
+        };
+    }
+}
+
+impl SseDecode for bool {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_u8().unwrap() != 0
+    }
+}
+
+impl SseDecode for crate::pyin::ChannelMix {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                return crate::pyin::ChannelMix::Downmix;
+            }
+            1 => {
+                let mut var_field0 = <u16>::sse_decode(deserializer);
+                return crate::pyin::ChannelMix::Pick(var_field0);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::scoring::Difficulty {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::scoring::Difficulty::Easy,
+            1 => crate::scoring::Difficulty::Medium,
+            2 => crate::scoring::Difficulty::Hard,
+            _ => unreachable!("Invalid variant for Difficulty: {}", inner),
+        };
     }
 }
 
-impl SseDecode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>>
-{
+impl SseDecode for f32 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <usize>::sse_decode(deserializer);
-        return decode_rust_opaque_moi(inner);
+        deserializer.cursor.read_f32::<NativeEndian>().unwrap()
     }
 }
 
-impl SseDecode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>>
-{
+impl SseDecode for f64 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <usize>::sse_decode(deserializer);
-        return decode_rust_opaque_moi(inner);
+        deserializer.cursor.read_f64::<NativeEndian>().unwrap()
     }
 }
 
-impl SseDecode for StreamSink<u8, flutter_rust_bridge::for_generated::SseCodec> {
+impl SseDecode for crate::pyin::api::FileAnalysisOptions {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <String>::sse_decode(deserializer);
-        return StreamSink::deserialize(inner);
+        let mut var_analysisSampleRateHz = <u32>::sse_decode(deserializer);
+        let mut var_updateIntervalMs = <u32>::sse_decode(deserializer);
+        let mut var_windowSizeMs = <u32>::sse_decode(deserializer);
+        let mut var_referenceA4Hz = <f32>::sse_decode(deserializer);
+        let mut var_startSec = <f64>::sse_decode(deserializer);
+        let mut var_endSec = <Option<f64>>::sse_decode(deserializer);
+        return crate::pyin::api::FileAnalysisOptions {
+            analysis_sample_rate_hz: var_analysisSampleRateHz,
+            update_interval_ms: var_updateIntervalMs,
+            window_size_ms: var_windowSizeMs,
+            reference_a4_hz: var_referenceA4Hz,
+            start_sec: var_startSec,
+            end_sec: var_endSec,
+        };
     }
 }
 
-impl SseDecode for String {
+impl SseDecode for crate::pyin::api::FileAnalysisUpdate {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <Vec<u8>>::sse_decode(deserializer);
-        return String::from_utf8(inner).unwrap();
+        let mut var_processedSec = <f64>::sse_decode(deserializer);
+        let mut var_totalSec = <Option<f64>>::sse_decode(deserializer);
+        let mut var_contour = <Option<crate::pyin::api::FileContour>>::sse_decode(deserializer);
+        return crate::pyin::api::FileAnalysisUpdate {
+            processed_sec: var_processedSec,
+            total_sec: var_totalSec,
+            contour: var_contour,
+        };
     }
 }
 
-impl SseDecode for crate::fingerprint::AudioFingerprint {
+impl SseDecode for crate::pyin::api::FileContour {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut var_path = <String>::sse_decode(deserializer);
-        let mut var_fingerprint = <String>::sse_decode(deserializer);
-        let mut var_durationSecs = <f64>::sse_decode(deserializer);
-        return crate::fingerprint::AudioFingerprint {
-            path: var_path,
-            fingerprint: var_fingerprint,
-            duration_secs: var_durationSecs,
+        let mut var_sourceSampleRateHz = <u32>::sse_decode(deserializer);
+        let mut var_durationSec = <f64>::sse_decode(deserializer);
+        let mut var_events = <Vec<crate::pyin::api::PitchEvent>>::sse_decode(deserializer);
+        return crate::pyin::api::FileContour {
+            source_sample_rate_hz: var_sourceSampleRateHz,
+            duration_sec: var_durationSec,
+            events: var_events,
         };
     }
 }
 
-impl SseDecode for f64 {
+impl SseDecode for crate::pyin::LevelReading {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_f64::<NativeEndian>().unwrap()
+        let mut var_rmsDbfs = <f32>::sse_decode(deserializer);
+        let mut var_peakDbfs = <f32>::sse_decode(deserializer);
+        let mut var_momentaryLufs = <f32>::sse_decode(deserializer);
+        let mut var_shortTermLufs = <f32>::sse_decode(deserializer);
+        let mut var_clippedSamples = <u32>::sse_decode(deserializer);
+        return crate::pyin::LevelReading {
+            rms_dbfs: var_rmsDbfs,
+            peak_dbfs: var_peakDbfs,
+            momentary_lufs: var_momentaryLufs,
+            short_term_lufs: var_shortTermLufs,
+            clipped_samples: var_clippedSamples,
+        };
     }
 }
 
@@ -526,6 +2022,30 @@ impl SseDecode for Vec<crate::fingerprint::AudioFingerprint> {
     }
 }
 
+impl SseDecode for Vec<crate::pyin::api::PitchEvent> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::pyin::api::PitchEvent>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<f32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<f32>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
 impl SseDecode for Vec<u8> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -538,16 +2058,205 @@ impl SseDecode for Vec<u8> {
     }
 }
 
+impl SseDecode for Vec<crate::scoring::ScoreEvent> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::scoring::ScoreEvent>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::scoring::SingerResult> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::scoring::SingerResult>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::pyin::TargetNote> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::pyin::TargetNote>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Option<f32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<f32>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<f64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<f64>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<crate::pyin::api::FileContour> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<crate::pyin::api::FileContour>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<crate::pyin::LevelReading> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<crate::pyin::LevelReading>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<u32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<u32>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for crate::pyin::PcmFormat {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::pyin::PcmFormat::I16LE,
+            1 => crate::pyin::PcmFormat::F32LE,
+            2 => crate::pyin::PcmFormat::I16BE,
+            3 => crate::pyin::PcmFormat::I24LE,
+            4 => crate::pyin::PcmFormat::I24BE,
+            5 => crate::pyin::PcmFormat::I32LE,
+            6 => crate::pyin::PcmFormat::I32BE,
+            7 => crate::pyin::PcmFormat::F32BE,
+            _ => unreachable!("Invalid variant for PcmFormat: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::PitchAlgorithm {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::pyin::PitchAlgorithm::Pyin,
+            1 => crate::pyin::PitchAlgorithm::Yin,
+            2 => crate::pyin::PitchAlgorithm::Mpm,
+            3 => crate::pyin::PitchAlgorithm::Hps,
+            _ => unreachable!("Invalid variant for PitchAlgorithm: {}", inner),
+        };
+    }
+}
+
 impl SseDecode for crate::pyin::api::PitchConfig {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut var_sampleRateHz = <u32>::sse_decode(deserializer);
         let mut var_updateIntervalMs = <u32>::sse_decode(deserializer);
         let mut var_windowSizeMs = <u32>::sse_decode(deserializer);
+        let mut var_referenceA4Hz = <f32>::sse_decode(deserializer);
+        let mut var_pcmFormat = <crate::pyin::PcmFormat>::sse_decode(deserializer);
+        let mut var_channels = <u16>::sse_decode(deserializer);
+        let mut var_channelMix = <crate::pyin::ChannelMix>::sse_decode(deserializer);
+        let mut var_analysisSampleRateHz = <Option<u32>>::sse_decode(deserializer);
+        let mut var_vadEnabled = <bool>::sse_decode(deserializer);
+        let mut var_octaveCorrectionEnabled = <bool>::sse_decode(deserializer);
+        let mut var_algorithm = <crate::pyin::PitchAlgorithm>::sse_decode(deserializer);
+        let mut var_multiResolutionEnabled = <bool>::sse_decode(deserializer);
         return crate::pyin::api::PitchConfig {
             sample_rate_hz: var_sampleRateHz,
             update_interval_ms: var_updateIntervalMs,
             window_size_ms: var_windowSizeMs,
+            reference_a4_hz: var_referenceA4Hz,
+            pcm_format: var_pcmFormat,
+            channels: var_channels,
+            channel_mix: var_channelMix,
+            analysis_sample_rate_hz: var_analysisSampleRateHz,
+            vad_enabled: var_vadEnabled,
+            octave_correction_enabled: var_octaveCorrectionEnabled,
+            algorithm: var_algorithm,
+            multi_resolution_enabled: var_multiResolutionEnabled,
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::api::PitchEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_samplePosition = <u64>::sse_decode(deserializer);
+        let mut var_timeSec = <f64>::sse_decode(deserializer);
+        let mut var_f0Hz = <Option<f32>>::sse_decode(deserializer);
+        let mut var_midiFractional = <Option<f32>>::sse_decode(deserializer);
+        let mut var_confidence = <f32>::sse_decode(deserializer);
+        let mut var_voiced = <bool>::sse_decode(deserializer);
+        let mut var_rmsDbfs = <f32>::sse_decode(deserializer);
+        let mut var_peakDbfs = <f32>::sse_decode(deserializer);
+        let mut var_loudnessLufs = <f32>::sse_decode(deserializer);
+        let mut var_isFinal = <bool>::sse_decode(deserializer);
+        let mut var_speechProb = <Option<f32>>::sse_decode(deserializer);
+        let mut var_noiseFloorDbfs = <Option<f32>>::sse_decode(deserializer);
+        return crate::pyin::api::PitchEvent {
+            sample_position: var_samplePosition,
+            time_sec: var_timeSec,
+            f0_hz: var_f0Hz,
+            midi_fractional: var_midiFractional,
+            confidence: var_confidence,
+            voiced: var_voiced,
+            rms_dbfs: var_rmsDbfs,
+            peak_dbfs: var_peakDbfs,
+            loudness_lufs: var_loudnessLufs,
+            is_final: var_isFinal,
+            speech_prob: var_speechProb,
+            noise_floor_dbfs: var_noiseFloorDbfs,
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::api::PitchStreamOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_onlyOnChange = <bool>::sse_decode(deserializer);
+        let mut var_minChangeCents = <f32>::sse_decode(deserializer);
+        let mut var_maxEventsPerSec = <u32>::sse_decode(deserializer);
+        let mut var_finalOnly = <bool>::sse_decode(deserializer);
+        return crate::pyin::api::PitchStreamOptions {
+            only_on_change: var_onlyOnChange,
+            min_change_cents: var_minChangeCents,
+            max_events_per_sec: var_maxEventsPerSec,
+            final_only: var_finalOnly,
         };
     }
 }
@@ -568,6 +2277,142 @@ impl SseDecode for crate::pyin::PyinError {
     }
 }
 
+impl SseDecode for crate::scoring::ScoreEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_kind = <crate::scoring::ScoreEventKind>::sse_decode(deserializer);
+        let mut var_songTimeSec = <f64>::sse_decode(deserializer);
+        let mut var_track = <u32>::sse_decode(deserializer);
+        let mut var_line = <u32>::sse_decode(deserializer);
+        let mut var_note = <Option<u32>>::sse_decode(deserializer);
+        let mut var_points = <f64>::sse_decode(deserializer);
+        let mut var_totalScore = <u32>::sse_decode(deserializer);
+        let mut var_goldenStreak = <u32>::sse_decode(deserializer);
+        return crate::scoring::ScoreEvent {
+            kind: var_kind,
+            song_time_sec: var_songTimeSec,
+            track: var_track,
+            line: var_line,
+            note: var_note,
+            points: var_points,
+            total_score: var_totalScore,
+            golden_streak: var_goldenStreak,
+        };
+    }
+}
+
+impl SseDecode for crate::scoring::ScoreEventKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::scoring::ScoreEventKind::NoteHit,
+            1 => crate::scoring::ScoreEventKind::NoteMissed,
+            2 => crate::scoring::ScoreEventKind::LineFinished,
+            3 => crate::scoring::ScoreEventKind::PerfectLine,
+            _ => unreachable!("Invalid variant for ScoreEventKind: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::ScorePriorConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_songStartSec = <f64>::sse_decode(deserializer);
+        let mut var_strength = <f32>::sse_decode(deserializer);
+        let mut var_sigmaCents = <f32>::sse_decode(deserializer);
+        let mut var_octaveWeight = <f32>::sse_decode(deserializer);
+        return crate::pyin::ScorePriorConfig {
+            song_start_sec: var_songStartSec,
+            strength: var_strength,
+            sigma_cents: var_sigmaCents,
+            octave_weight: var_octaveWeight,
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::api::ScoreStreamOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_track = <u32>::sse_decode(deserializer);
+        let mut var_harmony = <bool>::sse_decode(deserializer);
+        let mut var_difficulty = <crate::scoring::Difficulty>::sse_decode(deserializer);
+        let mut var_lineBonus = <bool>::sse_decode(deserializer);
+        let mut var_songStartSec = <f64>::sse_decode(deserializer);
+        return crate::pyin::api::ScoreStreamOptions {
+            track: var_track,
+            harmony: var_harmony,
+            difficulty: var_difficulty,
+            line_bonus: var_lineBonus,
+            song_start_sec: var_songStartSec,
+        };
+    }
+}
+
+impl SseDecode for crate::scoring::ScoreTotals {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_notes = <f64>::sse_decode(deserializer);
+        let mut var_golden = <f64>::sse_decode(deserializer);
+        let mut var_lineBonus = <f64>::sse_decode(deserializer);
+        return crate::scoring::ScoreTotals {
+            notes: var_notes,
+            golden: var_golden,
+            line_bonus: var_lineBonus,
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::api::SingerOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_name = <String>::sse_decode(deserializer);
+        let mut var_track = <u32>::sse_decode(deserializer);
+        let mut var_harmony = <bool>::sse_decode(deserializer);
+        let mut var_difficulty = <crate::scoring::Difficulty>::sse_decode(deserializer);
+        let mut var_lineBonus = <bool>::sse_decode(deserializer);
+        let mut var_streamStartSec = <f64>::sse_decode(deserializer);
+        return crate::pyin::api::SingerOptions {
+            name: var_name,
+            track: var_track,
+            harmony: var_harmony,
+            difficulty: var_difficulty,
+            line_bonus: var_lineBonus,
+            stream_start_sec: var_streamStartSec,
+        };
+    }
+}
+
+impl SseDecode for crate::scoring::SingerResult {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_name = <String>::sse_decode(deserializer);
+        let mut var_track = <usize>::sse_decode(deserializer);
+        let mut var_totals = <crate::scoring::ScoreTotals>::sse_decode(deserializer);
+        let mut var_score = <u32>::sse_decode(deserializer);
+        return crate::scoring::SingerResult {
+            name: var_name,
+            track: var_track,
+            totals: var_totals,
+            score: var_score,
+        };
+    }
+}
+
+impl SseDecode for crate::pyin::TargetNote {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_startSec = <f64>::sse_decode(deserializer);
+        let mut var_endSec = <f64>::sse_decode(deserializer);
+        let mut var_midi = <f32>::sse_decode(deserializer);
+        return crate::pyin::TargetNote {
+            start_sec: var_startSec,
+            end_sec: var_endSec,
+            midi: var_midi,
+        };
+    }
+}
+
 impl SseDecode for u16 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -582,6 +2427,13 @@ impl SseDecode for u32 {
     }
 }
 
+impl SseDecode for u64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_u64::<NativeEndian>().unwrap()
+    }
+}
+
 impl SseDecode for u8 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -608,13 +2460,6 @@ impl SseDecode for i32 {
     }
 }
 
-impl SseDecode for bool {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_u8().unwrap() != 0
-    }
-}
-
 fn pde_ffi_dispatcher_primary_impl(
     func_id: i32,
     port: flutter_rust_bridge::for_generated::MessagePort,
@@ -624,31 +2469,136 @@ fn pde_ffi_dispatcher_primary_impl(
 ) {
     // Codec=Pde (Serialization + dispatch), see doc to use other codecs
     match func_id {
-        1 => wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
+        1 => wire__crate__pyin__api__AudioAnalyzer_clear_backing_track_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        2 => wire__crate__pyin__api__AudioAnalyzer_clear_target_notes_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        3 => wire__crate__pyin__api__AudioAnalyzer_create_score_stream_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        4 => wire__crate__pyin__api__AudioAnalyzer_create_stream_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        5 => wire__crate__pyin__api__AudioAnalyzer_drain_score_events_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        6 => wire__crate__pyin__api__AudioAnalyzer_finish_impl(port, ptr, rust_vec_len, data_len),
+        7 => wire__crate__pyin__api__AudioAnalyzer_finish_collect_events_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        8 => wire__crate__pyin__api__AudioAnalyzer_finish_scoring_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        9 => wire__crate__pyin__api__AudioAnalyzer_latest_level_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        10 => wire__crate__pyin__api__AudioAnalyzer_new_impl(port, ptr, rust_vec_len, data_len),
+        11 => wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        12 => wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        13 => wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_events_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        14 => wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_fractional_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        15 => wire__crate__pyin__api__AudioAnalyzer_restore_impl(port, ptr, rust_vec_len, data_len),
+        16 => wire__crate__pyin__api__AudioAnalyzer_set_backing_track_delay_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        17 => wire__crate__pyin__api__AudioAnalyzer_set_backing_track_file_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        18 => wire__crate__pyin__api__AudioAnalyzer_set_song_start_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        19 => wire__crate__pyin__api__AudioAnalyzer_set_stream_options_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        2 => wire__crate__pyin__api__AudioAnalyzer_new_impl(port, ptr, rust_vec_len, data_len),
-        3 => wire__crate__pyin__api__AudioAnalyzer_process_chunk_impl(
+        20 => wire__crate__pyin__api__AudioAnalyzer_set_target_notes_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        4 => wire__crate__pyin__api__AudioAnalyzer_process_chunk_collect_impl(
+        21 => {
+            wire__crate__pyin__api__AudioAnalyzer_snapshot_impl(port, ptr, rust_vec_len, data_len)
+        }
+        22 => wire__crate__pyin__api__AudioAnalyzer_start_scoring_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        5 => {
+        23 => wire__crate__pyin__api__Party_add_singer_impl(port, ptr, rust_vec_len, data_len),
+        24 => {
+            wire__crate__pyin__api__Party_drain_score_events_impl(port, ptr, rust_vec_len, data_len)
+        }
+        25 => wire__crate__pyin__api__Party_finish_impl(port, ptr, rust_vec_len, data_len),
+        26 => wire__crate__pyin__api__Party_new_impl(port, ptr, rust_vec_len, data_len),
+        27 => wire__crate__pyin__api__Party_process_chunk_impl(port, ptr, rust_vec_len, data_len),
+        28 => wire__crate__pyin__api__Party_results_impl(port, ptr, rust_vec_len, data_len),
+        29 => wire__crate__pyin__api__analyze_file_impl(port, ptr, rust_vec_len, data_len),
+        30 => wire__crate__pyin__api__analyze_file_stream_impl(port, ptr, rust_vec_len, data_len),
+        31 => {
             wire__crate__fingerprint__get_batch_fingerprints_impl(port, ptr, rust_vec_len, data_len)
         }
-        6 => wire__crate__pyin__api__init_logging_impl(port, ptr, rust_vec_len, data_len),
-        7 => wire__crate__pyin__api__new_processor_impl(port, ptr, rust_vec_len, data_len),
-        8 => wire__crate__pyin__api__push_and_get_midi_impl(port, ptr, rust_vec_len, data_len),
+        32 => wire__crate__pyin__api__init_logging_impl(port, ptr, rust_vec_len, data_len),
+        33 => wire__crate__pyin__api__new_processor_impl(port, ptr, rust_vec_len, data_len),
+        34 => wire__crate__pyin__api__push_and_get_midi_impl(port, ptr, rust_vec_len, data_len),
         _ => unreachable!(),
     }
 }
@@ -682,6 +2632,21 @@ impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<AudioAnalyzer>> for AudioAnaly
     }
 }
 
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for FrbWrapper<Party> {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self.0)
+            .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for FrbWrapper<Party> {}
+
+impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<Party>> for Party {
+    fn into_into_dart(self) -> FrbWrapper<Party> {
+        self.into()
+    }
+}
+
 // Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for FrbWrapper<PyinProcessor> {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
@@ -720,12 +2685,202 @@ impl flutter_rust_bridge::IntoIntoDart<crate::fingerprint::AudioFingerprint>
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::BleedConfig {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.delay_sec.into_into_dart().into_dart(),
+            self.filter_ms.into_into_dart().into_dart(),
+            self.step_size.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::BleedConfig {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::BleedConfig> for crate::pyin::BleedConfig {
+    fn into_into_dart(self) -> crate::pyin::BleedConfig {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::ChannelMix {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::pyin::ChannelMix::Downmix => [0.into_dart()].into_dart(),
+            crate::pyin::ChannelMix::Pick(field0) => {
+                [1.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::ChannelMix {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::ChannelMix> for crate::pyin::ChannelMix {
+    fn into_into_dart(self) -> crate::pyin::ChannelMix {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::scoring::Difficulty {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Easy => 0.into_dart(),
+            Self::Medium => 1.into_dart(),
+            Self::Hard => 2.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::scoring::Difficulty {}
+impl flutter_rust_bridge::IntoIntoDart<crate::scoring::Difficulty> for crate::scoring::Difficulty {
+    fn into_into_dart(self) -> crate::scoring::Difficulty {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::api::FileAnalysisOptions {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.analysis_sample_rate_hz.into_into_dart().into_dart(),
+            self.update_interval_ms.into_into_dart().into_dart(),
+            self.window_size_ms.into_into_dart().into_dart(),
+            self.reference_a4_hz.into_into_dart().into_dart(),
+            self.start_sec.into_into_dart().into_dart(),
+            self.end_sec.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::pyin::api::FileAnalysisOptions
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::FileAnalysisOptions>
+    for crate::pyin::api::FileAnalysisOptions
+{
+    fn into_into_dart(self) -> crate::pyin::api::FileAnalysisOptions {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::api::FileAnalysisUpdate {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.processed_sec.into_into_dart().into_dart(),
+            self.total_sec.into_into_dart().into_dart(),
+            self.contour.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::pyin::api::FileAnalysisUpdate
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::FileAnalysisUpdate>
+    for crate::pyin::api::FileAnalysisUpdate
+{
+    fn into_into_dart(self) -> crate::pyin::api::FileAnalysisUpdate {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::api::FileContour {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.source_sample_rate_hz.into_into_dart().into_dart(),
+            self.duration_sec.into_into_dart().into_dart(),
+            self.events.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::api::FileContour {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::FileContour>
+    for crate::pyin::api::FileContour
+{
+    fn into_into_dart(self) -> crate::pyin::api::FileContour {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::LevelReading {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.rms_dbfs.into_into_dart().into_dart(),
+            self.peak_dbfs.into_into_dart().into_dart(),
+            self.momentary_lufs.into_into_dart().into_dart(),
+            self.short_term_lufs.into_into_dart().into_dart(),
+            self.clipped_samples.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::LevelReading {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::LevelReading> for crate::pyin::LevelReading {
+    fn into_into_dart(self) -> crate::pyin::LevelReading {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::PcmFormat {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::I16LE => 0.into_dart(),
+            Self::F32LE => 1.into_dart(),
+            Self::I16BE => 2.into_dart(),
+            Self::I24LE => 3.into_dart(),
+            Self::I24BE => 4.into_dart(),
+            Self::I32LE => 5.into_dart(),
+            Self::I32BE => 6.into_dart(),
+            Self::F32BE => 7.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::PcmFormat {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::PcmFormat> for crate::pyin::PcmFormat {
+    fn into_into_dart(self) -> crate::pyin::PcmFormat {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::PitchAlgorithm {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Pyin => 0.into_dart(),
+            Self::Yin => 1.into_dart(),
+            Self::Mpm => 2.into_dart(),
+            Self::Hps => 3.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::PitchAlgorithm {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::PitchAlgorithm>
+    for crate::pyin::PitchAlgorithm
+{
+    fn into_into_dart(self) -> crate::pyin::PitchAlgorithm {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::pyin::api::PitchConfig {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         [
             self.sample_rate_hz.into_into_dart().into_dart(),
             self.update_interval_ms.into_into_dart().into_dart(),
             self.window_size_ms.into_into_dart().into_dart(),
+            self.reference_a4_hz.into_into_dart().into_dart(),
+            self.pcm_format.into_into_dart().into_dart(),
+            self.channels.into_into_dart().into_dart(),
+            self.channel_mix.into_into_dart().into_dart(),
+            self.analysis_sample_rate_hz.into_into_dart().into_dart(),
+            self.vad_enabled.into_into_dart().into_dart(),
+            self.octave_correction_enabled.into_into_dart().into_dart(),
+            self.algorithm.into_into_dart().into_dart(),
+            self.multi_resolution_enabled.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -739,6 +2894,57 @@ impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::PitchConfig>
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::api::PitchEvent {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.sample_position.into_into_dart().into_dart(),
+            self.time_sec.into_into_dart().into_dart(),
+            self.f0_hz.into_into_dart().into_dart(),
+            self.midi_fractional.into_into_dart().into_dart(),
+            self.confidence.into_into_dart().into_dart(),
+            self.voiced.into_into_dart().into_dart(),
+            self.rms_dbfs.into_into_dart().into_dart(),
+            self.peak_dbfs.into_into_dart().into_dart(),
+            self.loudness_lufs.into_into_dart().into_dart(),
+            self.is_final.into_into_dart().into_dart(),
+            self.speech_prob.into_into_dart().into_dart(),
+            self.noise_floor_dbfs.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::api::PitchEvent {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::PitchEvent>
+    for crate::pyin::api::PitchEvent
+{
+    fn into_into_dart(self) -> crate::pyin::api::PitchEvent {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::api::PitchStreamOptions {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.only_on_change.into_into_dart().into_dart(),
+            self.min_change_cents.into_into_dart().into_dart(),
+            self.max_events_per_sec.into_into_dart().into_dart(),
+            self.final_only.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::pyin::api::PitchStreamOptions
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::PitchStreamOptions>
+    for crate::pyin::api::PitchStreamOptions
+{
+    fn into_into_dart(self) -> crate::pyin::api::PitchStreamOptions {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::pyin::PyinError {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         match self {
@@ -751,9 +2957,179 @@ impl flutter_rust_bridge::IntoDart for crate::pyin::PyinError {
         }
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::PyinError {}
-impl flutter_rust_bridge::IntoIntoDart<crate::pyin::PyinError> for crate::pyin::PyinError {
-    fn into_into_dart(self) -> crate::pyin::PyinError {
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::PyinError {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::PyinError> for crate::pyin::PyinError {
+    fn into_into_dart(self) -> crate::pyin::PyinError {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::scoring::ScoreEvent {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.kind.into_into_dart().into_dart(),
+            self.song_time_sec.into_into_dart().into_dart(),
+            self.track.into_into_dart().into_dart(),
+            self.line.into_into_dart().into_dart(),
+            self.note.into_into_dart().into_dart(),
+            self.points.into_into_dart().into_dart(),
+            self.total_score.into_into_dart().into_dart(),
+            self.golden_streak.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::scoring::ScoreEvent {}
+impl flutter_rust_bridge::IntoIntoDart<crate::scoring::ScoreEvent> for crate::scoring::ScoreEvent {
+    fn into_into_dart(self) -> crate::scoring::ScoreEvent {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::scoring::ScoreEventKind {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::NoteHit => 0.into_dart(),
+            Self::NoteMissed => 1.into_dart(),
+            Self::LineFinished => 2.into_dart(),
+            Self::PerfectLine => 3.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::scoring::ScoreEventKind
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::scoring::ScoreEventKind>
+    for crate::scoring::ScoreEventKind
+{
+    fn into_into_dart(self) -> crate::scoring::ScoreEventKind {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::ScorePriorConfig {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.song_start_sec.into_into_dart().into_dart(),
+            self.strength.into_into_dart().into_dart(),
+            self.sigma_cents.into_into_dart().into_dart(),
+            self.octave_weight.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::ScorePriorConfig {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::ScorePriorConfig>
+    for crate::pyin::ScorePriorConfig
+{
+    fn into_into_dart(self) -> crate::pyin::ScorePriorConfig {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::api::ScoreStreamOptions {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.track.into_into_dart().into_dart(),
+            self.harmony.into_into_dart().into_dart(),
+            self.difficulty.into_into_dart().into_dart(),
+            self.line_bonus.into_into_dart().into_dart(),
+            self.song_start_sec.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::pyin::api::ScoreStreamOptions
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::ScoreStreamOptions>
+    for crate::pyin::api::ScoreStreamOptions
+{
+    fn into_into_dart(self) -> crate::pyin::api::ScoreStreamOptions {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::scoring::ScoreTotals {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.notes.into_into_dart().into_dart(),
+            self.golden.into_into_dart().into_dart(),
+            self.line_bonus.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::scoring::ScoreTotals {}
+impl flutter_rust_bridge::IntoIntoDart<crate::scoring::ScoreTotals>
+    for crate::scoring::ScoreTotals
+{
+    fn into_into_dart(self) -> crate::scoring::ScoreTotals {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::api::SingerOptions {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.name.into_into_dart().into_dart(),
+            self.track.into_into_dart().into_dart(),
+            self.harmony.into_into_dart().into_dart(),
+            self.difficulty.into_into_dart().into_dart(),
+            self.line_bonus.into_into_dart().into_dart(),
+            self.stream_start_sec.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::pyin::api::SingerOptions
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::api::SingerOptions>
+    for crate::pyin::api::SingerOptions
+{
+    fn into_into_dart(self) -> crate::pyin::api::SingerOptions {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::scoring::SingerResult {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.name.into_into_dart().into_dart(),
+            self.track.into_into_dart().into_dart(),
+            self.totals.into_into_dart().into_dart(),
+            self.score.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::scoring::SingerResult {}
+impl flutter_rust_bridge::IntoIntoDart<crate::scoring::SingerResult>
+    for crate::scoring::SingerResult
+{
+    fn into_into_dart(self) -> crate::scoring::SingerResult {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::pyin::TargetNote {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.start_sec.into_into_dart().into_dart(),
+            self.end_sec.into_into_dart().into_dart(),
+            self.midi.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::pyin::TargetNote {}
+impl flutter_rust_bridge::IntoIntoDart<crate::pyin::TargetNote> for crate::pyin::TargetNote {
+    fn into_into_dart(self) -> crate::pyin::TargetNote {
         self
     }
 }
@@ -772,6 +3148,16 @@ impl SseEncode for AudioAnalyzer {
     }
 }
 
+impl SseEncode for Party {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>>>::sse_encode(
+            flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self),
+            serializer,
+        );
+    }
+}
+
 impl SseEncode for PyinProcessor {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -790,6 +3176,15 @@ impl SseEncode
     }
 }
 
+impl SseEncode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        let (ptr, size) = self.sse_encode_raw();
+        <usize>::sse_encode(ptr, serializer);
+        <i32>::sse_encode(size, serializer);
+    }
+}
+
 impl SseEncode
     for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PyinProcessor>>
 {
@@ -801,7 +3196,30 @@ impl SseEncode
     }
 }
 
-impl SseEncode for StreamSink<u8, flutter_rust_bridge::for_generated::SseCodec> {
+impl SseEncode
+    for StreamSink<
+        crate::pyin::api::FileAnalysisUpdate,
+        flutter_rust_bridge::for_generated::SseCodec,
+    >
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
+    }
+}
+
+impl SseEncode
+    for StreamSink<crate::pyin::api::PitchEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
+    }
+}
+
+impl SseEncode
+    for StreamSink<crate::scoring::ScoreEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         unimplemented!("")
@@ -824,6 +3242,64 @@ impl SseEncode for crate::fingerprint::AudioFingerprint {
     }
 }
 
+impl SseEncode for crate::pyin::BleedConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f64>::sse_encode(self.delay_sec, serializer);
+        <f32>::sse_encode(self.filter_ms, serializer);
+        <f32>::sse_encode(self.step_size, serializer);
+    }
+}
+
+impl SseEncode for bool {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_u8(self as _).unwrap();
+    }
+}
+
+impl SseEncode for crate::pyin::ChannelMix {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::pyin::ChannelMix::Downmix => {
+                <i32>::sse_encode(0, serializer);
+            }
+            crate::pyin::ChannelMix::Pick(field0) => {
+                <i32>::sse_encode(1, serializer);
+                <u16>::sse_encode(field0, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::scoring::Difficulty {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::scoring::Difficulty::Easy => 0,
+                crate::scoring::Difficulty::Medium => 1,
+                crate::scoring::Difficulty::Hard => 2,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for f32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_f32::<NativeEndian>(self).unwrap();
+    }
+}
+
 impl SseEncode for f64 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -831,6 +3307,47 @@ impl SseEncode for f64 {
     }
 }
 
+impl SseEncode for crate::pyin::api::FileAnalysisOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u32>::sse_encode(self.analysis_sample_rate_hz, serializer);
+        <u32>::sse_encode(self.update_interval_ms, serializer);
+        <u32>::sse_encode(self.window_size_ms, serializer);
+        <f32>::sse_encode(self.reference_a4_hz, serializer);
+        <f64>::sse_encode(self.start_sec, serializer);
+        <Option<f64>>::sse_encode(self.end_sec, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::api::FileAnalysisUpdate {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f64>::sse_encode(self.processed_sec, serializer);
+        <Option<f64>>::sse_encode(self.total_sec, serializer);
+        <Option<crate::pyin::api::FileContour>>::sse_encode(self.contour, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::api::FileContour {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u32>::sse_encode(self.source_sample_rate_hz, serializer);
+        <f64>::sse_encode(self.duration_sec, serializer);
+        <Vec<crate::pyin::api::PitchEvent>>::sse_encode(self.events, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::LevelReading {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f32>::sse_encode(self.rms_dbfs, serializer);
+        <f32>::sse_encode(self.peak_dbfs, serializer);
+        <f32>::sse_encode(self.momentary_lufs, serializer);
+        <f32>::sse_encode(self.short_term_lufs, serializer);
+        <u32>::sse_encode(self.clipped_samples, serializer);
+    }
+}
+
 impl SseEncode for Vec<String> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -851,6 +3368,26 @@ impl SseEncode for Vec<crate::fingerprint::AudioFingerprint> {
     }
 }
 
+impl SseEncode for Vec<crate::pyin::api::PitchEvent> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::pyin::api::PitchEvent>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<f32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <f32>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for Vec<u8> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -861,12 +3398,169 @@ impl SseEncode for Vec<u8> {
     }
 }
 
+impl SseEncode for Vec<crate::scoring::ScoreEvent> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::scoring::ScoreEvent>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::scoring::SingerResult> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::scoring::SingerResult>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::pyin::TargetNote> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::pyin::TargetNote>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<f32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <f32>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<f64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <f64>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<crate::pyin::api::FileContour> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <crate::pyin::api::FileContour>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<crate::pyin::LevelReading> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <crate::pyin::LevelReading>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<u32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <u32>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for crate::pyin::PcmFormat {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::pyin::PcmFormat::I16LE => 0,
+                crate::pyin::PcmFormat::F32LE => 1,
+                crate::pyin::PcmFormat::I16BE => 2,
+                crate::pyin::PcmFormat::I24LE => 3,
+                crate::pyin::PcmFormat::I24BE => 4,
+                crate::pyin::PcmFormat::I32LE => 5,
+                crate::pyin::PcmFormat::I32BE => 6,
+                crate::pyin::PcmFormat::F32BE => 7,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::pyin::PitchAlgorithm {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::pyin::PitchAlgorithm::Pyin => 0,
+                crate::pyin::PitchAlgorithm::Yin => 1,
+                crate::pyin::PitchAlgorithm::Mpm => 2,
+                crate::pyin::PitchAlgorithm::Hps => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
 impl SseEncode for crate::pyin::api::PitchConfig {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         <u32>::sse_encode(self.sample_rate_hz, serializer);
         <u32>::sse_encode(self.update_interval_ms, serializer);
         <u32>::sse_encode(self.window_size_ms, serializer);
+        <f32>::sse_encode(self.reference_a4_hz, serializer);
+        <crate::pyin::PcmFormat>::sse_encode(self.pcm_format, serializer);
+        <u16>::sse_encode(self.channels, serializer);
+        <crate::pyin::ChannelMix>::sse_encode(self.channel_mix, serializer);
+        <Option<u32>>::sse_encode(self.analysis_sample_rate_hz, serializer);
+        <bool>::sse_encode(self.vad_enabled, serializer);
+        <bool>::sse_encode(self.octave_correction_enabled, serializer);
+        <crate::pyin::PitchAlgorithm>::sse_encode(self.algorithm, serializer);
+        <bool>::sse_encode(self.multi_resolution_enabled, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::api::PitchEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u64>::sse_encode(self.sample_position, serializer);
+        <f64>::sse_encode(self.time_sec, serializer);
+        <Option<f32>>::sse_encode(self.f0_hz, serializer);
+        <Option<f32>>::sse_encode(self.midi_fractional, serializer);
+        <f32>::sse_encode(self.confidence, serializer);
+        <bool>::sse_encode(self.voiced, serializer);
+        <f32>::sse_encode(self.rms_dbfs, serializer);
+        <f32>::sse_encode(self.peak_dbfs, serializer);
+        <f32>::sse_encode(self.loudness_lufs, serializer);
+        <bool>::sse_encode(self.is_final, serializer);
+        <Option<f32>>::sse_encode(self.speech_prob, serializer);
+        <Option<f32>>::sse_encode(self.noise_floor_dbfs, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::api::PitchStreamOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.only_on_change, serializer);
+        <f32>::sse_encode(self.min_change_cents, serializer);
+        <u32>::sse_encode(self.max_events_per_sec, serializer);
+        <bool>::sse_encode(self.final_only, serializer);
     }
 }
 
@@ -885,6 +3579,99 @@ impl SseEncode for crate::pyin::PyinError {
     }
 }
 
+impl SseEncode for crate::scoring::ScoreEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <crate::scoring::ScoreEventKind>::sse_encode(self.kind, serializer);
+        <f64>::sse_encode(self.song_time_sec, serializer);
+        <u32>::sse_encode(self.track, serializer);
+        <u32>::sse_encode(self.line, serializer);
+        <Option<u32>>::sse_encode(self.note, serializer);
+        <f64>::sse_encode(self.points, serializer);
+        <u32>::sse_encode(self.total_score, serializer);
+        <u32>::sse_encode(self.golden_streak, serializer);
+    }
+}
+
+impl SseEncode for crate::scoring::ScoreEventKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::scoring::ScoreEventKind::NoteHit => 0,
+                crate::scoring::ScoreEventKind::NoteMissed => 1,
+                crate::scoring::ScoreEventKind::LineFinished => 2,
+                crate::scoring::ScoreEventKind::PerfectLine => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::pyin::ScorePriorConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f64>::sse_encode(self.song_start_sec, serializer);
+        <f32>::sse_encode(self.strength, serializer);
+        <f32>::sse_encode(self.sigma_cents, serializer);
+        <f32>::sse_encode(self.octave_weight, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::api::ScoreStreamOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u32>::sse_encode(self.track, serializer);
+        <bool>::sse_encode(self.harmony, serializer);
+        <crate::scoring::Difficulty>::sse_encode(self.difficulty, serializer);
+        <bool>::sse_encode(self.line_bonus, serializer);
+        <f64>::sse_encode(self.song_start_sec, serializer);
+    }
+}
+
+impl SseEncode for crate::scoring::ScoreTotals {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f64>::sse_encode(self.notes, serializer);
+        <f64>::sse_encode(self.golden, serializer);
+        <f64>::sse_encode(self.line_bonus, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::api::SingerOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.name, serializer);
+        <u32>::sse_encode(self.track, serializer);
+        <bool>::sse_encode(self.harmony, serializer);
+        <crate::scoring::Difficulty>::sse_encode(self.difficulty, serializer);
+        <bool>::sse_encode(self.line_bonus, serializer);
+        <f64>::sse_encode(self.stream_start_sec, serializer);
+    }
+}
+
+impl SseEncode for crate::scoring::SingerResult {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.name, serializer);
+        <usize>::sse_encode(self.track, serializer);
+        <crate::scoring::ScoreTotals>::sse_encode(self.totals, serializer);
+        <u32>::sse_encode(self.score, serializer);
+    }
+}
+
+impl SseEncode for crate::pyin::TargetNote {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f64>::sse_encode(self.start_sec, serializer);
+        <f64>::sse_encode(self.end_sec, serializer);
+        <f32>::sse_encode(self.midi, serializer);
+    }
+}
+
 impl SseEncode for u16 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -899,6 +3686,13 @@ impl SseEncode for u32 {
     }
 }
 
+impl SseEncode for u64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_u64::<NativeEndian>(self).unwrap();
+    }
+}
+
 impl SseEncode for u8 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -928,13 +3722,6 @@ impl SseEncode for i32 {
     }
 }
 
-impl SseEncode for bool {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        serializer.cursor.write_u8(self as _).unwrap();
-    }
-}
-
 #[cfg(not(target_family = "wasm"))]
 mod io {
     // This file is automatically generated, so please do not edit it.
@@ -968,6 +3755,20 @@ mod io {
         MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>>::decrement_strong_count(ptr as _);
     }
 
+    #[unsafe(no_mangle)]
+    pub extern "C" fn frbgen_couchraoke_companion_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>>::increment_strong_count(ptr as _);
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn frbgen_couchraoke_companion_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>>::decrement_strong_count(ptr as _);
+    }
+
     #[unsafe(no_mangle)]
     pub extern "C" fn frbgen_couchraoke_companion_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
         ptr: *const std::ffi::c_void,
@@ -1021,6 +3822,20 @@ mod web {
         MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<AudioAnalyzer>>::decrement_strong_count(ptr as _);
     }
 
+    #[wasm_bindgen]
+    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>>::increment_strong_count(ptr as _);
+    }
+
+    #[wasm_bindgen]
+    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerParty(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<Party>>::decrement_strong_count(ptr as _);
+    }
+
     #[wasm_bindgen]
     pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPyinProcessor(
         ptr: *const std::ffi::c_void,
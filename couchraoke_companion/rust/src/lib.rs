@@ -1,3 +1,6 @@
 mod frb_generated; /* AUTO INJECTED BY flutter_rust_bridge. This line may not be accurate, and you can change it according to your needs. */
+mod decode;
 pub mod fingerprint;
 pub mod pyin;
+pub mod scoring;
+pub mod song;
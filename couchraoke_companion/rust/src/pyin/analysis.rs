@@ -0,0 +1,162 @@
+//! Offline whole-file pitch analysis.
+//!
+//! Decodes a file through symphonia, downmixes to mono, resamples to the
+//! configured analysis rate and runs `Pyin` without the streaming decoder lag.
+//! Frames are instead committed in blocks, each frame once at least
+//! `LOOKAHEAD_SEC` of later audio has been analysed, with its state backtracked
+//! from the best path so far and forward-backward posteriors over the block.
+//! Paths have long merged by then, so the contour matches the exact
+//! most-likely path over the whole range, while memory stays bounded by the
+//! block rather than the file. The last, incomplete frame is padded with
+//! silence so the end of the range is analysed too.
+
+use std::ops::ControlFlow;
+
+use crate::decode::FileDecoder;
+
+use super::resample::Resampler;
+use super::{FrameEstimate, PcmFormat, Pyin, PyinConfig, TailPolicy};
+
+/// Audio analysed after a frame before it is committed.
+const LOOKAHEAD_SEC: f64 = 2.0;
+
+#[derive(Debug, Clone)]
+pub struct FileAnalysisConfig {
+    /// Analysis parameters. `viterbi_lag_frames` is ignored: frames are
+    /// committed in blocks instead (see the module docs).
+    pub pyin: PyinConfig,
+    /// Start of the analysed range, in seconds from the start of the file.
+    pub start_sec: f64,
+    /// End of the analysed range; `None` analyses to the end of the file.
+    pub end_sec: Option<f64>,
+}
+
+impl Default for FileAnalysisConfig {
+    fn default() -> Self {
+        Self {
+            pyin: PyinConfig::default(),
+            start_sec: 0.0,
+            end_sec: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisProgress {
+    /// Seconds of the file decoded so far.
+    pub processed_sec: f64,
+    /// File duration, if the container reports it.
+    pub total_sec: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileAnalysis {
+    pub source_sample_rate_hz: u32,
+    /// Duration of the file in seconds, as reported by the container, or else
+    /// of the audio decoded up to the end of the analysed range.
+    pub duration_sec: f64,
+    /// Every frame of the analysed range. `time_sec` is relative to the start
+    /// of the file, not to `start_sec`.
+    pub frames: Vec<FrameEstimate>,
+}
+
+pub fn analyze_file(path: &str, cfg: &FileAnalysisConfig) -> anyhow::Result<FileAnalysis> {
+    analyze_file_with_progress(path, cfg, |_| {})
+}
+
+/// Like `analyze_file`, calling `on_progress` once per decoded packet.
+pub fn analyze_file_with_progress<F>(
+    path: &str,
+    cfg: &FileAnalysisConfig,
+    mut on_progress: F,
+) -> anyhow::Result<FileAnalysis>
+where
+    F: FnMut(AnalysisProgress),
+{
+    if cfg.start_sec.is_nan() || cfg.start_sec < 0.0 {
+        return Err(anyhow::anyhow!("start_sec must be >= 0"));
+    }
+    if matches!(cfg.end_sec, Some(end) if end.is_nan() || end <= cfg.start_sec) {
+        return Err(anyhow::anyhow!("end_sec must be greater than start_sec"));
+    }
+
+    let decoder = FileDecoder::open(path)?;
+    let info = decoder.info();
+    let source_rate = info.sample_rate as f64;
+    let channels = info.channels.max(1);
+
+    let mut pyin_cfg = cfg.pyin.clone();
+    pyin_cfg.viterbi_lag_frames = usize::MAX;
+    let lookahead =
+        (LOOKAHEAD_SEC * pyin_cfg.sample_rate_hz as f64 / pyin_cfg.hop_size.max(1) as f64).ceil()
+            as usize;
+    let mut pyin = Pyin::new(pyin_cfg, PcmFormat::F32LE)
+        .map_err(|err| anyhow::anyhow!("invalid analysis config: {:?}", err))?;
+    let mut resampler = Resampler::new(info.sample_rate, cfg.pyin.sample_rate_hz);
+
+    let start_frame = (cfg.start_sec * source_rate).round() as u64;
+    let end_frame = cfg.end_sec.map(|end| (end * source_rate).round() as u64);
+    let total_sec = info.n_frames.map(|n| n as f64 / source_rate);
+
+    let mut position: u64 = 0;
+    let mut mono: Vec<f32> = Vec::new();
+    let mut resampled: Vec<f32> = Vec::new();
+    let mut frames: Vec<FrameEstimate> = Vec::new();
+
+    let decoded_frames = decoder.decode_until::<f32, _>(|samples| {
+        let packet_frames = (samples.len() / channels) as u64;
+        let packet_start = position;
+        position += packet_frames;
+
+        let from = start_frame.saturating_sub(packet_start).min(packet_frames);
+        let to = end_frame
+            .map_or(packet_frames, |end| end.saturating_sub(packet_start))
+            .min(packet_frames);
+        if from < to {
+            mono.clear();
+            mono.extend(
+                samples[from as usize * channels..to as usize * channels]
+                    .chunks_exact(channels)
+                    .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+            );
+            resampled.clear();
+            resampler.process(&mono, &mut resampled);
+            pyin.push_samples(&resampled)
+                .map_err(|err| anyhow::anyhow!("analysis failed: {:?}", err))?;
+            if pyin.pending_frames() >= 2 * lookahead {
+                frames.extend(pyin.commit_settled(lookahead));
+            }
+        }
+
+        on_progress(AnalysisProgress {
+            processed_sec: position as f64 / source_rate,
+            total_sec,
+        });
+        if end_frame.is_some_and(|end| position >= end) {
+            Ok(ControlFlow::Break(()))
+        } else {
+            Ok(ControlFlow::Continue(()))
+        }
+    })?;
+
+    resampled.clear();
+    resampler.flush(&mut resampled);
+    pyin.push_samples(&resampled)
+        .map_err(|err| anyhow::anyhow!("analysis failed: {:?}", err))?;
+    frames.extend(
+        pyin.finish(TailPolicy::ZeroPad)
+            .map_err(|err| anyhow::anyhow!("analysis failed: {:?}", err))?
+            .estimates,
+    );
+
+    let range_start_sec = start_frame.min(decoded_frames) as f64 / source_rate;
+    for frame in frames.iter_mut() {
+        frame.time_sec += range_start_sec;
+    }
+
+    Ok(FileAnalysis {
+        source_sample_rate_hz: info.sample_rate,
+        duration_sec: info.n_frames.unwrap_or(decoded_frames) as f64 / source_rate,
+        frames,
+    })
+}
@@ -1,178 +1,980 @@
-use super::{PcmFormat, Pyin, PyinConfig, PyinError};
+use super::pcm::PcmDecoder;
+use super::snapshot::{invalid, SnapshotKind, StateReader, StateWriter};
+use super::{
+    new_estimator, AnalysisProgress, BleedConfig, BleedReference, ChannelMix, FileAnalysisConfig, FrameEstimate,
+    LevelConfig, LevelMeter, LevelReading, PcmFormat, PcmInput, PitchAlgorithm, PitchEstimator,
+    Pyin, PyinConfig, MultiResolutionConfig, OctaveConfig, PyinError, ScorePriorConfig, TailPolicy,
+    TargetNote, VadConfig,
+};
 use crate::frb_generated::StreamSink;
+use crate::scoring::{
+    Difficulty, PartChoice, ScoreEvent, ScoringConfig, SingerResult, SingerScoring,
+};
+use crate::song::Song;
+use flutter_rust_bridge::frb;
 use std::collections::VecDeque;
 use std::sync::Once;
 
 const UNVOICED_MIDI: u16 = 255;
 
 pub struct PitchConfig {
+    /// Capture rate of the PCM handed to `process_chunk`.
     pub sample_rate_hz: u32,
     pub update_interval_ms: u32,
     pub window_size_ms: u32,
+    /// Frequency of A4 used for MIDI conversion, e.g. 440, 432 or 443 Hz.
+    pub reference_a4_hz: f32,
+    pub pcm_format: PcmFormat,
+    /// Number of interleaved channels in the PCM stream.
+    pub channels: u16,
+    pub channel_mix: ChannelMix,
+    /// Rate the detector runs at; the input is resampled when it differs from
+    /// `sample_rate_hz`. `None` analyses at the capture rate.
+    pub analysis_sample_rate_hz: Option<u32>,
+    /// Gate room noise and background chatter with the voice activity detector.
+    pub vad_enabled: bool,
+    /// Verify YIN candidates against the harmonic spectrum to fix octave errors.
+    pub octave_correction_enabled: bool,
+    /// Pitch estimator to run. Bleed cancellation, target notes, VAD and
+    /// octave correction only apply to `PitchAlgorithm::Pyin`.
+    pub algorithm: PitchAlgorithm,
+    /// Track high notes over a quarter and half of the window, so they follow
+    /// the voice sooner. Only applies to `PitchAlgorithm::Pyin`.
+    pub multi_resolution_enabled: bool,
 }
 
+impl Default for PitchConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate_hz: 48_000,
+            update_interval_ms: 10,
+            window_size_ms: 43,
+            reference_a4_hz: 440.0,
+            pcm_format: PcmFormat::I16LE,
+            channels: 1,
+            channel_mix: ChannelMix::Downmix,
+            analysis_sample_rate_hz: None,
+            vad_enabled: false,
+            octave_correction_enabled: false,
+            algorithm: PitchAlgorithm::Pyin,
+            multi_resolution_enabled: false,
+        }
+    }
+}
+
+/// One pitch estimate as delivered to Dart.
+#[derive(Debug, Clone)]
+pub struct PitchEvent {
+    /// Index of the first input sample of the analysis window.
+    pub sample_position: u64,
+    pub time_sec: f64,
+    pub f0_hz: Option<f32>,
+    /// Unrounded MIDI pitch relative to `PitchConfig::reference_a4_hz`.
+    pub midi_fractional: Option<f32>,
+    pub confidence: f32,
+    pub voiced: bool,
+    pub rms_dbfs: f32,
+    pub peak_dbfs: f32,
+    /// Short-term loudness in LUFS.
+    pub loudness_lufs: f32,
+    /// `false` while the pitch decoder may still revise this frame.
+    pub is_final: bool,
+    /// Voice activity probability, when `PitchConfig::vad_enabled`.
+    pub speech_prob: Option<f32>,
+    /// Estimated room noise level, when `PitchConfig::vad_enabled`.
+    pub noise_floor_dbfs: Option<f32>,
+}
+
+impl PitchEvent {
+    fn from_frame(frame: &FrameEstimate, sample_position: u64) -> Self {
+        Self {
+            sample_position,
+            time_sec: frame.time_sec,
+            f0_hz: frame.f0_hz,
+            midi_fractional: frame.midi_fractional,
+            confidence: frame.confidence,
+            voiced: frame.voiced,
+            rms_dbfs: frame.rms_dbfs,
+            peak_dbfs: frame.peak_dbfs,
+            loudness_lufs: frame.loudness_lufs,
+            is_final: frame.is_final,
+            speech_prob: frame.vad.map(|vad| vad.speech_prob),
+            noise_floor_dbfs: frame.vad.map(|vad| vad.noise_floor_dbfs),
+        }
+    }
+}
+
+/// Throttling applied to the `PitchEvent` stream.
+#[derive(Debug, Clone, Default)]
+pub struct PitchStreamOptions {
+    /// Only emit when voicing flips or the pitch moves by `min_change_cents` or more.
+    pub only_on_change: bool,
+    pub min_change_cents: f32,
+    /// Upper bound on events per second of audio; 0 disables the cap.
+    pub max_events_per_sec: u32,
+    /// Stream each frame once the pitch decoder has committed it, with
+    /// `is_final` set, rather than as soon as it is analysed. Final events
+    /// trail the live ones by the decoder's lag. Otherwise a live event not
+    /// yet final is followed by its final update, with the same
+    /// `sample_position`, once the decoder commits the frame.
+    pub final_only: bool,
+}
+
+/// Options of `AudioAnalyzer::create_score_stream`.
+#[derive(Debug, Clone)]
+pub struct ScoreStreamOptions {
+    /// Part of the chart to score: 0, or 1 for the second singer of a duet.
+    pub track: u32,
+    /// Score against every part and credit the best, for a singer free to
+    /// take either part of a duet; `track` is then ignored. Events of each
+    /// part are told apart by `ScoreEvent::track`.
+    pub harmony: bool,
+    pub difficulty: Difficulty,
+    pub line_bonus: bool,
+    /// Stream time (the clock of `PitchEvent::time_sec`) at which the song's
+    /// audio starts playing.
+    pub song_start_sec: f64,
+}
+
+/// Options of `analyze_file` and `analyze_file_stream`.
+#[derive(Debug, Clone)]
+pub struct FileAnalysisOptions {
+    /// Rate the detector runs at; the file is resampled to it.
+    pub analysis_sample_rate_hz: u32,
+    pub update_interval_ms: u32,
+    pub window_size_ms: u32,
+    pub reference_a4_hz: f32,
+    /// Start of the analysed range, in seconds from the start of the file.
+    pub start_sec: f64,
+    /// End of the analysed range; `None` analyses to the end of the file.
+    pub end_sec: Option<f64>,
+}
+
+impl Default for FileAnalysisOptions {
+    fn default() -> Self {
+        Self {
+            analysis_sample_rate_hz: 48_000,
+            update_interval_ms: 10,
+            window_size_ms: 43,
+            reference_a4_hz: 440.0,
+            start_sec: 0.0,
+            end_sec: None,
+        }
+    }
+}
+
+/// Pitch contour of a decoded file, as delivered to Dart.
+#[derive(Debug, Clone)]
+pub struct FileContour {
+    pub source_sample_rate_hz: u32,
+    pub duration_sec: f64,
+    /// One final event per frame of the analysed range. `sample_position`
+    /// counts samples of the file at its own rate.
+    pub events: Vec<PitchEvent>,
+}
+
+/// Progress of `analyze_file_stream`.
+#[derive(Debug, Clone)]
+pub struct FileAnalysisUpdate {
+    /// Seconds of the file decoded so far.
+    pub processed_sec: f64,
+    /// File duration, if the container reports it.
+    pub total_sec: Option<f64>,
+    /// The finished contour, on the last update only.
+    pub contour: Option<FileContour>,
+}
+
+/// Applies `PitchStreamOptions` to consecutive events.
+#[derive(Default)]
+struct PitchEventFilter {
+    options: PitchStreamOptions,
+    last_emitted: Option<PitchEvent>,
+    /// Frames streamed live before they were final, oldest first.
+    provisional: VecDeque<u64>,
+}
+
+impl PitchEventFilter {
+    /// Whether to stream the report of a frame: its first report if it
+    /// passes `accept`, and in live mode the final update of a frame
+    /// streamed provisional. Final-only streams take final reports instead.
+    fn accept_report(&mut self, frame_index: u64, first: bool, event: &PitchEvent) -> bool {
+        if self.options.final_only {
+            return event.is_final && self.accept(event);
+        }
+        if first {
+            let accepted = self.accept(event);
+            if accepted && !event.is_final {
+                self.provisional.push_back(frame_index);
+            }
+            return accepted;
+        }
+        while self.provisional.front().is_some_and(|&index| index < frame_index) {
+            self.provisional.pop_front();
+        }
+        if self.provisional.front() == Some(&frame_index) {
+            self.provisional.pop_front();
+            return true;
+        }
+        false
+    }
+
+    /// Forget the stream so far, keeping the options.
+    fn reset(&mut self) {
+        self.last_emitted = None;
+        self.provisional.clear();
+    }
+
+    fn accept(&mut self, event: &PitchEvent) -> bool {
+        if let Some(last) = &self.last_emitted {
+            if self.options.max_events_per_sec > 0 {
+                let min_gap = 1.0 / self.options.max_events_per_sec as f64;
+                if event.time_sec - last.time_sec < min_gap {
+                    return false;
+                }
+            }
+            if self.options.only_on_change && !self.changed(last, event) {
+                return false;
+            }
+        }
+        self.last_emitted = Some(event.clone());
+        true
+    }
+
+    fn changed(&self, last: &PitchEvent, event: &PitchEvent) -> bool {
+        if last.voiced != event.voiced {
+            return true;
+        }
+        match (last.midi_fractional, event.midi_fractional) {
+            (Some(a), Some(b)) => ((b - a) * 100.0).abs() >= self.options.min_change_cents,
+            (a, b) => a.is_some() != b.is_some(),
+        }
+    }
+}
+
+/// Feeds decoded samples to an estimator and reports every analysed frame
+/// first with the newest estimate there is for it by the end of the push
+/// (final if the decoder has committed it, provisional otherwise), then once
+/// more when the decoder commits it, unless that first report was final.
+/// Frames are reported in order within each kind of report.
+///
+/// The estimator's frame buffer is the only one, so a frame is first reported
+/// as soon as its last sample has been pushed.
+#[derive(Default)]
+struct FrameFeed {
+    /// Index of the next frame to report for the first time.
+    next_frame: u64,
+    /// Index of the next frame whose final estimate is due.
+    next_final: u64,
+}
+
+impl FrameFeed {
+    /// Push `samples` and pass `emit` each report with whether it is the
+    /// frame's first.
+    fn push(
+        &mut self,
+        estimator: &mut dyn PitchEstimator,
+        samples: &[f32],
+        emit: impl FnMut(&FrameEstimate, bool),
+    ) -> Result<(), PyinError> {
+        let committed = estimator.push_samples(samples)?;
+        self.report(&committed, &estimator.provisional_estimates(), emit);
+        Ok(())
+    }
+
+    /// Report the estimates of one push: those `committed` by it and the
+    /// `provisional` ones left after it.
+    fn report(
+        &mut self,
+        committed: &[FrameEstimate],
+        provisional: &[FrameEstimate],
+        mut emit: impl FnMut(&FrameEstimate, bool),
+    ) {
+        for frame in committed {
+            if frame.frame_index >= self.next_final {
+                emit(frame, frame.frame_index >= self.next_frame);
+                self.next_final = frame.frame_index + 1;
+                self.next_frame = self.next_frame.max(self.next_final);
+            }
+        }
+        for frame in provisional {
+            if frame.frame_index >= self.next_frame {
+                emit(frame, true);
+                self.next_frame = frame.frame_index + 1;
+            }
+        }
+    }
+}
+
+/// A pitch detection session fed PCM chunks from Dart.
+///
+/// Processing a chunk allocates, unlike `Pyin::push_bytes_into`: each frame
+/// becomes a `PitchEvent` for Dart. The allocation-free guarantee of the
+/// estimator stops at `Pyin`.
 pub struct AudioAnalyzer {
-    pyin: Pyin,
-    input_buffer: VecDeque<i16>,
+    estimator: Box<dyn PitchEstimator>,
+    decoder: PcmDecoder,
+    /// Mono samples at the analysis rate.
+    decoded: Vec<f32>,
+    feed: FrameFeed,
+    algorithm: PitchAlgorithm,
     hop_size_samples: usize,
-    frame_size_samples: usize,
-    sink: Option<StreamSink<u8>>,
-    leftover_bytes: Vec<u8>,
+    capture_rate_hz: u32,
+    analysis_rate_hz: u32,
+    level: LevelMeter,
+    latest_level: Option<LevelReading>,
+    sink: Option<StreamSink<PitchEvent>>,
+    event_filter: PitchEventFilter,
+    /// Length of the analysis window in seconds.
+    window_sec: f64,
+    scoring: Option<SingerScoring>,
+    score_sink: Option<StreamSink<ScoreEvent>>,
 }
 
 impl AudioAnalyzer {
     pub fn new(config: PitchConfig) -> Result<Self, PyinError> {
-        let frame_size_samples = ms_to_samples(config.sample_rate_hz, config.window_size_ms);
-        let hop_size_samples = ms_to_samples(config.sample_rate_hz, config.update_interval_ms);
+        let analysis_rate_hz = config
+            .analysis_sample_rate_hz
+            .unwrap_or(config.sample_rate_hz);
+        let frame_size_samples = ms_to_samples(analysis_rate_hz, config.window_size_ms);
+        let hop_size_samples = ms_to_samples(analysis_rate_hz, config.update_interval_ms);
         if frame_size_samples < hop_size_samples {
             return Err(PyinError::InvalidConfig(
                 "window_size must be >= update_interval".to_string(),
             ));
         }
         let cfg = PyinConfig {
-            sample_rate_hz: config.sample_rate_hz,
+            sample_rate_hz: analysis_rate_hz,
             frame_size: frame_size_samples,
             hop_size: hop_size_samples,
             fmin_hz: 40.0,
             fmax_hz: 2_000.0,
+            reference_a4_hz: config.reference_a4_hz,
+            vad: config.vad_enabled.then(VadConfig::default),
+            octave_correction: config.octave_correction_enabled.then(OctaveConfig::default),
+            multi_resolution: config
+                .multi_resolution_enabled
+                .then(|| MultiResolutionConfig::for_frame_size(frame_size_samples)),
             ..PyinConfig::default()
         };
-        let pyin = Pyin::new(cfg, PcmFormat::I16LE)?;
+        let input = PcmInput {
+            format: config.pcm_format,
+            channels: config.channels,
+            channel_mix: config.channel_mix,
+            sample_rate_hz: config.sample_rate_hz,
+        };
+        let level_config = LevelConfig::default();
+        let decoder = PcmDecoder::new(input, analysis_rate_hz)?
+            .with_clip_threshold(level_config.clip_threshold_dbfs);
+        let estimator = new_estimator(config.algorithm, cfg)?;
+        let level = LevelMeter::new(analysis_rate_hz, level_config)?;
         Ok(Self {
-            pyin,
-            input_buffer: VecDeque::new(),
+            estimator,
+            decoder,
+            decoded: Vec::new(),
+            feed: FrameFeed::default(),
+            algorithm: config.algorithm,
             hop_size_samples,
-            frame_size_samples,
+            capture_rate_hz: config.sample_rate_hz,
+            analysis_rate_hz,
+            level,
+            latest_level: None,
             sink: None,
-            leftover_bytes: Vec::new(),
+            event_filter: PitchEventFilter::default(),
+            window_sec: frame_size_samples as f64 / analysis_rate_hz as f64,
+            scoring: None,
+            score_sink: None,
         })
     }
 
-    pub fn create_stream(&mut self, sink: StreamSink<u8>) {
+    pub fn create_stream(&mut self, sink: StreamSink<PitchEvent>, options: PitchStreamOptions) {
         self.sink = Some(sink);
+        self.set_stream_options(options);
+    }
+
+    /// Select and throttle the events of further chunks.
+    pub fn set_stream_options(&mut self, options: PitchStreamOptions) {
+        self.event_filter = PitchEventFilter {
+            options,
+            ..PitchEventFilter::default()
+        };
+    }
+
+    /// Score further chunks against `chart`, an UltraStar .txt file, and push
+    /// a `ScoreEvent` to `sink` as each note and line finishes.
+    pub fn create_score_stream(
+        &mut self,
+        sink: StreamSink<ScoreEvent>,
+        chart: Vec<u8>,
+        options: ScoreStreamOptions,
+    ) -> Result<(), PyinError> {
+        self.start_scoring(&chart, options)?;
+        self.score_sink = Some(sink);
+        Ok(())
+    }
+
+    /// Score further chunks against `chart`, an UltraStar .txt file, replacing
+    /// any scoring in progress. Events wait in the session until
+    /// `drain_score_events` when there is no score stream.
+    ///
+    /// Frames are scored once the pitch decoder has committed them, so events
+    /// trail the audio by its lag.
+    pub fn start_scoring(
+        &mut self,
+        chart: &[u8],
+        options: ScoreStreamOptions,
+    ) -> Result<(), PyinError> {
+        self.score_song(&parse_chart(chart)?, options)
+    }
+
+    /// `start_scoring` against an already parsed chart.
+    fn score_song(&mut self, song: &Song, options: ScoreStreamOptions) -> Result<(), PyinError> {
+        let part = if options.harmony {
+            PartChoice::Harmony
+        } else {
+            PartChoice::Track(options.track as usize)
+        };
+        let config = ScoringConfig {
+            difficulty: options.difficulty,
+            line_bonus: options.line_bonus,
+            // Frames are scored at the centre of their window.
+            song_start_sec: options.song_start_sec - self.window_sec / 2.0,
+            ..ScoringConfig::default()
+        };
+        self.scoring = Some(SingerScoring::new(song, part, config)?);
+        self.score_sink = None;
+        Ok(())
+    }
+
+    /// The scoring started by `start_scoring` or `create_score_stream`.
+    #[frb(ignore)]
+    pub fn scoring(&self) -> Option<&SingerScoring> {
+        self.scoring.as_ref()
+    }
+
+    /// Take the score events not yet sent to a score stream.
+    pub fn drain_score_events(&mut self) -> Vec<ScoreEvent> {
+        self.scoring
+            .as_mut()
+            .map(SingerScoring::drain_events)
+            .unwrap_or_default()
+    }
+
+    /// Finish every note once the song is over, streaming the last events.
+    /// Ends the stream first (see `finish`), so the frames the pitch decoder
+    /// still holds back are scored too.
+    pub fn finish_scoring(&mut self) -> Result<(), PyinError> {
+        let result = self.finish();
+        if let Some(scoring) = self.scoring.as_mut() {
+            scoring.finish();
+        }
+        self.send_score_events();
+        result
+    }
+
+    fn send_score_events(&mut self) {
+        let (Some(scoring), Some(sink)) = (self.scoring.as_mut(), self.score_sink.as_ref()) else {
+            return;
+        };
+        for event in scoring.drain_events() {
+            let _ = sink.add(event);
+        }
+    }
+
+    /// Cancel the bleed of the song playing in the room from further chunks.
+    /// `reference` must be decoded at the analysis rate
+    /// (`PitchConfig::analysis_sample_rate_hz`, or the capture rate).
+    #[frb(ignore)]
+    pub fn set_backing_track(
+        &mut self,
+        reference: BleedReference,
+        config: BleedConfig,
+    ) -> Result<(), PyinError> {
+        self.pyin_mut("bleed cancellation")?
+            .set_bleed_reference(reference, &config)
+    }
+
+    /// Decode the backing track at `path` and cancel its bleed from the mic,
+    /// like `set_backing_track`.
+    pub fn set_backing_track_file(
+        &mut self,
+        path: String,
+        config: BleedConfig,
+    ) -> Result<(), PyinError> {
+        let reference = BleedReference::from_file(&path, self.analysis_rate_hz)
+            .map_err(|err| PyinError::InvalidConfig(format!("{:#}", err)))?;
+        self.set_backing_track(reference, config)
+    }
+
+    pub fn set_backing_track_delay(&mut self, delay_sec: f64) {
+        if let Some(pyin) = self.estimator.as_pyin_mut() {
+            pyin.set_bleed_delay(delay_sec);
+        }
+    }
+
+    pub fn clear_backing_track(&mut self) {
+        if let Some(pyin) = self.estimator.as_pyin_mut() {
+            pyin.clear_bleed_reference();
+        }
+    }
+
+    /// Input levels of the most recent chunk, for a mic level bar and
+    /// clipping warnings. `None` before the first chunk.
+    ///
+    /// `clipped_samples` counts the chunk's samples as captured, in every
+    /// channel that is mixed in, since averaging channels or resampling can
+    /// pull a clipped peak below the threshold.
+    pub fn latest_level(&self) -> Option<LevelReading> {
+        self.latest_level
+    }
+
+    /// Bias tracking toward the song's melody; see `Pyin::set_score_prior`.
+    /// `notes` are in song time (`Song::target_notes`); `config.song_start_sec`
+    /// is the stream time, on the clock of `PitchEvent::time_sec`, at which the
+    /// song's audio started playing.
+    pub fn set_target_notes(
+        &mut self,
+        notes: Vec<TargetNote>,
+        config: ScorePriorConfig,
+    ) -> Result<(), PyinError> {
+        self.pyin_mut("target notes")?.set_score_prior(notes, config)
+    }
+
+    /// Move the song to stream time `song_start_sec` for scoring and the
+    /// target notes, e.g. after the stream paused while the song played on.
+    /// Move the backing track with `set_backing_track_delay`.
+    pub fn set_song_start(&mut self, song_start_sec: f64) -> Result<(), PyinError> {
+        if !song_start_sec.is_finite() {
+            return Err(PyinError::InvalidConfig("song_start_sec must be finite".to_string()));
+        }
+        if let Some(pyin) = self.estimator.as_pyin_mut() {
+            pyin.set_score_prior_song_start(song_start_sec)?;
+        }
+        if let Some(scoring) = self.scoring.as_mut() {
+            // Frames are scored at the centre of their window.
+            scoring.set_song_start(song_start_sec - self.window_sec / 2.0);
+        }
+        Ok(())
+    }
+
+    pub fn clear_target_notes(&mut self) {
+        if let Some(pyin) = self.estimator.as_pyin_mut() {
+            pyin.clear_score_prior();
+        }
+    }
+
+    /// Serialise the session so far (decoder and estimator state, levels,
+    /// stream positions and the last streamed event) into a versioned blob,
+    /// e.g. before the app is suspended.
+    ///
+    /// Restore into an analyzer created with the same `PitchConfig`, with the
+    /// same backing track and target notes set and, if the stream is used,
+    /// after `create_stream`; see `Pyin::snapshot`. Scoring in progress is part
+    /// of the snapshot: start it with the same chart and options before
+    /// restoring.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut out = StateWriter::new(SnapshotKind::AudioAnalyzer);
+        out.usize(self.algorithm as usize);
+        out.usize(self.capture_rate_hz as usize);
+        out.usize(self.analysis_rate_hz as usize);
+        out.usize(self.hop_size_samples);
+        self.decoder.save_state(&mut out);
+        out.u64(self.feed.next_frame);
+        out.u64(self.feed.next_final);
+        self.level.save_state(&mut out);
+        out.option(self.latest_level.as_ref(), |out, level| {
+            out.f32(level.rms_dbfs);
+            out.f32(level.peak_dbfs);
+            out.f32(level.momentary_lufs);
+            out.f32(level.short_term_lufs);
+            out.u64(level.clipped_samples as u64);
+        });
+        out.option(self.event_filter.last_emitted.as_ref(), save_event);
+        out.usize(self.event_filter.provisional.len());
+        self.event_filter.provisional.iter().for_each(|&index| out.u64(index));
+        out.option(self.scoring.as_ref(), |out, scoring| scoring.save_state(out));
+        out.bytes(&self.estimator.snapshot());
+        out.finish()
+    }
+
+    /// Continue the session from a `snapshot`. On error the analyzer starts
+    /// over from an empty stream.
+    pub fn restore(&mut self, blob: &[u8]) -> Result<(), PyinError> {
+        let scoring = self.scoring.clone();
+        let result = StateReader::new(blob, SnapshotKind::AudioAnalyzer)
+            .and_then(|mut input| {
+                input.expect_usize("algorithm", self.algorithm as usize)?;
+                self.load_state(&mut input)?;
+                input.finish()
+            });
+        if result.is_err() {
+            self.decoder.reset();
+            self.feed = FrameFeed::default();
+            self.level.reset();
+            self.latest_level = None;
+            self.event_filter.reset();
+            self.scoring = scoring;
+            self.estimator.reset();
+        }
+        result
+    }
+
+    fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.expect_usize("capture rate", self.capture_rate_hz as usize)?;
+        input.expect_usize("analysis rate", self.analysis_rate_hz as usize)?;
+        input.expect_usize("hop size", self.hop_size_samples)?;
+        self.decoder.load_state(input)?;
+        self.feed.next_frame = input.u64()?;
+        self.feed.next_final = input.u64()?;
+        self.level.load_state(input)?;
+        self.latest_level = input.option(|input| {
+            Ok(LevelReading {
+                rms_dbfs: input.f32()?,
+                peak_dbfs: input.f32()?,
+                momentary_lufs: input.f32()?,
+                short_term_lufs: input.f32()?,
+                clipped_samples: u32::try_from(input.u64()?)
+                    .or_else(|_| invalid("clipped sample count out of range"))?,
+            })
+        })?;
+        self.event_filter.last_emitted = input.option(load_event)?;
+        self.event_filter.provisional.clear();
+        for _ in 0..input.usize()? {
+            self.event_filter.provisional.push_back(input.u64()?);
+        }
+        match (input.bool()?, self.scoring.as_mut()) {
+            (true, Some(scoring)) => scoring.load_state(input)?,
+            (false, None) => {}
+            (true, None) => return invalid("start scoring before restoring"),
+            (false, Some(_)) => return invalid("taken without scoring"),
+        }
+        self.estimator.restore(input.bytes()?)
     }
 
-    pub fn process_chunk(&mut self, pcm16le_bytes: Vec<u8>) -> Result<(), PyinError> {
+    fn pyin_mut(&mut self, feature: &str) -> Result<&mut Pyin, PyinError> {
+        self.estimator.as_pyin_mut().ok_or_else(|| {
+            PyinError::InvalidConfig(format!("{} requires PitchAlgorithm::Pyin", feature))
+        })
+    }
+
+    /// Process a chunk of PCM in the format given by `PitchConfig`.
+    pub fn process_chunk(&mut self, pcm_bytes: Vec<u8>) -> Result<(), PyinError> {
         let sink = self.sink.clone();
-        self.process_pcm_bytes(&pcm16le_bytes, |note| {
+        for event in self.process_chunk_collect_events(&pcm_bytes)? {
             if let Some(ref stream) = sink {
-                let _ = stream.add(note);
+                let _ = stream.add(event);
             }
-        })
+        }
+        self.send_score_events();
+        Ok(())
+    }
+
+    /// End the stream once the recording is over: complete the last frame
+    /// with silence and stream every frame still held back by the pitch
+    /// decoder, final. The analyzer then starts a new stream, keeping its
+    /// backing track, target notes and scoring.
+    pub fn finish(&mut self) -> Result<(), PyinError> {
+        let sink = self.sink.clone();
+        for event in self.finish_collect_events()? {
+            if let Some(ref stream) = sink {
+                let _ = stream.add(event);
+            }
+        }
+        self.send_score_events();
+        Ok(())
+    }
+
+    /// Like `finish`, but returns the events instead of streaming them.
+    pub fn finish_collect_events(&mut self) -> Result<Vec<PitchEvent>, PyinError> {
+        let mut frames = Vec::new();
+        let result = self.finish_pcm(|frame, first| frames.push((frame.clone(), first)));
+        let events = self.events(frames);
+        self.event_filter.reset();
+        result.map(|()| events)
     }
 
-    pub fn process_chunk_collect(&mut self, pcm16le_bytes: &[u8]) -> Result<Vec<u8>, PyinError> {
+    /// Process a chunk and return the `PitchEvent`s that pass the stream options
+    /// set by `create_stream` (all events if it was never called).
+    pub fn process_chunk_collect_events(
+        &mut self,
+        pcm_bytes: &[u8],
+    ) -> Result<Vec<PitchEvent>, PyinError> {
+        let mut frames = Vec::new();
+        self.process_pcm_bytes(pcm_bytes, |frame, first| frames.push((frame.clone(), first)))?;
+        Ok(self.events(frames))
+    }
+
+    /// The events of the frame reports that pass the stream options.
+    fn events(&mut self, frames: Vec<(FrameEstimate, bool)>) -> Vec<PitchEvent> {
+        let mut events = Vec::new();
+        for (frame, first) in &frames {
+            let event = PitchEvent::from_frame(frame, self.sample_position(frame));
+            if self.event_filter.accept_report(frame.frame_index, *first, &event) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    pub fn process_chunk_collect(&mut self, pcm_bytes: &[u8]) -> Result<Vec<u8>, PyinError> {
         let mut notes = Vec::new();
-        self.process_pcm_bytes(pcm16le_bytes, |note| notes.push(note))?;
+        self.process_pcm_bytes(pcm_bytes, |frame, first| {
+            if first {
+                notes.push(midi_or_unvoiced(frame));
+            }
+        })?;
         Ok(notes)
     }
 
+    /// Like `process_chunk_collect`, but returns the unrounded MIDI pitch per hop
+    /// (relative to `PitchConfig::reference_a4_hz`), or 255.0 when unvoiced.
+    pub fn process_chunk_collect_fractional(
+        &mut self,
+        pcm_bytes: &[u8],
+    ) -> Result<Vec<f32>, PyinError> {
+        let mut pitches = Vec::new();
+        self.process_pcm_bytes(pcm_bytes, |frame, first| {
+            if first {
+                pitches.push(frame.midi_fractional.unwrap_or(UNVOICED_MIDI as f32));
+            }
+        })?;
+        Ok(pitches)
+    }
+
+    /// Decode a chunk and pass the frame reports of `FrameFeed` to `emit`.
     fn process_pcm_bytes(
         &mut self,
-        pcm16le_bytes: &[u8],
-        mut emit: impl FnMut(u8),
+        pcm_bytes: &[u8],
+        mut emit: impl FnMut(&FrameEstimate, bool),
     ) -> Result<(), PyinError> {
-        let new_samples = parse_pcm16le_to_i16(pcm16le_bytes, &mut self.leftover_bytes);
-        self.input_buffer.extend(new_samples);
-
-        while self.input_buffer.len() >= self.frame_size_samples {
-            let mut hop_bytes = Vec::with_capacity(self.hop_size_samples * 2);
-            for sample in self.input_buffer.iter().take(self.hop_size_samples) {
-                hop_bytes.extend_from_slice(&sample.to_le_bytes());
+        self.decoded.clear();
+        self.decoder.decode(pcm_bytes, &mut self.decoded);
+        let mut level = self.level.process(&self.decoded);
+        level.clipped_samples = self.decoder.clipped_samples();
+        self.latest_level = Some(level);
+        let scoring = &mut self.scoring;
+        self.feed.push(self.estimator.as_mut(), &self.decoded, |frame, first| {
+            if let Some(scoring) = scoring.as_mut().filter(|_| frame.is_final) {
+                scoring.push_estimate(frame);
             }
-            let frames = self.pyin.push_bytes(&hop_bytes)?;
-            let note = frames
-                .last()
-                .and_then(|frame| frame.midi_note.map(|m| m as u8))
-                .unwrap_or(UNVOICED_MIDI as u8);
-            emit(note);
-            for _ in 0..self.hop_size_samples.min(self.input_buffer.len()) {
-                self.input_buffer.pop_front();
+            emit(frame, first)
+        })
+    }
+
+    /// Flush the decoder and the estimator and pass the frame reports of
+    /// `FrameFeed` to `emit`, then reset for a new stream.
+    fn finish_pcm(&mut self, mut emit: impl FnMut(&FrameEstimate, bool)) -> Result<(), PyinError> {
+        self.decoded.clear();
+        self.decoder.finish(&mut self.decoded);
+        let scoring = &mut self.scoring;
+        let mut report = |frame: &FrameEstimate, first: bool| {
+            if let Some(scoring) = scoring.as_mut().filter(|_| frame.is_final) {
+                scoring.push_estimate(frame);
             }
+            emit(frame, first)
+        };
+        let result = self
+            .feed
+            .push(self.estimator.as_mut(), &self.decoded, &mut report)
+            .and_then(|()| self.estimator.finish(TailPolicy::ZeroPad));
+        if let Ok(end) = &result {
+            self.feed.report(&end.estimates, &[], &mut report);
         }
-        Ok(())
+        self.feed = FrameFeed::default();
+        self.level.reset();
+        self.estimator.reset();
+        result.map(|_| ())
     }
+
+    /// Capture-rate index of the first sample of `frame`'s analysis window.
+    fn sample_position(&self, frame: &FrameEstimate) -> u64 {
+        let analysis_position = frame.frame_index * self.hop_size_samples as u64;
+        analysis_position * self.capture_rate_hz as u64 / self.analysis_rate_hz as u64
+    }
+}
+
+fn save_event(out: &mut StateWriter, event: &PitchEvent) {
+    let optional = |out: &mut StateWriter, value: Option<f32>| {
+        out.option(value.as_ref(), |out, v| out.f32(*v));
+    };
+    out.u64(event.sample_position);
+    out.f64(event.time_sec);
+    optional(out, event.f0_hz);
+    optional(out, event.midi_fractional);
+    out.f32(event.confidence);
+    out.bool(event.voiced);
+    out.f32(event.rms_dbfs);
+    out.f32(event.peak_dbfs);
+    out.f32(event.loudness_lufs);
+    out.bool(event.is_final);
+    optional(out, event.speech_prob);
+    optional(out, event.noise_floor_dbfs);
+}
+
+fn load_event(input: &mut StateReader) -> Result<PitchEvent, PyinError> {
+    Ok(PitchEvent {
+        sample_position: input.u64()?,
+        time_sec: input.f64()?,
+        f0_hz: input.option(|input| input.f32())?,
+        midi_fractional: input.option(|input| input.f32())?,
+        confidence: input.f32()?,
+        voiced: input.bool()?,
+        rms_dbfs: input.f32()?,
+        peak_dbfs: input.f32()?,
+        loudness_lufs: input.f32()?,
+        is_final: input.bool()?,
+        speech_prob: input.option(|input| input.f32())?,
+        noise_floor_dbfs: input.option(|input| input.f32())?,
+    })
 }
 
-fn parse_pcm16le_to_i16(bytes: &[u8], leftover: &mut Vec<u8>) -> Vec<i16> {
-    let mut data = Vec::with_capacity(leftover.len() + bytes.len());
-    data.extend_from_slice(leftover);
-    data.extend_from_slice(bytes);
+fn parse_chart(chart: &[u8]) -> Result<Song, PyinError> {
+    Song::parse(chart).map_err(|err| PyinError::InvalidConfig(format!("song chart: {}", err)))
+}
+
+/// Options of `Party::add_singer`.
+#[derive(Debug, Clone)]
+pub struct SingerOptions {
+    pub name: String,
+    /// Part of the chart the singer is scored against; see
+    /// `ScoreStreamOptions`.
+    pub track: u32,
+    pub harmony: bool,
+    pub difficulty: Difficulty,
+    pub line_bonus: bool,
+    /// Party clock time of the first sample of the singer's stream.
+    pub stream_start_sec: f64,
+}
+
+/// The singers of one song, each with their own analyzer, scored against one
+/// song clock.
+///
+/// Times are on the party clock, which the host chooses, e.g. its monotonic
+/// clock: the song's audio starts playing at `song_start_sec` and each
+/// singer's stream starts at their `stream_start_sec`, which places every
+/// phone's stream on the song's timeline.
+pub struct Party {
+    song: Song,
+    song_start_sec: f64,
+    names: Vec<String>,
+    analyzers: Vec<AudioAnalyzer>,
+}
+
+impl Party {
+    /// A party singing `chart`, an UltraStar .txt file, whose audio starts
+    /// playing at `song_start_sec` on the party clock.
+    pub fn new(chart: Vec<u8>, song_start_sec: f64) -> Result<Self, PyinError> {
+        Ok(Self {
+            song: parse_chart(&chart)?,
+            song_start_sec,
+            names: Vec::new(),
+            analyzers: Vec::new(),
+        })
+    }
+
+    /// Add a singer whose microphone captures PCM as described by `config`,
+    /// returning their index.
+    pub fn add_singer(
+        &mut self,
+        config: PitchConfig,
+        options: SingerOptions,
+    ) -> Result<u32, PyinError> {
+        let mut analyzer = AudioAnalyzer::new(config)?;
+        let score_options = ScoreStreamOptions {
+            track: options.track,
+            harmony: options.harmony,
+            difficulty: options.difficulty,
+            line_bonus: options.line_bonus,
+            song_start_sec: self.song_start_sec - options.stream_start_sec,
+        };
+        analyzer.score_song(&self.song, score_options)?;
+        self.analyzers.push(analyzer);
+        self.names.push(options.name);
+        Ok(self.analyzers.len() as u32 - 1)
+    }
 
-    let mut samples = Vec::with_capacity(data.len() / 2);
-    let mut idx = 0;
-    while idx + 1 < data.len() {
-        let sample = i16::from_le_bytes([data[idx], data[idx + 1]]);
-        samples.push(sample);
-        idx += 2;
+    /// Process a chunk of the singer's PCM.
+    pub fn process_chunk(&mut self, singer: u32, pcm_bytes: Vec<u8>) -> Result<(), PyinError> {
+        self.analyzer_mut(singer)?.process_chunk(pcm_bytes)
     }
 
-    leftover.clear();
-    if idx < data.len() {
-        leftover.push(data[idx]);
+    /// Take the singer's score events not yet taken, in song time.
+    pub fn drain_score_events(&mut self, singer: u32) -> Result<Vec<ScoreEvent>, PyinError> {
+        Ok(self.analyzer_mut(singer)?.drain_score_events())
     }
 
-    samples
+    /// Finish every singer once the song is over; see
+    /// `AudioAnalyzer::finish_scoring`. Returns the first error, after
+    /// finishing the others.
+    pub fn finish(&mut self) -> Result<(), PyinError> {
+        let mut result = Ok(());
+        for analyzer in &mut self.analyzers {
+            let finished = analyzer.finish_scoring();
+            result = result.and(finished);
+        }
+        result
+    }
+
+    /// Results of the singers, in the order they were added.
+    pub fn results(&self) -> Vec<SingerResult> {
+        self.names
+            .iter()
+            .zip(&self.analyzers)
+            .filter_map(|(name, analyzer)| Some(analyzer.scoring()?.result(name.as_str())))
+            .collect()
+    }
+
+    /// The singer's analyzer, e.g. to set their backing track or stream their
+    /// pitch.
+    #[frb(ignore)]
+    pub fn analyzer_mut(&mut self, singer: u32) -> Result<&mut AudioAnalyzer, PyinError> {
+        self.analyzers
+            .get_mut(singer as usize)
+            .ok_or_else(|| PyinError::InvalidConfig(format!("no singer {}", singer)))
+    }
+}
+
+fn midi_or_unvoiced(frame: &FrameEstimate) -> u8 {
+    frame.midi_note.unwrap_or(UNVOICED_MIDI as u8)
 }
 
 pub struct PyinProcessor {
     pyin: Option<Pyin>,
-    sample_queue: VecDeque<i16>,
-    frame_buf: Vec<i16>,
-    carry_byte: Option<u8>,
-    frame_size_samples: usize,
-    hop_size_samples: usize,
-    invalid_config: bool,
+    feed: FrameFeed,
 }
 
 impl PyinProcessor {
     fn new(sample_rate_hz: u32, window_ms: u32, hop_ms: u32) -> Self {
         let frame_size_samples = ms_to_samples(sample_rate_hz, window_ms);
         let hop_size_samples = ms_to_samples(sample_rate_hz, hop_ms);
-        let invalid_config = frame_size_samples < hop_size_samples;
-
-        if invalid_config {
+        let pyin = if frame_size_samples < hop_size_samples {
             log::error!(
                 "invalid processor config: frame_size_samples={} hop_size_samples={}",
                 frame_size_samples,
                 hop_size_samples
             );
-            return Self {
-                pyin: None,
-                sample_queue: VecDeque::new(),
-                frame_buf: Vec::new(),
-                carry_byte: None,
-                frame_size_samples,
-                hop_size_samples,
-                invalid_config,
+            None
+        } else {
+            let cfg = PyinConfig {
+                sample_rate_hz,
+                frame_size: frame_size_samples,
+                hop_size: hop_size_samples,
+                fmin_hz: 40.0,
+                fmax_hz: 2_000.0,
+                ..PyinConfig::default()
             };
-        }
-
-        let cfg = PyinConfig {
-            sample_rate_hz,
-            frame_size: frame_size_samples,
-            hop_size: hop_size_samples,
-            fmin_hz: 40.0,
-            fmax_hz: 2_000.0,
-            ..PyinConfig::default()
-        };
-
-        let pyin = match Pyin::new(cfg, PcmFormat::I16LE) {
-            Ok(v) => Some(v),
-            Err(err) => {
-                log::error!("failed to initialize pyin: {:?}", err);
-                None
+            let input = PcmInput::mono(PcmFormat::I16LE, sample_rate_hz);
+            match Pyin::with_input(cfg, input) {
+                Ok(pyin) => Some(pyin),
+                Err(err) => {
+                    log::error!("failed to initialize pyin: {:?}", err);
+                    None
+                }
             }
         };
 
         Self {
             pyin,
-            sample_queue: VecDeque::new(),
-            frame_buf: vec![0; frame_size_samples],
-            carry_byte: None,
-            frame_size_samples,
-            hop_size_samples,
-            invalid_config,
+            feed: FrameFeed::default(),
         }
     }
 }
@@ -191,58 +993,104 @@ pub fn init_logging() {
     });
 }
 
+/// Offline pitch contour of the audio file at `path`, e.g. to compare a
+/// singer against the song's own vocal track.
+pub fn analyze_file(path: String, options: FileAnalysisOptions) -> Result<FileContour, PyinError> {
+    file_contour(&path, &options, |_| {})
+}
+
+/// Like `analyze_file`, streaming an update to `sink` as each packet of the
+/// file is decoded, for a progress bar over long files, then a last one
+/// carrying the contour.
+pub fn analyze_file_stream(
+    path: String,
+    options: FileAnalysisOptions,
+    sink: StreamSink<FileAnalysisUpdate>,
+) -> Result<(), PyinError> {
+    let mut last = AnalysisProgress {
+        processed_sec: 0.0,
+        total_sec: None,
+    };
+    let contour = file_contour(&path, &options, |progress| {
+        last = progress;
+        let _ = sink.add(FileAnalysisUpdate {
+            processed_sec: progress.processed_sec,
+            total_sec: progress.total_sec,
+            contour: None,
+        });
+    })?;
+    let _ = sink.add(FileAnalysisUpdate {
+        processed_sec: last.processed_sec,
+        total_sec: last.total_sec,
+        contour: Some(contour),
+    });
+    Ok(())
+}
+
+fn file_contour(
+    path: &str,
+    options: &FileAnalysisOptions,
+    on_progress: impl FnMut(AnalysisProgress),
+) -> Result<FileContour, PyinError> {
+    let frame_size = ms_to_samples(options.analysis_sample_rate_hz, options.window_size_ms);
+    let hop_size = ms_to_samples(options.analysis_sample_rate_hz, options.update_interval_ms);
+    if frame_size < hop_size {
+        return Err(PyinError::InvalidConfig(
+            "window_size must be >= update_interval".to_string(),
+        ));
+    }
+    let cfg = FileAnalysisConfig {
+        pyin: PyinConfig {
+            sample_rate_hz: options.analysis_sample_rate_hz,
+            frame_size,
+            hop_size,
+            fmin_hz: 40.0,
+            fmax_hz: 2_000.0,
+            reference_a4_hz: options.reference_a4_hz,
+            ..PyinConfig::default()
+        },
+        start_sec: options.start_sec,
+        end_sec: options.end_sec,
+    };
+    let analysis = super::analyze_file_with_progress(path, &cfg, on_progress)
+        .map_err(|err| PyinError::InvalidConfig(format!("{:#}", err)))?;
+    let source_rate = analysis.source_sample_rate_hz as f64;
+    let events = analysis
+        .frames
+        .iter()
+        .map(|frame| PitchEvent::from_frame(frame, (frame.time_sec * source_rate).round() as u64))
+        .collect();
+    Ok(FileContour {
+        source_sample_rate_hz: analysis.source_sample_rate_hz,
+        duration_sec: analysis.duration_sec,
+        events,
+    })
+}
+
 pub fn new_processor(sample_rate_hz: u32, window_ms: u32, hop_ms: u32) -> PyinProcessor {
     PyinProcessor::new(sample_rate_hz, window_ms, hop_ms)
 }
 
 pub fn push_and_get_midi(proc: &mut PyinProcessor, pcm16le_bytes: Vec<u8>) -> u16 {
     let run = || -> u16 {
-        if proc.invalid_config || proc.pyin.is_none() {
+        let Some(pyin) = proc.pyin.as_mut() else {
             return UNVOICED_MIDI;
-        }
-
-        // Keep parsed mono samples in a deque to avoid O(n) front shifts while
-        // advancing by hop size across calls.
-        parse_pcm16le_bytes(proc, &pcm16le_bytes);
-
-        if proc.sample_queue.len() < proc.frame_size_samples {
-            return UNVOICED_MIDI;
-        }
-
-        let mut latest_midi = UNVOICED_MIDI;
-
-        while proc.sample_queue.len() >= proc.frame_size_samples {
-            for (idx, sample) in proc.sample_queue.iter().take(proc.frame_size_samples).enumerate() {
-                proc.frame_buf[idx] = *sample;
-            }
-
-            // Feed exactly one hop worth of new data into the existing pYIN stream engine.
-            let mut hop_bytes = Vec::with_capacity(proc.hop_size_samples * 2);
-            for sample in proc.sample_queue.iter().take(proc.hop_size_samples) {
-                hop_bytes.extend_from_slice(&sample.to_le_bytes());
-            }
-
-            if let Some(pyin) = proc.pyin.as_mut() {
-                match pyin.push_bytes(&hop_bytes) {
-                    Ok(frames) => {
-                        for frame in frames {
-                            if let Some(note) = frame.midi_note {
-                                latest_midi = note as u16;
-                            }
-                        }
-                    }
-                    Err(err) => {
-                        log::error!("pYIN push_bytes failed: {:?}", err);
-                        return UNVOICED_MIDI;
-                    }
-                }
+        };
+        let committed = match pyin.push_bytes(&pcm16le_bytes) {
+            Ok(committed) => committed,
+            Err(err) => {
+                log::error!("pYIN push_bytes failed: {:?}", err);
+                return UNVOICED_MIDI;
             }
+        };
 
-            for _ in 0..proc.hop_size_samples.min(proc.sample_queue.len()) {
-                proc.sample_queue.pop_front();
+        let mut latest_midi = UNVOICED_MIDI;
+        let provisional = pyin.provisional_estimates();
+        proc.feed.report(&committed, &provisional, |frame, first| {
+            if let Some(note) = frame.midi_note.filter(|_| first) {
+                latest_midi = note as u16;
             }
-        }
-
+        });
         latest_midi
     };
 
@@ -255,30 +1103,6 @@ pub fn push_and_get_midi(proc: &mut PyinProcessor, pcm16le_bytes: Vec<u8>) -> u1
     }
 }
 
-fn parse_pcm16le_bytes(proc: &mut PyinProcessor, bytes: &[u8]) {
-    let mut idx = 0usize;
-
-    if let Some(prev) = proc.carry_byte.take() {
-        if let Some(&next) = bytes.first() {
-            proc.sample_queue.push_back(i16::from_le_bytes([prev, next]));
-            idx = 1;
-        } else {
-            proc.carry_byte = Some(prev);
-            return;
-        }
-    }
-
-    while idx + 1 < bytes.len() {
-        proc.sample_queue
-            .push_back(i16::from_le_bytes([bytes[idx], bytes[idx + 1]]));
-        idx += 2;
-    }
-
-    if idx < bytes.len() {
-        proc.carry_byte = Some(bytes[idx]);
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::pyin::midi;
@@ -286,14 +1110,78 @@ mod tests {
 
     #[test]
     fn odd_byte_leftover_across_pushes() {
+        let bytes: Vec<u8> = (0..24_000)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / 48_000.0).sin())
+            .flat_map(|s| ((s * 32767.0) as i16).to_le_bytes())
+            .collect();
         let mut proc = new_processor(48_000, 20, 10);
-        let _ = push_and_get_midi(&mut proc, vec![0x34]);
-        assert_eq!(proc.sample_queue.len(), 0);
-        assert_eq!(proc.carry_byte, Some(0x34));
+        assert_eq!(push_and_get_midi(&mut proc, bytes[..1].to_vec()), UNVOICED_MIDI);
+
+        // Every later push starts mid-sample, so a dropped byte would garble
+        // the signal.
+        let notes: Vec<u16> = bytes[1..]
+            .chunks(1001)
+            .map(|chunk| push_and_get_midi(&mut proc, chunk.to_vec()))
+            .filter(|&note| note != UNVOICED_MIDI)
+            .collect();
+        assert!(notes.len() >= 10);
+        assert!(notes.iter().all(|&note| note == 57), "{:?}", notes);
+    }
+
+    fn event(time_sec: f64, midi: Option<f32>) -> PitchEvent {
+        PitchEvent {
+            sample_position: (time_sec * 48_000.0) as u64,
+            time_sec,
+            f0_hz: midi.map(|m| 440.0 * 2.0_f32.powf((m - 69.0) / 12.0)),
+            midi_fractional: midi,
+            confidence: 0.9,
+            voiced: midi.is_some(),
+            rms_dbfs: -20.0,
+            peak_dbfs: -17.0,
+            loudness_lufs: -20.0,
+            is_final: false,
+            speech_prob: None,
+            noise_floor_dbfs: None,
+        }
+    }
+
+    #[test]
+    fn event_filter_only_on_change() {
+        let mut filter = PitchEventFilter {
+            options: PitchStreamOptions {
+                only_on_change: true,
+                min_change_cents: 20.0,
+                max_events_per_sec: 0,
+                final_only: false,
+            },
+            last_emitted: None,
+            provisional: VecDeque::new(),
+        };
+        let pitches = [Some(60.0), Some(60.1), Some(60.25), None, None, Some(60.25)];
+        let accepted: Vec<bool> = pitches
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| filter.accept(&event(i as f64 * 0.01, m)))
+            .collect();
+        assert_eq!(accepted, vec![true, false, true, true, false, true]);
+    }
 
-        let _ = push_and_get_midi(&mut proc, vec![0x12, 0x78, 0x56]);
-        assert_eq!(proc.sample_queue.front().copied(), Some(0x1234));
-        assert_eq!(proc.sample_queue.get(1).copied(), Some(0x5678));
+    #[test]
+    fn event_filter_caps_rate() {
+        let mut filter = PitchEventFilter {
+            options: PitchStreamOptions {
+                only_on_change: false,
+                min_change_cents: 0.0,
+                max_events_per_sec: 20,
+                final_only: false,
+            },
+            last_emitted: None,
+            provisional: VecDeque::new(),
+        };
+        let accepted = (0..100)
+            .filter(|i| filter.accept(&event(*i as f64 * 0.005, Some(60.0))))
+            .count();
+        assert_eq!(accepted, 10);
     }
 
     #[test]
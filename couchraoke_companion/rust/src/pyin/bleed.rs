@@ -0,0 +1,255 @@
+//! Backing-track bleed cancellation.
+//!
+//! When the song plays from a TV in the same room, the mic picks up the
+//! original recording, lead vocal included. Since we know which song is
+//! playing, its decoded audio is used as the reference of a normalised LMS
+//! (NLMS) adaptive filter that learns the loudspeaker-to-mic path and subtracts
+//! the predicted bleed from the mic signal before pitch analysis.
+
+use std::sync::Arc;
+
+use crate::decode::FileDecoder;
+
+use super::resample::Resampler;
+use super::snapshot::{StateReader, StateWriter};
+use super::PyinError;
+
+#[derive(Debug, Clone)]
+pub struct BleedConfig {
+    /// Stream time (the clock of `FrameEstimate::time_sec`) at which the start
+    /// of the reference reaches the mic, i.e. playback start plus output and
+    /// acoustic latency. May be negative if playback started before the stream.
+    pub delay_sec: f64,
+    /// Length of the adaptive filter. It must cover the room response and the
+    /// error of `delay_sec`; the first quarter sits before the estimated delay.
+    pub filter_ms: f32,
+    /// NLMS step size in (0, 2). Smaller values adapt more slowly but are less
+    /// disturbed by the singer's voice.
+    pub step_size: f32,
+}
+
+impl Default for BleedConfig {
+    fn default() -> Self {
+        Self {
+            delay_sec: 0.0,
+            filter_ms: 20.0,
+            step_size: 0.05,
+        }
+    }
+}
+
+/// Decoded mono song audio at the analysis sample rate.
+#[derive(Debug, Clone)]
+pub struct BleedReference {
+    sample_rate_hz: u32,
+    samples: Arc<Vec<f32>>,
+}
+
+impl BleedReference {
+    pub fn from_samples(samples: Vec<f32>, sample_rate_hz: u32) -> Self {
+        Self {
+            sample_rate_hz,
+            samples: Arc::new(samples),
+        }
+    }
+
+    /// Decode `path` through symphonia, downmix it and resample it to
+    /// `sample_rate_hz`, which must match the detector's analysis rate.
+    pub fn from_file(path: &str, sample_rate_hz: u32) -> anyhow::Result<Self> {
+        let decoder = FileDecoder::open(path)?;
+        let info = decoder.info();
+        let channels = info.channels.max(1);
+        let mut resampler = Resampler::new(info.sample_rate, sample_rate_hz);
+        let mut mono = Vec::new();
+        let mut samples = Vec::new();
+
+        decoder.decode_all::<f32, _>(|interleaved| {
+            mono.clear();
+            mono.extend(
+                interleaved
+                    .chunks_exact(channels)
+                    .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+            );
+            resampler.process(&mono, &mut samples);
+            Ok(())
+        })?;
+        resampler.flush(&mut samples);
+
+        Ok(Self::from_samples(samples, sample_rate_hz))
+    }
+
+    pub fn sample_rate_hz(&self) -> u32 {
+        self.sample_rate_hz
+    }
+
+    pub fn duration_sec(&self) -> f64 {
+        self.samples.len() as f64 / self.sample_rate_hz.max(1) as f64
+    }
+}
+
+/// NLMS canceller aligned to the absolute sample position of the mic stream.
+pub(crate) struct BleedCanceller {
+    reference: BleedReference,
+    weights: Vec<f32>,
+    /// Taps placed before the estimated delay.
+    lead: i64,
+    delay_samples: i64,
+    step_size: f32,
+}
+
+/// Keeps the NLMS update finite while the reference is silent.
+const POWER_EPSILON: f32 = 1e-6;
+
+impl BleedCanceller {
+    pub fn new(
+        reference: BleedReference,
+        cfg: &BleedConfig,
+        sample_rate_hz: u32,
+    ) -> Result<Self, PyinError> {
+        let invalid = |msg: String| Err(PyinError::InvalidConfig(msg));
+        if reference.sample_rate_hz != sample_rate_hz {
+            return invalid(format!(
+                "bleed reference is at {} Hz but the detector runs at {} Hz",
+                reference.sample_rate_hz, sample_rate_hz
+            ));
+        }
+        if !(cfg.filter_ms.is_finite() && cfg.filter_ms > 0.0) {
+            return invalid("bleed filter_ms must be > 0".to_string());
+        }
+        if !(cfg.step_size > 0.0 && cfg.step_size < 2.0) {
+            return invalid("bleed step_size must be in (0, 2)".to_string());
+        }
+        if !cfg.delay_sec.is_finite() {
+            return invalid("bleed delay_sec must be finite".to_string());
+        }
+        let taps = ((cfg.filter_ms / 1000.0 * sample_rate_hz as f32).round() as usize).max(1);
+        Ok(Self {
+            reference,
+            weights: vec![0.0; taps],
+            lead: (taps / 4) as i64,
+            delay_samples: (cfg.delay_sec * sample_rate_hz as f64).round() as i64,
+            step_size: cfg.step_size,
+        })
+    }
+
+    /// Update the delay estimate, keeping the learned filter.
+    pub fn set_delay_sec(&mut self, delay_sec: f64) {
+        self.delay_samples = (delay_sec * self.reference.sample_rate_hz as f64).round() as i64;
+    }
+
+    pub fn reset(&mut self) {
+        self.weights.iter_mut().for_each(|w| *w = 0.0);
+    }
+
+    /// The learned filter and delay; the reference itself is not included.
+    pub fn save_state(&self, out: &mut StateWriter) {
+        out.f32s(self.weights.iter());
+        out.i64(self.delay_samples);
+    }
+
+    pub fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.f32s_exact("bleed filter", &mut self.weights)?;
+        self.delay_samples = input.i64()?;
+        Ok(())
+    }
+
+    /// Cancel the bleed in `samples`, whose first sample sits at absolute
+    /// stream position `position`.
+    pub fn process(&mut self, position: u64, samples: &mut [f32]) {
+        let reference = self.reference.samples.as_slice();
+        let taps = self.weights.len() as i64;
+        for (offset, sample) in samples.iter_mut().enumerate() {
+            // Newest reference sample in the filter window.
+            let newest = position as i64 + offset as i64 - self.delay_samples + self.lead;
+            let oldest = newest - taps + 1;
+            if newest < 0 || oldest >= reference.len() as i64 {
+                continue;
+            }
+
+            // Window over the reference, newest first, clipped to the song.
+            let first_tap = (newest - reference.len() as i64 + 1).max(0) as usize;
+            let last_tap = newest.min(taps - 1) as usize;
+            let window = |tap: usize| reference[(newest - tap as i64) as usize];
+
+            let mut estimate = 0.0;
+            let mut power = 0.0;
+            for tap in first_tap..=last_tap {
+                let x = window(tap);
+                estimate += self.weights[tap] * x;
+                power += x * x;
+            }
+            let error = *sample - estimate;
+            let gain = self.step_size * error / (power + POWER_EPSILON);
+            for tap in first_tap..=last_tap {
+                self.weights[tap] += gain * window(tap);
+            }
+            *sample = error;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn power(samples: &[f32]) -> f32 {
+        samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32
+    }
+
+    #[test]
+    fn cancels_delayed_filtered_reference_in_chunks() {
+        let rate = 16_000;
+        let mut rng = StdRng::seed_from_u64(3);
+        let reference: Vec<f32> = (0..rate * 3).map(|_| rng.gen_range(-0.5..0.5)).collect();
+        // Bleed arrives 0.1 s late through a short two-path room response.
+        let delay = 1_600;
+        let mic: Vec<f32> = (0..reference.len())
+            .map(|n| {
+                let at = |k: usize| if n >= k { reference[n - k] } else { 0.0 };
+                0.6 * at(delay) + 0.25 * at(delay + 37)
+            })
+            .collect();
+
+        let cfg = BleedConfig {
+            delay_sec: 0.1,
+            step_size: 0.2,
+            ..BleedConfig::default()
+        };
+        let mut canceller = BleedCanceller::new(
+            BleedReference::from_samples(reference, rate as u32),
+            &cfg,
+            16_000,
+        )
+        .unwrap();
+        let mut output = mic.clone();
+        let mut position = 0;
+        for chunk in output.chunks_mut(333) {
+            canceller.process(position, chunk);
+            position += chunk.len() as u64;
+        }
+
+        let tail = mic.len() - rate;
+        let reduction_db = 10.0 * (power(&mic[tail..]) / power(&output[tail..])).log10();
+        assert!(reduction_db > 25.0, "reduction {} dB", reduction_db);
+    }
+
+    #[test]
+    fn passes_signal_through_outside_the_reference() {
+        let reference = BleedReference::from_samples(vec![0.5; 100], 16_000);
+        let cfg = BleedConfig {
+            delay_sec: 1.0,
+            ..BleedConfig::default()
+        };
+        let mut canceller = BleedCanceller::new(reference, &cfg, 16_000).unwrap();
+        let mut samples = vec![0.3; 1000];
+        canceller.process(0, &mut samples);
+        assert!(samples.iter().all(|s| *s == 0.3));
+    }
+
+    #[test]
+    fn rejects_mismatched_sample_rate() {
+        let reference = BleedReference::from_samples(vec![0.0; 10], 44_100);
+        assert!(BleedCanceller::new(reference, &BleedConfig::default(), 48_000).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+//! Pitch estimation algorithms behind one interface.
+//!
+//! `Pyin` is the reference estimator. The alternatives analyse every frame on
+//! its own, without HMM smoothing, and cost a fraction of it: YIN with a single
+//! threshold, the McLeod pitch method (MPM) and the harmonic product spectrum
+//! (HPS). All of them take mono samples at `PyinConfig::sample_rate_hz` and
+//! report `FrameEstimate`s on the same frame grid, so they can be compared on
+//! the same input.
+
+use super::framing::FrameRing;
+use super::hps::HpsMethod;
+use super::level::{FrameLevels, LevelReading};
+use super::midi::{cents_offset, fractional_midi_from_hz, midi_from_fractional};
+use super::mpm::MpmMethod;
+use super::snapshot::{SnapshotKind, StateReader, StateWriter};
+use super::yin::{
+    cumulative_mean_normalized_difference, parabolic_interpolation, DifferenceFunction,
+};
+use super::{FrameEstimate, PcmFormat, Pyin, PyinConfig, PyinError, StreamEnd, TailPolicy};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PitchAlgorithm {
+    /// Probabilistic YIN with HMM smoothing: the most robust and the most
+    /// expensive.
+    #[default]
+    Pyin,
+    /// YIN with a single absolute threshold.
+    Yin,
+    /// McLeod pitch method over the normalised square difference function.
+    Mpm,
+    /// Harmonic product spectrum.
+    Hps,
+}
+
+/// Streaming pitch estimator over mono samples.
+pub trait PitchEstimator: Send + Sync {
+    /// Push samples in [-1, 1] at the analysis rate and return the estimates
+    /// completed by them, oldest first.
+    fn push_samples(&mut self, samples: &[f32]) -> Result<Vec<FrameEstimate>, PyinError>;
+
+    /// Estimates of analysed frames that may still change, oldest first.
+    fn provisional_estimates(&self) -> Vec<FrameEstimate> {
+        Vec::new()
+    }
+
+    fn reset(&mut self);
+
+    /// End the stream and return every estimate not returned before, all
+    /// final; see `Pyin::finish`. The estimator is then reset.
+    fn finish(&mut self, tail: TailPolicy) -> Result<StreamEnd, PyinError>;
+
+    /// Serialise the streaming state into a versioned blob; see
+    /// `Pyin::snapshot`.
+    fn snapshot(&self) -> Vec<u8>;
+
+    /// Continue from a `snapshot` of an estimator with the same algorithm and
+    /// configuration. On error the estimator is reset.
+    fn restore(&mut self, blob: &[u8]) -> Result<(), PyinError>;
+
+    /// The pYIN estimator, for the features only it supports (bleed
+    /// cancellation, score prior).
+    fn as_pyin_mut(&mut self) -> Option<&mut Pyin> {
+        None
+    }
+}
+
+impl PitchEstimator for Pyin {
+    fn push_samples(&mut self, samples: &[f32]) -> Result<Vec<FrameEstimate>, PyinError> {
+        Pyin::push_samples(self, samples)
+    }
+
+    fn provisional_estimates(&self) -> Vec<FrameEstimate> {
+        Pyin::provisional_estimates(self)
+    }
+
+    fn reset(&mut self) {
+        Pyin::reset(self);
+    }
+
+    fn finish(&mut self, tail: TailPolicy) -> Result<StreamEnd, PyinError> {
+        Pyin::finish(self, tail)
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        Pyin::snapshot(self)
+    }
+
+    fn restore(&mut self, blob: &[u8]) -> Result<(), PyinError> {
+        Pyin::restore(self, blob)
+    }
+
+    fn as_pyin_mut(&mut self) -> Option<&mut Pyin> {
+        Some(self)
+    }
+}
+
+/// Create the estimator for `algorithm`. The frame-wise algorithms use the
+/// rate, framing, range and tuning fields of `cfg`; the HMM, VAD and octave
+/// settings only apply to pYIN.
+pub fn new_estimator(
+    algorithm: PitchAlgorithm,
+    cfg: PyinConfig,
+) -> Result<Box<dyn PitchEstimator>, PyinError> {
+    Ok(match algorithm {
+        PitchAlgorithm::Pyin => Box::new(Pyin::new(cfg, PcmFormat::F32LE)?),
+        PitchAlgorithm::Yin => {
+            let method = YinMethod::new(&cfg);
+            Box::new(FramewiseEstimator::new(cfg, method)?)
+        }
+        PitchAlgorithm::Mpm => {
+            let method = MpmMethod::new(&cfg);
+            Box::new(FramewiseEstimator::new(cfg, method)?)
+        }
+        PitchAlgorithm::Hps => {
+            let method = HpsMethod::new(&cfg);
+            Box::new(FramewiseEstimator::new(cfg, method)?)
+        }
+    })
+}
+
+/// Best pitch of one frame.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FramePitch {
+    pub f0_hz: f32,
+    /// How strongly periodic the frame is at `f0_hz`, in [0, 1]: the
+    /// algorithm's own clarity measure.
+    pub strength: f32,
+    pub voiced: bool,
+}
+
+/// A pitch algorithm that looks at one frame at a time.
+pub(crate) trait FrameMethod: Send + Sync {
+    fn estimate(&mut self, frame: &[f32]) -> FramePitch;
+}
+
+/// Lag search range of the time-domain methods for `cfg.fmin_hz..=fmax_hz`.
+pub(crate) fn lag_range(cfg: &PyinConfig) -> (usize, usize) {
+    let max_tau = (cfg.sample_rate_hz as f32 / cfg.fmin_hz).floor() as usize;
+    let min_tau = (cfg.sample_rate_hz as f32 / cfg.fmax_hz).ceil() as usize;
+    let max_tau = max_tau.min(cfg.frame_size.saturating_sub(1));
+    let min_tau = min_tau.max(1).min(max_tau);
+    (min_tau, max_tau)
+}
+
+/// Runs a `FrameMethod` over the same frame grid and level metering as `Pyin`.
+/// Every estimate is final as soon as its frame is complete.
+pub(crate) struct FramewiseEstimator<M> {
+    cfg: PyinConfig,
+    method: M,
+    ring: FrameRing,
+    frames: u64,
+    levels: FrameLevels,
+}
+
+impl<M: FrameMethod> FramewiseEstimator<M> {
+    pub fn new(cfg: PyinConfig, method: M) -> Result<Self, PyinError> {
+        let invalid = |msg: &str| Err(PyinError::InvalidConfig(msg.to_string()));
+        if cfg.sample_rate_hz == 0 {
+            return invalid("sample_rate_hz must be > 0");
+        }
+        if cfg.frame_size == 0 || cfg.hop_size == 0 {
+            return invalid("frame_size and hop_size must be > 0");
+        }
+        if !(cfg.fmin_hz > 0.0 && cfg.fmax_hz > cfg.fmin_hz) {
+            return invalid("fmin_hz must be > 0 and below fmax_hz");
+        }
+        if !(cfg.reference_a4_hz.is_finite() && cfg.reference_a4_hz > 0.0) {
+            return invalid("reference_a4_hz must be > 0");
+        }
+        Ok(Self {
+            levels: FrameLevels::new(cfg.sample_rate_hz)?,
+            ring: FrameRing::new(cfg.frame_size, cfg.hop_size),
+            cfg,
+            method,
+            frames: 0,
+        })
+    }
+
+    /// Analyse the buffered frame, if it is complete.
+    fn analyse_frame(&mut self, output: &mut Vec<FrameEstimate>) {
+        let Some((frame_start, frame)) = self.ring.frame() else {
+            return;
+        };
+        let pitch = self.method.estimate(frame);
+        let levels = self.levels.measure(frame_start, frame);
+        self.ring.advance();
+        output.push(self.estimate(self.frames, pitch, levels));
+        self.frames += 1;
+    }
+
+    fn estimate(&self, frame_index: u64, pitch: FramePitch, levels: LevelReading) -> FrameEstimate {
+        let f0_hz = pitch.voiced.then_some(pitch.f0_hz);
+        let midi_fractional = f0_hz.map(|f0| fractional_midi_from_hz(f0, self.cfg.reference_a4_hz));
+        FrameEstimate {
+            frame_index,
+            time_sec: frame_index as f64 * self.cfg.hop_size as f64
+                / self.cfg.sample_rate_hz as f64,
+            f0_hz,
+            voiced: pitch.voiced,
+            confidence: if pitch.voiced {
+                pitch.strength
+            } else {
+                1.0 - pitch.strength
+            },
+            voicing_probability: pitch.strength,
+            midi_note: midi_fractional.map(midi_from_fractional),
+            midi_fractional,
+            cents_offset: midi_fractional.map(cents_offset),
+            candidates: self
+                .cfg
+                .return_candidates
+                .then(|| vec![(pitch.f0_hz, pitch.strength)]),
+            posterior: None,
+            is_final: true,
+            rms_dbfs: levels.rms_dbfs,
+            peak_dbfs: levels.peak_dbfs,
+            loudness_lufs: levels.short_term_lufs,
+            vad: None,
+        }
+    }
+}
+
+impl<M: FrameMethod> PitchEstimator for FramewiseEstimator<M> {
+    fn push_samples(&mut self, samples: &[f32]) -> Result<Vec<FrameEstimate>, PyinError> {
+        let mut output = Vec::new();
+        let mut rest = samples;
+        while !rest.is_empty() {
+            rest = &rest[self.ring.fill(rest, |_, _| {})..];
+            self.analyse_frame(&mut output);
+        }
+        Ok(output)
+    }
+
+    fn reset(&mut self) {
+        self.ring.reset();
+        self.frames = 0;
+        self.levels.reset();
+    }
+
+    fn finish(&mut self, tail: TailPolicy) -> Result<StreamEnd, PyinError> {
+        let unanalysed = self.ring.unanalysed();
+        let mut end = StreamEnd {
+            estimates: Vec::new(),
+            padded_samples: 0,
+            dropped_samples: 0,
+            dropped_bytes: 0,
+        };
+        match tail {
+            TailPolicy::ZeroPad if unanalysed > 0 => {
+                end.padded_samples = self.ring.pad();
+                self.analyse_frame(&mut end.estimates);
+            }
+            _ => end.dropped_samples = unanalysed,
+        }
+        self.reset();
+        Ok(end)
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        let mut out = StateWriter::new(SnapshotKind::Framewise);
+        out.usize(self.cfg.sample_rate_hz as usize);
+        out.usize(self.cfg.frame_size);
+        out.usize(self.cfg.hop_size);
+        self.ring.save_state(&mut out);
+        self.levels.save_state(&mut out);
+        out.u64(self.frames);
+        out.finish()
+    }
+
+    fn restore(&mut self, blob: &[u8]) -> Result<(), PyinError> {
+        let result = StateReader::new(blob, SnapshotKind::Framewise).and_then(|mut input| {
+            input.expect_usize("sample_rate_hz", self.cfg.sample_rate_hz as usize)?;
+            input.expect_usize("frame_size", self.cfg.frame_size)?;
+            input.expect_usize("hop_size", self.cfg.hop_size)?;
+            self.ring.load_state(&mut input)?;
+            self.levels.load_state(&mut input)?;
+            self.frames = input.u64()?;
+            input.finish()
+        });
+        if result.is_err() {
+            self.reset();
+        }
+        result
+    }
+}
+
+/// Threshold on the cumulative mean normalised difference below which a dip
+/// counts as the period (de Cheveigné & Kawahara use 0.1 to 0.15).
+const YIN_THRESHOLD: f32 = 0.15;
+
+/// YIN with a single absolute threshold: the first dip of the cumulative mean
+/// normalised difference below `YIN_THRESHOLD`, followed down to its minimum.
+/// Frames without such a dip are unvoiced.
+pub(crate) struct YinMethod {
+    sample_rate_hz: u32,
+    min_tau: usize,
+    max_tau: usize,
+    difference: DifferenceFunction,
+    diff: Vec<f32>,
+    cmnd: Vec<f32>,
+}
+
+impl YinMethod {
+    pub fn new(cfg: &PyinConfig) -> Self {
+        let (min_tau, max_tau) = lag_range(cfg);
+        Self {
+            sample_rate_hz: cfg.sample_rate_hz,
+            min_tau,
+            max_tau,
+            difference: DifferenceFunction::new(cfg.frame_size),
+            diff: Vec::new(),
+            cmnd: Vec::new(),
+        }
+    }
+}
+
+impl FrameMethod for YinMethod {
+    fn estimate(&mut self, frame: &[f32]) -> FramePitch {
+        self.difference.compute(frame, self.max_tau, &mut self.diff);
+        cumulative_mean_normalized_difference(&self.diff, &mut self.cmnd);
+        let cmnd = &self.cmnd;
+        let range = self.min_tau..=self.max_tau;
+        let dip = range.clone().find(|&tau| cmnd[tau] < YIN_THRESHOLD).map(|mut tau| {
+            while tau < self.max_tau && cmnd[tau + 1] < cmnd[tau] {
+                tau += 1;
+            }
+            tau
+        });
+        let (tau, voiced) = match dip {
+            Some(tau) => (tau, true),
+            None => {
+                let global_min = range
+                    .min_by(|&a, &b| cmnd[a].total_cmp(&cmnd[b]))
+                    .unwrap_or(self.min_tau);
+                (global_min, false)
+            }
+        };
+        let refined_tau = parabolic_interpolation(cmnd, tau).max(1.0);
+        FramePitch {
+            f0_hz: self.sample_rate_hz as f32 / refined_tau,
+            strength: (1.0 - cmnd[tau]).clamp(0.0, 1.0),
+            voiced,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq_hz: f32, seconds: f32, rate: u32) -> Vec<f32> {
+        (0..(seconds * rate as f32) as usize)
+            .map(|i| (2.0 * std::f32::consts::PI * freq_hz * i as f32 / rate as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn framewise_estimators_share_the_pyin_frame_grid() {
+        let cfg = PyinConfig::default();
+        let mut samples = sine(220.0, 0.5, cfg.sample_rate_hz);
+        samples.extend(std::iter::repeat_n(0.0, cfg.sample_rate_hz as usize / 2));
+        let expected = (samples.len() - cfg.frame_size) / cfg.hop_size + 1;
+
+        for algorithm in [PitchAlgorithm::Yin, PitchAlgorithm::Mpm, PitchAlgorithm::Hps] {
+            let mut estimator = new_estimator(algorithm, cfg.clone()).unwrap();
+            let mut frames = Vec::new();
+            for chunk in samples.chunks(700) {
+                frames.extend(estimator.push_samples(chunk).unwrap());
+            }
+            assert_eq!(frames.len(), expected, "{:?}", algorithm);
+            assert!(estimator.provisional_estimates().is_empty());
+            assert!(estimator.as_pyin_mut().is_none());
+            for (index, frame) in frames.iter().enumerate() {
+                assert_eq!(frame.frame_index, index as u64);
+                assert!(frame.is_final);
+            }
+
+            let tone = &frames[10..(expected / 2 - 10)];
+            assert!(
+                tone.iter()
+                    .all(|f| f.voiced && (f.f0_hz.unwrap() / 220.0 - 1.0).abs() < 0.01),
+                "{:?}",
+                algorithm
+            );
+            let quiet = &frames[expected / 2 + 10..];
+            assert!(quiet.iter().all(|f| !f.voiced), "{:?}", algorithm);
+
+            estimator.reset();
+            let again = estimator.push_samples(&samples[..cfg.frame_size]).unwrap();
+            assert_eq!(again[0].frame_index, 0);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Framing of a sample stream into overlapping analysis frames.
+
+use std::collections::VecDeque;
+
+use super::snapshot::{StateReader, StateWriter};
+use super::PyinError;
+
+/// Ring buffer holding the samples of the next analysis frame.
+///
+/// It never holds more than one frame: input is taken only while the frame is
+/// incomplete, and advancing by a hop drops the oldest samples in place, so
+/// streaming through it does not allocate.
+pub(crate) struct FrameRing {
+    samples: VecDeque<f32>,
+    frame_size: usize,
+    hop_size: usize,
+    /// Input samples still to drop before the next frame, when the hop is
+    /// longer than the frame.
+    skip: usize,
+    /// Stream position of the first buffered sample.
+    start: u64,
+    /// Leading buffered samples that an analysed frame already covered.
+    covered: usize,
+}
+
+impl FrameRing {
+    pub fn new(frame_size: usize, hop_size: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(frame_size),
+            frame_size,
+            hop_size,
+            skip: 0,
+            start: 0,
+            covered: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.skip = 0;
+        self.start = 0;
+        self.covered = 0;
+    }
+
+    /// Take samples from the front of `input` until the frame is complete and
+    /// return how many were consumed. `preprocess` is called once on every
+    /// sample entering the frame, with the stream position of the first one.
+    pub fn fill(&mut self, input: &[f32], mut preprocess: impl FnMut(u64, &mut [f32])) -> usize {
+        let skipped = self.skip.min(input.len());
+        self.skip -= skipped;
+        let taken = (self.frame_size - self.samples.len()).min(input.len() - skipped);
+        if taken == 0 {
+            return skipped;
+        }
+        let old_len = self.samples.len();
+        self.samples
+            .extend(input[skipped..skipped + taken].iter().copied());
+        let position = self.start + old_len as u64;
+        preprocess(position, &mut self.samples.make_contiguous()[old_len..]);
+        skipped + taken
+    }
+
+    /// The complete frame and the stream position of its first sample, or
+    /// `None` while samples are missing.
+    pub fn frame(&mut self) -> Option<(u64, &[f32])> {
+        if self.samples.len() < self.frame_size {
+            return None;
+        }
+        Some((self.start, self.samples.make_contiguous()))
+    }
+
+    /// Drop the oldest hop of samples.
+    pub fn advance(&mut self) {
+        let dropped = self.hop_size.min(self.samples.len());
+        self.samples.drain(..dropped);
+        self.skip = self.hop_size - dropped;
+        self.start += self.hop_size as u64;
+        self.covered = self.samples.len();
+    }
+
+    pub fn save_state(&self, out: &mut StateWriter) {
+        out.f32s(self.samples.iter());
+        out.usize(self.skip);
+        out.u64(self.start);
+        out.usize(self.covered);
+    }
+
+    pub fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.f32s_into_deque(&mut self.samples)?;
+        self.skip = input.usize()?;
+        self.start = input.u64()?;
+        self.covered = input.usize()?;
+        if self.samples.len() > self.frame_size
+            || self.skip >= self.hop_size
+            || self.covered > self.samples.len()
+        {
+            return Err(PyinError::InvalidConfig(
+                "invalid snapshot: frame buffer does not fit the framing".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Buffered samples that no analysed frame has covered yet.
+    pub fn unanalysed(&self) -> usize {
+        self.samples.len() - self.covered
+    }
+
+    /// Complete the frame with silence, bypassing preprocessing, and return
+    /// how many zeros were added.
+    pub fn pad(&mut self) -> usize {
+        let padding = self.frame_size - self.samples.len();
+        self.samples.resize(self.frame_size, 0.0);
+        padding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(frame_size: usize, hop_size: usize, chunk: usize) -> Vec<(u64, Vec<f32>)> {
+        let input: Vec<f32> = (0..40).map(|i| i as f32).collect();
+        let mut ring = FrameRing::new(frame_size, hop_size);
+        let mut frames = Vec::new();
+        for mut rest in input.chunks(chunk) {
+            while !rest.is_empty() {
+                let taken = ring.fill(rest, |_, _| {});
+                rest = &rest[taken..];
+                if let Some((start, frame)) = ring.frame() {
+                    frames.push((start, frame.to_vec()));
+                    ring.advance();
+                }
+            }
+        }
+        frames
+    }
+
+    #[test]
+    fn frames_overlap_by_hop_whatever_the_chunking() {
+        let expected: Vec<(u64, Vec<f32>)> = (0..=6)
+            .map(|k| (5 * k, (5 * k..5 * k + 8).map(|i| i as f32).collect()))
+            .collect();
+        for chunk in [1, 3, 8, 40] {
+            assert_eq!(frames(8, 5, chunk), expected, "chunk {}", chunk);
+        }
+        // Hops longer than the frame skip the samples in between.
+        let gapped = frames(4, 6, 7);
+        assert_eq!(gapped[1], (6, vec![6.0, 7.0, 8.0, 9.0]));
+        assert_eq!(gapped.len(), 7);
+    }
+
+    #[test]
+    fn preprocessing_sees_every_framed_sample_once() {
+        let mut ring = FrameRing::new(6, 2);
+        let mut seen = Vec::new();
+        let mut rest: &[f32] = &[0.0; 20];
+        let mut frames = 0;
+        while !rest.is_empty() {
+            let taken = ring.fill(rest, |position, samples| {
+                seen.extend((0..samples.len() as u64).map(|i| position + i));
+                samples.fill(1.0);
+            });
+            rest = &rest[taken..];
+            if let Some((_, frame)) = ring.frame() {
+                assert!(frame.iter().all(|&s| s == 1.0));
+                frames += 1;
+                ring.advance();
+            }
+        }
+        assert_eq!(frames, 8);
+        assert_eq!(seen, (0..20).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn padding_completes_the_tail_frame_with_silence() {
+        let mut ring = FrameRing::new(6, 4);
+        let input = [1.0; 8];
+        let mut rest: &[f32] = &input;
+        while !rest.is_empty() {
+            rest = &rest[ring.fill(rest, |_, _| {})..];
+            if ring.frame().is_some() {
+                ring.advance();
+            }
+        }
+        // Frame 0 covered samples 0..6; samples 6 and 7 are new.
+        assert_eq!(ring.unanalysed(), 2);
+        assert_eq!(ring.pad(), 2);
+        assert_eq!(ring.frame(), Some((4, &[1.0, 1.0, 1.0, 1.0, 0.0, 0.0][..])));
+        ring.advance();
+        assert_eq!(ring.unanalysed(), 0);
+    }
+}
@@ -1,92 +1,289 @@
+use std::ops::RangeInclusive;
+
 use super::pyin_stage1::Stage1CandidateFrame;
+use super::{PyinConfig, PyinError};
 
-// Extended beyond the paper's A5 upper bound to support higher notes (e.g. C6) in live use.
-pub const NUM_BINS: usize = 600;
-const BIN_START_HZ: f32 = 55.0;
-const BIN_CENTS: f32 = 10.0;
-const MAX_PITCH_JUMP: i32 = 25;
+const MAX_JUMP_BINS: usize = (1 << 14) - 1;
+/// Half-width of the window of pitch changes counted as an octave jump.
+const OCTAVE_WINDOW_CENTS: f32 = 50.0;
 
 #[derive(Debug, Clone)]
 pub struct HmmParams {
     pub bin_freqs: Vec<f32>,
+    /// Log probability of each pitch change, indexed by `delta_index`; `-inf`
+    /// for changes the model does not allow.
     pub log_pitch_transition: Vec<f32>,
     pub log_voicing_stay: f32,
     pub log_voicing_switch: f32,
+    bin_start_hz: f32,
+    bin_cents: f32,
+    reach_bins: usize,
+    jump_ranges: Vec<RangeInclusive<i32>>,
 }
 
 impl HmmParams {
-    pub fn new() -> Self {
-        let bin_freqs = (0..NUM_BINS)
+    pub fn new(cfg: &PyinConfig) -> Result<Self, PyinError> {
+        let grid = cfg.pitch_grid();
+        validate(cfg, grid.start_hz, grid.bin_cents, grid.num_bins)?;
+
+        let bin_freqs = (0..grid.num_bins)
             .map(|i| {
-                let semitones = (i as f32 * BIN_CENTS) / 100.0;
-                BIN_START_HZ * 2.0_f32.powf(semitones / 12.0)
+                let semitones = (i as f32 * grid.bin_cents) / 100.0;
+                grid.start_hz * 2.0_f32.powf(semitones / 12.0)
             })
             .collect();
-        let log_pitch_transition = pitch_transition_log_probs();
-        Self {
+        let max_jump_bins = (cfg.max_pitch_jump_cents / grid.bin_cents).round() as usize;
+        let octave_jump_prob = cfg
+            .octave_correction
+            .as_ref()
+            .map_or(0.0, |o| o.octave_jump_prob);
+        let octave = (octave_jump_prob > 0.0).then(|| {
+            let octave_bins = (1200.0 / grid.bin_cents).round() as usize;
+            let window_bins = (OCTAVE_WINDOW_CENTS / grid.bin_cents).round() as usize;
+            (octave_bins, window_bins.min(octave_bins - 1))
+        });
+        let (log_pitch_transition, jump_ranges) =
+            pitch_transition_log_probs(max_jump_bins, octave, octave_jump_prob);
+        let reach_bins = jump_ranges.last().map_or(0, |r| *r.end() as usize);
+        Ok(Self {
             bin_freqs,
             log_pitch_transition,
-            log_voicing_stay: 0.99_f32.ln(),
-            log_voicing_switch: 0.01_f32.ln(),
+            log_voicing_stay: (1.0 - cfg.voicing_switch_prob).ln(),
+            log_voicing_switch: cfg.voicing_switch_prob.ln(),
+            bin_start_hz: grid.start_hz,
+            bin_cents: grid.bin_cents,
+            reach_bins,
+            jump_ranges,
+        })
+    }
+
+    pub fn num_bins(&self) -> usize {
+        self.bin_freqs.len()
+    }
+
+    /// Spacing of the pitch bins.
+    pub fn bin_cents(&self) -> f32 {
+        self.bin_cents
+    }
+
+    /// Largest pitch move, in bins, allowed between consecutive frames,
+    /// octave jumps included.
+    pub fn reach_bins(&self) -> usize {
+        self.reach_bins
+    }
+
+    /// Disjoint ranges of allowed pitch changes (next bin minus previous bin),
+    /// in increasing order.
+    pub fn jump_ranges(&self) -> &[RangeInclusive<i32>] {
+        &self.jump_ranges
+    }
+
+    pub fn freq_to_bin(&self, freq_hz: f32) -> Option<usize> {
+        if freq_hz < self.bin_start_hz {
+            return None;
+        }
+        let cents = 1200.0 * (freq_hz / self.bin_start_hz).log2();
+        let bin = (cents / self.bin_cents).round() as i32;
+        if bin >= 0 && (bin as usize) < self.num_bins() {
+            Some(bin as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn delta_index(&self, delta: i32) -> Option<usize> {
+        let max_delta = self.reach_bins as i32;
+        if delta < -max_delta || delta > max_delta {
+            return None;
         }
+        Some((delta + max_delta) as usize)
     }
 }
 
+fn validate(
+    cfg: &PyinConfig,
+    start_hz: f32,
+    bin_cents: f32,
+    num_bins: usize,
+) -> Result<(), PyinError> {
+    let invalid = |msg: &str| Err(PyinError::InvalidConfig(msg.to_string()));
+    if !(cfg.fmin_hz > 0.0 && cfg.fmax_hz > cfg.fmin_hz) {
+        return invalid("fmin_hz must be > 0 and below fmax_hz");
+    }
+    if start_hz <= 0.0 || bin_cents <= 0.0 || num_bins < 2 {
+        return invalid("pitch grid needs start_hz > 0, bin_cents > 0 and at least 2 bins");
+    }
+    // Allow half a bin of slack at either end for rounding.
+    let half_bin = 2.0_f32.powf(bin_cents / 2400.0);
+    let top_hz = start_hz * 2.0_f32.powf((num_bins - 1) as f32 * bin_cents / 1200.0);
+    if start_hz > cfg.fmin_hz * half_bin || top_hz * half_bin < cfg.fmax_hz {
+        return invalid("pitch grid must cover fmin_hz..=fmax_hz");
+    }
+    if !cfg.max_pitch_jump_cents.is_finite() || cfg.max_pitch_jump_cents < 0.0 {
+        return invalid("max_pitch_jump_cents must be >= 0");
+    }
+    // Backpointers store the jump in 15 bits.
+    if cfg.max_pitch_jump_cents / bin_cents > MAX_JUMP_BINS as f32 {
+        return invalid("max_pitch_jump_cents spans too many bins");
+    }
+    if let Some(octave) = cfg.octave_correction.as_ref() {
+        if !(octave.octave_jump_prob >= 0.0 && octave.octave_jump_prob < 1.0) {
+            return invalid("octave_jump_prob must be in [0, 1)");
+        }
+        if octave.octave_jump_prob > 0.0
+            && (1200.0 + OCTAVE_WINDOW_CENTS) / bin_cents > MAX_JUMP_BINS as f32
+        {
+            return invalid("octave jumps span too many bins");
+        }
+    }
+    if !(cfg.voicing_switch_prob > 0.0 && cfg.voicing_switch_prob < 1.0) {
+        return invalid("voicing_switch_prob must be in (0, 1)");
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct ObservationFrame {
     pub p_star: Vec<f32>,
     pub sum_p: f32,
 }
 
-pub fn observation_from_candidates(frame: &Stage1CandidateFrame) -> ObservationFrame {
-    let mut p_star = vec![0.0_f32; NUM_BINS];
-    for candidate in frame.candidates.iter() {
-        if let Some(bin) = freq_to_bin(candidate.frequency_hz) {
-            p_star[bin] += candidate.probability;
+impl ObservationFrame {
+    pub fn new(num_bins: usize) -> Self {
+        Self {
+            p_star: vec![0.0; num_bins],
+            sum_p: 0.0,
         }
     }
-    let mut sum_p: f32 = p_star.iter().sum();
-    if sum_p > 1.0 {
-        // Clamp to avoid negative unvoiced probability due to numerical accumulation.
-        sum_p = 1.0;
+}
+
+/// Overwrite `obs` with the observation of `frame`.
+pub fn observation_from_candidates(
+    frame: &Stage1CandidateFrame,
+    params: &HmmParams,
+    obs: &mut ObservationFrame,
+) {
+    obs.p_star.clear();
+    obs.p_star.resize(params.num_bins(), 0.0);
+    for candidate in frame.candidates.iter() {
+        if let Some(bin) = params.freq_to_bin(candidate.frequency_hz) {
+            obs.p_star[bin] += candidate.probability;
+        }
     }
+    // Clamp to avoid negative unvoiced probability due to numerical accumulation.
     // pYIN Stage 2 observation model (Eq. 6): p_{m,v} is split equally
     // between voiced and unvoiced, with unvoiced sharing the remaining mass.
-    ObservationFrame { p_star, sum_p }
+    obs.sum_p = obs.p_star.iter().sum::<f32>().min(1.0);
 }
 
-pub fn freq_to_bin(freq_hz: f32) -> Option<usize> {
-    if freq_hz < BIN_START_HZ {
-        return None;
+/// Eq. (8) triangle of half-width `max_jump_bins`, plus, when `octave` gives
+/// `(octave_bins, window_bins)`, triangles of half-width `window_bins` around
+/// plus and minus one octave sharing `octave_jump_prob`.
+fn pitch_transition_log_probs(
+    max_jump_bins: usize,
+    octave: Option<(usize, usize)>,
+    octave_jump_prob: f32,
+) -> (Vec<f32>, Vec<RangeInclusive<i32>>) {
+    let max_delta = max_jump_bins as i32;
+    let reach = octave.map_or(max_delta, |(bins, window)| {
+        max_delta.max((bins + window) as i32)
+    });
+    let mut probs = vec![0.0_f32; 2 * reach as usize + 1];
+    let mut add_triangle = |centre: i32, half_width: i32, mass: f32| {
+        let total = ((half_width + 1) * (half_width + 1)) as f32;
+        for delta in -half_width..=half_width {
+            let weight = (half_width + 1 - delta.abs()) as f32;
+            probs[(centre + delta + reach) as usize] += mass * weight / total;
+        }
+    };
+    match octave {
+        Some((bins, window)) => {
+            add_triangle(0, max_delta, 1.0 - octave_jump_prob);
+            add_triangle(-(bins as i32), window as i32, 0.5 * octave_jump_prob);
+            add_triangle(bins as i32, window as i32, 0.5 * octave_jump_prob);
+        }
+        None => add_triangle(0, max_delta, 1.0),
     }
-    let cents = 1200.0 * (freq_hz / BIN_START_HZ).log2();
-    let bin = (cents / BIN_CENTS).round() as i32;
-    if bin >= 0 && (bin as usize) < NUM_BINS {
-        Some(bin as usize)
-    } else {
-        None
+
+    let mut ranges: Vec<RangeInclusive<i32>> = Vec::new();
+    for (index, &prob) in probs.iter().enumerate() {
+        if prob <= 0.0 {
+            continue;
+        }
+        let delta = index as i32 - reach;
+        match ranges.last_mut() {
+            Some(range) if *range.end() == delta - 1 => *range = *range.start()..=delta,
+            _ => ranges.push(delta..=delta),
+        }
     }
+    let log_probs = probs
+        .into_iter()
+        .map(|p| if p > 0.0 { p.ln() } else { f32::NEG_INFINITY })
+        .collect();
+    (log_probs, ranges)
 }
 
-fn pitch_transition_log_probs() -> Vec<f32> {
-    let mut weights = Vec::new();
-    let max_delta = MAX_PITCH_JUMP;
-    let mut sum = 0.0;
-    for delta in -max_delta..=max_delta {
-        let weight = (max_delta + 1 - delta.abs()) as f32;
-        weights.push(weight);
-        sum += weight;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pyin::{OctaveConfig, PitchGrid};
+
+    #[test]
+    fn default_grid_covers_fmin_to_fmax() {
+        let cfg = PyinConfig {
+            fmin_hz: 40.0,
+            fmax_hz: 2_000.0,
+            ..PyinConfig::default()
+        };
+        let params = HmmParams::new(&cfg).unwrap();
+        assert_eq!(params.freq_to_bin(40.0), Some(0));
+        assert!(params.freq_to_bin(2_000.0).is_some());
+        assert_eq!(params.reach_bins(), 25);
+        assert_eq!(params.jump_ranges(), &[-25..=25]);
     }
-    for w in weights.iter_mut() {
-        *w = (*w / sum).ln();
+
+    #[test]
+    fn octave_jumps_add_penalised_transitions() {
+        let cfg = PyinConfig {
+            octave_correction: Some(OctaveConfig::default()),
+            ..PyinConfig::default()
+        };
+        let params = HmmParams::new(&cfg).unwrap();
+        assert_eq!(params.reach_bins(), 125);
+        assert_eq!(params.jump_ranges(), &[-125..=-115, -25..=25, 115..=125]);
+
+        let prob =
+            |delta: i32| params.log_pitch_transition[params.delta_index(delta).unwrap()].exp();
+        let total: f32 = params.log_pitch_transition.iter().map(|l| l.exp()).sum();
+        assert!((total - 1.0).abs() < 1e-4);
+        assert!(prob(120) < 0.01 * prob(0));
+        assert_eq!(prob(60), 0.0);
     }
-    weights
-}
 
-pub fn delta_index(delta: i32) -> Option<usize> {
-    let max_delta = MAX_PITCH_JUMP;
-    if delta < -max_delta || delta > max_delta {
-        return None;
+    #[test]
+    fn inconsistent_configs_are_rejected() {
+        let narrow_grid = PyinConfig {
+            pitch_grid: Some(PitchGrid {
+                start_hz: 55.0,
+                bin_cents: 10.0,
+                num_bins: 600,
+            }),
+            fmin_hz: 40.0,
+            ..PyinConfig::default()
+        };
+        assert!(HmmParams::new(&narrow_grid).is_err());
+
+        let bad_switch = PyinConfig {
+            voicing_switch_prob: 1.0,
+            ..PyinConfig::default()
+        };
+        assert!(HmmParams::new(&bad_switch).is_err());
+
+        let inverted = PyinConfig {
+            fmin_hz: 800.0,
+            fmax_hz: 400.0,
+            ..PyinConfig::default()
+        };
+        assert!(HmmParams::new(&inverted).is_err());
     }
-    Some((delta + max_delta) as usize)
 }
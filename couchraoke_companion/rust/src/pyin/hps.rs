@@ -0,0 +1,238 @@
+//! Harmonic product spectrum.
+//!
+//! A fundamental is scored by the product of the spectrum's magnitudes at its
+//! first `HARMONICS` multiples. The product is taken over the spectral peaks
+//! rather than every bin: with a finely sampled spectrum, the multiples of a
+//! wrong low candidate can fall on the flanks of one strong peak and outscore
+//! the true pitch. A multiple without a peak within `HARMONIC_TOLERANCE`
+//! counts as a magnitude `FLOOR_DB` below the strongest peak, so a missing
+//! harmonic, the fundamental included, costs a fixed penalty instead of
+//! zeroing the product.
+//!
+//! A pure tone explains its sub-octaves as well as itself, so among scores
+//! within `TIE_DB` of the best the highest candidate wins, and no candidate may
+//! lie above the lowest peak within `STRONG_PEAK_DB` of the strongest, which
+//! rules out octave-up errors on voices with an audible fundamental. The
+//! clarity of a frame is the share of its power at the harmonics, discounted
+//! by the share a flat spectrum would have there.
+
+use std::sync::Arc;
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+
+use super::estimator::{FrameMethod, FramePitch};
+use super::yin::parabolic_interpolation;
+use super::PyinConfig;
+
+/// Multiples of a candidate in its score, the fundamental included.
+const HARMONICS: usize = 5;
+/// Zero-padding factor of the FFT, for finer peak positions.
+const PADDING: usize = 4;
+const FLOOR_DB: f32 = -30.0;
+const TIE_DB: f32 = 6.0;
+const STRONG_PEAK_DB: f32 = -20.0;
+/// Largest relative distance of a peak from a multiple it is matched to.
+const HARMONIC_TOLERANCE: f32 = 0.03;
+/// Clarity from which a frame counts as voiced.
+const VOICED_THRESHOLD: f32 = 0.5;
+const POWER_EPSILON: f32 = 1e-12;
+
+#[derive(Debug, Clone, Copy)]
+struct Peak {
+    /// Position in (fractional) bins.
+    bin: f32,
+    magnitude: f32,
+}
+
+pub(crate) struct HpsMethod {
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    magnitude: Vec<f32>,
+    log_magnitude: Vec<f32>,
+    peaks: Vec<Peak>,
+    bin_hz: f32,
+    fmin_hz: f32,
+    fmax_hz: f32,
+    /// Half-width, in bins, of the band around each harmonic counted as
+    /// harmonic power: one bin of the unpadded spectrum.
+    harmonic_band: usize,
+}
+
+impl HpsMethod {
+    pub fn new(cfg: &PyinConfig) -> Self {
+        let frame_size = cfg.frame_size.max(1);
+        let fft_size = frame_size.next_power_of_two() * PADDING;
+        let num_bins = fft_size / 2 + 1;
+        let window = (0..frame_size)
+            .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / frame_size as f32).cos())
+            .collect();
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+        Self {
+            scratch: vec![Complex { re: 0.0, im: 0.0 }; fft.get_inplace_scratch_len()],
+            fft,
+            window,
+            buffer: vec![Complex { re: 0.0, im: 0.0 }; fft_size],
+            magnitude: vec![0.0; num_bins],
+            log_magnitude: vec![0.0; num_bins],
+            peaks: Vec::new(),
+            bin_hz: cfg.sample_rate_hz as f32 / fft_size as f32,
+            fmin_hz: cfg.fmin_hz,
+            fmax_hz: cfg.fmax_hz,
+            harmonic_band: (fft_size / frame_size).max(1),
+        }
+    }
+
+    fn spectrum(&mut self, frame: &[f32]) {
+        self.buffer.fill(Complex { re: 0.0, im: 0.0 });
+        for ((slot, &sample), &w) in self.buffer.iter_mut().zip(frame).zip(&self.window) {
+            slot.re = sample * w;
+        }
+        self.fft.process_with_scratch(&mut self.buffer, &mut self.scratch);
+        for ((magnitude, log), c) in self
+            .magnitude
+            .iter_mut()
+            .zip(self.log_magnitude.iter_mut())
+            .zip(&self.buffer)
+        {
+            *magnitude = c.norm();
+            *log = (*magnitude + POWER_EPSILON).ln();
+        }
+    }
+
+    /// Local maxima above `floor`, refined on the log spectrum.
+    fn find_peaks(&mut self, floor: f32) {
+        self.peaks.clear();
+        let min_bin = (self.fmin_hz / self.bin_hz).floor().max(1.0) as usize;
+        for bin in min_bin..self.magnitude.len() - 1 {
+            let m = self.magnitude[bin];
+            if m > floor && m > self.magnitude[bin - 1] && m >= self.magnitude[bin + 1] {
+                self.peaks.push(Peak {
+                    bin: parabolic_interpolation(&self.log_magnitude, bin),
+                    magnitude: m,
+                });
+            }
+        }
+    }
+
+    /// Sum of the log magnitudes of the peaks matching the first `HARMONICS`
+    /// multiples of `f0_bin`.
+    fn log_product(&self, f0_bin: f32, floor: f32) -> f32 {
+        (1..=HARMONICS)
+            .map(|h| {
+                let target = h as f32 * f0_bin;
+                self.peaks
+                    .iter()
+                    .filter(|p| (p.bin / target - 1.0).abs() <= HARMONIC_TOLERANCE)
+                    .fold(floor, |max, p| max.max(p.magnitude))
+                    .ln()
+            })
+            .sum()
+    }
+
+    /// Share of the power within `harmonic_band` of a multiple of `f0_bin`,
+    /// rescaled so that a flat spectrum scores 0.
+    fn clarity(&self, f0_bin: f32) -> f32 {
+        let min_bin = (self.fmin_hz / self.bin_hz / 2.0) as usize;
+        let (mut harmonic, mut total) = (0.0, 0.0);
+        for (bin, &m) in self.magnitude.iter().enumerate().skip(min_bin) {
+            let power = m * m;
+            total += power;
+            let nearest = (bin as f32 / f0_bin).round().max(1.0) * f0_bin;
+            if (bin as f32 - nearest).abs() <= self.harmonic_band as f32 {
+                harmonic += power;
+            }
+        }
+        if total <= POWER_EPSILON {
+            return 0.0;
+        }
+        let chance = ((2 * self.harmonic_band + 1) as f32 / f0_bin).min(1.0);
+        if chance >= 1.0 {
+            return 0.0;
+        }
+        ((harmonic / total - chance) / (1.0 - chance)).clamp(0.0, 1.0)
+    }
+}
+
+impl FrameMethod for HpsMethod {
+    fn estimate(&mut self, frame: &[f32]) -> FramePitch {
+        self.spectrum(frame);
+        let unvoiced = FramePitch {
+            f0_hz: self.fmin_hz,
+            strength: 0.0,
+            voiced: false,
+        };
+        let strongest = self.magnitude.iter().fold(0.0_f32, |max, &m| max.max(m));
+        if strongest <= POWER_EPSILON {
+            return unvoiced;
+        }
+        let floor = strongest * 10.0_f32.powf(FLOOR_DB / 20.0);
+        self.find_peaks(floor);
+        let strong = strongest * 10.0_f32.powf(STRONG_PEAK_DB / 20.0);
+        let Some(lowest_strong) = self.peaks.iter().find(|p| p.magnitude >= strong) else {
+            return unvoiced;
+        };
+        let ceiling_bin = lowest_strong.bin * (1.0 + HARMONIC_TOLERANCE);
+
+        let (min_bin, max_bin) = (self.fmin_hz / self.bin_hz, self.fmax_hz / self.bin_hz);
+        let scored: Vec<(f32, f32)> = self
+            .peaks
+            .iter()
+            .flat_map(|p| (1..=HARMONICS).map(move |h| p.bin / h as f32))
+            .filter(|&bin| bin >= min_bin && bin <= max_bin.min(ceiling_bin))
+            .map(|bin| (bin, self.log_product(bin, floor)))
+            .collect();
+        let Some(best) = scored.iter().map(|&(_, score)| score).max_by(f32::total_cmp) else {
+            return unvoiced;
+        };
+        let tie = TIE_DB / 20.0 * std::f32::consts::LN_10;
+        let f0_bin = scored
+            .iter()
+            .filter(|&&(_, score)| score >= best - tie)
+            .map(|&(bin, _)| bin)
+            .fold(0.0, f32::max);
+
+        let strength = self.clarity(f0_bin);
+        FramePitch {
+            f0_hz: f0_bin * self.bin_hz,
+            strength,
+            voiced: strength >= VOICED_THRESHOLD,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn harmonic_frame(cfg: &PyinConfig, f0: f32, amplitudes: &[f32]) -> Vec<f32> {
+        (0..cfg.frame_size)
+            .map(|i| {
+                let t = i as f32 / cfg.sample_rate_hz as f32;
+                amplitudes
+                    .iter()
+                    .enumerate()
+                    .map(|(k, a)| a * (2.0 * std::f32::consts::PI * f0 * (k + 1) as f32 * t).sin())
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_fundamentals_including_a_missing_one() {
+        let cfg = PyinConfig::default();
+        let mut method = HpsMethod::new(&cfg);
+        for (f0, amplitudes) in [
+            (440.0, vec![1.0]),
+            (147.0, vec![1.0, 0.6, 0.4, 0.3, 0.2]),
+            // Telephone-band voice: no energy at the fundamental itself.
+            (131.0, vec![0.0, 1.0, 0.8, 0.6, 0.4, 0.3]),
+        ] {
+            let pitch = method.estimate(&harmonic_frame(&cfg, f0, &amplitudes));
+            assert!(pitch.voiced, "{} Hz: {:?}", f0, pitch);
+            assert!((pitch.f0_hz / f0 - 1.0).abs() < 0.01, "{} Hz: {:?}", f0, pitch);
+        }
+        assert!(!method.estimate(&vec![0.0; cfg.frame_size]).voiced);
+    }
+}
@@ -0,0 +1,355 @@
+//! Input level metering.
+//!
+//! Sample-peak and RMS levels in dBFS, and loudness in LUFS following ITU-R
+//! BS.1770: the signal is K-weighted (a high shelf modelling the head plus a
+//! high-pass) and its mean square taken over a sliding window, 400 ms for
+//! momentary and 3 s for short-term loudness. Input is treated as a single
+//! (mono) channel, so a full-scale 1 kHz sine reads -3 LUFS.
+
+use std::collections::VecDeque;
+
+use super::snapshot::{StateReader, StateWriter};
+use super::PyinError;
+
+#[derive(Debug, Clone)]
+pub struct LevelConfig {
+    pub momentary_sec: f32,
+    pub short_term_sec: f32,
+    /// Samples at or above this level count as clipped.
+    pub clip_threshold_dbfs: f32,
+}
+
+impl Default for LevelConfig {
+    fn default() -> Self {
+        Self {
+            momentary_sec: 0.4,
+            short_term_sec: 3.0,
+            clip_threshold_dbfs: -0.1,
+        }
+    }
+}
+
+/// Levels after one block of input.
+#[derive(Debug, Clone, Copy)]
+pub struct LevelReading {
+    /// RMS level of the block.
+    pub rms_dbfs: f32,
+    /// Largest absolute sample of the block.
+    pub peak_dbfs: f32,
+    pub momentary_lufs: f32,
+    pub short_term_lufs: f32,
+    /// Samples of the block at or above `LevelConfig::clip_threshold_dbfs`.
+    pub clipped_samples: u32,
+}
+
+/// Level floor reported for digital silence.
+const MIN_DBFS: f32 = -120.0;
+/// Loudness blocks; the sliding windows move in steps of one block.
+const BLOCK_SEC: f32 = 0.1;
+/// BS.1770 offset between K-weighted mean square and LUFS.
+const LUFS_OFFSET: f32 = -0.691;
+
+fn rms_dbfs(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return MIN_DBFS;
+    }
+    let mean_sq = samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32;
+    (10.0 * mean_sq.log10()).max(MIN_DBFS)
+}
+
+fn peak_dbfs(samples: &[f32]) -> f32 {
+    let peak = samples.iter().fold(0.0_f32, |max, s| max.max(s.abs()));
+    (20.0 * peak.log10()).max(MIN_DBFS)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f32; 3],
+    a: [f32; 2],
+    z: [f32; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// The two K-weighting stages, with the BS.1770 48 kHz filters re-derived for
+/// `sample_rate_hz`.
+fn k_weighting(sample_rate_hz: u32) -> [Biquad; 2] {
+    let rate = sample_rate_hz as f64;
+
+    let k = (std::f64::consts::PI * 1681.974450955533 / rate).tan();
+    let q = 0.7071752369554196;
+    let vh = 10.0_f64.powf(3.999843853973347 / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [
+            ((vh + vb * k / q + k * k) / a0) as f32,
+            (2.0 * (k * k - vh) / a0) as f32,
+            ((vh - vb * k / q + k * k) / a0) as f32,
+        ],
+        a: [
+            (2.0 * (k * k - 1.0) / a0) as f32,
+            ((1.0 - k / q + k * k) / a0) as f32,
+        ],
+        z: [0.0; 2],
+    };
+
+    let k = (std::f64::consts::PI * 38.13547087602444 / rate).tan();
+    let q = 0.5003270373238773;
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [
+            (2.0 * (k * k - 1.0) / a0) as f32,
+            ((1.0 - k / q + k * k) / a0) as f32,
+        ],
+        z: [0.0; 2],
+    };
+    [shelf, high_pass]
+}
+
+/// Streaming level meter over mono samples in [-1, 1].
+pub struct LevelMeter {
+    cfg: LevelConfig,
+    filters: [Biquad; 2],
+    clip_level: f32,
+    block_len: usize,
+    momentary_blocks: usize,
+    short_term_blocks: usize,
+    /// Mean K-weighted square of each completed block, newest last; holds the
+    /// short-term window.
+    blocks: VecDeque<f32>,
+    block_sum: f64,
+    block_fill: usize,
+}
+
+impl LevelMeter {
+    pub fn new(sample_rate_hz: u32, cfg: LevelConfig) -> Result<Self, PyinError> {
+        let invalid = |msg: &str| Err(PyinError::InvalidConfig(msg.to_string()));
+        if sample_rate_hz == 0 {
+            return invalid("sample_rate_hz must be > 0");
+        }
+        if !(cfg.momentary_sec >= BLOCK_SEC && cfg.short_term_sec >= cfg.momentary_sec) {
+            return invalid("level windows need 0.1 <= momentary_sec <= short_term_sec");
+        }
+        if !cfg.clip_threshold_dbfs.is_finite() {
+            return invalid("clip_threshold_dbfs must be finite");
+        }
+        let short_term_blocks = (cfg.short_term_sec / BLOCK_SEC).round() as usize;
+        Ok(Self {
+            filters: k_weighting(sample_rate_hz),
+            clip_level: 10.0_f32.powf(cfg.clip_threshold_dbfs / 20.0),
+            block_len: ((sample_rate_hz as f32 * BLOCK_SEC).round() as usize).max(1),
+            momentary_blocks: (cfg.momentary_sec / BLOCK_SEC).round() as usize,
+            short_term_blocks,
+            blocks: VecDeque::with_capacity(short_term_blocks),
+            block_sum: 0.0,
+            block_fill: 0,
+            cfg,
+        })
+    }
+
+    pub fn config(&self) -> &LevelConfig {
+        &self.cfg
+    }
+
+    pub fn reset(&mut self) {
+        for filter in self.filters.iter_mut() {
+            filter.z = [0.0; 2];
+        }
+        self.blocks.clear();
+        self.block_sum = 0.0;
+        self.block_fill = 0;
+    }
+
+    pub(crate) fn save_state(&self, out: &mut StateWriter) {
+        for filter in &self.filters {
+            out.f32s(filter.z.iter());
+        }
+        out.f32s(self.blocks.iter());
+        out.f64(self.block_sum);
+        out.usize(self.block_fill);
+    }
+
+    pub(crate) fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        for filter in self.filters.iter_mut() {
+            input.f32s_exact("K-weighting filter", &mut filter.z)?;
+        }
+        input.f32s_into_deque(&mut self.blocks)?;
+        self.block_sum = input.f64()?;
+        self.block_fill = input.usize()?;
+        if self.blocks.len() > self.short_term_blocks || self.block_fill >= self.block_len {
+            return Err(PyinError::InvalidConfig(
+                "invalid snapshot: loudness blocks do not fit the meter".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Meter the next block of samples.
+    pub fn process(&mut self, samples: &[f32]) -> LevelReading {
+        for &sample in samples {
+            let weighted = self
+                .filters
+                .iter_mut()
+                .fold(sample, |x, filter| filter.process(x));
+            self.block_sum += (weighted * weighted) as f64;
+            self.block_fill += 1;
+            if self.block_fill == self.block_len {
+                if self.blocks.len() == self.short_term_blocks {
+                    self.blocks.pop_front();
+                }
+                self.blocks
+                    .push_back((self.block_sum / self.block_len as f64) as f32);
+                self.block_sum = 0.0;
+                self.block_fill = 0;
+            }
+        }
+
+        LevelReading {
+            rms_dbfs: rms_dbfs(samples),
+            peak_dbfs: peak_dbfs(samples),
+            momentary_lufs: self.loudness(self.momentary_blocks),
+            short_term_lufs: self.loudness(self.short_term_blocks),
+            clipped_samples: samples
+                .iter()
+                .filter(|s| s.abs() >= self.clip_level)
+                .count() as u32,
+        }
+    }
+
+    /// Loudness over the newest `blocks` completed blocks, or over what has
+    /// been seen so far while fewer are available.
+    fn loudness(&self, blocks: usize) -> f32 {
+        let (sum, count) = if self.blocks.is_empty() {
+            if self.block_fill == 0 {
+                return MIN_DBFS;
+            }
+            ((self.block_sum / self.block_fill as f64) as f32, 1)
+        } else {
+            self.blocks
+                .iter()
+                .rev()
+                .take(blocks)
+                .fold((0.0, 0), |(sum, count), &block| (sum + block, count + 1))
+        };
+        let mean_sq = sum / count as f32;
+        (LUFS_OFFSET + 10.0 * mean_sq.log10()).max(MIN_DBFS)
+    }
+}
+
+/// Levels of overlapping analysis frames. Each input sample is fed to the
+/// loudness meter once, however many frames it falls in.
+pub(crate) struct FrameLevels {
+    meter: LevelMeter,
+    /// Stream position up to which samples have been metered.
+    metered: u64,
+}
+
+impl FrameLevels {
+    pub fn new(sample_rate_hz: u32) -> Result<Self, PyinError> {
+        Ok(Self {
+            meter: LevelMeter::new(sample_rate_hz, LevelConfig::default())?,
+            metered: 0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.meter.reset();
+        self.metered = 0;
+    }
+
+    pub fn save_state(&self, out: &mut StateWriter) {
+        self.meter.save_state(out);
+        out.u64(self.metered);
+    }
+
+    pub fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        self.meter.load_state(input)?;
+        self.metered = input.u64()?;
+        Ok(())
+    }
+
+    /// Levels of the frame starting at stream sample `frame_start`. RMS and
+    /// peak cover the whole frame, loudness runs up to its end and clipping
+    /// counts the samples not metered before.
+    pub fn measure(&mut self, frame_start: u64, frame: &[f32]) -> LevelReading {
+        let unmetered = (self.metered.saturating_sub(frame_start) as usize).min(frame.len());
+        let reading = self.meter.process(&frame[unmetered..]);
+        self.metered = self.metered.max(frame_start + frame.len() as u64);
+        LevelReading {
+            rms_dbfs: rms_dbfs(frame),
+            peak_dbfs: peak_dbfs(frame),
+            ..reading
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq_hz: f32, amplitude: f32, seconds: f32, rate: u32) -> Vec<f32> {
+        (0..(seconds * rate as f32) as usize)
+            .map(|i| {
+                amplitude * (2.0 * std::f32::consts::PI * freq_hz * i as f32 / rate as f32).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn full_scale_sine_reads_minus_three() {
+        for rate in [16_000, 44_100, 48_000] {
+            let mut meter = LevelMeter::new(rate, LevelConfig::default()).unwrap();
+            let mut reading = None;
+            for chunk in sine(997.0, 1.0, 4.0, rate).chunks(1_000) {
+                reading = Some(meter.process(chunk));
+            }
+            let reading = reading.unwrap();
+            assert!((reading.rms_dbfs + 3.01).abs() < 0.1);
+            assert!(reading.peak_dbfs.abs() < 0.05);
+            assert!(
+                (reading.momentary_lufs + 3.0).abs() < 0.2,
+                "{} Hz: {:?}",
+                rate,
+                reading
+            );
+            assert!(
+                (reading.short_term_lufs + 3.0).abs() < 0.2,
+                "{} Hz: {:?}",
+                rate,
+                reading
+            );
+            assert!(reading.clipped_samples > 0);
+        }
+    }
+
+    #[test]
+    fn k_weighting_discounts_bass_and_windows_follow_level_changes() {
+        let rate = 48_000;
+        let mut bass = LevelMeter::new(rate, LevelConfig::default()).unwrap();
+        let bass_reading = bass.process(&sine(40.0, 0.5, 1.0, rate));
+        let mut mid = LevelMeter::new(rate, LevelConfig::default()).unwrap();
+        let mid_reading = mid.process(&sine(1_000.0, 0.5, 1.0, rate));
+        assert!(mid_reading.momentary_lufs - bass_reading.momentary_lufs > 3.0);
+        assert_eq!(mid_reading.clipped_samples, 0);
+
+        // One second after a 20 dB drop, momentary loudness has followed and
+        // short-term loudness is still in between.
+        let mut meter = LevelMeter::new(rate, LevelConfig::default()).unwrap();
+        meter.process(&sine(1_000.0, 0.5, 3.0, rate));
+        let reading = meter.process(&sine(1_000.0, 0.05, 1.0, rate));
+        assert!((reading.momentary_lufs - mid_reading.momentary_lufs + 20.0).abs() < 0.5);
+        assert!(reading.short_term_lufs > reading.momentary_lufs + 5.0);
+
+        meter.reset();
+        assert_eq!(meter.process(&[]).short_term_lufs, MIN_DBFS);
+    }
+}
@@ -1,7 +1,41 @@
+pub const DEFAULT_REFERENCE_A4_HZ: f32 = 440.0;
+
 pub fn midi_from_hz(freq_hz: f32) -> u8 {
     if freq_hz <= 0.0 {
         return 0;
     }
-    let midi = 69.0 + 12.0 * (freq_hz / 440.0).log2();
+    midi_from_fractional(fractional_midi_from_hz(freq_hz, DEFAULT_REFERENCE_A4_HZ))
+}
+
+/// Unrounded MIDI pitch, with A4 tuned to `reference_a4_hz` (MIDI 69.0).
+/// `freq_hz` must be positive.
+pub fn fractional_midi_from_hz(freq_hz: f32, reference_a4_hz: f32) -> f32 {
+    69.0 + 12.0 * (freq_hz / reference_a4_hz).log2()
+}
+
+/// Nearest MIDI note, clamped to the valid MIDI range.
+pub fn midi_from_fractional(midi: f32) -> u8 {
     midi.round().clamp(0.0, 127.0) as u8
 }
+
+/// Deviation of `midi` from its nearest note, in cents (-50..=50).
+pub fn cents_offset(midi: f32) -> f32 {
+    (midi - midi.round()) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fractional_midi_follows_reference_tuning() {
+        assert!((fractional_midi_from_hz(440.0, 440.0) - 69.0).abs() < 1e-4);
+        assert!((fractional_midi_from_hz(432.0, 432.0) - 69.0).abs() < 1e-4);
+        let sharp = fractional_midi_from_hz(440.0 * 2.0_f32.powf(45.0 / 1200.0), 440.0);
+        assert!((cents_offset(sharp) - 45.0).abs() < 0.1);
+        assert_eq!(midi_from_fractional(sharp), 69);
+        // 440 Hz is ~32 cents sharp of A4 when tuned to 432 Hz.
+        let against_432 = fractional_midi_from_hz(440.0, 432.0);
+        assert!((cents_offset(against_432) - 31.8).abs() < 0.5);
+    }
+}
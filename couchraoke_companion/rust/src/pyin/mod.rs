@@ -5,19 +5,42 @@
 //! (Mauch & Dixon). The implementation follows the paper's equations and is designed
 //! for streaming PCM input.
 
+pub mod analysis;
 pub mod api;
+mod bleed;
+mod estimator;
+mod framing;
 mod hmm;
-mod midi;
+mod hps;
+mod level;
+pub mod midi;
+mod mpm;
+mod notes;
+mod octave;
 mod pcm;
 mod pyin_stage1;
+mod resample;
+mod score_prior;
+pub(crate) mod snapshot;
+mod vad;
+mod vibrato;
 mod viterbi;
 mod yin;
 
+use std::collections::VecDeque;
+
+use bleed::BleedCanceller;
+use framing::FrameRing;
 use hmm::{HmmParams, ObservationFrame};
-use midi::midi_from_hz;
-use pcm::parse_pcm_bytes;
-use pyin_stage1::{Stage1CandidateFrame, Stage1Config};
-use viterbi::ViterbiTracker;
+use level::FrameLevels;
+use midi::{cents_offset, fractional_midi_from_hz, midi_from_fractional};
+use octave::OctaveCorrector;
+use pcm::{float_sample, PcmDecoder};
+use pyin_stage1::{Candidate, Stage1Analyzer, Stage1CandidateFrame, Stage1Config};
+use score_prior::ScorePrior;
+use snapshot::{invalid, SnapshotKind, StateReader, StateWriter};
+use vad::VoiceActivityDetector;
+use viterbi::{DecodedFrame, ViterbiTracker};
 
 #[derive(Debug, Clone)]
 pub enum PyinError {
@@ -43,6 +66,15 @@ impl BetaPrior {
     }
 }
 
+/// Pitch states of the HMM: `num_bins` log-spaced bins starting at `start_hz`,
+/// `bin_cents` apart.
+#[derive(Debug, Clone, Copy)]
+pub struct PitchGrid {
+    pub start_hz: f32,
+    pub bin_cents: f32,
+    pub num_bins: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct PyinConfig {
     pub sample_rate_hz: u32,
@@ -53,6 +85,29 @@ pub struct PyinConfig {
     pub beta_prior: BetaPrior,
     pub pa_absolute_min: f32,
     pub return_candidates: bool,
+    /// Report the posterior of every HMM pitch bin (`FrameEstimate::posterior`),
+    /// for debugging.
+    pub return_posteriors: bool,
+    /// Number of frames the Viterbi decoder waits before committing a frame.
+    /// Larger values smooth more at the cost of output latency.
+    pub viterbi_lag_frames: usize,
+    /// HMM pitch grid. `None` derives a 10-cent grid spanning `fmin_hz..=fmax_hz`.
+    pub pitch_grid: Option<PitchGrid>,
+    /// Largest pitch change between consecutive frames (Eq. 8 triangle half-width).
+    pub max_pitch_jump_cents: f32,
+    /// Probability of switching between voiced and unvoiced per frame (Eq. 7).
+    pub voicing_switch_prob: f32,
+    /// Tuning reference for MIDI conversion: the frequency of A4 (MIDI 69).
+    pub reference_a4_hz: f32,
+    /// Voice activity detection scaling down the voiced observation of
+    /// non-speech frames. `None` leaves voicing to the YIN candidates alone.
+    pub vad: Option<VadConfig>,
+    /// Harmonic verification of the YIN candidates plus penalised octave jumps
+    /// in the HMM. `None` keeps plain pYIN behaviour.
+    pub octave_correction: Option<OctaveConfig>,
+    /// Shorter stage 1 windows for high pitches, so they follow the newest
+    /// audio. `None` analyses every pitch over the whole frame.
+    pub multi_resolution: Option<MultiResolutionConfig>,
 }
 
 impl Default for PyinConfig {
@@ -66,6 +121,37 @@ impl Default for PyinConfig {
             beta_prior: BetaPrior::Mean10,
             pa_absolute_min: 0.01,
             return_candidates: false,
+            return_posteriors: false,
+            viterbi_lag_frames: 8,
+            pitch_grid: None,
+            max_pitch_jump_cents: 250.0,
+            voicing_switch_prob: 0.01,
+            reference_a4_hz: midi::DEFAULT_REFERENCE_A4_HZ,
+            vad: None,
+            octave_correction: None,
+            multi_resolution: None,
+        }
+    }
+}
+
+impl PyinConfig {
+    const DEFAULT_BIN_CENTS: f32 = 10.0;
+
+    /// The configured pitch grid, or one derived from `fmin_hz`/`fmax_hz`.
+    pub fn pitch_grid(&self) -> PitchGrid {
+        if let Some(grid) = self.pitch_grid {
+            return grid;
+        }
+        let span_cents = 1200.0 * (self.fmax_hz / self.fmin_hz).log2();
+        let num_bins = if span_cents.is_finite() && span_cents > 0.0 {
+            (span_cents / Self::DEFAULT_BIN_CENTS).ceil() as usize + 1
+        } else {
+            0
+        };
+        PitchGrid {
+            start_hz: self.fmin_hz,
+            bin_cents: Self::DEFAULT_BIN_CENTS,
+            num_bins,
         }
     }
 }
@@ -76,128 +162,672 @@ pub struct FrameEstimate {
     pub time_sec: f64,
     pub f0_hz: Option<f32>,
     pub voiced: bool,
-    /// Confidence is derived from the observation probability assigned to the
-    /// winning HMM state (voiced or unvoiced). See `hmm::observation_from_candidates`.
+    /// Posterior probability of the decoded voicing and, for voiced frames, of
+    /// a pitch within 50 cents of the decoded HMM bin. Frames committed while
+    /// streaming use the forward posterior given the input up to the frame;
+    /// frames committed by a flush use the forward-backward posterior given all
+    /// input, and those of offline analysis given at least the next two seconds.
     pub confidence: f32,
+    /// Posterior probability that the frame is voiced, on the same terms as
+    /// `confidence`.
+    pub voicing_probability: f32,
     pub midi_note: Option<u8>,
+    /// Unrounded MIDI pitch relative to `PyinConfig::reference_a4_hz`.
+    pub midi_fractional: Option<f32>,
+    /// Deviation from `midi_note` in cents, in -50..=50.
+    pub cents_offset: Option<f32>,
     pub candidates: Option<Vec<(f32, f32)>>,
+    /// Posterior of each voiced HMM pitch bin (see `PyinConfig::pitch_grid`),
+    /// summing to `voicing_probability`; `None` unless
+    /// `PyinConfig::return_posteriors` is set.
+    pub posterior: Option<Vec<f32>>,
+    /// `true` once the decoder has committed this frame; provisional estimates
+    /// (see `Pyin::provisional_estimates`) may still change.
+    pub is_final: bool,
+    /// RMS level of the analysis frame in dBFS (full-scale sine = -3 dB).
+    pub rms_dbfs: f32,
+    /// Largest absolute sample of the analysis frame, in dBFS.
+    pub peak_dbfs: f32,
+    /// Short-term (3 s) loudness up to the end of the analysis frame, in LUFS.
+    pub loudness_lufs: f32,
+    /// Voice activity decision and noise floor; `None` unless `PyinConfig::vad`
+    /// is set.
+    pub vad: Option<VadFrame>,
 }
 
+/// What `Pyin::finish` does with input samples after the last complete frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TailPolicy {
+    /// Complete the last frame with silence and analyse it.
+    #[default]
+    ZeroPad,
+    /// Discard the samples of the incomplete last frame.
+    Drop,
+}
+
+/// Result of `Pyin::finish`.
+#[derive(Debug, Clone)]
+pub struct StreamEnd {
+    /// Every estimate not returned before, oldest first, all final.
+    pub estimates: Vec<FrameEstimate>,
+    /// Zeros appended to complete the last frame.
+    pub padded_samples: usize,
+    /// Input samples that no analysed frame covers.
+    pub dropped_samples: usize,
+    /// Trailing bytes that did not make up a whole PCM frame.
+    pub dropped_bytes: usize,
+}
+
+/// Per-frame data kept until the decoder commits the frame.
+struct PendingFrame {
+    stage1: Stage1CandidateFrame,
+    rms_dbfs: f32,
+    peak_dbfs: f32,
+    loudness_lufs: f32,
+    vad: Option<VadFrame>,
+}
+
+/// Streaming pYIN detector.
+///
+/// Every buffer of the analysis is allocated up front or on the first frames
+/// and reused afterwards, so once the decoder lag has filled, `push_bytes_into`
+/// and `push_samples_into` do not allocate unless candidates or posteriors are
+/// requested.
 pub struct Pyin {
     cfg: PyinConfig,
-    pcm_format: PcmFormat,
-    sample_buffer: Vec<f32>,
-    leftover_bytes: Vec<u8>,
-    stage1_frames: Vec<Stage1CandidateFrame>,
-    observation_frames: Vec<ObservationFrame>,
+    input: PcmDecoder,
+    ring: FrameRing,
+    decoded: Vec<f32>,
+    stage1: Stage1Analyzer,
+    observation: ObservationFrame,
+    /// Candidate buffers of committed frames, for reuse.
+    spare_stage1: Vec<Stage1CandidateFrame>,
+    bleed: Option<BleedCanceller>,
+    levels: FrameLevels,
+    vad: Option<VoiceActivityDetector>,
+    octave: Option<OctaveCorrector>,
+    score_prior: Option<ScorePrior>,
+    pending: VecDeque<PendingFrame>,
     viterbi: ViterbiTracker,
-    last_emitted: usize,
 }
 
 impl Pyin {
+    /// Create a detector for mono input captured at `cfg.sample_rate_hz`.
     pub fn new(cfg: PyinConfig, pcm_format: PcmFormat) -> Result<Self, PyinError> {
+        let input = PcmInput::mono(pcm_format, cfg.sample_rate_hz);
+        Self::with_input(cfg, input)
+    }
+
+    /// Create a detector for arbitrary PCM input. Multichannel input is reduced
+    /// to mono according to `input.channel_mix` and resampled to
+    /// `cfg.sample_rate_hz` when the capture rate differs.
+    pub fn with_input(cfg: PyinConfig, input: PcmInput) -> Result<Self, PyinError> {
+        if cfg.sample_rate_hz == 0 {
+            return Err(PyinError::InvalidConfig(
+                "sample_rate_hz must be > 0".to_string(),
+            ));
+        }
         if cfg.frame_size == 0 || cfg.hop_size == 0 {
             return Err(PyinError::InvalidConfig(
                 "frame_size and hop_size must be > 0".to_string(),
             ));
         }
-        let hmm_params = HmmParams::new();
+        if !(cfg.reference_a4_hz.is_finite() && cfg.reference_a4_hz > 0.0) {
+            return Err(PyinError::InvalidConfig(
+                "reference_a4_hz must be > 0".to_string(),
+            ));
+        }
+        let hmm_params = HmmParams::new(&cfg)?;
+        let observation = ObservationFrame::new(hmm_params.num_bins());
+        let viterbi = ViterbiTracker::new(hmm_params, cfg.viterbi_lag_frames)
+            .with_bin_posteriors(cfg.return_posteriors);
+        let input = PcmDecoder::new(input, cfg.sample_rate_hz)?;
+        let vad = match cfg.vad.clone() {
+            Some(vad_cfg) => Some(VoiceActivityDetector::new(
+                vad_cfg,
+                cfg.frame_size,
+                cfg.hop_size,
+                cfg.sample_rate_hz,
+            )?),
+            None => None,
+        };
+        let levels = FrameLevels::new(cfg.sample_rate_hz)?;
+        let octave = match cfg.octave_correction.clone() {
+            Some(octave_cfg) => Some(OctaveCorrector::new(
+                octave_cfg,
+                cfg.frame_size,
+                cfg.sample_rate_hz,
+                cfg.fmin_hz,
+                cfg.fmax_hz,
+            )?),
+            None => None,
+        };
         Ok(Self {
+            ring: FrameRing::new(cfg.frame_size, cfg.hop_size),
+            stage1: Stage1Analyzer::new(Stage1Config::from_config(&cfg))?,
+            observation,
+            spare_stage1: Vec::new(),
+            pending: VecDeque::with_capacity(cfg.viterbi_lag_frames.saturating_add(1).min(1024)),
             cfg,
-            pcm_format,
-            sample_buffer: Vec::new(),
-            leftover_bytes: Vec::new(),
-            stage1_frames: Vec::new(),
-            observation_frames: Vec::new(),
-            viterbi: ViterbiTracker::new(hmm_params),
-            last_emitted: 0,
+            input,
+            decoded: Vec::new(),
+            bleed: None,
+            levels,
+            vad,
+            octave,
+            score_prior: None,
+            viterbi,
         })
     }
 
     pub fn reset(&mut self) {
-        self.sample_buffer.clear();
-        self.leftover_bytes.clear();
-        self.stage1_frames.clear();
-        self.observation_frames.clear();
-        self.viterbi = ViterbiTracker::new(HmmParams::new());
-        self.last_emitted = 0;
+        self.ring.reset();
+        self.input.reset();
+        self.levels.reset();
+        if let Some(bleed) = self.bleed.as_mut() {
+            bleed.reset();
+        }
+        if let Some(vad) = self.vad.as_mut() {
+            vad.reset();
+        }
+        self.spare_stage1
+            .extend(self.pending.drain(..).map(|pending| pending.stage1));
+        let hmm_params = self.viterbi.params().clone();
+        self.viterbi = ViterbiTracker::new(hmm_params, self.cfg.viterbi_lag_frames)
+            .with_bin_posteriors(self.cfg.return_posteriors);
     }
 
+    /// Push PCM bytes and return the estimates committed by the decoder.
+    ///
+    /// Every frame is returned exactly once, `viterbi_lag_frames` frames after it
+    /// was analysed, with `is_final` set. The last frames of a stream are returned by
+    /// `finish`.
     pub fn push_bytes(&mut self, chunk: &[u8]) -> Result<Vec<FrameEstimate>, PyinError> {
-        let new_samples = parse_pcm_bytes(chunk, self.pcm_format, &mut self.leftover_bytes);
-        self.sample_buffer.extend_from_slice(&new_samples);
+        let mut output = Vec::new();
+        self.push_bytes_into(chunk, &mut output)?;
+        Ok(output)
+    }
 
-        let stage1_cfg = Stage1Config::from_config(&self.cfg);
+    /// Like `push_bytes`, but appends the estimates to `output`. With the
+    /// capacity of `output` reused across calls, steady-state streaming does
+    /// not allocate.
+    pub fn push_bytes_into(
+        &mut self,
+        chunk: &[u8],
+        output: &mut Vec<FrameEstimate>,
+    ) -> Result<(), PyinError> {
+        let mut decoded = std::mem::take(&mut self.decoded);
+        decoded.clear();
+        self.input.decode(chunk, &mut decoded);
+        let result = self.push_samples_into(&decoded, output);
+        self.decoded = decoded;
+        result
+    }
 
-        while self.sample_buffer.len() >= self.cfg.frame_size {
-            let frame = &self.sample_buffer[..self.cfg.frame_size];
-            let candidate_frame = pyin_stage1::process_frame(frame, &stage1_cfg);
-            self.stage1_frames.push(candidate_frame);
-            self.sample_buffer.drain(..self.cfg.hop_size.min(self.sample_buffer.len()));
-        }
+    /// Subtract the bleed of a known backing track from all further input.
+    ///
+    /// The reference must be at `PyinConfig::sample_rate_hz`. Replaces any
+    /// previously set reference and starts adapting from scratch.
+    pub fn set_bleed_reference(
+        &mut self,
+        reference: BleedReference,
+        cfg: &BleedConfig,
+    ) -> Result<(), PyinError> {
+        self.bleed = Some(BleedCanceller::new(reference, cfg, self.cfg.sample_rate_hz)?);
+        Ok(())
+    }
 
-        if self.stage1_frames.is_empty() {
-            return Ok(Vec::new());
+    /// Update `BleedConfig::delay_sec` of the current reference, e.g. after
+    /// re-synchronising with playback. Does nothing without a reference.
+    pub fn set_bleed_delay(&mut self, delay_sec: f64) {
+        if let Some(bleed) = self.bleed.as_mut() {
+            bleed.set_delay_sec(delay_sec);
         }
+    }
+
+    pub fn clear_bleed_reference(&mut self) {
+        self.bleed = None;
+    }
+
+    /// Bias tracking toward the expected melody while one of `notes` is active.
+    /// `notes` are in song time, placed on the stream by
+    /// `ScorePriorConfig::song_start_sec`. Replaces any previously set target
+    /// notes.
+    pub fn set_score_prior(
+        &mut self,
+        notes: Vec<TargetNote>,
+        cfg: ScorePriorConfig,
+    ) -> Result<(), PyinError> {
+        let prior = ScorePrior::new(notes, cfg, self.viterbi.params(), self.cfg.reference_a4_hz)?;
+        self.score_prior = Some(prior);
+        Ok(())
+    }
 
-        while self.observation_frames.len() < self.stage1_frames.len() {
-            let frame = &self.stage1_frames[self.observation_frames.len()];
-            let obs = hmm::observation_from_candidates(frame);
-            self.viterbi.push(&obs);
-            self.observation_frames.push(obs);
+    /// Update `ScorePriorConfig::song_start_sec` of the current target notes,
+    /// e.g. after the stream paused while the song played on. Does nothing
+    /// without target notes.
+    pub fn set_score_prior_song_start(&mut self, song_start_sec: f64) -> Result<(), PyinError> {
+        if !song_start_sec.is_finite() {
+            return Err(PyinError::InvalidConfig(
+                "score prior song_start_sec must be finite".to_string(),
+            ));
+        }
+        if let Some(prior) = self.score_prior.as_mut() {
+            prior.set_song_start(song_start_sec);
         }
+        Ok(())
+    }
+
+    pub fn clear_score_prior(&mut self) {
+        self.score_prior = None;
+    }
 
-        let state_path = self.viterbi.best_path();
+    /// Layout of the PCM bytes accepted by `push_bytes`.
+    pub fn input(&self) -> &PcmInput {
+        self.input.input()
+    }
 
+    /// Push mono samples in [-1, 1] at `PyinConfig::sample_rate_hz`, bypassing
+    /// the PCM decoding of `push_bytes`, and return the committed estimates.
+    /// Like float PCM, samples are clamped and non-finite ones become silence.
+    ///
+    /// Samples go straight into the analysis frame: a frame is analysed as soon
+    /// as its last sample arrives, however the input is chunked.
+    pub fn push_samples(&mut self, new_samples: &[f32]) -> Result<Vec<FrameEstimate>, PyinError> {
         let mut output = Vec::new();
-        for (idx, state) in state_path.into_iter().enumerate().skip(self.last_emitted) {
-            let time_sec = idx as f64 * self.cfg.hop_size as f64 / self.cfg.sample_rate_hz as f64;
-            let obs = &self.observation_frames[idx];
-            let (f0_hz, voiced, confidence) = if state.voiced {
-                let f0 = self.viterbi.params().bin_freqs[state.bin];
-                let conf = obs.p_star[state.bin];
-                (Some(f0), true, conf)
-            } else {
-                let conf = 1.0 - obs.sum_p;
-                (None, false, conf)
-            };
-            let midi_note = f0_hz.map(midi_from_hz);
-            let candidates = if self.cfg.return_candidates {
-                Some(
-                    self.stage1_frames[idx]
-                        .candidates
-                        .iter()
-                        .map(|c| (c.frequency_hz, c.probability))
-                        .collect(),
-                )
-            } else {
-                None
-            };
-
-            output.push(FrameEstimate {
-                frame_index: idx as u64,
-                time_sec,
-                f0_hz,
-                voiced,
-                confidence,
-                midi_note,
-                candidates,
+        self.push_samples_into(new_samples, &mut output)?;
+        Ok(output)
+    }
+
+    /// Like `push_samples`, but appends the estimates to `output`; see
+    /// `push_bytes_into`.
+    pub fn push_samples_into(
+        &mut self,
+        new_samples: &[f32],
+        output: &mut Vec<FrameEstimate>,
+    ) -> Result<(), PyinError> {
+        let mut rest = new_samples;
+        while !rest.is_empty() {
+            let bleed = &mut self.bleed;
+            let taken = self.ring.fill(rest, |position, samples| {
+                for sample in samples.iter_mut() {
+                    *sample = float_sample(*sample);
+                }
+                if let Some(bleed) = bleed.as_mut() {
+                    bleed.process(position, samples);
+                }
             });
+            rest = &rest[taken..];
+            self.analyse_frame(output);
         }
 
-        self.last_emitted = self.stage1_frames.len();
-        Ok(output)
+        Ok(())
+    }
+
+    /// End the stream and return every estimate still held back, final and
+    /// with an exact backtrace, together with what became of the tail of the
+    /// input. The detector is then reset for a new stream, keeping its bleed
+    /// reference and score prior.
+    pub fn finish(&mut self, tail: TailPolicy) -> Result<StreamEnd, PyinError> {
+        let mut decoded = std::mem::take(&mut self.decoded);
+        decoded.clear();
+        let dropped_bytes = self.input.finish(&mut decoded);
+        let mut estimates = Vec::new();
+        let result = self.push_samples_into(&decoded, &mut estimates);
+        self.decoded = decoded;
+        result?;
+
+        let unanalysed = self.ring.unanalysed();
+        let mut end = StreamEnd {
+            estimates,
+            padded_samples: 0,
+            dropped_samples: 0,
+            dropped_bytes,
+        };
+        match tail {
+            TailPolicy::ZeroPad if unanalysed > 0 => {
+                end.padded_samples = self.ring.pad();
+                self.analyse_frame(&mut end.estimates);
+            }
+            _ => end.dropped_samples = unanalysed,
+        }
+        end.estimates.extend(self.commit_pending());
+        self.reset();
+        Ok(end)
+    }
+
+    /// Serialise the streaming state into a compact versioned blob: buffered
+    /// input, levels, noise floor, learned bleed filter and delay, frames
+    /// awaiting the decoder and the decoder itself.
+    ///
+    /// The configuration, bleed reference and target notes are not included.
+    /// Restore into a detector created with the same configuration and input
+    /// format, with the same reference and notes set; it then produces exactly
+    /// the output this one would have.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut out = StateWriter::new(SnapshotKind::Pyin);
+        self.save_state(&mut out);
+        out.finish()
+    }
+
+    /// Continue from a `snapshot`. On error the detector is reset.
+    pub fn restore(&mut self, blob: &[u8]) -> Result<(), PyinError> {
+        let result = StateReader::new(blob, SnapshotKind::Pyin).and_then(|mut input| {
+            self.load_state(&mut input)?;
+            input.finish()
+        });
+        if result.is_err() {
+            self.reset();
+        }
+        result
+    }
+
+    fn save_state(&self, out: &mut StateWriter) {
+        out.usize(self.cfg.sample_rate_hz as usize);
+        out.usize(self.cfg.frame_size);
+        out.usize(self.cfg.hop_size);
+        let pcm = self.input();
+        out.usize(pcm.sample_rate_hz as usize);
+        out.usize(pcm.format as usize);
+        out.usize(pcm.channels as usize);
+        out.usize(channel_mix_code(pcm.channel_mix));
+        self.input.save_state(out);
+        self.ring.save_state(out);
+        self.levels.save_state(out);
+        out.option(self.bleed.as_ref(), |out, bleed| bleed.save_state(out));
+        out.option(self.vad.as_ref(), |out, vad| vad.save_state(out));
+        out.usize(self.pending.len());
+        for pending in &self.pending {
+            out.usize(pending.stage1.candidates.len());
+            for candidate in &pending.stage1.candidates {
+                out.f32(candidate.frequency_hz);
+                out.f32(candidate.probability);
+            }
+            out.f32(pending.rms_dbfs);
+            out.f32(pending.peak_dbfs);
+            out.f32(pending.loudness_lufs);
+            out.option(pending.vad.as_ref(), |out, vad| {
+                out.bool(vad.speech);
+                out.f32(vad.speech_prob);
+                out.f32(vad.noise_floor_dbfs);
+                out.f32(vad.spectral_flatness);
+            });
+        }
+        self.viterbi.save_state(out);
+    }
+
+    fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.expect_usize("sample_rate_hz", self.cfg.sample_rate_hz as usize)?;
+        input.expect_usize("frame_size", self.cfg.frame_size)?;
+        input.expect_usize("hop_size", self.cfg.hop_size)?;
+        let pcm = *self.input();
+        input.expect_usize("capture rate", pcm.sample_rate_hz as usize)?;
+        input.expect_usize("sample format", pcm.format as usize)?;
+        input.expect_usize("channels", pcm.channels as usize)?;
+        input.expect_usize("channel mix", channel_mix_code(pcm.channel_mix))?;
+        self.input.load_state(input)?;
+        self.ring.load_state(input)?;
+        self.levels.load_state(input)?;
+        match (input.bool()?, self.bleed.as_mut()) {
+            (true, Some(bleed)) => bleed.load_state(input)?,
+            (true, None) => return invalid("set the bleed reference before restoring"),
+            (false, Some(bleed)) => bleed.reset(),
+            (false, None) => {}
+        }
+        match (input.bool()?, self.vad.as_mut()) {
+            (true, Some(vad)) => vad.load_state(input)?,
+            (false, None) => {}
+            _ => return invalid("voice activity detection is configured differently"),
+        }
+
+        self.spare_stage1
+            .extend(self.pending.drain(..).map(|pending| pending.stage1));
+        let pending = input.usize()?;
+        for _ in 0..pending {
+            let mut stage1 = self
+                .spare_stage1
+                .pop()
+                .unwrap_or_else(|| self.stage1.empty_frame());
+            stage1.candidates.clear();
+            let candidates = input.usize()?;
+            for _ in 0..candidates {
+                stage1.candidates.push(Candidate {
+                    frequency_hz: input.f32()?,
+                    probability: input.f32()?,
+                });
+            }
+            self.pending.push_back(PendingFrame {
+                stage1,
+                rms_dbfs: input.f32()?,
+                peak_dbfs: input.f32()?,
+                loudness_lufs: input.f32()?,
+                vad: input.option(|input| {
+                    Ok(VadFrame {
+                        speech: input.bool()?,
+                        speech_prob: input.f32()?,
+                        noise_floor_dbfs: input.f32()?,
+                        spectral_flatness: input.f32()?,
+                    })
+                })?,
+            });
+        }
+        self.viterbi.load_state(input)?;
+        if self.pending.len() != self.viterbi.pending_frames() {
+            return invalid("pending frames do not match the decoder");
+        }
+        Ok(())
+    }
+
+    /// Analyse the buffered frame, if complete, and append the estimate the
+    /// decoder commits in turn to `output`.
+    fn analyse_frame(&mut self, output: &mut Vec<FrameEstimate>) {
+        let Some((frame_start, frame)) = self.ring.frame() else {
+            return;
+        };
+
+        let mut stage1 = self
+            .spare_stage1
+            .pop()
+            .unwrap_or_else(|| self.stage1.empty_frame());
+        self.stage1.process(frame, &mut stage1);
+        if let Some(octave) = self.octave.as_mut() {
+            octave.correct(frame, &mut stage1);
+        }
+        let levels = self.levels.measure(frame_start, frame);
+        let vad = self.vad.as_mut().map(|vad| vad.process(frame, levels.rms_dbfs));
+        self.ring.advance();
+
+        let obs = &mut self.observation;
+        hmm::observation_from_candidates(&stage1, self.viterbi.params(), obs);
+        let voiced_weight = match (&self.vad, &vad) {
+            (Some(detector), Some(frame)) => frame.observation_weight(detector.config()),
+            _ => 1.0,
+        };
+        if voiced_weight < 1.0 {
+            obs.p_star.iter_mut().for_each(|p| *p *= voiced_weight);
+            obs.sum_p *= voiced_weight;
+        }
+        if let Some(prior) = self.score_prior.as_mut() {
+            let frame_index = self.viterbi.committed_frames() + self.pending.len();
+            let centre = (frame_index * self.cfg.hop_size) as f64
+                + self.cfg.frame_size as f64 / 2.0;
+            prior.apply(centre / self.cfg.sample_rate_hz as f64, obs);
+        }
+        let committed = self.viterbi.push(obs);
+        self.pending.push_back(PendingFrame {
+            stage1,
+            rms_dbfs: levels.rms_dbfs,
+            peak_dbfs: levels.peak_dbfs,
+            loudness_lufs: levels.short_term_lufs,
+            vad,
+        });
+        if let Some(decoded) = committed {
+            let frame_index = self.viterbi.committed_frames() as u64 - 1;
+            let pending = self
+                .pending
+                .pop_front()
+                .expect("committed frame must be pending");
+            output.push(self.estimate(frame_index, &pending, decoded, true));
+            self.spare_stage1.push(pending.stage1);
+        }
+    }
+
+    /// Current best estimates for the frames not yet committed, oldest first.
+    ///
+    /// These carry `is_final == false` and may be revised by later input; the
+    /// committed version is returned by `push_bytes` once the lag has elapsed.
+    pub fn provisional_estimates(&self) -> Vec<FrameEstimate> {
+        let first_index = self.viterbi.committed_frames() as u64;
+        self.viterbi
+            .provisional_path()
+            .into_iter()
+            .zip(self.pending.iter())
+            .enumerate()
+            .map(|(offset, (decoded, pending))| {
+                self.estimate(first_index + offset as u64, pending, decoded, false)
+            })
+            .collect()
+    }
+
+    /// Commit every pending frame with an exact backtrace and return them.
+    fn commit_pending(&mut self) -> Vec<FrameEstimate> {
+        let path = self.viterbi.finish();
+        self.committed_estimates(path)
+    }
+
+    /// Commit every pending frame but the newest `lookahead`, with
+    /// forward-backward posteriors over all of them (see
+    /// `ViterbiTracker::commit_settled`), and return them.
+    fn commit_settled(&mut self, lookahead: usize) -> Vec<FrameEstimate> {
+        let path = self.viterbi.commit_settled(lookahead);
+        self.committed_estimates(path)
+    }
+
+    /// Estimates of the oldest pending frames, just committed as `path`.
+    fn committed_estimates(&mut self, path: Vec<DecodedFrame>) -> Vec<FrameEstimate> {
+        let first_index = (self.viterbi.committed_frames() - path.len()) as u64;
+        let pending: Vec<PendingFrame> = self.pending.drain(..path.len()).collect();
+        let estimates = path
+            .into_iter()
+            .zip(pending.iter())
+            .enumerate()
+            .map(|(offset, (decoded, pending))| {
+                self.estimate(first_index + offset as u64, pending, decoded, true)
+            })
+            .collect();
+        self.spare_stage1
+            .extend(pending.into_iter().map(|pending| pending.stage1));
+        estimates
+    }
+
+    /// Number of analysed frames the decoder has not committed yet.
+    fn pending_frames(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn estimate(
+        &self,
+        frame_index: u64,
+        pending: &PendingFrame,
+        decoded: DecodedFrame,
+        is_final: bool,
+    ) -> FrameEstimate {
+        let time_sec =
+            frame_index as f64 * self.cfg.hop_size as f64 / self.cfg.sample_rate_hz as f64;
+        let DecodedFrame { state, posterior } = decoded;
+        let f0_hz = state
+            .voiced
+            .then(|| bin_estimate(&pending.stage1, self.viterbi.params(), state.bin));
+        let midi_fractional = f0_hz.map(|f0| fractional_midi_from_hz(f0, self.cfg.reference_a4_hz));
+        let midi_note = midi_fractional.map(midi_from_fractional);
+        let candidates = if self.cfg.return_candidates {
+            Some(
+                pending
+                    .stage1
+                    .candidates
+                    .iter()
+                    .map(|c| (c.frequency_hz, c.probability))
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        FrameEstimate {
+            frame_index,
+            time_sec,
+            f0_hz,
+            voiced: state.voiced,
+            confidence: posterior.decoded,
+            voicing_probability: posterior.voiced,
+            midi_note,
+            midi_fractional,
+            cents_offset: midi_fractional.map(cents_offset),
+            candidates,
+            posterior: posterior.voiced_bins,
+            is_final,
+            rms_dbfs: pending.rms_dbfs,
+            peak_dbfs: pending.peak_dbfs,
+            loudness_lufs: pending.loudness_lufs,
+            vad: pending.vad,
+        }
+    }
+}
+
+/// Frequency of a voiced state.
+///
+/// The frequency is the probability-weighted mean of the stage-1 candidates that
+/// fall into `bin`, so the output is not quantised to the HMM grid. Falls back to
+/// the bin centre.
+fn bin_estimate(stage1: &Stage1CandidateFrame, params: &HmmParams, bin: usize) -> f32 {
+    let (weighted, total) = stage1
+        .candidates
+        .iter()
+        .filter(|c| params.freq_to_bin(c.frequency_hz) == Some(bin))
+        .fold((0.0, 0.0), |(w, t), c| {
+            (w + c.frequency_hz * c.probability, t + c.probability)
+        });
+    if total > 0.0 {
+        weighted / total
+    } else {
+        params.bin_freqs[bin]
+    }
+}
+
+/// `mix` as a snapshot value: 0 to downmix, else the picked channel plus one.
+fn channel_mix_code(mix: ChannelMix) -> usize {
+    match mix {
+        ChannelMix::Downmix => 0,
+        ChannelMix::Pick(channel) => channel as usize + 1,
     }
 }
 
+pub use analysis::{
+    analyze_file, analyze_file_with_progress, AnalysisProgress, FileAnalysis, FileAnalysisConfig,
+};
 pub use api::{
-    init_logging, new_processor, push_and_get_midi, AudioAnalyzer, PitchConfig, PyinProcessor,
+    init_logging, new_processor, push_and_get_midi, AudioAnalyzer, FileAnalysisOptions,
+    FileAnalysisUpdate, FileContour, Party, PitchConfig, PitchEvent, PitchStreamOptions,
+    PyinProcessor, ScoreStreamOptions, SingerOptions,
+};
+pub use bleed::{BleedConfig, BleedReference};
+pub use estimator::{new_estimator, PitchAlgorithm, PitchEstimator};
+pub use level::{LevelConfig, LevelMeter, LevelReading};
+pub use notes::{segment_notes, NoteConfig, NoteEvent, NoteSegmenter};
+pub use octave::OctaveConfig;
+pub use pyin_stage1::MultiResolutionConfig;
+pub use pcm::{ChannelMix, PcmFormat, PcmInput};
+pub use score_prior::{ScorePriorConfig, TargetNote};
+pub use vad::{VadConfig, VadFrame};
+pub use vibrato::{
+    analyze_vibrato, summarize_vibrato, vibrato_segments, VibratoAnalyzer, VibratoConfig,
+    VibratoFrame, VibratoSummary,
 };
-pub use pcm::PcmFormat;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use midi::midi_from_hz;
 
     #[test]
     fn midi_conversion_basics() {
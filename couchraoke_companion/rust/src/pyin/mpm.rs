@@ -0,0 +1,154 @@
+//! McLeod pitch method.
+//!
+//! "A Smarter Way to Find Pitch" (McLeod & Wyvill). The normalised square
+//! difference function n(τ) = 2 r(τ) / m(τ), with r the autocorrelation and m
+//! the energy of the two overlapping parts, lies in [-1, 1] and peaks near 1 at
+//! multiples of the period. The period is the first key maximum (the highest
+//! point of a positive lobe) within `KEY_MAXIMUM_RATIO` of the highest one,
+//! and its height is the clarity of the frame.
+
+use super::estimator::{lag_range, FrameMethod, FramePitch};
+use super::yin::{parabolic_interpolation, DifferenceFunction};
+use super::PyinConfig;
+
+/// Key maxima at least this fraction of the highest one are period candidates.
+const KEY_MAXIMUM_RATIO: f32 = 0.9;
+/// Clarity from which a frame counts as voiced.
+const CLARITY_THRESHOLD: f32 = 0.6;
+
+pub(crate) struct MpmMethod {
+    sample_rate_hz: u32,
+    min_tau: usize,
+    max_tau: usize,
+    difference: DifferenceFunction,
+    diff: Vec<f32>,
+    prefix_sq: Vec<f32>,
+    nsdf: Vec<f32>,
+}
+
+impl MpmMethod {
+    pub fn new(cfg: &PyinConfig) -> Self {
+        let (min_tau, max_tau) = lag_range(cfg);
+        Self {
+            sample_rate_hz: cfg.sample_rate_hz,
+            min_tau,
+            max_tau,
+            difference: DifferenceFunction::new(cfg.frame_size),
+            diff: Vec::new(),
+            prefix_sq: Vec::new(),
+            nsdf: Vec::new(),
+        }
+    }
+}
+
+impl FrameMethod for MpmMethod {
+    fn estimate(&mut self, frame: &[f32]) -> FramePitch {
+        self.normalized_square_difference(frame);
+        let nsdf = &self.nsdf;
+        let maxima = key_maxima(nsdf, self.min_tau);
+        let highest = maxima.iter().fold(0.0_f32, |max, &tau| max.max(nsdf[tau]));
+        let Some(&tau) = maxima
+            .iter()
+            .find(|&&tau| highest > 0.0 && nsdf[tau] >= KEY_MAXIMUM_RATIO * highest)
+        else {
+            return FramePitch {
+                f0_hz: self.sample_rate_hz as f32 / self.max_tau.max(1) as f32,
+                strength: 0.0,
+                voiced: false,
+            };
+        };
+        let refined_tau = parabolic_interpolation(nsdf, tau).max(1.0);
+        let clarity = nsdf[tau].clamp(0.0, 1.0);
+        FramePitch {
+            f0_hz: self.sample_rate_hz as f32 / refined_tau,
+            strength: clarity,
+            voiced: clarity >= CLARITY_THRESHOLD,
+        }
+    }
+}
+
+impl MpmMethod {
+    /// n(τ) for τ in [0, max_tau] into `nsdf`. With d the YIN difference
+    /// function, m(τ) - d(τ) = 2 r(τ), so n(τ) = 1 - d(τ) / m(τ).
+    fn normalized_square_difference(&mut self, frame: &[f32]) {
+        let n = frame.len();
+        self.difference.compute(frame, self.max_tau, &mut self.diff);
+        self.prefix_sq.clear();
+        self.prefix_sq.push(0.0);
+        let mut running = 0.0;
+        for &sample in frame {
+            running += sample * sample;
+            self.prefix_sq.push(running);
+        }
+        let prefix_sq = &self.prefix_sq;
+        self.nsdf.clear();
+        self.nsdf.extend(self.diff.iter().enumerate().map(|(tau, &d)| {
+            if tau >= n {
+                return 0.0;
+            }
+            let energy = prefix_sq[n - tau] + prefix_sq[n] - prefix_sq[tau];
+            if energy > 0.0 {
+                1.0 - d / energy
+            } else {
+                0.0
+            }
+        }));
+    }
+}
+
+/// Highest point of each positive lobe after the first negative-going zero
+/// crossing, restricted to lags from `min_tau`. A lobe still open at the end
+/// of the range has no maximum yet.
+fn key_maxima(nsdf: &[f32], min_tau: usize) -> Vec<usize> {
+    let mut maxima = Vec::new();
+    let Some(first_negative) = nsdf.iter().position(|&v| v < 0.0) else {
+        return maxima;
+    };
+    let mut lobe_max: Option<usize> = None;
+    for tau in first_negative..nsdf.len() {
+        if nsdf[tau] > 0.0 {
+            if tau >= min_tau && lobe_max.is_none_or(|best| nsdf[tau] > nsdf[best]) {
+                lobe_max = Some(tau);
+            }
+        } else if let Some(best) = lobe_max.take() {
+            maxima.push(best);
+        }
+    }
+    maxima
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_period_of_a_harmonic_tone_and_rejects_noise() {
+        let cfg = PyinConfig::default();
+        let rate = cfg.sample_rate_hz as f32;
+        let tone: Vec<f32> = (0..cfg.frame_size)
+            .map(|i| {
+                let t = i as f32 / rate;
+                // Strong second harmonic: plain autocorrelation peaks are
+                // close at the period and half of it.
+                [(1.0, 0.5), (2.0, 1.0), (3.0, 0.3)]
+                    .iter()
+                    .map(|(k, a)| a * (2.0 * std::f32::consts::PI * 196.0 * k * t).sin())
+                    .sum()
+            })
+            .collect();
+        let mut method = MpmMethod::new(&cfg);
+        let pitch = method.estimate(&tone);
+        assert!(pitch.voiced && pitch.strength > 0.9, "{:?}", pitch);
+        assert!((pitch.f0_hz / 196.0 - 1.0).abs() < 0.005, "{:?}", pitch);
+
+        let mut state = 1_u32;
+        let noise: Vec<f32> = (0..cfg.frame_size)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as f32 / (1 << 24) as f32 - 0.5
+            })
+            .collect();
+        assert!(!method.estimate(&noise).voiced);
+        assert!(!method.estimate(&vec![0.0; cfg.frame_size]).voiced);
+    }
+}
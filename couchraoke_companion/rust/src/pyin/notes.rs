@@ -0,0 +1,544 @@
+//! Note-level transcription on top of the frame-level pYIN output.
+//!
+//! Follows the note HMM used by Tony / pYIN-notes (Mauch et al.): every
+//! semitone in the tracked range has an *attack* and a *stable* state, and a
+//! single *silence* state separates notes. Frames are decoded with the same
+//! fixed-lag strategy as the pitch tracker, and a note is emitted once the
+//! decoder has committed its last frame.
+
+use std::collections::VecDeque;
+
+use super::midi::fractional_midi_from_hz;
+use super::{FrameEstimate, PyinConfig, PyinError};
+
+#[derive(Debug, Clone)]
+pub struct NoteConfig {
+    /// Frames the note decoder waits before committing a frame.
+    pub lag_frames: usize,
+    /// Notes shorter than this are dropped.
+    pub min_note_sec: f64,
+    /// Probability that a silent frame is followed by a note onset.
+    pub onset_prob: f32,
+    /// Probability that a note ends in silence on a given frame.
+    pub offset_prob: f32,
+    /// Probability that a stable note moves straight into a new note (legato).
+    pub note_change_prob: f32,
+    /// Probability that an attack frame is followed by another attack frame.
+    pub attack_stay_prob: f32,
+    /// Pitch spread of the stable state, in semitones.
+    pub stable_sigma_semitones: f32,
+    /// Pitch spread of the attack state, in semitones.
+    pub attack_sigma_semitones: f32,
+    /// Probability that the frame tracker reports a frame as voiced inside a note.
+    pub voiced_prob: f32,
+    /// Frames within this distance of the note median count as stable.
+    pub stable_cents: f32,
+}
+
+impl Default for NoteConfig {
+    fn default() -> Self {
+        Self {
+            lag_frames: 20,
+            min_note_sec: 0.05,
+            onset_prob: 0.01,
+            offset_prob: 0.01,
+            note_change_prob: 0.01,
+            attack_stay_prob: 0.7,
+            stable_sigma_semitones: 0.5,
+            attack_sigma_semitones: 1.5,
+            voiced_prob: 0.9,
+            stable_cents: 50.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NoteEvent {
+    pub onset_sec: f64,
+    pub offset_sec: f64,
+    pub first_frame: u64,
+    pub last_frame: u64,
+    /// Median pitch of the voiced frames in cents above MIDI note 0 (A4 = 6900),
+    /// relative to `PyinConfig::reference_a4_hz`.
+    pub median_cents: f32,
+    pub midi_note: u8,
+    /// Fraction of voiced frames within `NoteConfig::stable_cents` of the median.
+    pub stability: f32,
+}
+
+/// Log-domain transition and emission terms, precomputed from `NoteConfig`.
+struct NoteModel {
+    min_midi: i32,
+    num_notes: usize,
+    log_silence_stay: f32,
+    log_onset: f32,
+    log_attack_stay: f32,
+    log_attack_to_stable: f32,
+    log_offset: f32,
+    log_stable_stay: f32,
+    log_change: f32,
+    log_voiced: f32,
+    log_unvoiced: f32,
+    log_uniform_pitch: f32,
+    stable_sigma: f32,
+    attack_sigma: f32,
+}
+
+impl NoteModel {
+    fn new(pyin_cfg: &PyinConfig, cfg: &NoteConfig) -> Result<Self, PyinError> {
+        let invalid = |msg: &str| Err(PyinError::InvalidConfig(msg.to_string()));
+        if pyin_cfg.fmin_hz <= 0.0 || pyin_cfg.fmax_hz <= pyin_cfg.fmin_hz {
+            return invalid("fmin_hz must be > 0 and below fmax_hz");
+        }
+        let probs = [
+            cfg.onset_prob,
+            cfg.offset_prob,
+            cfg.note_change_prob,
+            cfg.attack_stay_prob,
+            cfg.voiced_prob,
+        ];
+        if probs.iter().any(|p| !(*p > 0.0 && *p < 1.0)) {
+            return invalid("note transition and voicing probabilities must be in (0, 1)");
+        }
+        if cfg.offset_prob + cfg.note_change_prob >= 1.0
+            || cfg.attack_stay_prob + cfg.offset_prob >= 1.0
+        {
+            return invalid("note transition probabilities leave no mass for staying in a note");
+        }
+        if cfg.stable_sigma_semitones <= 0.0 || cfg.attack_sigma_semitones <= 0.0 {
+            return invalid("note pitch sigmas must be > 0");
+        }
+
+        let reference = pyin_cfg.reference_a4_hz;
+        let min_midi = fractional_midi_from_hz(pyin_cfg.fmin_hz, reference).floor() as i32;
+        let max_midi = fractional_midi_from_hz(pyin_cfg.fmax_hz, reference).ceil() as i32;
+        let num_notes = (max_midi - min_midi + 1) as usize;
+        Ok(Self {
+            min_midi,
+            num_notes,
+            log_silence_stay: (1.0 - cfg.onset_prob).ln(),
+            log_onset: (cfg.onset_prob / num_notes as f32).ln(),
+            log_attack_stay: cfg.attack_stay_prob.ln(),
+            log_attack_to_stable: (1.0 - cfg.attack_stay_prob - cfg.offset_prob).ln(),
+            log_offset: cfg.offset_prob.ln(),
+            log_stable_stay: (1.0 - cfg.offset_prob - cfg.note_change_prob).ln(),
+            log_change: (cfg.note_change_prob / num_notes as f32).ln(),
+            log_voiced: cfg.voiced_prob.ln(),
+            log_unvoiced: (1.0 - cfg.voiced_prob).ln(),
+            log_uniform_pitch: -(num_notes as f32).ln(),
+            stable_sigma: cfg.stable_sigma_semitones,
+            attack_sigma: cfg.attack_sigma_semitones,
+        })
+    }
+
+    fn num_states(&self) -> usize {
+        1 + 2 * self.num_notes
+    }
+
+    fn midi_of(&self, note: usize) -> f32 {
+        (self.min_midi + note as i32) as f32
+    }
+}
+
+const SILENCE: usize = 0;
+
+fn attack_state(note: usize) -> usize {
+    1 + 2 * note
+}
+
+fn stable_state(note: usize) -> usize {
+    2 + 2 * note
+}
+
+/// `Some(note)` for attack/stable states, `None` for silence.
+fn note_of(state: usize) -> Option<usize> {
+    if state == SILENCE {
+        None
+    } else {
+        Some((state - 1) / 2)
+    }
+}
+
+fn is_attack(state: usize) -> bool {
+    state != SILENCE && (state - 1).is_multiple_of(2)
+}
+
+fn log_gaussian(delta: f32, sigma: f32) -> f32 {
+    -0.5 * (delta / sigma).powi(2) - (sigma * (2.0 * std::f32::consts::PI).sqrt()).ln()
+}
+
+/// Frame data kept until the decoder commits the frame.
+struct PendingNoteFrame {
+    frame_index: u64,
+    time_sec: f64,
+    cents: Option<f32>,
+}
+
+/// Note being built from committed frames.
+struct OpenNote {
+    first_frame: u64,
+    last_frame: u64,
+    onset_sec: f64,
+    cents: Vec<f32>,
+}
+
+/// Streaming note segmenter fed with `FrameEstimate`s in frame order.
+pub struct NoteSegmenter {
+    cfg: NoteConfig,
+    model: NoteModel,
+    hop_sec: f64,
+    lag: usize,
+    scores: Vec<f32>,
+    backpointers: VecDeque<Vec<u32>>,
+    pending: VecDeque<PendingNoteFrame>,
+    started: bool,
+    prev_state: usize,
+    open: Option<OpenNote>,
+}
+
+impl NoteSegmenter {
+    pub fn new(pyin_cfg: &PyinConfig, cfg: NoteConfig) -> Result<Self, PyinError> {
+        let lag = cfg.lag_frames;
+        Self::with_lag(pyin_cfg, cfg, lag)
+    }
+
+    fn with_lag(pyin_cfg: &PyinConfig, cfg: NoteConfig, lag: usize) -> Result<Self, PyinError> {
+        let model = NoteModel::new(pyin_cfg, &cfg)?;
+        let num_states = model.num_states();
+        Ok(Self {
+            cfg,
+            model,
+            hop_sec: pyin_cfg.hop_size as f64 / pyin_cfg.sample_rate_hz as f64,
+            lag,
+            scores: vec![f32::NEG_INFINITY; num_states],
+            backpointers: VecDeque::new(),
+            pending: VecDeque::new(),
+            started: false,
+            prev_state: SILENCE,
+            open: None,
+        })
+    }
+
+    /// Feed the next frame and return the notes that ended on committed frames.
+    pub fn push(&mut self, frame: &FrameEstimate) -> Vec<NoteEvent> {
+        let cents = frame
+            .midi_fractional
+            .filter(|_| frame.voiced)
+            .map(|midi| midi * 100.0);
+        self.decode_frame(cents.map(|c| c / 100.0));
+        self.pending.push_back(PendingNoteFrame {
+            frame_index: frame.frame_index,
+            time_sec: frame.time_sec,
+            cents,
+        });
+
+        let mut notes = Vec::new();
+        if self.pending.len() > self.lag {
+            let state = self.backtrack_oldest();
+            self.backpointers.pop_front();
+            let pending = self.pending.pop_front().expect("pending frame");
+            self.commit(state, pending, &mut notes);
+        }
+        notes
+    }
+
+    /// Commit every pending frame and close the current note.
+    ///
+    /// The segmenter starts over afterwards, as if freshly created.
+    pub fn finish(&mut self) -> Vec<NoteEvent> {
+        let mut notes = Vec::new();
+        let path = self.provisional_path();
+        let pending: Vec<PendingNoteFrame> = self.pending.drain(..).collect();
+        for (state, frame) in path.into_iter().zip(pending) {
+            self.commit(state, frame, &mut notes);
+        }
+        self.close_note(&mut notes);
+
+        self.scores.fill(f32::NEG_INFINITY);
+        self.backpointers.clear();
+        self.started = false;
+        self.prev_state = SILENCE;
+        notes
+    }
+
+    fn decode_frame(&mut self, midi: Option<f32>) {
+        let m = &self.model;
+        let emission = |state: usize| -> f32 {
+            match (midi, note_of(state)) {
+                (None, None) => m.log_voiced,
+                (None, Some(_)) => m.log_unvoiced,
+                (Some(_), None) => m.log_unvoiced + m.log_uniform_pitch,
+                (Some(p), Some(note)) => {
+                    let sigma = if is_attack(state) {
+                        m.attack_sigma
+                    } else {
+                        m.stable_sigma
+                    };
+                    m.log_voiced + log_gaussian(p - m.midi_of(note), sigma)
+                }
+            }
+        };
+
+        let num_states = m.num_states();
+        if !self.started {
+            // Decoding always starts in silence.
+            self.scores[SILENCE] = emission(SILENCE);
+            self.started = true;
+            return;
+        }
+
+        let prev = &self.scores;
+        let mut curr = vec![f32::NEG_INFINITY; num_states];
+        let mut back = vec![0_u32; num_states];
+
+        let mut best_stable = (f32::NEG_INFINITY, SILENCE);
+        for note in 0..m.num_notes {
+            let score = prev[stable_state(note)];
+            if score > best_stable.0 {
+                best_stable = (score, stable_state(note));
+            }
+        }
+
+        let mut silence = (prev[SILENCE] + m.log_silence_stay, SILENCE);
+        for note in 0..m.num_notes {
+            for from in [attack_state(note), stable_state(note)] {
+                let score = prev[from] + m.log_offset;
+                if score > silence.0 {
+                    silence = (score, from);
+                }
+            }
+        }
+        curr[SILENCE] = silence.0 + emission(SILENCE);
+        back[SILENCE] = silence.1 as u32;
+
+        for note in 0..m.num_notes {
+            let attack = attack_state(note);
+            let stable = stable_state(note);
+
+            let mut best = (prev[SILENCE] + m.log_onset, SILENCE);
+            let stay = prev[attack] + m.log_attack_stay;
+            if stay > best.0 {
+                best = (stay, attack);
+            }
+            let change = best_stable.0 + m.log_change;
+            if change > best.0 {
+                best = (change, best_stable.1);
+            }
+            curr[attack] = best.0 + emission(attack);
+            back[attack] = best.1 as u32;
+
+            let from_attack = prev[attack] + m.log_attack_to_stable;
+            let stay = prev[stable] + m.log_stable_stay;
+            let best = if stay >= from_attack {
+                (stay, stable)
+            } else {
+                (from_attack, attack)
+            };
+            curr[stable] = best.0 + emission(stable);
+            back[stable] = best.1 as u32;
+        }
+
+        let max_score = curr.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        if max_score.is_finite() {
+            for score in curr.iter_mut() {
+                *score -= max_score;
+            }
+        }
+        self.scores = curr;
+        self.backpointers.push_back(back);
+    }
+
+    fn best_state(&self) -> usize {
+        let mut best = SILENCE;
+        for (idx, score) in self.scores.iter().enumerate() {
+            if *score > self.scores[best] {
+                best = idx;
+            }
+        }
+        best
+    }
+
+    /// Best states for all pending frames, oldest first.
+    fn provisional_path(&self) -> Vec<usize> {
+        let pending = self.pending.len();
+        if pending == 0 {
+            return Vec::new();
+        }
+        let mut path = vec![self.best_state(); pending];
+        // `backpointers` holds one entry per transition into a pending frame;
+        // the oldest one may belong to the first frame ever, which has none.
+        let offset = self.backpointers.len() + 1 - pending;
+        for t in (1..pending).rev() {
+            path[t - 1] = self.backpointers[offset + t - 1][path[t]] as usize;
+        }
+        path
+    }
+
+    fn backtrack_oldest(&self) -> usize {
+        self.provisional_path()[0]
+    }
+
+    fn commit(&mut self, state: usize, frame: PendingNoteFrame, notes: &mut Vec<NoteEvent>) {
+        let starts_note = match note_of(state) {
+            None => false,
+            Some(note) => {
+                note_of(self.prev_state) != Some(note)
+                    || (is_attack(state) && !is_attack(self.prev_state))
+            }
+        };
+        if state == SILENCE || starts_note {
+            self.close_note(notes);
+        }
+        if starts_note {
+            self.open = Some(OpenNote {
+                first_frame: frame.frame_index,
+                last_frame: frame.frame_index,
+                onset_sec: frame.time_sec,
+                cents: Vec::new(),
+            });
+        }
+        if state != SILENCE {
+            if let Some(open) = self.open.as_mut() {
+                open.last_frame = frame.frame_index;
+                if let Some(cents) = frame.cents {
+                    open.cents.push(cents);
+                }
+            }
+        }
+        self.prev_state = state;
+    }
+
+    fn close_note(&mut self, notes: &mut Vec<NoteEvent>) {
+        let Some(mut open) = self.open.take() else {
+            return;
+        };
+        if open.cents.is_empty() {
+            return;
+        }
+        let onset_sec = open.onset_sec;
+        let frames = open.last_frame - open.first_frame + 1;
+        let offset_sec = onset_sec + frames as f64 * self.hop_sec;
+        if offset_sec - onset_sec < self.cfg.min_note_sec {
+            return;
+        }
+
+        open.cents.sort_by(|a, b| a.total_cmp(b));
+        let median_cents = open.cents[open.cents.len() / 2];
+        let stable = open
+            .cents
+            .iter()
+            .filter(|c| (**c - median_cents).abs() <= self.cfg.stable_cents)
+            .count();
+        notes.push(NoteEvent {
+            onset_sec,
+            offset_sec,
+            first_frame: open.first_frame,
+            last_frame: open.last_frame,
+            median_cents,
+            midi_note: (median_cents / 100.0).round().clamp(0.0, 127.0) as u8,
+            stability: stable as f32 / open.cents.len() as f32,
+        });
+    }
+}
+
+/// Segment a complete frame sequence into notes, decoding it in one pass
+/// without the streaming lag approximation.
+pub fn segment_notes(
+    frames: &[FrameEstimate],
+    pyin_cfg: &PyinConfig,
+    cfg: NoteConfig,
+) -> Result<Vec<NoteEvent>, PyinError> {
+    let mut segmenter = NoteSegmenter::with_lag(pyin_cfg, cfg, usize::MAX)?;
+    for frame in frames {
+        segmenter.push(frame);
+    }
+    Ok(segmenter.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(index: u64, f0_hz: Option<f32>) -> FrameEstimate {
+        let cfg = PyinConfig::default();
+        let midi_fractional = f0_hz.map(|f0| fractional_midi_from_hz(f0, cfg.reference_a4_hz));
+        FrameEstimate {
+            frame_index: index,
+            time_sec: index as f64 * cfg.hop_size as f64 / cfg.sample_rate_hz as f64,
+            f0_hz,
+            voiced: f0_hz.is_some(),
+            confidence: 0.9,
+            voicing_probability: if f0_hz.is_some() { 0.9 } else { 0.1 },
+            midi_note: None,
+            midi_fractional,
+            cents_offset: None,
+            candidates: None,
+            posterior: None,
+            is_final: true,
+            rms_dbfs: -20.0,
+            peak_dbfs: -17.0,
+            loudness_lufs: -20.0,
+            vad: None,
+        }
+    }
+
+    fn track(segments: &[(Option<f32>, usize)]) -> Vec<FrameEstimate> {
+        let mut frames = Vec::new();
+        for &(f0, len) in segments {
+            for _ in 0..len {
+                frames.push(frame(frames.len() as u64, f0));
+            }
+        }
+        frames
+    }
+
+    #[test]
+    fn notes_separated_by_silence() {
+        let frames = track(&[(None, 20), (Some(220.0), 60), (None, 30), (Some(330.0), 60), (None, 20)]);
+        let notes = segment_notes(&frames, &PyinConfig::default(), NoteConfig::default()).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].midi_note, 57);
+        assert_eq!(notes[1].midi_note, 64);
+        assert!(notes[0].first_frame >= 18 && notes[0].first_frame <= 22);
+        assert!(notes.iter().all(|n| n.stability > 0.99));
+    }
+
+    #[test]
+    fn legato_pitch_change_splits_notes() {
+        let frames = track(&[(Some(220.0), 60), (Some(247.0), 60)]);
+        let notes = segment_notes(&frames, &PyinConfig::default(), NoteConfig::default()).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].midi_note, 57);
+        assert_eq!(notes[1].midi_note, 59);
+        assert!(notes[1].first_frame.abs_diff(60) <= 2);
+    }
+
+    #[test]
+    fn short_dropout_does_not_split_note() {
+        let frames = track(&[(Some(220.0), 40), (None, 2), (Some(220.0), 40)]);
+        let notes = segment_notes(&frames, &PyinConfig::default(), NoteConfig::default()).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].median_cents.round(), 5700.0);
+    }
+
+    #[test]
+    fn streaming_matches_offline_on_clean_input() {
+        let frames = track(&[(None, 10), (Some(262.0), 50), (None, 30), (Some(392.0), 50)]);
+        let offline = segment_notes(&frames, &PyinConfig::default(), NoteConfig::default()).unwrap();
+
+        let mut segmenter = NoteSegmenter::new(&PyinConfig::default(), NoteConfig::default()).unwrap();
+        let mut streamed = Vec::new();
+        for f in &frames {
+            streamed.extend(segmenter.push(f));
+        }
+        assert_eq!(streamed.len(), 1, "first note closes before the stream ends");
+        streamed.extend(segmenter.finish());
+
+        assert_eq!(streamed.len(), offline.len());
+        for (a, b) in streamed.iter().zip(offline.iter()) {
+            assert_eq!(a.midi_note, b.midi_note);
+            assert_eq!(a.first_frame, b.first_frame);
+            assert_eq!(a.last_frame, b.last_frame);
+        }
+    }
+}
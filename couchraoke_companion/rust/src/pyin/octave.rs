@@ -0,0 +1,273 @@
+//! Octave-error correction.
+//!
+//! YIN confuses octaves when the odd harmonics are weak (nasal voices pick the
+//! octave above) or when a noisy period makes a double period look as good
+//! (breathy voices pick the octave below). Each stage-1 candidate is checked
+//! against the spectrum: a fundamental must have a harmonic peak at some odd
+//! multiple of itself, and the octave below is only plausible if its own odd
+//! multiples show peaks. Candidate mass that fails the check moves to the
+//! matching octave. The HMM side of the correction, a penalised direct octave
+//! jump, is built into `HmmParams`.
+
+use std::sync::Arc;
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+
+use super::pyin_stage1::{Candidate, Stage1CandidateFrame};
+use super::PyinError;
+
+#[derive(Debug, Clone)]
+pub struct OctaveConfig {
+    /// Fraction of a candidate's mass moved to the other octave when the
+    /// spectrum contradicts it, in [0, 1].
+    pub strength: f32,
+    /// Height of a harmonic peak above the spectrum halfway to its neighbours
+    /// at which the harmonic counts as present.
+    pub harmonic_prominence_db: f32,
+    /// Per-frame probability of a direct jump of one octave in the HMM. Without
+    /// it the pitch can only glide by `max_pitch_jump_cents` per frame, so a
+    /// track that settled on the wrong octave cannot recover. 0 disables jumps.
+    pub octave_jump_prob: f32,
+}
+
+impl Default for OctaveConfig {
+    fn default() -> Self {
+        Self {
+            strength: 0.9,
+            harmonic_prominence_db: 10.0,
+            octave_jump_prob: 0.002,
+        }
+    }
+}
+
+/// Logistic slope of the harmonic presence test, in dB.
+const PROMINENCE_SLOPE_DB: f32 = 2.0;
+/// Peaks further below the strongest bin than this never count as harmonics.
+const HARMONIC_RANGE_DB: f32 = 40.0;
+/// Odd multiples checked for a fundamental.
+const ODD_HARMONICS: [f32; 3] = [1.0, 3.0, 5.0];
+const POWER_EPSILON: f32 = 1e-12;
+
+pub(crate) struct OctaveCorrector {
+    cfg: OctaveConfig,
+    fmin_hz: f32,
+    fmax_hz: f32,
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    power: Vec<f32>,
+    /// Candidates moved to the other octave in the current frame.
+    moved: Vec<Candidate>,
+    bin_hz: f32,
+    /// Half-width of the window's main lobe.
+    main_lobe_hz: f32,
+}
+
+impl OctaveCorrector {
+    pub fn new(
+        cfg: OctaveConfig,
+        frame_size: usize,
+        sample_rate_hz: u32,
+        fmin_hz: f32,
+        fmax_hz: f32,
+    ) -> Result<Self, PyinError> {
+        if !(cfg.strength >= 0.0 && cfg.strength <= 1.0) {
+            return Err(PyinError::InvalidConfig(
+                "octave strength must be in [0, 1]".to_string(),
+            ));
+        }
+        if !cfg.harmonic_prominence_db.is_finite() {
+            return Err(PyinError::InvalidConfig(
+                "octave harmonic_prominence_db must be finite".to_string(),
+            ));
+        }
+        // Zero-padding to twice the frame samples each peak and the valleys
+        // beside it finely enough to compare them.
+        let fft_size = 2 * frame_size;
+        let window = (0..frame_size)
+            .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / frame_size as f32).cos())
+            .collect();
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+        Ok(Self {
+            cfg,
+            fmin_hz,
+            fmax_hz,
+            scratch: vec![Complex { re: 0.0, im: 0.0 }; fft.get_inplace_scratch_len()],
+            fft,
+            window,
+            buffer: vec![Complex { re: 0.0, im: 0.0 }; fft_size],
+            power: vec![0.0; fft_size / 2 + 1],
+            moved: Vec::new(),
+            bin_hz: sample_rate_hz as f32 / fft_size as f32,
+            main_lobe_hz: 2.0 * sample_rate_hz as f32 / frame_size as f32,
+        })
+    }
+
+    /// Move the mass of candidates the spectrum of `frame` contradicts to the
+    /// octave it supports. The total candidate mass is unchanged.
+    pub fn correct(&mut self, frame: &[f32], stage1: &mut Stage1CandidateFrame) {
+        if stage1.candidates.is_empty() || self.cfg.strength == 0.0 {
+            return;
+        }
+        self.power_spectrum(frame);
+        let strongest = self.power.iter().fold(0.0_f32, |max, &p| max.max(p));
+        if strongest <= POWER_EPSILON {
+            return;
+        }
+
+        self.moved.clear();
+        for candidate in stage1.candidates.iter_mut() {
+            let f0 = candidate.frequency_hz;
+            let too_high = if f0 / 2.0 >= self.fmin_hz {
+                self.odd_harmonic_presence(f0 / 2.0, strongest)
+                    .unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            let too_low = if f0 * 2.0 <= self.fmax_hz {
+                self.odd_harmonic_presence(f0, strongest)
+                    .map_or(0.0, |p| 1.0 - p)
+            } else {
+                0.0
+            };
+            let total = too_high + too_low;
+            if total <= 0.0 {
+                continue;
+            }
+            let scale = self.cfg.strength * candidate.probability / total.max(1.0);
+            for (factor, share) in [(0.5, too_high), (2.0, too_low)] {
+                if share > 0.0 {
+                    self.moved.push(Candidate {
+                        frequency_hz: f0 * factor,
+                        probability: scale * share,
+                    });
+                }
+            }
+            candidate.probability -= scale * total;
+        }
+        stage1.candidates.extend_from_slice(&self.moved);
+    }
+
+    /// Probability that `f0` has a harmonic peak at one of its first odd
+    /// multiples, i.e. that it is not the octave below the true pitch. `None`
+    /// when the frame is too short to separate peaks `f0 / 2` apart.
+    fn odd_harmonic_presence(&self, f0: f32, strongest: f32) -> Option<f32> {
+        if f0 / 2.0 < self.main_lobe_hz {
+            return None;
+        }
+        let presence = ODD_HARMONICS
+            .iter()
+            .map(|k| k * f0)
+            .take_while(|&hz| (hz + f0 / 2.0) / self.bin_hz < self.power.len() as f32 - 1.0)
+            .map(|hz| {
+                let peak = self.level(hz);
+                if peak * 10.0_f32.powf(HARMONIC_RANGE_DB / 10.0) < strongest {
+                    return 0.0;
+                }
+                let valley = 0.5 * (self.level(hz - f0 / 2.0) + self.level(hz + f0 / 2.0));
+                let prominence_db = 10.0 * (peak / (valley + POWER_EPSILON)).log10();
+                logistic((prominence_db - self.cfg.harmonic_prominence_db) / PROMINENCE_SLOPE_DB)
+            })
+            .fold(0.0, f32::max);
+        Some(presence)
+    }
+
+    /// Largest power within one bin of `hz`.
+    fn level(&self, hz: f32) -> f32 {
+        let centre = (hz / self.bin_hz).round() as usize;
+        let last = self.power.len() - 1;
+        self.power[centre.saturating_sub(1).min(last)..=(centre + 1).min(last)]
+            .iter()
+            .fold(0.0, |max, &p| max.max(p))
+    }
+
+    fn power_spectrum(&mut self, frame: &[f32]) {
+        self.buffer.fill(Complex { re: 0.0, im: 0.0 });
+        for ((slot, &sample), &w) in self.buffer.iter_mut().zip(frame).zip(&self.window) {
+            slot.re = sample * w;
+        }
+        self.fft.process_with_scratch(&mut self.buffer, &mut self.scratch);
+        for (power, c) in self.power.iter_mut().zip(&self.buffer) {
+            *power = c.norm_sqr();
+        }
+    }
+}
+
+fn logistic(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+    const FRAME: usize = 2048;
+
+    fn harmonic_frame(f0: f32, amplitudes: &[f32]) -> Vec<f32> {
+        (0..FRAME)
+            .map(|i| {
+                let t = i as f32 / RATE as f32;
+                amplitudes
+                    .iter()
+                    .enumerate()
+                    .map(|(k, a)| a * (2.0 * std::f32::consts::PI * f0 * (k + 1) as f32 * t).sin())
+                    .sum::<f32>()
+            })
+            .collect()
+    }
+
+    fn corrected(frame: &[f32], candidate_hz: f32) -> Vec<(f32, f32)> {
+        let mut corrector =
+            OctaveCorrector::new(OctaveConfig::default(), FRAME, RATE, 50.0, 1200.0).unwrap();
+        let mut stage1 = Stage1CandidateFrame {
+            candidates: vec![Candidate {
+                frequency_hz: candidate_hz,
+                probability: 0.8,
+            }],
+        };
+        corrector.correct(frame, &mut stage1);
+        stage1
+            .candidates
+            .iter()
+            .map(|c| (c.frequency_hz, c.probability))
+            .collect()
+    }
+
+    fn mass_near(candidates: &[(f32, f32)], hz: f32) -> f32 {
+        candidates
+            .iter()
+            .filter(|(f, _)| (f / hz - 1.0).abs() < 0.01)
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    #[test]
+    fn moves_octave_errors_to_the_supported_pitch() {
+        // Nasal tone: weak but present odd harmonics.
+        let nasal = harmonic_frame(150.0, &[0.05, 1.0, 0.1, 0.5, 0.05, 0.3]);
+        let up = corrected(&nasal, 300.0);
+        assert!(mass_near(&up, 150.0) > 0.6, "{:?}", up);
+
+        let rich = harmonic_frame(220.0, &[1.0, 0.5, 0.3, 0.2]);
+        let down = corrected(&rich, 110.0);
+        assert!(mass_near(&down, 220.0) > 0.6, "{:?}", down);
+
+        let total: f32 = up.iter().chain(&down).map(|(_, p)| p).sum();
+        assert!((total - 1.6).abs() < 1e-5);
+    }
+
+    #[test]
+    fn keeps_correct_candidates() {
+        for (f0, amplitudes) in [
+            (150.0, vec![0.05, 1.0, 0.1, 0.5, 0.05, 0.3]),
+            (220.0, vec![1.0, 0.5, 0.3, 0.2]),
+            (87.0, vec![0.6, 1.0, 0.4, 0.3, 0.2]),
+            (440.0, vec![1.0]),
+        ] {
+            let kept = corrected(&harmonic_frame(f0, &amplitudes), f0);
+            assert!(mass_near(&kept, f0) > 0.75, "{} Hz: {:?}", f0, kept);
+        }
+    }
+}
@@ -1,70 +1,275 @@
-#[derive(Debug, Clone, Copy)]
+use std::cell::Cell;
+
+use super::resample::Resampler;
+use super::snapshot::{StateReader, StateWriter};
+use super::PyinError;
+
+/// Sample encoding of incoming PCM bytes. Integer formats are signed; 24-bit
+/// samples are packed into three bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum PcmFormat {
+    #[default]
     I16LE,
     F32LE,
+    I16BE,
+    I24LE,
+    I24BE,
+    I32LE,
+    I32BE,
+    F32BE,
 }
 
-impl Default for PcmFormat {
-    fn default() -> Self {
-        PcmFormat::I16LE
+impl PcmFormat {
+    pub fn bytes_per_sample(self) -> usize {
+        match self {
+            PcmFormat::I16LE | PcmFormat::I16BE => 2,
+            PcmFormat::I24LE | PcmFormat::I24BE => 3,
+            PcmFormat::I32LE | PcmFormat::I32BE | PcmFormat::F32LE | PcmFormat::F32BE => 4,
+        }
+    }
+
+    /// Decode one sample from exactly `bytes_per_sample()` bytes.
+    fn decode(self, b: &[u8]) -> f32 {
+        match self {
+            PcmFormat::I16LE => i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+            PcmFormat::I16BE => i16::from_be_bytes([b[0], b[1]]) as f32 / 32768.0,
+            PcmFormat::I24LE => {
+                (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0
+            }
+            PcmFormat::I24BE => {
+                (i32::from_be_bytes([b[0], b[1], b[2], 0]) >> 8) as f32 / 8_388_608.0
+            }
+            PcmFormat::I32LE => {
+                i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0
+            }
+            PcmFormat::I32BE => {
+                i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0
+            }
+            PcmFormat::F32LE => float_sample(f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+            PcmFormat::F32BE => float_sample(f32::from_be_bytes([b[0], b[1], b[2], b[3]])),
+        }
     }
 }
 
-/// Parse PCM bytes into normalized f32 samples in [-1, 1].
-///
-/// The parser keeps track of leftover bytes so that partial samples across
-/// chunks are handled safely.
-pub fn parse_pcm_bytes(bytes: &[u8], format: PcmFormat, leftover: &mut Vec<u8>) -> Vec<f32> {
-    match format {
-        PcmFormat::I16LE => parse_i16le(bytes, leftover),
-        PcmFormat::F32LE => parse_f32le(bytes, leftover),
+/// Clamp a float sample to full scale; NaN and infinities, which would poison
+/// every later frame, become silence.
+pub(crate) fn float_sample(value: f32) -> f32 {
+    if value.is_finite() {
+        value.clamp(-1.0, 1.0)
+    } else {
+        0.0
     }
 }
 
-fn parse_i16le(bytes: &[u8], leftover: &mut Vec<u8>) -> Vec<f32> {
-    let mut data = Vec::with_capacity(leftover.len() + bytes.len());
-    data.extend_from_slice(leftover);
-    data.extend_from_slice(bytes);
+/// How interleaved multichannel input is reduced to the mono analysis signal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChannelMix {
+    /// Average all channels.
+    #[default]
+    Downmix,
+    /// Use a single channel (zero-based), e.g. the vocal mic on a USB interface.
+    Pick(u16),
+}
 
-    let mut samples = Vec::with_capacity(data.len() / 2);
-    let mut idx = 0;
-    while idx + 1 < data.len() {
-        let sample = i16::from_le_bytes([data[idx], data[idx + 1]]);
-        samples.push(sample as f32 / 32768.0);
-        idx += 2;
+/// Layout of the PCM stream handed to `Pyin::push_bytes`.
+#[derive(Debug, Clone, Copy)]
+pub struct PcmInput {
+    pub format: PcmFormat,
+    /// Number of interleaved channels.
+    pub channels: u16,
+    pub channel_mix: ChannelMix,
+    /// Capture rate. When it differs from `PyinConfig::sample_rate_hz` the
+    /// signal is resampled to the analysis rate.
+    pub sample_rate_hz: u32,
+}
+
+impl PcmInput {
+    pub fn mono(format: PcmFormat, sample_rate_hz: u32) -> Self {
+        Self {
+            format,
+            channels: 1,
+            channel_mix: ChannelMix::Downmix,
+            sample_rate_hz,
+        }
     }
 
-    leftover.clear();
-    if idx < data.len() {
-        leftover.push(data[idx]);
+    fn validate(&self) -> Result<(), PyinError> {
+        if self.channels == 0 {
+            return Err(PyinError::InvalidConfig("channels must be > 0".to_string()));
+        }
+        if let ChannelMix::Pick(channel) = self.channel_mix {
+            if channel >= self.channels {
+                return Err(PyinError::InvalidConfig(format!(
+                    "picked channel {} but input has {} channels",
+                    channel, self.channels
+                )));
+            }
+        }
+        if self.sample_rate_hz == 0 {
+            return Err(PyinError::InvalidConfig(
+                "input sample_rate_hz must be > 0".to_string(),
+            ));
+        }
+        Ok(())
     }
+}
 
-    samples
+/// Stateful conversion of raw PCM bytes into mono samples at the analysis rate.
+pub(crate) struct PcmDecoder {
+    input: PcmInput,
+    leftover: Vec<u8>,
+    mono: Vec<f32>,
+    resampler: Resampler,
+    /// Input samples at or above this magnitude count as clipped.
+    clip_level: f32,
+    /// Clipped samples of the latest `decode`.
+    clipped: u32,
 }
 
-fn parse_f32le(bytes: &[u8], leftover: &mut Vec<u8>) -> Vec<f32> {
-    let mut data = Vec::with_capacity(leftover.len() + bytes.len());
-    data.extend_from_slice(leftover);
-    data.extend_from_slice(bytes);
+impl PcmDecoder {
+    pub fn new(input: PcmInput, output_rate_hz: u32) -> Result<Self, PyinError> {
+        input.validate()?;
+        Ok(Self {
+            input,
+            leftover: Vec::new(),
+            mono: Vec::new(),
+            resampler: Resampler::new(input.sample_rate_hz, output_rate_hz),
+            clip_level: f32::INFINITY,
+            clipped: 0,
+        })
+    }
+
+    /// Count input samples at or above `clip_threshold_dbfs` in
+    /// `clipped_samples`.
+    pub fn with_clip_threshold(mut self, clip_threshold_dbfs: f32) -> Self {
+        self.clip_level = 10.0_f32.powf(clip_threshold_dbfs / 20.0);
+        self
+    }
+
+    /// Clipped samples of the latest `decode`, counted at the capture rate in
+    /// every channel that goes into the mono signal, before mixing and
+    /// resampling can hide them.
+    pub fn clipped_samples(&self) -> u32 {
+        self.clipped
+    }
+
+    pub fn input(&self) -> &PcmInput {
+        &self.input
+    }
+
+    /// Decode `bytes`, appending the resulting samples to `output`. Partial
+    /// frames are kept until the rest of their bytes arrive.
+    pub fn decode(&mut self, bytes: &[u8], output: &mut Vec<f32>) {
+        self.mono.clear();
+        self.clipped = decode_interleaved(
+            bytes,
+            self.input.format,
+            self.input.channels as usize,
+            self.input.channel_mix,
+            self.clip_level,
+            &mut self.leftover,
+            &mut self.mono,
+        );
+        self.resampler.process(&self.mono, output);
+    }
+
+    /// End the stream: append the samples the resampler still holds to
+    /// `output`, discard a trailing partial frame and return its length in
+    /// bytes. The decoder is then ready for a new stream.
+    pub fn finish(&mut self, output: &mut Vec<f32>) -> usize {
+        let dropped_bytes = self.leftover.len();
+        self.leftover.clear();
+        self.resampler.flush(output);
+        dropped_bytes
+    }
+
+    pub fn reset(&mut self) {
+        self.leftover.clear();
+        self.resampler.reset();
+    }
+
+    pub fn save_state(&self, out: &mut StateWriter) {
+        out.bytes(&self.leftover);
+        self.resampler.save_state(out);
+    }
 
-    let mut samples = Vec::with_capacity(data.len() / 4);
-    let mut idx = 0;
-    while idx + 3 < data.len() {
-        let sample = f32::from_le_bytes([data[idx], data[idx + 1], data[idx + 2], data[idx + 3]]);
-        samples.push(sample.clamp(-1.0, 1.0));
-        idx += 4;
+    pub fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        let leftover = input.bytes()?;
+        let frame_bytes = self.input.format.bytes_per_sample() * self.input.channels as usize;
+        if leftover.len() >= frame_bytes {
+            return Err(PyinError::InvalidConfig(
+                "invalid snapshot: PCM leftover longer than a frame".to_string(),
+            ));
+        }
+        self.leftover.clear();
+        self.leftover.extend_from_slice(leftover);
+        self.resampler.load_state(input)
     }
+}
 
-    leftover.clear();
-    leftover.extend_from_slice(&data[idx..]);
+/// Decode interleaved frames into mono samples in [-1, 1], carrying an
+/// incomplete trailing frame over in `leftover`. Returns the number of mixed
+/// samples at or above `clip_level`.
+fn decode_interleaved(
+    bytes: &[u8],
+    format: PcmFormat,
+    channels: usize,
+    mix: ChannelMix,
+    clip_level: f32,
+    leftover: &mut Vec<u8>,
+    output: &mut Vec<f32>,
+) -> u32 {
+    let sample_bytes = format.bytes_per_sample();
+    let frame_bytes = sample_bytes * channels;
+    let clipped = Cell::new(0);
+    let decode = |b: &[u8]| {
+        let sample = format.decode(b);
+        if sample.abs() >= clip_level {
+            clipped.set(clipped.get() + 1);
+        }
+        sample
+    };
+    let frame = |data: &[u8]| match mix {
+        ChannelMix::Pick(channel) => {
+            let start = channel as usize * sample_bytes;
+            decode(&data[start..start + sample_bytes])
+        }
+        ChannelMix::Downmix => {
+            data.chunks_exact(sample_bytes).map(decode).sum::<f32>() / channels as f32
+        }
+    };
 
-    samples
+    let mut rest = bytes;
+    if !leftover.is_empty() {
+        let needed = (frame_bytes - leftover.len()).min(rest.len());
+        leftover.extend_from_slice(&rest[..needed]);
+        rest = &rest[needed..];
+        if leftover.len() < frame_bytes {
+            return 0;
+        }
+        output.push(frame(leftover));
+        leftover.clear();
+    }
+
+    let mut chunks = rest.chunks_exact(frame_bytes);
+    output.extend(chunks.by_ref().map(frame));
+    leftover.extend_from_slice(chunks.remainder());
+    clipped.get()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn decode_chunks(input: PcmInput, chunks: &[&[u8]]) -> Vec<f32> {
+        let mut decoder = PcmDecoder::new(input, input.sample_rate_hz).unwrap();
+        let mut output = Vec::new();
+        for chunk in chunks {
+            decoder.decode(chunk, &mut output);
+        }
+        output
+    }
+
     #[test]
     fn pcm_parsing_roundtrip_i16le() {
         let samples: [i16; 4] = [0, 32767, -32768, 12345];
@@ -73,13 +278,83 @@ mod tests {
             bytes.extend_from_slice(&s.to_le_bytes());
         }
 
-        let mut leftover = Vec::new();
-        let parsed = parse_pcm_bytes(&bytes, PcmFormat::I16LE, &mut leftover);
-        assert!(leftover.is_empty());
+        let parsed = decode_chunks(PcmInput::mono(PcmFormat::I16LE, 48_000), &[&bytes]);
         let decoded: Vec<i16> = parsed
             .iter()
             .map(|v| (v * 32768.0).round() as i16)
             .collect();
         assert_eq!(decoded, samples);
     }
+
+    #[test]
+    fn integer_formats_decode_to_same_value() {
+        let value = -0.25_f32;
+        let encodings: [(PcmFormat, Vec<u8>); 6] = [
+            (PcmFormat::I16BE, (-8192_i16).to_be_bytes().to_vec()),
+            (
+                PcmFormat::I24LE,
+                (-2_097_152_i32).to_le_bytes()[..3].to_vec(),
+            ),
+            (
+                PcmFormat::I24BE,
+                (-2_097_152_i32).to_be_bytes()[1..].to_vec(),
+            ),
+            (PcmFormat::I32LE, (-536_870_912_i32).to_le_bytes().to_vec()),
+            (PcmFormat::I32BE, (-536_870_912_i32).to_be_bytes().to_vec()),
+            (PcmFormat::F32BE, value.to_be_bytes().to_vec()),
+        ];
+        for (format, bytes) in encodings.iter() {
+            let parsed = decode_chunks(PcmInput::mono(*format, 48_000), &[bytes]);
+            assert_eq!(parsed, vec![value], "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn non_finite_float_samples_decode_to_silence() {
+        let bytes: Vec<u8> = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 2.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let parsed = decode_chunks(PcmInput::mono(PcmFormat::F32LE, 48_000), &[&bytes]);
+        assert_eq!(parsed, vec![0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn interleaved_frames_split_across_chunks() {
+        // Stereo 24-bit: left = 0.5, right = -0.25, split mid-sample.
+        let mut frame = (4_194_304_i32).to_le_bytes()[..3].to_vec();
+        frame.extend_from_slice(&(-2_097_152_i32).to_le_bytes()[..3]);
+        let bytes: Vec<u8> = frame.iter().chain(frame.iter()).copied().collect();
+        let chunks: [&[u8]; 3] = [&bytes[..4], &bytes[4..5], &bytes[5..]];
+
+        let stereo = |channel_mix| PcmInput {
+            format: PcmFormat::I24LE,
+            channels: 2,
+            channel_mix,
+            sample_rate_hz: 48_000,
+        };
+        assert_eq!(
+            decode_chunks(stereo(ChannelMix::Downmix), &chunks),
+            vec![0.125; 2]
+        );
+        assert_eq!(
+            decode_chunks(stereo(ChannelMix::Pick(0)), &chunks),
+            vec![0.5; 2]
+        );
+        assert_eq!(
+            decode_chunks(stereo(ChannelMix::Pick(1)), &chunks),
+            vec![-0.25; 2]
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_channel_pick() {
+        let input = PcmInput {
+            format: PcmFormat::I16LE,
+            channels: 2,
+            channel_mix: ChannelMix::Pick(2),
+            sample_rate_hz: 48_000,
+        };
+        assert!(PcmDecoder::new(input, 48_000).is_err());
+    }
 }
@@ -1,7 +1,7 @@
-use super::{BetaPrior, PyinConfig};
+use super::{BetaPrior, PyinConfig, PyinError};
 use super::yin::{
-    cumulative_mean_normalized_difference, difference_function, local_minima,
-    parabolic_interpolation,
+    cumulative_mean_normalized_difference, local_minima, parabolic_interpolation,
+    DifferenceFunction,
 };
 
 #[derive(Debug, Clone)]
@@ -13,6 +13,7 @@ pub struct Stage1Config {
     pub thresholds: Vec<f32>,
     pub threshold_priors: Vec<f32>,
     pub pa_absolute_min: f32,
+    pub multi_resolution: Option<MultiResolutionConfig>,
 }
 
 impl Stage1Config {
@@ -27,6 +28,44 @@ impl Stage1Config {
             thresholds,
             threshold_priors,
             pa_absolute_min: cfg.pa_absolute_min,
+            multi_resolution: cfg.multi_resolution.clone(),
+        }
+    }
+}
+
+/// Shorter analysis windows for high pitches.
+///
+/// Each window holds the newest samples of the frame and only looks for
+/// periods it contains `min_periods` times. For every YIN threshold, stage 1
+/// takes the period found by the shortest window with a dip below it, so a
+/// high note follows the most recent audio while low notes are still resolved
+/// over the whole frame.
+#[derive(Debug, Clone)]
+pub struct MultiResolutionConfig {
+    /// Lengths of the shorter windows in samples, each below
+    /// `PyinConfig::frame_size`.
+    pub window_sizes: Vec<usize>,
+    /// Periods a window must span to resolve a pitch.
+    pub min_periods: f32,
+}
+
+impl Default for MultiResolutionConfig {
+    /// 10.7 and 21.3 ms windows at 48 kHz, resolving pitches from 281 and
+    /// 141 Hz up.
+    fn default() -> Self {
+        Self {
+            window_sizes: vec![512, 1024],
+            min_periods: 3.0,
+        }
+    }
+}
+
+impl MultiResolutionConfig {
+    /// Windows of a quarter and half of `frame_size` samples.
+    pub fn for_frame_size(frame_size: usize) -> Self {
+        Self {
+            window_sizes: vec![frame_size / 4, frame_size / 2],
+            ..Self::default()
         }
     }
 }
@@ -42,62 +81,163 @@ pub struct Stage1CandidateFrame {
     pub candidates: Vec<Candidate>,
 }
 
-pub fn process_frame(frame: &[f32], cfg: &Stage1Config) -> Stage1CandidateFrame {
-    let max_tau = (cfg.sample_rate_hz as f32 / cfg.fmin_hz).floor() as usize;
-    let min_tau = (cfg.sample_rate_hz as f32 / cfg.fmax_hz).ceil() as usize;
-    let max_tau = max_tau.min(cfg.frame_size.saturating_sub(1));
-    let min_tau = min_tau.max(1).min(max_tau);
+/// The cumulative mean normalised difference over the newest `size` samples
+/// of a frame, for periods up to `max_tau`.
+struct Resolution {
+    size: usize,
+    max_tau: usize,
+    difference: DifferenceFunction,
+    diff: Vec<f32>,
+    cmnd: Vec<f32>,
+    minima: Vec<usize>,
+}
+
+impl Resolution {
+    fn new(size: usize, max_tau: usize) -> Self {
+        Self {
+            size,
+            max_tau,
+            difference: DifferenceFunction::new(size),
+            diff: Vec::with_capacity(max_tau + 1),
+            cmnd: Vec::with_capacity(max_tau + 1),
+            minima: Vec::with_capacity(max_tau),
+        }
+    }
+
+    fn analyse(&mut self, frame: &[f32]) {
+        let window = &frame[frame.len().saturating_sub(self.size)..];
+        self.difference.compute(window, self.max_tau, &mut self.diff);
+        cumulative_mean_normalized_difference(&self.diff, &mut self.cmnd);
+        local_minima(&self.cmnd, &mut self.minima);
+    }
 
-    let diff = difference_function(frame, max_tau);
-    let cmnd = cumulative_mean_normalized_difference(&diff);
+    /// Y(x_t, s) of the paper: the smallest local minimum in
+    /// `min_tau..=max_tau` below `threshold`.
+    fn first_dip(&self, min_tau: usize, threshold: f32) -> Option<usize> {
+        self.minima
+            .iter()
+            .copied()
+            .filter(|&tau| tau >= min_tau && tau <= self.max_tau)
+            .find(|&tau| self.cmnd[tau] < threshold)
+    }
+}
 
-    let minima = local_minima(&cmnd);
-    let global_min_tau = (min_tau..=max_tau)
-        .min_by(|&a, &b| cmnd[a].partial_cmp(&cmnd[b]).unwrap())
-        .unwrap_or(min_tau);
+/// Stage 1 of pYIN with the buffers it needs, reused from frame to frame.
+pub struct Stage1Analyzer {
+    cfg: Stage1Config,
+    min_tau: usize,
+    /// Shortest window first; the last one is the whole frame.
+    resolutions: Vec<Resolution>,
+    /// Probability mass of each selected resolution and period, in order of
+    /// first selection.
+    candidate_map: Vec<(usize, usize, f32)>,
+}
 
-    let mut candidate_map: Vec<(usize, f32)> = Vec::new();
+impl Stage1Analyzer {
+    pub fn new(cfg: Stage1Config) -> Result<Self, PyinError> {
+        let max_tau = (cfg.sample_rate_hz as f32 / cfg.fmin_hz).floor() as usize;
+        let min_tau = (cfg.sample_rate_hz as f32 / cfg.fmax_hz).ceil() as usize;
+        let max_tau = max_tau.min(cfg.frame_size.saturating_sub(1));
+        let min_tau = min_tau.max(1).min(max_tau);
 
-    for (idx, threshold) in cfg.thresholds.iter().enumerate() {
-        let mut selected_tau = None;
-        for &tau in minima.iter() {
-            if tau < min_tau || tau > max_tau {
-                continue;
+        let mut resolutions = Vec::new();
+        if let Some(multi) = &cfg.multi_resolution {
+            let invalid = |msg: String| Err(PyinError::InvalidConfig(msg));
+            if !(multi.min_periods.is_finite() && multi.min_periods >= 1.0) {
+                return invalid("multi-resolution min_periods must be >= 1".to_string());
             }
-            if cmnd[tau] < *threshold {
-                selected_tau = Some(tau);
-                break;
+            let mut sizes = multi.window_sizes.clone();
+            sizes.sort_unstable();
+            sizes.dedup();
+            for size in sizes {
+                if size == 0 || size >= cfg.frame_size {
+                    return invalid(format!(
+                        "multi-resolution window of {} samples must be shorter than the frame",
+                        size
+                    ));
+                }
+                let window_max_tau = ((size as f32 / multi.min_periods) as usize).min(max_tau);
+                if window_max_tau < min_tau {
+                    return invalid(format!(
+                        "multi-resolution window of {} samples is too short for fmax_hz",
+                        size
+                    ));
+                }
+                resolutions.push(Resolution::new(size, window_max_tau));
             }
         }
-        // pYIN Stage 1: Eq. (4) and (5) from the paper.
-        // Y(x_t, s_i) returns the smallest local minimum below s_i; otherwise
-        // we fall back to the global minimum with the absolute-minimum strategy
-        // weight pa (a(s_i, τ) = pa).
-        let (tau, a_weight) = if let Some(tau) = selected_tau {
-            (tau, 1.0)
-        } else {
-            (global_min_tau, cfg.pa_absolute_min)
-        };
-        let prior = cfg.threshold_priors[idx];
-        let weight = a_weight * prior;
-        if let Some(entry) = candidate_map.iter_mut().find(|(t, _)| *t == tau) {
-            entry.1 += weight;
-        } else {
-            candidate_map.push((tau, weight));
-        }
+        resolutions.push(Resolution::new(cfg.frame_size, max_tau));
+
+        Ok(Self {
+            candidate_map: Vec::with_capacity(cfg.thresholds.len()),
+            min_tau,
+            resolutions,
+            cfg,
+        })
     }
 
-    let mut candidates = Vec::new();
-    for (tau, prob) in candidate_map {
-        let refined_tau = parabolic_interpolation(&cmnd, tau).max(1.0);
-        let frequency = cfg.sample_rate_hz as f32 / refined_tau;
-        candidates.push(Candidate {
-            frequency_hz: frequency,
-            probability: prob,
-        });
+    /// An empty frame with room for every candidate `process` can produce,
+    /// plus the octave moves of `OctaveCorrector`.
+    pub fn empty_frame(&self) -> Stage1CandidateFrame {
+        Stage1CandidateFrame {
+            candidates: Vec::with_capacity(3 * self.cfg.thresholds.len()),
+        }
     }
 
-    Stage1CandidateFrame { candidates }
+    /// Replace the candidates in `out` with those of `frame`.
+    pub fn process(&mut self, frame: &[f32], out: &mut Stage1CandidateFrame) {
+        let min_tau = self.min_tau;
+        let cfg = &self.cfg;
+        for resolution in self.resolutions.iter_mut() {
+            resolution.analyse(frame);
+        }
+        let full_index = self.resolutions.len() - 1;
+        let full = &self.resolutions[full_index];
+        let global_min_tau = (min_tau..=full.max_tau)
+            .min_by(|&a, &b| full.cmnd[a].total_cmp(&full.cmnd[b]))
+            .unwrap_or(min_tau);
+
+        self.candidate_map.clear();
+        for (idx, threshold) in cfg.thresholds.iter().enumerate() {
+            let selected = self
+                .resolutions
+                .iter()
+                .enumerate()
+                .find_map(|(res, resolution)| {
+                    resolution.first_dip(min_tau, *threshold).map(|tau| (res, tau))
+                });
+            // pYIN Stage 1: Eq. (4) and (5) from the paper.
+            // Y(x_t, s_i) returns the smallest local minimum below s_i; otherwise
+            // we fall back to the global minimum with the absolute-minimum strategy
+            // weight pa (a(s_i, τ) = pa).
+            let ((res, tau), a_weight) = if let Some(selected) = selected {
+                (selected, 1.0)
+            } else {
+                ((full_index, global_min_tau), cfg.pa_absolute_min)
+            };
+            let prior = cfg.threshold_priors[idx];
+            let weight = a_weight * prior;
+            if let Some(entry) = self
+                .candidate_map
+                .iter_mut()
+                .find(|(r, t, _)| *r == res && *t == tau)
+            {
+                entry.2 += weight;
+            } else {
+                self.candidate_map.push((res, tau, weight));
+            }
+        }
+
+        out.candidates.clear();
+        for &(res, tau, prob) in self.candidate_map.iter() {
+            let refined_tau = parabolic_interpolation(&self.resolutions[res].cmnd, tau).max(1.0);
+            let frequency = cfg.sample_rate_hz as f32 / refined_tau;
+            out.candidates.push(Candidate {
+                frequency_hz: frequency,
+                probability: prob,
+            });
+        }
+    }
 }
 
 /// Compute discrete beta prior weights over thresholds.
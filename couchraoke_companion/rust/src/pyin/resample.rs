@@ -0,0 +1,282 @@
+use super::snapshot::{StateReader, StateWriter};
+use super::PyinError;
+
+/// Streaming sample-rate converter using a Hann-windowed sinc kernel.
+///
+/// Output sample `k` sits at input position `k * input_rate / output_rate`,
+/// computed exactly in integer arithmetic so long streams do not drift. When
+/// downsampling, the kernel cutoff follows the output Nyquist frequency.
+///
+/// The fractional positions repeat every `output_rate / gcd(input_rate,
+/// output_rate)` outputs, so for the usual rate pairs the kernel is tabulated
+/// once per phase and each output is a plain dot product.
+pub struct Resampler {
+    input_rate: u64,
+    output_rate: u64,
+    half_width: usize,
+    cutoff: f64,
+    /// Distinct fractional positions: the output rate over the rates' gcd.
+    phases: u64,
+    /// `2 * half_width` kernel taps per phase, phase after phase; `None` when
+    /// there are too many phases to tabulate and taps are computed per output.
+    table: Option<Vec<f32>>,
+    /// Input samples starting at absolute index `buffer_start`.
+    buffer: Vec<f32>,
+    buffer_start: u64,
+    next_output: u64,
+}
+
+/// Zero crossings of the sinc kernel on either side of the centre, at the
+/// kernel's own cutoff.
+const ZERO_CROSSINGS: f64 = 16.0;
+/// Fraction of the Nyquist frequency kept, leaving room for the transition band.
+const PASSBAND: f64 = 0.95;
+/// Most phases tabulated; every common pair of audio rates needs far fewer.
+const MAX_TABLE_PHASES: u64 = 4096;
+
+impl Resampler {
+    pub fn new(input_rate_hz: u32, output_rate_hz: u32) -> Self {
+        let input_rate = input_rate_hz.max(1) as u64;
+        let output_rate = output_rate_hz.max(1) as u64;
+        let cutoff = PASSBAND * (output_rate as f64 / input_rate as f64).min(1.0);
+        let half_width = (ZERO_CROSSINGS / cutoff).ceil() as usize;
+        let phases = output_rate / gcd(input_rate, output_rate);
+        let mut resampler = Self {
+            input_rate,
+            output_rate,
+            half_width,
+            cutoff,
+            phases,
+            table: None,
+            buffer: Vec::new(),
+            buffer_start: 0,
+            next_output: 0,
+        };
+        if input_rate != output_rate && phases <= MAX_TABLE_PHASES {
+            let table = (0..phases)
+                .flat_map(|phase| {
+                    let frac = phase as f64 / phases as f64;
+                    let resampler = &resampler;
+                    (0..2 * half_width).map(move |tap| resampler.tap(tap, frac) as f32)
+                })
+                .collect();
+            resampler.table = Some(table);
+        }
+        resampler
+    }
+
+    pub fn is_passthrough(&self) -> bool {
+        self.input_rate == self.output_rate
+    }
+
+    /// Resample `input`, appending every output sample that is fully determined
+    /// by the input seen so far to `output`.
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        if self.is_passthrough() {
+            output.extend_from_slice(input);
+            return;
+        }
+        self.buffer.extend_from_slice(input);
+        self.drain(output);
+    }
+
+    /// Pad the stream with silence so the outputs covering the last input
+    /// samples are produced, then reset for a new stream.
+    pub fn flush(&mut self, output: &mut Vec<f32>) {
+        if !self.is_passthrough() {
+            let input_end = self.buffer_start + self.buffer.len() as u64;
+            let output_end = (input_end * self.output_rate).div_ceil(self.input_rate);
+            self.buffer
+                .extend(std::iter::repeat_n(0.0, self.half_width + 1));
+            while self.next_output < output_end {
+                let value = self.output_at(self.next_output);
+                output.push(value);
+                self.next_output += 1;
+            }
+        }
+        self.reset();
+    }
+
+    pub(crate) fn save_state(&self, out: &mut StateWriter) {
+        out.f32s(self.buffer.iter());
+        out.u64(self.buffer_start);
+        out.u64(self.next_output);
+    }
+
+    pub(crate) fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.f32s_into(&mut self.buffer)?;
+        self.buffer_start = input.u64()?;
+        self.next_output = input.u64()?;
+        Ok(())
+    }
+
+    /// Discard buffered input and start a new stream.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.buffer_start = 0;
+        self.next_output = 0;
+    }
+
+    fn drain(&mut self, output: &mut Vec<f32>) {
+        let buffer_end = self.buffer_start + self.buffer.len() as u64;
+        loop {
+            let (base, _) = self.position(self.next_output);
+            if base + self.half_width as u64 >= buffer_end {
+                break;
+            }
+            let value = self.output_at(self.next_output);
+            output.push(value);
+            self.next_output += 1;
+        }
+
+        // Keep only the history the next output still needs.
+        let (base, _) = self.position(self.next_output);
+        let keep_from = (base + 1).saturating_sub(self.half_width as u64);
+        if keep_from > self.buffer_start {
+            let drop = ((keep_from - self.buffer_start) as usize).min(self.buffer.len());
+            self.buffer.drain(..drop);
+            self.buffer_start += drop as u64;
+        }
+    }
+
+    /// Integer input position of output sample `k` and the phase of its
+    /// fractional part, in units of `1 / phases`.
+    fn position(&self, k: u64) -> (u64, u64) {
+        let num = k * self.input_rate;
+        (
+            num / self.output_rate,
+            (num % self.output_rate) * self.phases / self.output_rate,
+        )
+    }
+
+    fn output_at(&self, k: u64) -> f32 {
+        let (base, phase) = self.position(k);
+        let taps = 2 * self.half_width;
+        let first = base as i64 + 1 - self.half_width as i64;
+        // Inputs before the start of the stream (or already discarded) are
+        // silence.
+        let skip = (self.buffer_start as i64 - first).clamp(0, taps as i64) as usize;
+        let start = (first + skip as i64 - self.buffer_start as i64) as usize;
+        let samples = self.buffer.get(start..).unwrap_or(&[]);
+        match &self.table {
+            Some(table) => {
+                let weights = &table[phase as usize * taps..][skip..taps];
+                weights.iter().zip(samples).map(|(w, s)| w * s).sum()
+            }
+            None => {
+                let frac = phase as f64 / self.phases as f64;
+                let acc: f64 = (skip..taps)
+                    .zip(samples)
+                    .map(|(tap, &s)| s as f64 * self.tap(tap, frac))
+                    .sum();
+                acc as f32
+            }
+        }
+    }
+
+    /// Weight of kernel tap `tap` (input `base + 1 - half_width + tap`) for an
+    /// output at fractional position `frac`.
+    fn tap(&self, tap: usize, frac: f64) -> f64 {
+        let offset = tap as f64 + 1.0 - self.half_width as f64;
+        self.kernel(offset - frac)
+    }
+
+    fn kernel(&self, distance: f64) -> f64 {
+        let half_width = self.half_width as f64;
+        if distance.abs() >= half_width {
+            return 0.0;
+        }
+        let x = distance * self.cutoff;
+        let sinc = if x.abs() < 1e-9 {
+            1.0
+        } else {
+            (std::f64::consts::PI * x).sin() / (std::f64::consts::PI * x)
+        };
+        let window = 0.5 * (1.0 + (std::f64::consts::PI * distance / half_width).cos());
+        self.cutoff * sinc * window
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn chunked_resampling_matches_one_shot_and_keeps_length() {
+        let input = sine(440.0, 44_100, 44_100);
+        let mut one_shot = Vec::new();
+        let mut resampler = Resampler::new(44_100, 16_000);
+        resampler.process(&input, &mut one_shot);
+        resampler.flush(&mut one_shot);
+        assert_eq!(one_shot.len(), 16_000);
+
+        let mut chunked = Vec::new();
+        let mut resampler = Resampler::new(44_100, 16_000);
+        for chunk in input.chunks(777) {
+            resampler.process(chunk, &mut chunked);
+        }
+        resampler.flush(&mut chunked);
+        assert_eq!(chunked, one_shot);
+    }
+
+    #[test]
+    fn tabulated_kernel_matches_direct_evaluation() {
+        let input = sine(440.0, 44_100, 4_410);
+        let resample = |tabulated: bool| {
+            let mut resampler = Resampler::new(44_100, 48_000);
+            assert_eq!(resampler.phases, 160);
+            if !tabulated {
+                resampler.table = None;
+            }
+            let mut output = Vec::new();
+            resampler.process(&input, &mut output);
+            resampler.flush(&mut output);
+            output
+        };
+        let (tabulated, direct) = (resample(true), resample(false));
+        assert_eq!(tabulated.len(), direct.len());
+        assert!(tabulated.iter().zip(&direct).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+
+    #[test]
+    fn preserves_in_band_sine() {
+        let input = sine(440.0, 48_000, 48_000);
+        let mut output = Vec::new();
+        let mut resampler = Resampler::new(48_000, 22_050);
+        resampler.process(&input, &mut output);
+        let expected = sine(440.0, 22_050, output.len());
+        // Skip the start-up transient.
+        let max_err = output
+            .iter()
+            .zip(expected.iter())
+            .skip(200)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(max_err < 0.02, "max error {}", max_err);
+    }
+
+    #[test]
+    fn attenuates_content_above_output_nyquist() {
+        let input = sine(15_000.0, 48_000, 48_000);
+        let mut output = Vec::new();
+        let mut resampler = Resampler::new(48_000, 16_000);
+        resampler.process(&input, &mut output);
+        let rms = (output.iter().skip(200).map(|s| s * s).sum::<f32>()
+            / (output.len() - 200) as f32)
+            .sqrt();
+        assert!(rms < 0.01, "rms {}", rms);
+    }
+}
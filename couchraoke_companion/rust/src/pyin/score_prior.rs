@@ -0,0 +1,239 @@
+//! Score-informed prior over the pitch states.
+//!
+//! During a song the target melody is known. While a target note is active,
+//! the voiced observation of each HMM bin is reweighted by its distance to the
+//! target pitch and its octaves. Bins far from the target keep a floor weight,
+//! so a singer who is off the note is still tracked where they actually are.
+//!
+//! Only the observations change; the HMM's initial and transition
+//! distributions stay as they are. The reweighting moves the frame's voiced
+//! candidate mass between bins without changing its total, so it shifts which
+//! candidate the decoder follows but neither voicing nor bins without
+//! candidates, which stay at the emission floor.
+//!
+//! Target notes are in song time, the clock of the song's audio file (see
+//! `Song::target_notes`), and `ScorePriorConfig::song_start_sec` places song
+//! time 0 on the stream clock.
+
+use super::hmm::{HmmParams, ObservationFrame};
+use super::midi::fractional_midi_from_hz;
+use super::PyinError;
+
+/// One note of the expected melody, in song time: seconds from the start of
+/// the song's audio, not of the stream.
+#[derive(Debug, Clone, Copy)]
+pub struct TargetNote {
+    pub start_sec: f64,
+    pub end_sec: f64,
+    /// Expected pitch as (fractional) MIDI note relative to
+    /// `PyinConfig::reference_a4_hz`.
+    pub midi: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScorePriorConfig {
+    /// Stream time (the clock of `FrameEstimate::time_sec`) at which song time 0
+    /// is heard, i.e. when playback of the song's audio started relative to
+    /// the first sample pushed.
+    pub song_start_sec: f64,
+    /// How far the prior may pull, in [0, 1). Bins away from the target and its
+    /// octaves keep `1 - strength` of their weight.
+    pub strength: f32,
+    /// Width of the boost around the target, in cents.
+    pub sigma_cents: f32,
+    /// Boost of the octaves above and below the target relative to the target
+    /// itself, in [0, 1].
+    pub octave_weight: f32,
+}
+
+impl Default for ScorePriorConfig {
+    fn default() -> Self {
+        Self {
+            song_start_sec: 0.0,
+            strength: 0.6,
+            sigma_cents: 150.0,
+            octave_weight: 0.5,
+        }
+    }
+}
+
+pub(crate) struct ScorePrior {
+    notes: Vec<TargetNote>,
+    cfg: ScorePriorConfig,
+    /// Fractional MIDI pitch of each HMM bin.
+    bin_midi: Vec<f32>,
+    /// Per-bin weights of the most recently used note, `cached_note`.
+    weights: Vec<f32>,
+    cached_note: Option<usize>,
+}
+
+impl ScorePrior {
+    pub fn new(
+        mut notes: Vec<TargetNote>,
+        cfg: ScorePriorConfig,
+        params: &HmmParams,
+        reference_a4_hz: f32,
+    ) -> Result<Self, PyinError> {
+        let invalid = |msg: &str| Err(PyinError::InvalidConfig(msg.to_string()));
+        if !(cfg.strength >= 0.0 && cfg.strength < 1.0) {
+            return invalid("score prior strength must be in [0, 1)");
+        }
+        if !(cfg.sigma_cents.is_finite() && cfg.sigma_cents > 0.0) {
+            return invalid("score prior sigma_cents must be > 0");
+        }
+        if !(cfg.octave_weight >= 0.0 && cfg.octave_weight <= 1.0) {
+            return invalid("score prior octave_weight must be in [0, 1]");
+        }
+        if !cfg.song_start_sec.is_finite() {
+            return invalid("score prior song_start_sec must be finite");
+        }
+        if notes
+            .iter()
+            .any(|n| !(n.midi.is_finite() && n.start_sec.is_finite() && n.end_sec > n.start_sec))
+        {
+            return invalid("target notes need a finite pitch and end_sec > start_sec");
+        }
+        notes.sort_by(|a, b| a.start_sec.total_cmp(&b.start_sec));
+
+        let bin_midi = params
+            .bin_freqs
+            .iter()
+            .map(|&f| fractional_midi_from_hz(f, reference_a4_hz))
+            .collect();
+        Ok(Self {
+            notes,
+            cfg,
+            weights: Vec::with_capacity(params.num_bins()),
+            bin_midi,
+            cached_note: None,
+        })
+    }
+
+    pub fn set_song_start(&mut self, song_start_sec: f64) {
+        self.cfg.song_start_sec = song_start_sec;
+    }
+
+    /// Reweight the voiced observation of the frame centred on stream time
+    /// `time_sec`. The total voiced mass is kept, so voicing is unaffected.
+    pub fn apply(&mut self, time_sec: f64, obs: &mut ObservationFrame) {
+        let Some(note) = self.active_note(time_sec - self.cfg.song_start_sec) else {
+            return;
+        };
+        let weights = self.weights(note);
+
+        let before: f32 = obs.p_star.iter().sum();
+        let mut after = 0.0;
+        for (p, w) in obs.p_star.iter_mut().zip(weights) {
+            *p *= w;
+            after += *p;
+        }
+        if after > 0.0 {
+            let scale = before / after;
+            obs.p_star.iter_mut().for_each(|p| *p *= scale);
+        }
+    }
+
+    fn active_note(&self, song_sec: f64) -> Option<usize> {
+        let next = self.notes.partition_point(|n| n.start_sec <= song_sec);
+        let index = next.checked_sub(1)?;
+        (song_sec < self.notes[index].end_sec).then_some(index)
+    }
+
+    fn weights(&mut self, note: usize) -> &[f32] {
+        if self.cached_note != Some(note) {
+            let target = self.notes[note].midi;
+            let cfg = &self.cfg;
+            let sigma = cfg.sigma_cents / 100.0;
+            let boost = |semitones: f32| (-0.5 * (semitones / sigma).powi(2)).exp();
+            self.weights.clear();
+            self.weights.extend(self.bin_midi.iter().map(|&midi| {
+                let distance = midi - target;
+                let octave = boost(distance - 12.0).max(boost(distance + 12.0));
+                let affinity = boost(distance).max(cfg.octave_weight * octave);
+                (1.0 - cfg.strength) + cfg.strength * affinity
+            }));
+            self.cached_note = Some(note);
+        }
+        &self.weights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pyin::PyinConfig;
+
+    fn prior(notes: Vec<TargetNote>) -> (ScorePrior, HmmParams) {
+        let cfg = PyinConfig::default();
+        let params = HmmParams::new(&cfg).unwrap();
+        let prior = ScorePrior::new(
+            notes,
+            ScorePriorConfig::default(),
+            &params,
+            cfg.reference_a4_hz,
+        )
+        .unwrap();
+        (prior, params)
+    }
+
+    fn flat_obs(params: &HmmParams) -> ObservationFrame {
+        let num_bins = params.num_bins();
+        ObservationFrame {
+            p_star: vec![0.5 / num_bins as f32; num_bins],
+            sum_p: 0.5,
+        }
+    }
+
+    #[test]
+    fn boosts_target_then_octaves_and_keeps_voiced_mass() {
+        let (mut prior, params) = prior(vec![TargetNote {
+            start_sec: 1.0,
+            end_sec: 2.0,
+            midi: 60.0,
+        }]);
+        let mut obs = flat_obs(&params);
+        prior.apply(1.5, &mut obs);
+
+        let at = |hz: f32| obs.p_star[params.freq_to_bin(hz).unwrap()];
+        let (target, octave_up, tritone) = (at(261.63), at(523.25), at(370.0));
+        assert!(target > octave_up && octave_up > tritone);
+        assert!((tritone / target - 0.4).abs() < 0.01);
+        assert!((obs.p_star.iter().sum::<f32>() - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn song_start_places_notes_on_the_stream_clock() {
+        let cfg = PyinConfig::default();
+        let params = HmmParams::new(&cfg).unwrap();
+        let note = TargetNote {
+            start_sec: 1.0,
+            end_sec: 2.0,
+            midi: 60.0,
+        };
+        let prior_cfg = ScorePriorConfig {
+            song_start_sec: 10.0,
+            ..ScorePriorConfig::default()
+        };
+        let mut prior =
+            ScorePrior::new(vec![note], prior_cfg, &params, cfg.reference_a4_hz).unwrap();
+        let mut obs = flat_obs(&params);
+        prior.apply(1.5, &mut obs);
+        assert_eq!(obs.p_star, flat_obs(&params).p_star);
+        prior.apply(11.5, &mut obs);
+        assert_ne!(obs.p_star, flat_obs(&params).p_star);
+    }
+
+    #[test]
+    fn leaves_frames_outside_notes_untouched() {
+        let (mut prior, params) = prior(vec![TargetNote {
+            start_sec: 1.0,
+            end_sec: 2.0,
+            midi: 60.0,
+        }]);
+        for time in [0.5, 2.0, 3.0] {
+            let mut obs = flat_obs(&params);
+            prior.apply(time, &mut obs);
+            assert_eq!(obs.p_star, flat_obs(&params).p_star);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Versioned binary snapshots of streaming state.
+//!
+//! A snapshot holds what an engine has learned from its input (buffered
+//! samples, filter and HMM state, stream positions), not its configuration: it
+//! is restored into an engine built from the same configuration, with the same
+//! bleed reference and target notes set. Restoring then continues the stream
+//! exactly where the snapshot was taken.
+//!
+//! Layout: the magic bytes, a format version and a tag naming the engine, then
+//! the engine's fields in a fixed order, all little-endian. Sequences are
+//! prefixed with their length.
+
+use std::collections::VecDeque;
+
+use super::PyinError;
+
+const MAGIC: [u8; 4] = *b"PYSS";
+/// Bumped whenever the layout of any engine changes; older snapshots are
+/// rejected rather than misread.
+pub(crate) const FORMAT_VERSION: u16 = 4;
+
+/// Engine a snapshot was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SnapshotKind {
+    Pyin = 1,
+    Framewise = 2,
+    AudioAnalyzer = 3,
+}
+
+pub(crate) fn invalid<T>(msg: &str) -> Result<T, PyinError> {
+    Err(PyinError::InvalidConfig(format!("invalid snapshot: {}", msg)))
+}
+
+pub(crate) struct StateWriter {
+    bytes: Vec<u8>,
+}
+
+impl StateWriter {
+    pub fn new(kind: SnapshotKind) -> Self {
+        let mut writer = Self { bytes: Vec::new() };
+        writer.bytes.extend_from_slice(&MAGIC);
+        writer.bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        writer.u8(kind as u8);
+        writer
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn i64(&mut self, value: i64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn usize(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    pub fn f32(&mut self, value: f32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn bytes(&mut self, values: &[u8]) {
+        self.usize(values.len());
+        self.bytes.extend_from_slice(values);
+    }
+
+    pub fn f32s<'a>(&mut self, values: impl ExactSizeIterator<Item = &'a f32>) {
+        self.usize(values.len());
+        values.for_each(|&v| self.f32(v));
+    }
+
+    pub fn u16s(&mut self, values: &[u16]) {
+        self.usize(values.len());
+        for v in values {
+            self.bytes.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+
+    /// Write whether `value` is present and, if so, the value itself.
+    pub fn option<T>(&mut self, value: Option<&T>, write: impl FnOnce(&mut Self, &T)) {
+        self.bool(value.is_some());
+        if let Some(value) = value {
+            write(self, value);
+        }
+    }
+}
+
+pub(crate) struct StateReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> StateReader<'a> {
+    /// Check the header of `blob` and start reading after it.
+    pub fn new(blob: &'a [u8], kind: SnapshotKind) -> Result<Self, PyinError> {
+        let mut reader = Self { bytes: blob };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return invalid("not a pitch engine snapshot");
+        }
+        let version = u16::from_le_bytes(reader.array()?);
+        if version != FORMAT_VERSION {
+            return invalid(&format!(
+                "format version {} is not supported (expected {})",
+                version, FORMAT_VERSION
+            ));
+        }
+        if reader.u8()? != kind as u8 {
+            return invalid(&format!("not a {:?} snapshot", kind));
+        }
+        Ok(reader)
+    }
+
+    /// Fail unless the whole snapshot has been read.
+    pub fn finish(self) -> Result<(), PyinError> {
+        if !self.bytes.is_empty() {
+            return invalid("trailing bytes");
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PyinError> {
+        if self.bytes.len() < len {
+            return invalid("truncated");
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], PyinError> {
+        Ok(self.take(N)?.try_into().expect("took N bytes"))
+    }
+
+    pub fn u8(&mut self) -> Result<u8, PyinError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool, PyinError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => invalid("bad flag"),
+        }
+    }
+
+    pub fn u64(&mut self) -> Result<u64, PyinError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    pub fn i64(&mut self) -> Result<i64, PyinError> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    pub fn usize(&mut self) -> Result<usize, PyinError> {
+        usize::try_from(self.u64()?).or_else(|_| invalid("length out of range"))
+    }
+
+    pub fn f32(&mut self) -> Result<f32, PyinError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    pub fn f64(&mut self) -> Result<f64, PyinError> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    /// Read a length written for elements of `size` bytes, checking that the
+    /// elements are there before anything is allocated for them.
+    fn len(&mut self, size: usize) -> Result<usize, PyinError> {
+        let len = self.usize()?;
+        if len.checked_mul(size).is_none_or(|bytes| bytes > self.bytes.len()) {
+            return invalid("truncated");
+        }
+        Ok(len)
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8], PyinError> {
+        let len = self.len(1)?;
+        self.take(len)
+    }
+
+    /// Read a length and check it against the one the engine expects.
+    pub fn expect_len(&mut self, what: &str, expected: usize, size: usize) -> Result<(), PyinError> {
+        let len = self.len(size)?;
+        if len != expected {
+            return invalid(&format!("{} has {} entries, expected {}", what, len, expected));
+        }
+        Ok(())
+    }
+
+    /// Read a value and check it against the engine's configuration.
+    pub fn expect_usize(&mut self, what: &str, expected: usize) -> Result<(), PyinError> {
+        let value = self.usize()?;
+        if value != expected {
+            return invalid(&format!(
+                "taken with {} = {}, this engine has {}",
+                what, value, expected
+            ));
+        }
+        Ok(())
+    }
+
+    /// Replace the contents of `values` with a sequence written by
+    /// `StateWriter::f32s`.
+    pub fn f32s_into(&mut self, values: &mut Vec<f32>) -> Result<(), PyinError> {
+        let len = self.len(4)?;
+        values.clear();
+        for _ in 0..len {
+            values.push(self.f32()?);
+        }
+        Ok(())
+    }
+
+    pub fn f32s_into_deque(&mut self, values: &mut VecDeque<f32>) -> Result<(), PyinError> {
+        let len = self.len(4)?;
+        values.clear();
+        for _ in 0..len {
+            values.push_back(self.f32()?);
+        }
+        Ok(())
+    }
+
+    /// Fill `values` from a sequence of exactly its length.
+    pub fn f32s_exact(&mut self, what: &str, values: &mut [f32]) -> Result<(), PyinError> {
+        self.expect_len(what, values.len(), 4)?;
+        for value in values.iter_mut() {
+            *value = self.f32()?;
+        }
+        Ok(())
+    }
+
+    pub fn u16s_exact(&mut self, what: &str, values: &mut [u16]) -> Result<(), PyinError> {
+        self.expect_len(what, values.len(), 2)?;
+        for value in values.iter_mut() {
+            *value = u16::from_le_bytes(self.array()?);
+        }
+        Ok(())
+    }
+
+    pub fn option<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, PyinError>,
+    ) -> Result<Option<T>, PyinError> {
+        if self.bool()? {
+            read(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_round_trip_and_headers_are_checked() {
+        let mut writer = StateWriter::new(SnapshotKind::Framewise);
+        writer.u64(u64::MAX);
+        writer.i64(-3);
+        writer.f32s([1.5_f32, f32::NEG_INFINITY].iter());
+        writer.option(Some(&0.25_f64), |w, v| w.f64(*v));
+        writer.bytes(&[7, 8]);
+        let blob = writer.finish();
+
+        let mut reader = StateReader::new(&blob, SnapshotKind::Framewise).unwrap();
+        assert_eq!(reader.u64().unwrap(), u64::MAX);
+        assert_eq!(reader.i64().unwrap(), -3);
+        let mut values = Vec::new();
+        reader.f32s_into(&mut values).unwrap();
+        assert_eq!(values, vec![1.5, f32::NEG_INFINITY]);
+        assert_eq!(reader.option(|r| r.f64()).unwrap(), Some(0.25));
+        assert_eq!(reader.bytes().unwrap(), &[7, 8]);
+        reader.finish().unwrap();
+
+        assert!(StateReader::new(&blob, SnapshotKind::Pyin).is_err());
+        let mut old = blob.clone();
+        old[4] = 0;
+        assert!(StateReader::new(&old, SnapshotKind::Framewise).is_err());
+        let mut reader = StateReader::new(&blob[..blob.len() - 1], SnapshotKind::Framewise).unwrap();
+        reader.u64().unwrap();
+        reader.i64().unwrap();
+        assert!(reader.f32s_into(&mut values).is_ok());
+        reader.option(|r| r.f64()).unwrap();
+        assert!(reader.bytes().is_err());
+    }
+}
@@ -0,0 +1,347 @@
+//! Voice activity detection ahead of the pitch HMM.
+//!
+//! YIN alone reports chatter and background music as voiced. Each frame is
+//! scored on its level above the room noise floor and on its spectral flatness
+//! (sung vowels are tonal, room noise is not), and the voiced observation mass
+//! of non-speech frames is scaled down before decoding. The noise floor is the
+//! minimum frame level over a sliding window (minimum statistics), so it follows
+//! the room without being pulled up by phrases shorter than the window. Until
+//! the window has filled, it is the minimum level seen so far as soon as a
+//! short stretch of noise-like (spectrally flat) frames has been heard, so a
+//! noisy room calibrates within a fraction of a second while a singer who
+//! starts straight away is not taken for the room.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+
+use super::snapshot::{StateReader, StateWriter};
+use super::PyinError;
+
+#[derive(Debug, Clone)]
+pub struct VadConfig {
+    /// Level above the noise floor at which a frame is as likely speech as not.
+    pub snr_threshold_db: f32,
+    /// Spectral flatness (0 = pure tone, 1 = white noise) at which a frame is
+    /// as likely speech as not.
+    pub flatness_threshold: f32,
+    /// Window over which the noise floor is the minimum frame level. Sounds
+    /// held for longer than this become part of the floor.
+    pub noise_window_sec: f32,
+    /// Noise-like frames, in seconds, after which the noise floor is the
+    /// minimum level so far, before a full window has been seen.
+    pub calibration_sec: f32,
+    /// Upper bound on the noise floor until calibrated.
+    pub initial_noise_floor_dbfs: f32,
+    /// Weight kept on the voiced observation of a certain non-speech frame;
+    /// 0 gates such frames completely.
+    pub min_weight: f32,
+    /// Upper edge of the band used for the flatness measure.
+    pub max_flatness_hz: f32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            snr_threshold_db: 9.0,
+            flatness_threshold: 0.35,
+            noise_window_sec: 5.0,
+            calibration_sec: 0.5,
+            initial_noise_floor_dbfs: -70.0,
+            min_weight: 0.05,
+            max_flatness_hz: 4_000.0,
+        }
+    }
+}
+
+/// Per-frame VAD output.
+#[derive(Debug, Clone, Copy)]
+pub struct VadFrame {
+    /// `speech_prob >= 0.5`.
+    pub speech: bool,
+    pub speech_prob: f32,
+    /// Estimated room noise level.
+    pub noise_floor_dbfs: f32,
+    pub spectral_flatness: f32,
+}
+
+impl VadFrame {
+    /// Factor applied to the frame's voiced observation mass.
+    pub(crate) fn observation_weight(&self, cfg: &VadConfig) -> f32 {
+        cfg.min_weight + (1.0 - cfg.min_weight) * self.speech_prob
+    }
+}
+
+/// Logistic slope of the level term, in dB.
+const SNR_SLOPE_DB: f32 = 3.0;
+/// Logistic slope of the flatness term.
+const FLATNESS_SLOPE: f32 = 0.05;
+/// Added to every power bin so digital silence has a defined flatness.
+const POWER_EPSILON: f32 = 1e-12;
+/// Sub-windows of the noise window; the floor moves in steps of one block.
+const NOISE_BLOCKS: usize = 8;
+
+pub(crate) struct VoiceActivityDetector {
+    cfg: VadConfig,
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    /// FFT bins used for the flatness measure.
+    band: std::ops::Range<usize>,
+    /// Minimum level of each completed block, newest last.
+    block_minima: VecDeque<f32>,
+    block_min: f32,
+    block_frames: usize,
+    frames_per_block: usize,
+    calibration_frames: usize,
+    /// Noise-like frames seen, up to `calibration_frames`.
+    noise_frames: usize,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(
+        cfg: VadConfig,
+        frame_size: usize,
+        hop_size: usize,
+        sample_rate_hz: u32,
+    ) -> Result<Self, PyinError> {
+        let invalid = |msg: &str| Err(PyinError::InvalidConfig(msg.to_string()));
+        if !(cfg.flatness_threshold > 0.0 && cfg.flatness_threshold < 1.0) {
+            return invalid("vad flatness_threshold must be in (0, 1)");
+        }
+        if !(cfg.min_weight >= 0.0 && cfg.min_weight <= 1.0) {
+            return invalid("vad min_weight must be in [0, 1]");
+        }
+        if !(cfg.noise_window_sec.is_finite() && cfg.noise_window_sec > 0.0) {
+            return invalid("vad noise_window_sec must be > 0");
+        }
+        if !(cfg.snr_threshold_db.is_finite() && cfg.initial_noise_floor_dbfs.is_finite()) {
+            return invalid("vad levels must be finite");
+        }
+        if !(cfg.calibration_sec.is_finite() && cfg.calibration_sec >= 0.0) {
+            return invalid("vad calibration_sec must be >= 0");
+        }
+
+        let window_frames = cfg.noise_window_sec * sample_rate_hz as f32 / hop_size as f32;
+        let frames_per_block = ((window_frames / NOISE_BLOCKS as f32).round() as usize).max(1);
+        let calibration_frames =
+            (cfg.calibration_sec * sample_rate_hz as f32 / hop_size as f32).ceil() as usize;
+        let bin_hz = sample_rate_hz as f32 / frame_size as f32;
+        let top = ((cfg.max_flatness_hz / bin_hz) as usize).clamp(2, frame_size / 2 + 1);
+        let window = (0..frame_size)
+            .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / frame_size as f32).cos())
+            .collect();
+
+        let fft = FftPlanner::new().plan_fft_forward(frame_size);
+        Ok(Self {
+            scratch: vec![Complex { re: 0.0, im: 0.0 }; fft.get_inplace_scratch_len()],
+            fft,
+            window,
+            buffer: vec![Complex { re: 0.0, im: 0.0 }; frame_size],
+            // Skip DC.
+            band: 1..top,
+            block_minima: VecDeque::with_capacity(NOISE_BLOCKS),
+            block_min: f32::INFINITY,
+            block_frames: 0,
+            frames_per_block,
+            calibration_frames,
+            noise_frames: 0,
+            cfg,
+        })
+    }
+
+    pub fn config(&self) -> &VadConfig {
+        &self.cfg
+    }
+
+    pub fn reset(&mut self) {
+        self.block_minima.clear();
+        self.block_min = f32::INFINITY;
+        self.block_frames = 0;
+        self.noise_frames = 0;
+    }
+
+    pub fn save_state(&self, out: &mut StateWriter) {
+        out.f32s(self.block_minima.iter());
+        out.f32(self.block_min);
+        out.usize(self.block_frames);
+        out.usize(self.noise_frames);
+    }
+
+    pub fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.f32s_into_deque(&mut self.block_minima)?;
+        self.block_min = input.f32()?;
+        self.block_frames = input.usize()?;
+        self.noise_frames = input.usize()?.min(self.calibration_frames);
+        if self.block_minima.len() > NOISE_BLOCKS || self.block_frames >= self.frames_per_block {
+            return Err(PyinError::InvalidConfig(
+                "invalid snapshot: noise floor blocks do not fit the detector".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn process(&mut self, frame: &[f32], rms_dbfs: f32) -> VadFrame {
+        let flatness = self.spectral_flatness(frame);
+        if flatness >= self.cfg.flatness_threshold && self.noise_frames < self.calibration_frames {
+            self.noise_frames += 1;
+        }
+        self.track_level(rms_dbfs);
+        let noise_floor_dbfs = self.noise_floor_dbfs();
+
+        let snr_db = rms_dbfs - noise_floor_dbfs;
+        let loud = logistic((snr_db - self.cfg.snr_threshold_db) / SNR_SLOPE_DB);
+        let tonal = logistic((self.cfg.flatness_threshold - flatness) / FLATNESS_SLOPE);
+        let speech_prob = loud * tonal;
+
+        VadFrame {
+            speech: speech_prob >= 0.5,
+            speech_prob,
+            noise_floor_dbfs,
+            spectral_flatness: flatness,
+        }
+    }
+
+    fn track_level(&mut self, rms_dbfs: f32) {
+        self.block_min = self.block_min.min(rms_dbfs);
+        self.block_frames += 1;
+        if self.block_frames == self.frames_per_block {
+            if self.block_minima.len() == NOISE_BLOCKS {
+                self.block_minima.pop_front();
+            }
+            self.block_minima.push_back(self.block_min);
+            self.block_min = f32::INFINITY;
+            self.block_frames = 0;
+        }
+    }
+
+    fn noise_floor_dbfs(&self) -> f32 {
+        let floor = self
+            .block_minima
+            .iter()
+            .fold(self.block_min, |floor, &block| floor.min(block));
+        let calibrated = self.block_minima.len() == NOISE_BLOCKS
+            || self.noise_frames >= self.calibration_frames;
+        if !calibrated {
+            floor.min(self.cfg.initial_noise_floor_dbfs)
+        } else {
+            floor
+        }
+    }
+
+    /// Geometric over arithmetic mean of the windowed power spectrum.
+    fn spectral_flatness(&mut self, frame: &[f32]) -> f32 {
+        for ((slot, &sample), &w) in self.buffer.iter_mut().zip(frame).zip(&self.window) {
+            *slot = Complex {
+                re: sample * w,
+                im: 0.0,
+            };
+        }
+        self.fft.process_with_scratch(&mut self.buffer, &mut self.scratch);
+
+        let (log_sum, sum) = self.buffer[self.band.clone()]
+            .iter()
+            .map(|c| c.norm_sqr() + POWER_EPSILON)
+            .fold((0.0_f64, 0.0_f64), |(l, s), p| {
+                (l + (p as f64).ln(), s + p as f64)
+            });
+        let n = self.band.len() as f64;
+        ((log_sum / n).exp() / (sum / n)).clamp(0.0, 1.0) as f32
+    }
+}
+
+fn logistic(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const RATE: u32 = 16_000;
+    const FRAME: usize = 1024;
+    const HOP: usize = 256;
+
+    fn rms_dbfs(frame: &[f32]) -> f32 {
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        20.0 * rms.max(1e-6).log10()
+    }
+
+    fn run(vad: &mut VoiceActivityDetector, signal: &[f32]) -> Vec<VadFrame> {
+        signal
+            .windows(FRAME)
+            .step_by(HOP)
+            .map(|frame| vad.process(frame, rms_dbfs(frame)))
+            .collect()
+    }
+
+    #[test]
+    fn tone_over_noise_is_speech_and_noise_alone_is_not() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let noise: Vec<f32> = (0..RATE as usize * 6)
+            .map(|_| rng.gen_range(-0.05..0.05))
+            .collect();
+        // Two seconds of singing, shorter than the noise window.
+        let tone: Vec<f32> = noise[..RATE as usize * 2]
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                n + 0.4 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / RATE as f32).sin()
+            })
+            .collect();
+
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), FRAME, HOP, RATE).unwrap();
+        let noise_frames = run(&mut vad, &noise);
+        let tail = &noise_frames[noise_frames.len() / 2..];
+        assert!(tail.iter().all(|f| !f.speech));
+        // The floor has calibrated to the room: uniform noise at ±0.05 is ~-31 dBFS.
+        let floor = tail.last().unwrap().noise_floor_dbfs;
+        assert!((floor + 31.0).abs() < 3.0, "floor {}", floor);
+
+        let tone_frames = run(&mut vad, &tone);
+        assert!(tone_frames.iter().all(|f| f.speech));
+        assert!(tone_frames.iter().all(|f| f.spectral_flatness < 0.1));
+    }
+
+    #[test]
+    fn noise_floor_calibrates_before_the_window_fills() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let noise: Vec<f32> = (0..RATE as usize)
+            .map(|_| rng.gen_range(-0.05..0.05))
+            .collect();
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), FRAME, HOP, RATE).unwrap();
+        let frames = run(&mut vad, &noise);
+        // The first frames are held to the initial floor...
+        assert!(frames[..10].iter().all(|f| f.noise_floor_dbfs <= -70.0));
+        // ...and half a second of noise in, the floor is the room's, well
+        // before the five-second window has filled.
+        let calibrated = (RATE as usize / 2).div_ceil(HOP);
+        for frame in &frames[calibrated..] {
+            assert!((frame.noise_floor_dbfs + 31.0).abs() < 3.0, "{:?}", frame);
+            assert!(!frame.speech);
+        }
+
+        // A note sung from the first frame is not noise-like, so it does not
+        // calibrate the floor to itself.
+        let tone: Vec<f32> = (0..RATE as usize)
+            .map(|i| 0.4 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / RATE as f32).sin())
+            .collect();
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), FRAME, HOP, RATE).unwrap();
+        assert!(run(&mut vad, &tone).iter().all(|f| f.speech));
+    }
+
+    #[test]
+    fn loud_noise_burst_is_not_speech() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let burst: Vec<f32> = (0..RATE as usize / 2)
+            .map(|_| rng.gen_range(-0.5..0.5))
+            .collect();
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), FRAME, HOP, RATE).unwrap();
+        let frames = run(&mut vad, &burst);
+        assert!(frames.iter().all(|f| f.spectral_flatness > 0.5));
+        assert!(frames.iter().all(|f| !f.speech));
+    }
+}
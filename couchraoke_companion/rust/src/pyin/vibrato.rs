@@ -0,0 +1,477 @@
+//! Vibrato detection on top of the frame-level pYIN output.
+//!
+//! Around each voiced frame, the pitch contour of the surrounding voiced run is
+//! detrended and its autocorrelation searched for a peak in the vibrato rate
+//! range. The peak lag gives the rate, its height the regularity, and the RMS
+//! of the detrended contour the extent. Averaging the contour over one vibrato
+//! period gives the centre pitch, which is what a listener hears as the note.
+
+use std::collections::VecDeque;
+
+use super::{FrameEstimate, PyinConfig, PyinError};
+
+#[derive(Debug, Clone)]
+pub struct VibratoConfig {
+    /// Length of the contour analysed around each frame.
+    pub window_sec: f64,
+    pub min_rate_hz: f32,
+    pub max_rate_hz: f32,
+    /// Smallest extent reported as vibrato; slower or shallower wobble is
+    /// treated as intonation drift.
+    pub min_extent_cents: f32,
+    /// Largest extent reported as vibrato; wider swings are trills or note
+    /// changes.
+    pub max_extent_cents: f32,
+    /// Smallest normalised autocorrelation at the vibrato period, in [0, 1].
+    pub min_regularity: f32,
+}
+
+impl Default for VibratoConfig {
+    fn default() -> Self {
+        Self {
+            window_sec: 0.8,
+            min_rate_hz: 4.0,
+            max_rate_hz: 8.0,
+            min_extent_cents: 15.0,
+            max_extent_cents: 200.0,
+            min_regularity: 0.5,
+        }
+    }
+}
+
+/// Vibrato measurement of one frame.
+#[derive(Debug, Clone, Copy)]
+pub struct VibratoFrame {
+    pub frame_index: u64,
+    pub time_sec: f64,
+    /// `true` if the contour around this frame passes every `VibratoConfig`
+    /// test; the measurements below are only meaningful then.
+    pub vibrato: bool,
+    pub rate_hz: f32,
+    /// Half the peak-to-peak pitch swing, in cents.
+    pub extent_cents: f32,
+    /// Normalised autocorrelation of the contour at the vibrato period; 1 for
+    /// a perfectly periodic vibrato.
+    pub regularity: f32,
+    /// Fractional MIDI pitch with the vibrato averaged out; the frame's own
+    /// pitch when there is no vibrato, `None` when unvoiced.
+    pub centre_midi: Option<f32>,
+}
+
+/// Vibrato of a note or any other run of frames.
+#[derive(Debug, Clone, Copy)]
+pub struct VibratoSummary {
+    pub first_frame: u64,
+    pub last_frame: u64,
+    pub start_sec: f64,
+    pub end_sec: f64,
+    /// Means over the frames with vibrato.
+    pub rate_hz: f32,
+    pub extent_cents: f32,
+    pub regularity: f32,
+    /// Fraction of the voiced frames with vibrato.
+    pub coverage: f32,
+}
+
+struct Measurement {
+    rate_hz: f32,
+    extent_cents: f32,
+    regularity: f32,
+    period_frames: f32,
+}
+
+/// Frame data kept until the frames after it have been seen.
+struct PendingVibratoFrame {
+    frame_index: u64,
+    time_sec: f64,
+    cents: Option<f32>,
+}
+
+/// Streaming vibrato analyzer fed with `FrameEstimate`s in frame order. Each
+/// frame is reported half a window after it was pushed. Frames need two
+/// periods at `min_rate_hz` of voiced contour within half a window on either
+/// side, so the first and last few frames of a note are never vibrato.
+pub struct VibratoAnalyzer {
+    cfg: VibratoConfig,
+    frame_rate_hz: f32,
+    half_window: usize,
+    /// Shortest voiced run that holds two periods at `min_rate_hz`.
+    min_run: usize,
+    min_lag: usize,
+    max_lag: usize,
+    history: VecDeque<PendingVibratoFrame>,
+    /// Position in `history` of the next frame to report.
+    next: usize,
+}
+
+impl VibratoAnalyzer {
+    pub fn new(pyin_cfg: &PyinConfig, cfg: VibratoConfig) -> Result<Self, PyinError> {
+        let invalid = |msg: &str| Err(PyinError::InvalidConfig(msg.to_string()));
+        if pyin_cfg.sample_rate_hz == 0 || pyin_cfg.hop_size == 0 {
+            return invalid("sample_rate_hz and hop_size must be > 0");
+        }
+        let frame_rate_hz = pyin_cfg.sample_rate_hz as f32 / pyin_cfg.hop_size as f32;
+        if !(cfg.min_rate_hz > 0.0 && cfg.max_rate_hz > cfg.min_rate_hz) {
+            return invalid("vibrato rates need 0 < min_rate_hz < max_rate_hz");
+        }
+        if cfg.max_rate_hz * 4.0 > frame_rate_hz {
+            return invalid("vibrato max_rate_hz needs at least four frames per period");
+        }
+        if !(cfg.window_sec.is_finite() && cfg.window_sec * cfg.min_rate_hz as f64 >= 2.0) {
+            return invalid("vibrato window_sec must hold two periods at min_rate_hz");
+        }
+        if !(cfg.min_extent_cents >= 0.0 && cfg.max_extent_cents > cfg.min_extent_cents) {
+            return invalid("vibrato extents need 0 <= min_extent_cents < max_extent_cents");
+        }
+        if !(cfg.min_regularity >= 0.0 && cfg.min_regularity <= 1.0) {
+            return invalid("vibrato min_regularity must be in [0, 1]");
+        }
+
+        let window_frames = (cfg.window_sec as f32 * frame_rate_hz).round() as usize;
+        let max_lag = (frame_rate_hz / cfg.min_rate_hz).ceil() as usize;
+        Ok(Self {
+            half_window: window_frames / 2,
+            min_run: 2 * max_lag,
+            min_lag: ((frame_rate_hz / cfg.max_rate_hz).floor() as usize).max(2),
+            max_lag,
+            frame_rate_hz,
+            history: VecDeque::with_capacity(window_frames + 1),
+            next: 0,
+            cfg,
+        })
+    }
+
+    /// Feed the next frame and return the frames whose look-ahead is complete.
+    pub fn push(&mut self, frame: &FrameEstimate) -> Vec<VibratoFrame> {
+        self.history.push_back(PendingVibratoFrame {
+            frame_index: frame.frame_index,
+            time_sec: frame.time_sec,
+            cents: frame
+                .midi_fractional
+                .filter(|_| frame.voiced)
+                .map(|midi| midi * 100.0),
+        });
+
+        let mut out = Vec::new();
+        while self.history.len() - self.next > self.half_window {
+            out.push(self.analyze(self.next));
+            self.next += 1;
+        }
+        while self.next > self.half_window {
+            self.history.pop_front();
+            self.next -= 1;
+        }
+        out
+    }
+
+    /// Report every remaining frame. The analyzer starts over afterwards.
+    pub fn finish(&mut self) -> Vec<VibratoFrame> {
+        let out = (self.next..self.history.len())
+            .map(|index| self.analyze(index))
+            .collect();
+        self.history.clear();
+        self.next = 0;
+        out
+    }
+
+    fn analyze(&self, index: usize) -> VibratoFrame {
+        let frame = &self.history[index];
+        let mut result = VibratoFrame {
+            frame_index: frame.frame_index,
+            time_sec: frame.time_sec,
+            vibrato: false,
+            rate_hz: 0.0,
+            extent_cents: 0.0,
+            regularity: 0.0,
+            centre_midi: frame.cents.map(|c| c / 100.0),
+        };
+        if frame.cents.is_none() {
+            return result;
+        }
+
+        // Voiced run around the frame, clipped to the window.
+        let voiced = |i: usize| self.history[i].cents.is_some();
+        let mut first = index;
+        while first > 0 && index - first < self.half_window && voiced(first - 1) {
+            first -= 1;
+        }
+        let mut last = index;
+        while last + 1 < self.history.len() && last - index < self.half_window && voiced(last + 1) {
+            last += 1;
+        }
+        if last + 1 - first < self.min_run {
+            return result;
+        }
+        let contour: Vec<f32> = (first..=last)
+            .map(|i| self.history[i].cents.expect("run is voiced"))
+            .collect();
+        let Some(m) = self.measure(&contour) else {
+            return result;
+        };
+
+        result.rate_hz = m.rate_hz;
+        result.extent_cents = m.extent_cents;
+        result.regularity = m.regularity;
+        result.vibrato = m.regularity >= self.cfg.min_regularity
+            && m.extent_cents >= self.cfg.min_extent_cents
+            && m.extent_cents <= self.cfg.max_extent_cents;
+        if result.vibrato {
+            // Mean over one period centred on the frame, shifted to stay
+            // inside the run.
+            let period = (m.period_frames.round() as usize).clamp(1, contour.len());
+            let centre = index - first;
+            let start = centre
+                .saturating_sub(period / 2)
+                .min(contour.len() - period);
+            let mean = contour[start..start + period].iter().sum::<f32>() / period as f32;
+            result.centre_midi = Some(mean / 100.0);
+        }
+        result
+    }
+
+    fn measure(&self, contour: &[f32]) -> Option<Measurement> {
+        let detrended = detrend(contour);
+        let n = detrended.len();
+        let energy = detrended.iter().map(|x| x * x).sum::<f32>() / n as f32;
+        if energy <= 0.0 {
+            return None;
+        }
+        // Unbiased, normalised autocorrelation.
+        let autocorr = |lag: usize| {
+            let sum: f32 = detrended[..n - lag]
+                .iter()
+                .zip(&detrended[lag..])
+                .map(|(a, b)| a * b)
+                .sum();
+            sum / (n - lag) as f32 / energy
+        };
+        let max_lag = self.max_lag.min(n / 2);
+        if max_lag <= self.min_lag {
+            return None;
+        }
+        let values: Vec<f32> = (self.min_lag - 1..=max_lag + 1).map(autocorr).collect();
+        // Highest local maximum inside the rate range.
+        let (peak, height) = (1..values.len() - 1)
+            .filter(|&i| values[i] > values[i - 1] && values[i] >= values[i + 1])
+            .map(|i| (i, values[i]))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        let (left, right) = (values[peak - 1], values[peak + 1]);
+        let curvature = left - 2.0 * height + right;
+        let shift = if curvature < 0.0 {
+            0.5 * (left - right) / curvature
+        } else {
+            0.0
+        };
+        let period_frames = (self.min_lag - 1 + peak) as f32 + shift;
+        let rate_hz = self.frame_rate_hz / period_frames;
+        if rate_hz < self.cfg.min_rate_hz || rate_hz > self.cfg.max_rate_hz {
+            return None;
+        }
+        Some(Measurement {
+            rate_hz,
+            // A sinusoid of amplitude A has RMS A / sqrt(2).
+            extent_cents: (2.0 * energy).sqrt(),
+            regularity: height.clamp(0.0, 1.0),
+            period_frames,
+        })
+    }
+}
+
+/// Remove the least-squares line, so glides do not read as modulation.
+fn detrend(contour: &[f32]) -> Vec<f32> {
+    let n = contour.len() as f32;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = contour.iter().sum::<f32>() / n;
+    let (mut sxy, mut sxx) = (0.0, 0.0);
+    for (i, y) in contour.iter().enumerate() {
+        let dx = i as f32 - mean_x;
+        sxy += dx * (y - mean_y);
+        sxx += dx * dx;
+    }
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    contour
+        .iter()
+        .enumerate()
+        .map(|(i, y)| y - mean_y - slope * (i as f32 - mean_x))
+        .collect()
+}
+
+/// Measure a complete frame sequence in one pass.
+pub fn analyze_vibrato(
+    frames: &[FrameEstimate],
+    pyin_cfg: &PyinConfig,
+    cfg: VibratoConfig,
+) -> Result<Vec<VibratoFrame>, PyinError> {
+    let mut analyzer = VibratoAnalyzer::new(pyin_cfg, cfg)?;
+    let mut out = Vec::with_capacity(frames.len());
+    for frame in frames {
+        out.extend(analyzer.push(frame));
+    }
+    out.extend(analyzer.finish());
+    Ok(out)
+}
+
+/// Summarise the vibrato of `frames`, e.g. the frames of one `NoteEvent`.
+/// `None` if none of them has vibrato.
+pub fn summarize_vibrato(frames: &[VibratoFrame]) -> Option<VibratoSummary> {
+    let (first, last) = (frames.first()?, frames.last()?);
+    let with_vibrato: Vec<&VibratoFrame> = frames.iter().filter(|f| f.vibrato).collect();
+    if with_vibrato.is_empty() {
+        return None;
+    }
+    let voiced = frames.iter().filter(|f| f.centre_midi.is_some()).count();
+    let count = with_vibrato.len() as f32;
+    let mean = |value: fn(&VibratoFrame) -> f32| {
+        with_vibrato.iter().map(|f| value(f)).sum::<f32>() / count
+    };
+    Some(VibratoSummary {
+        first_frame: first.frame_index,
+        last_frame: last.frame_index,
+        start_sec: first.time_sec,
+        end_sec: last.time_sec,
+        rate_hz: mean(|f| f.rate_hz),
+        extent_cents: mean(|f| f.extent_cents),
+        regularity: mean(|f| f.regularity),
+        coverage: count / voiced as f32,
+    })
+}
+
+/// Maximal runs of consecutive vibrato frames, each summarised.
+pub fn vibrato_segments(frames: &[VibratoFrame]) -> Vec<VibratoSummary> {
+    frames
+        .chunk_by(|a, b| a.vibrato == b.vibrato)
+        .filter(|run| run[0].vibrato)
+        .filter_map(summarize_vibrato)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn frame(index: u64, midi: Option<f32>) -> FrameEstimate {
+        let cfg = PyinConfig::default();
+        FrameEstimate {
+            frame_index: index,
+            time_sec: index as f64 * cfg.hop_size as f64 / cfg.sample_rate_hz as f64,
+            f0_hz: midi.map(|m| cfg.reference_a4_hz * 2.0_f32.powf((m - 69.0) / 12.0)),
+            voiced: midi.is_some(),
+            confidence: 0.9,
+            voicing_probability: if midi.is_some() { 0.9 } else { 0.1 },
+            midi_note: None,
+            midi_fractional: midi,
+            cents_offset: None,
+            candidates: None,
+            posterior: None,
+            is_final: true,
+            rms_dbfs: -20.0,
+            peak_dbfs: -17.0,
+            loudness_lufs: -20.0,
+            vad: None,
+        }
+    }
+
+    /// `seconds` of frames at MIDI `base` modulated by a sine of `rate_hz`
+    /// and `extent_cents`.
+    fn contour(base: f32, rate_hz: f32, extent_cents: f32, seconds: f32) -> Vec<FrameEstimate> {
+        let cfg = PyinConfig::default();
+        let frame_rate = cfg.sample_rate_hz as f32 / cfg.hop_size as f32;
+        (0..(seconds * frame_rate) as usize)
+            .map(|i| {
+                let t = i as f32 / frame_rate;
+                let cents = extent_cents * (2.0 * std::f32::consts::PI * rate_hz * t).sin();
+                frame(i as u64, Some(base + cents / 100.0))
+            })
+            .collect()
+    }
+
+    fn analyze(frames: &[FrameEstimate]) -> Vec<VibratoFrame> {
+        analyze_vibrato(frames, &PyinConfig::default(), VibratoConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn measures_rate_extent_and_centre() {
+        let frames = contour(60.0, 5.5, 50.0, 2.0);
+        let out = analyze(&frames);
+        assert_eq!(out.len(), frames.len());
+
+        let summary = summarize_vibrato(&out).unwrap();
+        assert!(
+            (summary.rate_hz - 5.5).abs() < 0.2,
+            "rate {}",
+            summary.rate_hz
+        );
+        assert!(
+            (summary.extent_cents - 50.0).abs() < 5.0,
+            "extent {}",
+            summary.extent_cents
+        );
+        assert!(summary.regularity > 0.8);
+        assert!(summary.coverage > 0.85);
+
+        for f in out.iter().filter(|f| f.vibrato) {
+            let centre = f.centre_midi.unwrap();
+            assert!((centre - 60.0).abs() < 0.1, "centre {}", centre);
+        }
+    }
+
+    #[test]
+    fn steady_and_jittery_pitch_are_not_vibrato() {
+        let steady: Vec<FrameEstimate> = (0..300).map(|i| frame(i, Some(57.0))).collect();
+        assert!(analyze(&steady).iter().all(|f| !f.vibrato));
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let jitter: Vec<FrameEstimate> = (0..300)
+            .map(|i| frame(i, Some(57.0 + rng.gen_range(-0.3..0.3))))
+            .collect();
+        assert!(analyze(&jitter).iter().all(|f| !f.vibrato));
+
+        // A glide with vibrato on top is still vibrato.
+        let glide: Vec<FrameEstimate> = contour(57.0, 6.0, 40.0, 2.0)
+            .into_iter()
+            .enumerate()
+            .map(|(i, f)| {
+                frame(
+                    i as u64,
+                    Some(f.midi_fractional.unwrap() + i as f32 * 0.005),
+                )
+            })
+            .collect();
+        let out = analyze(&glide);
+        assert!(out.iter().filter(|f| f.vibrato).count() > out.len() / 2);
+    }
+
+    #[test]
+    fn segments_split_at_unvoiced_gaps_and_streaming_matches_offline() {
+        let mut frames = contour(64.0, 5.0, 60.0, 1.5);
+        frames.extend((0..40).map(|_| frame(0, None)));
+        frames.extend(contour(67.0, 6.5, 30.0, 1.5));
+        for (i, f) in frames.iter_mut().enumerate() {
+            *f = frame(i as u64, f.midi_fractional);
+        }
+
+        let offline = analyze(&frames);
+        let segments = vibrato_segments(&offline);
+        assert_eq!(segments.len(), 2);
+        assert!((segments[0].rate_hz - 5.0).abs() < 0.3);
+        assert!((segments[1].rate_hz - 6.5).abs() < 0.3);
+        assert!((segments[1].extent_cents - 30.0).abs() < 5.0);
+
+        let mut analyzer =
+            VibratoAnalyzer::new(&PyinConfig::default(), VibratoConfig::default()).unwrap();
+        let mut streamed = Vec::new();
+        for f in &frames {
+            streamed.extend(analyzer.push(f));
+        }
+        assert!(streamed.len() < frames.len());
+        streamed.extend(analyzer.finish());
+        assert_eq!(streamed.len(), offline.len());
+        for (a, b) in streamed.iter().zip(&offline) {
+            assert_eq!(a.frame_index, b.frame_index);
+            assert_eq!(a.vibrato, b.vibrato);
+            assert_eq!(a.centre_midi, b.centre_midi);
+        }
+    }
+}
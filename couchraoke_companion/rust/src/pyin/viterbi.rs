@@ -1,4 +1,8 @@
-use super::hmm::{delta_index, HmmParams, ObservationFrame, NUM_BINS};
+use std::collections::VecDeque;
+
+use super::hmm::{HmmParams, ObservationFrame};
+use super::snapshot::{invalid, StateReader, StateWriter};
+use super::PyinError;
 
 #[derive(Debug, Clone, Copy)]
 pub struct HmmState {
@@ -6,115 +10,660 @@ pub struct HmmState {
     pub voiced: bool,
 }
 
+/// Posterior of a decoded frame's state under the HMM.
+#[derive(Debug, Clone)]
+pub struct StatePosterior {
+    /// Total posterior mass of the voiced states.
+    pub voiced: f32,
+    /// Posterior mass of the decoded voicing, restricted for voiced frames to
+    /// bins within `CONFIDENCE_WINDOW_CENTS` of the decoded bin.
+    pub decoded: f32,
+    /// Posterior of each voiced bin, when enabled with `with_bin_posteriors`.
+    pub voiced_bins: Option<Vec<f32>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedFrame {
+    pub state: HmmState,
+    pub posterior: StatePosterior,
+}
+
+/// Half-width of the pitch window counted towards `StatePosterior::decoded`.
+const CONFIDENCE_WINDOW_CENTS: f32 = 50.0;
+/// Emission floor, matching the one `safe_log` applies to the Viterbi scores.
+const EMISSION_FLOOR: f32 = 1e-12;
+
+/// Observation likelihoods of one frame for the forward-backward passes: one
+/// value shared by the unvoiced states, and the voiced bins that have
+/// candidate mass, in increasing order. Every other voiced bin has
+/// `EMISSION_FLOOR`.
+///
+/// Unlike the Viterbi scores, the unvoiced likelihood is spread over the bins,
+/// as in the reference pYIN implementation: an unvoiced frame says nothing
+/// about pitch, and the forward pass, which sums over every unvoiced pitch path
+/// instead of following one, would otherwise rate unvoiced far too likely.
+struct Emission {
+    unvoiced: f32,
+    voiced: Vec<(usize, f32)>,
+}
+
+impl Emission {
+    fn with_capacity(num_bins: usize) -> Self {
+        Self {
+            unvoiced: 0.0,
+            voiced: Vec::with_capacity(num_bins),
+        }
+    }
+
+    /// Overwrite with the likelihoods of `obs`.
+    fn set(&mut self, obs: &ObservationFrame) {
+        let num_bins = obs.p_star.len() as f32;
+        self.unvoiced = (0.5 * (1.0 - obs.sum_p) / num_bins).max(EMISSION_FLOOR);
+        self.voiced.clear();
+        self.voiced.extend(
+            obs.p_star
+                .iter()
+                .enumerate()
+                .filter(|(_, &p)| p > 0.0)
+                .map(|(bin, &p)| (bin, (0.5 * p).max(EMISSION_FLOOR))),
+        );
+    }
+
+    /// Multiply a per-state vector by the likelihoods.
+    fn apply(&self, probs: &mut [f32]) {
+        let num_bins = probs.len() / 2;
+        let (unvoiced, voiced) = probs.split_at_mut(num_bins);
+        unvoiced.iter_mut().for_each(|p| *p *= self.unvoiced);
+        let mut candidates = self.voiced.iter().peekable();
+        for (bin, p) in voiced.iter_mut().enumerate() {
+            let weight = match candidates.next_if(|(b, _)| *b == bin) {
+                Some(&(_, weight)) => weight,
+                None => EMISSION_FLOOR,
+            };
+            *p *= weight;
+        }
+    }
+}
+
+/// Fixed-lag Viterbi decoder.
+///
+/// Frame `t` is committed once frame `t + lag` has been observed, by backtracking
+/// from the currently best state. Only the backpointers of uncommitted frames are
+/// kept, so per-push cost and memory are bounded by `lag` rather than by the
+/// length of the session.
+///
+/// Alongside the decoding, a forward pass over the same HMM gives each
+/// committed frame its filtered posterior, the state distribution given the
+/// observations up to that frame. The forward pass runs `lag` frames behind,
+/// as frames are committed, so it keeps one distribution rather than one per
+/// pending frame. `finish` runs forward-backward over the pending frames,
+/// giving posteriors given every observation.
+///
+/// Buffers released by committed frames are kept for the frames that follow,
+/// so once `lag` frames have been pushed, `push` no longer allocates (apart
+/// from the bin posteriors, when enabled).
 pub struct ViterbiTracker {
     params: HmmParams,
-    backpointers: Vec<Vec<usize>>,
+    lag: usize,
+    /// Backpointers for frames `committed + 1 .. frames`, oldest first, encoded
+    /// relative to the current bin (see `encode_backpointer`).
+    backpointers: VecDeque<Vec<u16>>,
     prev_scores: Vec<f32>,
+    /// Scores of the frame being pushed, swapped with `prev_scores`.
+    curr_scores: Vec<f32>,
+    /// Backpointer and emission buffers of committed frames, for reuse.
+    spare_backpointers: Vec<Vec<u16>>,
+    spare_emissions: Vec<Emission>,
     frames: usize,
+    committed: usize,
+    /// `exp` of `HmmParams::log_pitch_transition`, and of the voicing terms.
+    pitch_transition: Vec<f32>,
+    /// `pitch_transition` indexed by the negated pitch change.
+    reverse_pitch_transition: Vec<f32>,
+    voicing_stay: f32,
+    voicing_switch: f32,
+    /// Filtered posterior of the last committed frame; `None` before the first.
+    forward: Option<Vec<f32>>,
+    /// Buffer the next filtered posterior is computed into.
+    next_forward: Vec<f32>,
+    /// Emissions of frames `committed .. frames`, oldest first.
+    emissions: VecDeque<Emission>,
+    bin_posteriors: bool,
 }
 
 impl ViterbiTracker {
-    pub fn new(params: HmmParams) -> Self {
-        let num_states = NUM_BINS * 2;
+    pub fn new(params: HmmParams, lag: usize) -> Self {
+        let num_states = params.num_bins() * 2;
+        let pitch_transition: Vec<f32> =
+            params.log_pitch_transition.iter().map(|l| l.exp()).collect();
         Self {
+            reverse_pitch_transition: pitch_transition.iter().rev().copied().collect(),
+            pitch_transition,
+            voicing_stay: params.log_voicing_stay.exp(),
+            voicing_switch: params.log_voicing_switch.exp(),
             params,
-            backpointers: Vec::new(),
+            lag,
+            backpointers: VecDeque::with_capacity(lag.saturating_add(1).min(1024)),
             prev_scores: vec![f32::NEG_INFINITY; num_states],
+            curr_scores: vec![f32::NEG_INFINITY; num_states],
+            spare_backpointers: Vec::new(),
+            spare_emissions: Vec::new(),
             frames: 0,
+            committed: 0,
+            forward: None,
+            next_forward: vec![0.0; num_states],
+            emissions: VecDeque::with_capacity(lag.saturating_add(1).min(1024)),
+            bin_posteriors: false,
         }
     }
 
-    pub fn push(&mut self, obs: &ObservationFrame) {
-        let num_states = NUM_BINS * 2;
-        let mut curr = vec![f32::NEG_INFINITY; num_states];
-        let mut back = vec![0; num_states];
+    /// Also report the posterior of every voiced bin
+    /// (`StatePosterior::voiced_bins`).
+    pub fn with_bin_posteriors(mut self, enabled: bool) -> Self {
+        self.bin_posteriors = enabled;
+        self
+    }
+
+    /// Push one observation and return the frame committed by it, if any.
+    pub fn push(&mut self, obs: &ObservationFrame) -> Option<DecodedFrame> {
+        let num_bins = self.params.num_bins();
+        let num_states = num_bins * 2;
+        let reach = self.params.reach_bins();
 
         if self.frames == 0 {
-            let log_init = (1.0 / NUM_BINS as f32).ln();
+            let log_init = (1.0 / num_bins as f32).ln();
             let unvoiced_log = safe_log(0.5 * (1.0 - obs.sum_p));
-            for bin in 0..NUM_BINS {
-                let idx = state_index(bin, false);
+            for bin in 0..num_bins {
+                let idx = state_index(num_bins, bin, false);
                 self.prev_scores[idx] = log_init + unvoiced_log;
             }
         } else {
+            let mut curr = std::mem::take(&mut self.curr_scores);
+            curr.clear();
+            curr.resize(num_states, f32::NEG_INFINITY);
+            let mut back = self.spare_backpointers.pop().unwrap_or_default();
+            back.clear();
+            back.resize(num_states, 0);
             let unvoiced_log = safe_log(0.5 * (1.0 - obs.sum_p));
-            for next_bin in 0..NUM_BINS {
+            for next_bin in 0..num_bins {
                 let voiced_log = safe_log(0.5 * obs.p_star[next_bin]);
 
                 for &next_voiced in &[false, true] {
                     let obs_log = if next_voiced { voiced_log } else { unvoiced_log };
                     let mut best_prev = f32::NEG_INFINITY;
                     let mut best_state = 0;
-                    let min_prev = next_bin.saturating_sub(25);
-                    let max_prev = (next_bin + 25).min(NUM_BINS - 1);
-                    for prev_bin in min_prev..=max_prev {
-                        let delta = next_bin as i32 - prev_bin as i32;
-                        let pitch_log = self.params.log_pitch_transition[delta_index(delta).unwrap()];
-                        for &prev_voiced in &[false, true] {
-                            // Eq. (7): voicing transition. Eq. (8): triangular pitch transition.
-                            let voicing_log = if prev_voiced == next_voiced {
-                                self.params.log_voicing_stay
-                            } else {
-                                self.params.log_voicing_switch
-                            };
-                            let prev_idx = state_index(prev_bin, prev_voiced);
-                            let score = self.prev_scores[prev_idx] + pitch_log + voicing_log;
-                            if score > best_prev {
-                                best_prev = score;
-                                best_state = prev_idx;
+                    for jumps in self.params.jump_ranges() {
+                        // `delta = next_bin - prev_bin`, so the largest jump is the lowest
+                        // previous bin.
+                        let min_prev = (next_bin as i32 - jumps.end()).max(0);
+                        let max_prev = (next_bin as i32 - jumps.start()).min(num_bins as i32 - 1);
+                        for prev_bin in min_prev..=max_prev {
+                            let delta = next_bin as i32 - prev_bin;
+                            let pitch_log = self.params.log_pitch_transition
+                                [self.params.delta_index(delta).unwrap()];
+                            for &prev_voiced in &[false, true] {
+                                // Eq. (7): voicing transition. Eq. (8): triangular pitch
+                                // transition.
+                                let voicing_log = if prev_voiced == next_voiced {
+                                    self.params.log_voicing_stay
+                                } else {
+                                    self.params.log_voicing_switch
+                                };
+                                let prev_idx =
+                                    state_index(num_bins, prev_bin as usize, prev_voiced);
+                                let score = self.prev_scores[prev_idx] + pitch_log + voicing_log;
+                                if score > best_prev {
+                                    best_prev = score;
+                                    best_state = prev_idx;
+                                }
                             }
                         }
                     }
-                    let idx = state_index(next_bin, next_voiced);
+                    let idx = state_index(num_bins, next_bin, next_voiced);
                     curr[idx] = best_prev + obs_log;
-                    back[idx] = best_state;
+                    back[idx] = encode_backpointer(num_bins, reach, next_bin, best_state);
                 }
             }
-            self.prev_scores = curr;
+            self.curr_scores = std::mem::replace(&mut self.prev_scores, curr);
+            self.backpointers.push_back(back);
         }
 
-        self.backpointers.push(back);
+        // Scores only matter relative to each other; renormalising keeps them in
+        // f32 range over arbitrarily long sessions.
+        let max_score = self.prev_scores[best_final_state(&self.prev_scores)];
+        if max_score.is_finite() {
+            for score in self.prev_scores.iter_mut() {
+                *score -= max_score;
+            }
+        }
+
+        let mut emission = self
+            .spare_emissions
+            .pop()
+            .unwrap_or_else(|| Emission::with_capacity(num_bins));
+        emission.set(obs);
+        self.emissions.push_back(emission);
         self.frames += 1;
+        if self.frames - self.committed > self.lag {
+            let state = self.backtrack_to_committed();
+            self.committed += 1;
+            if let Some(back) = self.backpointers.pop_front() {
+                self.spare_backpointers.push(back);
+            }
+            let emission = self.emissions.pop_front().expect("one emission per pending frame");
+            let mut forward = std::mem::take(&mut self.next_forward);
+            forward.resize(num_states, 0.0);
+            self.forward_step(self.forward.as_deref(), &emission, &mut forward);
+            let posterior = self.summarize(&forward, state);
+            self.next_forward = self.forward.replace(forward).unwrap_or_default();
+            self.spare_emissions.push(emission);
+            Some(DecodedFrame {
+                state: state_from_index(self.params.num_bins(), state),
+                posterior,
+            })
+        } else {
+            None
+        }
     }
 
-    pub fn best_path(&self) -> Vec<HmmState> {
-        if self.frames == 0 {
+    /// Best states for the frames that have not been committed yet, oldest
+    /// first, with their filtered posteriors.
+    ///
+    /// These are provisional: a later observation may still change them.
+    pub fn provisional_path(&self) -> Vec<DecodedFrame> {
+        let num_bins = self.params.num_bins();
+        let mut forward = self.forward.clone();
+        self.best_path()
+            .into_iter()
+            .zip(self.emissions.iter())
+            .map(|(state, emission)| {
+                let next = self.forwarded(forward.as_deref(), emission);
+                let posterior = self.summarize(&next, state);
+                forward = Some(next);
+                DecodedFrame {
+                    state: state_from_index(num_bins, state),
+                    posterior,
+                }
+            })
+            .collect()
+    }
+
+    /// Commit every pending frame with an exact backtrace from the best final
+    /// state and return them, oldest first, with forward-backward posteriors.
+    pub fn finish(&mut self) -> Vec<DecodedFrame> {
+        self.commit_settled(0)
+    }
+
+    /// Commit every pending frame but the newest `lookahead` and return them,
+    /// oldest first: backtracked from the best current state, with
+    /// forward-backward posteriors over every pending frame.
+    ///
+    /// Called every `lookahead` frames on a tracker with an unbounded lag, this
+    /// decodes a long stream in overlapping blocks, each frame seeing at least
+    /// `lookahead` later ones, with memory bounded by the block length rather
+    /// than by the stream.
+    pub fn commit_settled(&mut self, lookahead: usize) -> Vec<DecodedFrame> {
+        let pending = self.frames - self.committed;
+        if pending <= lookahead {
             return Vec::new();
         }
-        let mut best_final = 0;
-        let mut best_score = f32::NEG_INFINITY;
-        for (idx, score) in self.prev_scores.iter().enumerate() {
-            if *score > best_score {
-                best_score = *score;
-                best_final = idx;
+        let count = pending - lookahead;
+        let mut path = self.best_path();
+        path.truncate(count);
+        let posteriors = self.smoothed_posteriors(&path, pending);
+        self.committed += count;
+        let released = count.min(self.backpointers.len());
+        self.spare_backpointers.extend(self.backpointers.drain(..released));
+        self.spare_emissions.extend(self.emissions.drain(..count));
+        let num_bins = self.params.num_bins();
+        path.into_iter()
+            .zip(posteriors)
+            .map(|(state, posterior)| DecodedFrame {
+                state: state_from_index(num_bins, state),
+                posterior,
+            })
+            .collect()
+    }
+
+    /// Number of frames committed so far.
+    pub fn committed_frames(&self) -> usize {
+        self.committed
+    }
+
+    /// Number of frames pushed but not committed yet.
+    pub fn pending_frames(&self) -> usize {
+        self.frames - self.committed
+    }
+
+    pub fn params(&self) -> &HmmParams {
+        &self.params
+    }
+
+    /// Serialise the decoding state: scores, pending backpointers and
+    /// emissions, and the forward posterior.
+    pub(crate) fn save_state(&self, out: &mut StateWriter) {
+        out.usize(self.params.num_bins());
+        out.usize(self.lag);
+        out.usize(self.frames);
+        out.usize(self.committed);
+        out.f32s(self.prev_scores.iter());
+        out.usize(self.backpointers.len());
+        for back in &self.backpointers {
+            out.u16s(back);
+        }
+        out.option(self.forward.as_ref(), |out, forward| out.f32s(forward.iter()));
+        out.usize(self.emissions.len());
+        for emission in &self.emissions {
+            out.f32(emission.unvoiced);
+            out.usize(emission.voiced.len());
+            for &(bin, likelihood) in &emission.voiced {
+                out.usize(bin);
+                out.f32(likelihood);
+            }
+        }
+    }
+
+    /// Continue from `save_state` of a tracker with the same HMM and lag. On
+    /// error the state is unspecified until the next successful load.
+    pub(crate) fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        let num_bins = self.params.num_bins();
+        let num_states = num_bins * 2;
+        let reach = self.params.reach_bins();
+        input.expect_usize("HMM pitch bins", num_bins)?;
+        input.expect_usize("Viterbi lag", self.lag)?;
+        let frames = input.usize()?;
+        let committed = input.usize()?;
+        if committed > frames || frames - committed > self.lag {
+            return invalid("more pending frames than the lag");
+        }
+        let pending = frames - committed;
+        input.f32s_exact("Viterbi scores", &mut self.prev_scores)?;
+
+        self.spare_backpointers.extend(self.backpointers.drain(..));
+        let backpointers = input.usize()?;
+        if backpointers > pending {
+            return invalid("more backpointers than pending frames");
+        }
+        for _ in 0..backpointers {
+            let mut back = self.spare_backpointers.pop().unwrap_or_default();
+            back.resize(num_states, 0);
+            input.u16s_exact("backpointers", &mut back)?;
+            // Every code must lead to a previous bin inside the grid.
+            let in_grid = back.iter().enumerate().all(|(state, &code)| {
+                let offset = (code >> 1) as usize;
+                let next_bin = state % num_bins;
+                next_bin + offset >= reach && next_bin + offset - reach < num_bins
+            });
+            if !in_grid {
+                return invalid("backpointer outside the pitch grid");
             }
+            self.backpointers.push_back(back);
         }
 
-        let mut path = vec![best_final; self.frames];
-        for t in (1..self.frames).rev() {
-            path[t - 1] = self.backpointers[t][path[t]];
+        let forward = input.option(|input| {
+            let mut forward = vec![0.0; num_states];
+            input.f32s_exact("forward posterior", &mut forward)?;
+            Ok(forward)
+        })?;
+
+        self.spare_emissions.extend(self.emissions.drain(..));
+        input.expect_len("emissions", pending, 12)?;
+        for _ in 0..pending {
+            let mut emission = self
+                .spare_emissions
+                .pop()
+                .unwrap_or_else(|| Emission::with_capacity(num_bins));
+            emission.unvoiced = input.f32()?;
+            emission.voiced.clear();
+            let voiced = input.usize()?;
+            if voiced > num_bins {
+                return invalid("more voiced emissions than pitch bins");
+            }
+            for _ in 0..voiced {
+                let bin = input.usize()?;
+                let in_order = emission.voiced.last().is_none_or(|&(last, _)| last < bin);
+                if bin >= num_bins || !in_order {
+                    return invalid("voiced emissions out of order");
+                }
+                emission.voiced.push((bin, input.f32()?));
+            }
+            self.emissions.push_back(emission);
         }
 
-        path.into_iter().map(state_from_index).collect()
+        self.frames = frames;
+        self.committed = committed;
+        self.forward = forward;
+        Ok(())
     }
 
-    pub fn params(&self) -> &HmmParams {
-        &self.params
+    /// Best state indices of the pending frames, oldest first.
+    fn best_path(&self) -> Vec<usize> {
+        let pending = self.frames - self.committed;
+        if pending == 0 {
+            return Vec::new();
+        }
+        let mut path = vec![best_final_state(&self.prev_scores); pending];
+        for t in (1..pending).rev() {
+            path[t - 1] = self.follow(&self.backpointers[t - 1], path[t]);
+        }
+        path
+    }
+
+    fn backtrack_to_committed(&self) -> usize {
+        let mut state = best_final_state(&self.prev_scores);
+        for back in self.backpointers.iter().rev() {
+            state = self.follow(back, state);
+        }
+        state
+    }
+
+    /// Forward-backward posteriors, over the first `window` pending frames, of
+    /// the frames on `path`, summarised against it, and the forward pass
+    /// advanced to the last frame of `path`.
+    ///
+    /// Forward distributions are kept only at the start of blocks of about
+    /// `sqrt(window)` frames and recomputed block by block during the
+    /// backward pass, so an unbounded lag costs `O(sqrt(frames))` distributions
+    /// of memory instead of one per frame.
+    fn smoothed_posteriors(&mut self, path: &[usize], window: usize) -> Vec<StatePosterior> {
+        if path.is_empty() {
+            return Vec::new();
+        }
+        let block = ((window as f64).sqrt().ceil() as usize).max(1);
+        let mut checkpoints = Vec::with_capacity(path.len().div_ceil(block));
+        let mut forward = self.forward.take();
+        for (t, emission) in self.emissions.range(..path.len()).enumerate() {
+            if t % block == 0 {
+                checkpoints.push(forward.clone());
+            }
+            forward = Some(self.forwarded(forward.as_deref(), emission));
+        }
+        self.forward = forward;
+
+        // Frames after `path` only contribute their backward messages.
+        let mut backward = vec![1.0; self.prev_scores.len()];
+        for t in (path.len()..window).rev() {
+            backward = self.backward_step(&backward, &self.emissions[t]);
+        }
+        let mut posteriors = Vec::with_capacity(path.len());
+        let mut gamma = vec![0.0; backward.len()];
+        for (index, checkpoint) in checkpoints.into_iter().enumerate().rev() {
+            let start = index * block;
+            let end = (start + block).min(path.len());
+            let mut forwards: Vec<Vec<f32>> = Vec::with_capacity(end - start);
+            for emission in self.emissions.range(start..end) {
+                let prev = forwards.last().map(Vec::as_slice).or(checkpoint.as_deref());
+                let next = self.forwarded(prev, emission);
+                forwards.push(next);
+            }
+            for t in (start..end).rev() {
+                for ((g, a), b) in gamma.iter_mut().zip(&forwards[t - start]).zip(&backward) {
+                    *g = a * b;
+                }
+                normalize(&mut gamma);
+                posteriors.push(self.summarize(&gamma, path[t]));
+                backward = self.backward_step(&backward, &self.emissions[t]);
+            }
+        }
+        posteriors.reverse();
+        posteriors
+    }
+
+    /// `forward_step` into a new vector.
+    fn forwarded(&self, prev: Option<&[f32]>, emission: &Emission) -> Vec<f32> {
+        let mut next = vec![0.0; self.prev_scores.len()];
+        self.forward_step(prev, emission, &mut next);
+        next
     }
+
+    /// Filtered distribution of the next frame, written to `next`, from that
+    /// of the previous frame, or from the initial (unvoiced, uniform pitch)
+    /// distribution.
+    fn forward_step(&self, prev: Option<&[f32]>, emission: &Emission, next: &mut [f32]) {
+        let num_bins = self.params.num_bins();
+        let reach = self.params.reach_bins() as i32;
+        match prev {
+            None => {
+                next[..num_bins].fill(1.0);
+                next[num_bins..].fill(0.0);
+            }
+            Some(prev) => {
+                let (prev_unvoiced, prev_voiced) = prev.split_at(num_bins);
+                for next_bin in 0..num_bins {
+                    let (mut unvoiced, mut voiced) = (0.0, 0.0);
+                    for jumps in self.params.jump_ranges() {
+                        let min_prev = (next_bin as i32 - jumps.end()).max(0);
+                        let max_prev = (next_bin as i32 - jumps.start()).min(num_bins as i32 - 1);
+                        if min_prev > max_prev {
+                            continue;
+                        }
+                        let bins = min_prev as usize..=max_prev as usize;
+                        // Indexed by `prev_bin - next_bin + reach`, so it runs
+                        // forwards alongside the previous bins.
+                        let first = (min_prev - next_bin as i32 + reach) as usize;
+                        let pitch = &self.reverse_pitch_transition
+                            [first..first + bins.clone().count()];
+                        for ((&w, &u), &v) in pitch
+                            .iter()
+                            .zip(&prev_unvoiced[bins.clone()])
+                            .zip(&prev_voiced[bins])
+                        {
+                            unvoiced += w * u;
+                            voiced += w * v;
+                        }
+                    }
+                    next[next_bin] = self.voicing_stay * unvoiced + self.voicing_switch * voiced;
+                    next[num_bins + next_bin] =
+                        self.voicing_switch * unvoiced + self.voicing_stay * voiced;
+                }
+            }
+        }
+        emission.apply(next);
+        normalize(next);
+    }
+
+    /// Backward message of a frame from that of the following frame, whose
+    /// emission is `emission`.
+    fn backward_step(&self, next: &[f32], emission: &Emission) -> Vec<f32> {
+        let num_bins = self.params.num_bins();
+        let reach = self.params.reach_bins() as i32;
+        let mut weighted = next.to_vec();
+        emission.apply(&mut weighted);
+        let (next_unvoiced, next_voiced) = weighted.split_at(num_bins);
+        let mut prev = vec![0.0; num_bins * 2];
+        for prev_bin in 0..num_bins {
+            let (mut unvoiced, mut voiced) = (0.0, 0.0);
+            for jumps in self.params.jump_ranges() {
+                let min_next = (prev_bin as i32 + jumps.start()).max(0);
+                let max_next = (prev_bin as i32 + jumps.end()).min(num_bins as i32 - 1);
+                if min_next > max_next {
+                    continue;
+                }
+                let bins = min_next as usize..=max_next as usize;
+                let first = (min_next - prev_bin as i32 + reach) as usize;
+                let pitch = &self.pitch_transition[first..first + bins.clone().count()];
+                for ((&w, &u), &v) in pitch
+                    .iter()
+                    .zip(&next_unvoiced[bins.clone()])
+                    .zip(&next_voiced[bins])
+                {
+                    unvoiced += w * u;
+                    voiced += w * v;
+                }
+            }
+            prev[prev_bin] = self.voicing_stay * unvoiced + self.voicing_switch * voiced;
+            prev[num_bins + prev_bin] = self.voicing_switch * unvoiced + self.voicing_stay * voiced;
+        }
+        normalize(&mut prev);
+        prev
+    }
+
+    fn summarize(&self, posterior: &[f32], state: usize) -> StatePosterior {
+        let num_bins = self.params.num_bins();
+        let voiced_bins = &posterior[num_bins..];
+        let voiced: f32 = voiced_bins.iter().sum();
+        let decoded = match state_from_index(num_bins, state) {
+            HmmState { voiced: false, .. } => 1.0 - voiced,
+            HmmState { bin, .. } => {
+                let window =
+                    (CONFIDENCE_WINDOW_CENTS / self.params.bin_cents()).round() as usize;
+                voiced_bins[bin.saturating_sub(window)..(bin + window + 1).min(num_bins)]
+                    .iter()
+                    .sum()
+            }
+        };
+        StatePosterior {
+            voiced,
+            decoded,
+            voiced_bins: self.bin_posteriors.then(|| voiced_bins.to_vec()),
+        }
+    }
+
+    fn follow(&self, back: &[u16], state: usize) -> usize {
+        let num_bins = self.params.num_bins();
+        let next_bin = state_from_index(num_bins, state).bin;
+        decode_backpointer(num_bins, self.params.reach_bins(), next_bin, back[state])
+    }
+}
+
+fn best_final_state(scores: &[f32]) -> usize {
+    let mut best_final = 0;
+    let mut best_score = f32::NEG_INFINITY;
+    for (idx, score) in scores.iter().enumerate() {
+        if *score > best_score {
+            best_score = *score;
+            best_final = idx;
+        }
+    }
+    best_final
 }
 
-fn state_index(bin: usize, voiced: bool) -> usize {
+/// Backpointers only ever reach `reach` bins away, so they are stored as a
+/// small offset plus the voicing flag instead of a full state index.
+fn encode_backpointer(num_bins: usize, reach: usize, next_bin: usize, prev_state: usize) -> u16 {
+    let prev = state_from_index(num_bins, prev_state);
+    let offset = prev.bin + reach - next_bin;
+    ((offset << 1) | prev.voiced as usize) as u16
+}
+
+fn decode_backpointer(num_bins: usize, reach: usize, next_bin: usize, code: u16) -> usize {
+    let code = code as usize;
+    let prev_bin = next_bin + (code >> 1) - reach;
+    state_index(num_bins, prev_bin, code & 1 == 1)
+}
+
+fn state_index(num_bins: usize, bin: usize, voiced: bool) -> usize {
     if voiced {
-        NUM_BINS + bin
+        num_bins + bin
     } else {
         bin
     }
 }
 
-fn state_from_index(idx: usize) -> HmmState {
-    if idx >= NUM_BINS {
+fn state_from_index(num_bins: usize, idx: usize) -> HmmState {
+    if idx >= num_bins {
         HmmState {
-            bin: idx - NUM_BINS,
+            bin: idx - num_bins,
             voiced: true,
         }
     } else {
@@ -122,8 +671,205 @@ fn state_from_index(idx: usize) -> HmmState {
     }
 }
 
+/// Scale `probs` to sum to one; a vector that has underflowed becomes uniform.
+fn normalize(probs: &mut [f32]) {
+    let total: f32 = probs.iter().sum();
+    if total > 0.0 && total.is_finite() {
+        probs.iter_mut().for_each(|p| *p /= total);
+    } else {
+        probs.fill(1.0 / probs.len() as f32);
+    }
+}
+
 fn safe_log(prob: f32) -> f32 {
     // Numerical stability: avoid log(0) while preserving relative scoring.
     const FLOOR: f32 = 1e-12;
     prob.max(FLOOR).ln()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pyin::snapshot::SnapshotKind;
+    use crate::pyin::PyinConfig;
+
+    fn params() -> HmmParams {
+        HmmParams::new(&PyinConfig::default()).unwrap()
+    }
+
+    fn voiced_obs(bin: usize) -> ObservationFrame {
+        let mut p_star = vec![0.0; params().num_bins()];
+        p_star[bin] = 0.9;
+        ObservationFrame { p_star, sum_p: 0.9 }
+    }
+
+    #[test]
+    fn restored_tracker_continues_identically() {
+        let obs: Vec<ObservationFrame> = (0..60)
+            .map(|i| match i % 20 {
+                0..=3 => ObservationFrame {
+                    p_star: vec![0.0; params().num_bins()],
+                    sum_p: 0.0,
+                },
+                k => voiced_obs(250 + k * 3),
+            })
+            .collect();
+        let decode = |tracker: &mut ViterbiTracker, obs: &[ObservationFrame]| {
+            let mut frames: Vec<DecodedFrame> =
+                obs.iter().filter_map(|o| tracker.push(o)).collect();
+            frames.extend(tracker.provisional_path());
+            frames.extend(tracker.finish());
+            frames
+                .iter()
+                .map(|d| (d.state.bin, d.state.voiced, d.posterior.decoded.to_bits()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut original = ViterbiTracker::new(params(), 5);
+        obs[..33].iter().for_each(|o| {
+            original.push(o);
+        });
+        let mut out = StateWriter::new(SnapshotKind::Pyin);
+        original.save_state(&mut out);
+        let blob = out.finish();
+        let load = |tracker: &mut ViterbiTracker, blob: &[u8]| {
+            let mut input = StateReader::new(blob, SnapshotKind::Pyin)?;
+            tracker.load_state(&mut input)?;
+            input.finish()
+        };
+        let mut restored = ViterbiTracker::new(params(), 5);
+        load(&mut restored, &blob).unwrap();
+        assert_eq!(restored.committed_frames(), original.committed_frames());
+        assert_eq!(decode(&mut restored, &obs[33..]), decode(&mut original, &obs[33..]));
+
+        assert!(load(&mut ViterbiTracker::new(params(), 6), &blob).is_err());
+        assert!(load(&mut restored, &blob[..blob.len() - 3]).is_err());
+    }
+
+    #[test]
+    fn fixed_lag_commits_after_lag_and_bounds_history() {
+        let lag = 4;
+        let mut tracker = ViterbiTracker::new(params(), lag);
+        let mut committed = Vec::new();
+        for i in 0..200 {
+            if let Some(decoded) = tracker.push(&voiced_obs(300 + (i % 3))) {
+                committed.push(decoded.state);
+            }
+            assert!(tracker.backpointers.len() <= lag);
+            assert_eq!(tracker.provisional_path().len(), (i + 1).min(lag));
+        }
+        assert_eq!(committed.len(), 200 - lag);
+        assert_eq!(tracker.committed_frames(), 200 - lag);
+        assert!(committed.iter().skip(1).all(|s| s.voiced && (300..303).contains(&s.bin)));
+    }
+
+    #[test]
+    fn finish_commits_exact_backtrace() {
+        let mut fixed = ViterbiTracker::new(params(), 3);
+        let mut full = ViterbiTracker::new(params(), usize::MAX);
+        let mut committed = Vec::new();
+        for i in 0..40 {
+            let obs = voiced_obs(if i < 20 { 200 } else { 215 });
+            committed.extend(fixed.push(&obs).map(|d| d.state));
+            assert!(full.push(&obs).is_none());
+        }
+        committed.extend(fixed.finish().into_iter().map(|d| d.state));
+        let exact: Vec<HmmState> = full.finish().into_iter().map(|d| d.state).collect();
+        assert_eq!(committed.len(), 40);
+        assert_eq!(exact.len(), 40);
+        assert!(full.provisional_path().is_empty());
+        assert!(exact.iter().skip(1).all(|s| s.voiced));
+        assert_eq!(exact[39].bin, 215);
+    }
+
+    #[test]
+    fn settled_blocks_match_exact_decoding_with_bounded_history() {
+        let frames: Vec<ObservationFrame> = (0..300)
+            .map(|i| match i % 50 {
+                0..=5 => obs(300, 0.0),
+                20 => obs(300, 0.002),
+                k => obs(200 + k, 0.9),
+            })
+            .collect();
+        let mut exact = ViterbiTracker::new(params(), usize::MAX);
+        let mut blocks = ViterbiTracker::new(params(), usize::MAX);
+        let mut committed = Vec::new();
+        for frame in frames.iter() {
+            exact.push(frame);
+            blocks.push(frame);
+            if blocks.pending_frames() >= 20 {
+                committed.extend(blocks.commit_settled(10));
+            }
+            assert!(blocks.backpointers.len() < 20 && blocks.emissions.len() <= 20);
+        }
+        committed.extend(blocks.finish());
+        let exact = exact.finish();
+
+        assert_eq!(committed.len(), frames.len());
+        for (block, exact) in committed.iter().zip(&exact) {
+            assert_eq!(block.state.bin, exact.state.bin);
+            assert_eq!(block.state.voiced, exact.state.voiced);
+            assert!((block.posterior.voiced - exact.posterior.voiced).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn zero_lag_commits_every_frame() {
+        let mut tracker = ViterbiTracker::new(params(), 0);
+        for _ in 0..10 {
+            assert!(tracker.push(&voiced_obs(100)).is_some());
+            assert!(tracker.provisional_path().is_empty());
+        }
+    }
+
+    fn obs(bin: usize, sum_p: f32) -> ObservationFrame {
+        let mut p_star = vec![0.0; params().num_bins()];
+        p_star[bin] = sum_p;
+        ObservationFrame { p_star, sum_p }
+    }
+
+    #[test]
+    fn forward_posterior_is_normalised_and_calibrated() {
+        let mut tracker = ViterbiTracker::new(params(), 2).with_bin_posteriors(true);
+        let mut committed = Vec::new();
+        for i in 0..60 {
+            let frame = if i < 30 { obs(300, 0.9) } else { obs(300, 0.0) };
+            committed.extend(tracker.push(&frame));
+        }
+        for decoded in committed.iter() {
+            let bins = decoded.posterior.voiced_bins.as_ref().unwrap();
+            assert_eq!(bins.len(), params().num_bins());
+            assert!((bins.iter().sum::<f32>() - decoded.posterior.voiced).abs() < 1e-4);
+        }
+        for decoded in committed[5..25].iter().chain(&committed[35..]) {
+            assert!(decoded.posterior.decoded > 0.95, "{:?}", decoded);
+        }
+        assert!(committed[10].state.voiced && committed[10].posterior.voiced > 0.95);
+        assert!(!committed[50].state.voiced && committed[50].posterior.voiced < 0.05);
+    }
+
+    #[test]
+    fn forward_backward_uses_later_observations() {
+        // A weak frame inside a voiced run: the filtered posterior only sees
+        // the run before it, the smoothed one both sides.
+        let frames: Vec<ObservationFrame> = (0..41)
+            .map(|i| if i == 20 { obs(300, 0.002) } else { obs(300, 0.9) })
+            .collect();
+        let mut streaming = ViterbiTracker::new(params(), 0);
+        let filtered: Vec<DecodedFrame> =
+            frames.iter().map(|f| streaming.push(f).unwrap()).collect();
+        let mut offline = ViterbiTracker::new(params(), usize::MAX);
+        for frame in frames.iter() {
+            assert!(offline.push(frame).is_none());
+        }
+        let smoothed = offline.finish();
+
+        assert_eq!(smoothed.len(), frames.len());
+        assert!(smoothed[20].posterior.voiced > filtered[20].posterior.voiced + 0.05);
+        assert!(smoothed[20].posterior.voiced > 0.95);
+        // Nothing follows the last frame, so both passes agree on it.
+        let last = frames.len() - 1;
+        assert!((smoothed[last].posterior.voiced - filtered[last].posterior.voiced).abs() < 1e-4);
+        assert!(smoothed.iter().all(|d| d.posterior.voiced_bins.is_none()));
+    }
+}
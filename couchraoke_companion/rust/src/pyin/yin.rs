@@ -1,54 +1,98 @@
-use rustfft::{num_complex::Complex, FftPlanner};
+use std::sync::Arc;
 
-/// Compute the YIN difference function d(τ) for τ in [0, max_tau].
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+
+/// The YIN difference function d(τ) with its FFT plans and buffers, planned
+/// once for a frame size so that analysing frame after frame does not
+/// allocate.
 ///
 /// This uses an FFT-based autocorrelation to avoid the O(N·τ) nested loop,
 /// then applies: d(τ) = Σ_{j=0..N-τ-1} x_j^2 + Σ_{j=0..N-τ-1} x_{j+τ}^2 - 2·r(τ).
-pub fn difference_function(frame: &[f32], max_tau: usize) -> Vec<f32> {
-    let n = frame.len();
-    let mut diff = vec![0.0; max_tau + 1];
-    if n == 0 || max_tau == 0 {
-        return diff;
+pub struct DifferenceFunction {
+    fft: Arc<dyn Fft<f32>>,
+    ifft: Arc<dyn Fft<f32>>,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    prefix_sq: Vec<f32>,
+}
+
+impl DifferenceFunction {
+    pub fn new(frame_size: usize) -> Self {
+        let fft_len = (frame_size.max(1) * 2).next_power_of_two();
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(fft_len);
+        let ifft = planner.plan_fft_inverse(fft_len);
+        let scratch_len = fft
+            .get_inplace_scratch_len()
+            .max(ifft.get_inplace_scratch_len());
+        Self {
+            fft,
+            ifft,
+            buffer: vec![Complex { re: 0.0, im: 0.0 }; fft_len],
+            scratch: vec![Complex { re: 0.0, im: 0.0 }; scratch_len],
+            prefix_sq: Vec::with_capacity(frame_size + 1),
+        }
     }
 
-    let fft_len = (n * 2).next_power_of_two();
-    let mut planner = FftPlanner::new();
-    let fft = planner.plan_fft_forward(fft_len);
-    let ifft = planner.plan_fft_inverse(fft_len);
+    /// Write d(τ) for τ in [0, max_tau] to `diff`. A frame longer than the one
+    /// planned for is handled by planning again.
+    pub fn compute(&mut self, frame: &[f32], max_tau: usize, diff: &mut Vec<f32>) {
+        let n = frame.len();
+        diff.clear();
+        diff.resize(max_tau + 1, 0.0);
+        if n == 0 || max_tau == 0 {
+            return;
+        }
+        if 2 * n > self.buffer.len() {
+            *self = Self::new(n);
+        }
 
-    let mut buffer = vec![Complex { re: 0.0_f32, im: 0.0_f32 }; fft_len];
-    for (idx, &sample) in frame.iter().enumerate() {
-        buffer[idx].re = sample;
-    }
+        let fft_len = self.buffer.len();
+        self.buffer.fill(Complex { re: 0.0, im: 0.0 });
+        for (slot, &sample) in self.buffer.iter_mut().zip(frame) {
+            slot.re = sample;
+        }
 
-    fft.process(&mut buffer);
-    for value in buffer.iter_mut() {
-        let power = value.re * value.re + value.im * value.im;
-        *value = Complex { re: power, im: 0.0 };
-    }
-    ifft.process(&mut buffer);
+        self.fft.process_with_scratch(&mut self.buffer, &mut self.scratch);
+        for value in self.buffer.iter_mut() {
+            let power = value.re * value.re + value.im * value.im;
+            *value = Complex { re: power, im: 0.0 };
+        }
+        self.ifft.process_with_scratch(&mut self.buffer, &mut self.scratch);
 
-    let scale = 1.0 / fft_len as f32;
-    let mut prefix_sq = vec![0.0_f32; n + 1];
-    for (idx, &sample) in frame.iter().enumerate() {
-        prefix_sq[idx + 1] = prefix_sq[idx] + sample * sample;
-    }
+        let scale = 1.0 / fft_len as f32;
+        self.prefix_sq.clear();
+        self.prefix_sq.push(0.0);
+        let mut running = 0.0;
+        for &sample in frame {
+            running += sample * sample;
+            self.prefix_sq.push(running);
+        }
 
-    for tau in 1..=max_tau.min(n - 1) {
-        let sum_head = prefix_sq[n - tau];
-        let sum_tail = prefix_sq[n] - prefix_sq[tau];
-        let autocorr = buffer[tau].re * scale;
-        diff[tau] = sum_head + sum_tail - 2.0 * autocorr;
+        let prefix_sq = &self.prefix_sq;
+        for (tau, (d, autocorr)) in diff
+            .iter_mut()
+            .zip(&self.buffer)
+            .enumerate()
+            .take(n)
+            .skip(1)
+        {
+            let sum_head = prefix_sq[n - tau];
+            let sum_tail = prefix_sq[n] - prefix_sq[tau];
+            *d = sum_head + sum_tail - 2.0 * autocorr.re * scale;
+        }
     }
-
-    diff
 }
 
-/// Compute the cumulative mean normalized difference function d'(τ).
+/// Compute the cumulative mean normalized difference function d'(τ) into `cmnd`.
 ///
 /// d'(τ) = d(τ) / ((1/τ) * Σ_{j=1..τ} d(j))
-pub fn cumulative_mean_normalized_difference(diff: &[f32]) -> Vec<f32> {
-    let mut cmnd = vec![0.0; diff.len()];
+pub fn cumulative_mean_normalized_difference(diff: &[f32], cmnd: &mut Vec<f32>) {
+    cmnd.clear();
+    cmnd.resize(diff.len(), 0.0);
+    if diff.is_empty() {
+        return;
+    }
     cmnd[0] = 1.0;
     let mut running_sum = 0.0;
     for tau in 1..diff.len() {
@@ -59,20 +103,19 @@ pub fn cumulative_mean_normalized_difference(diff: &[f32]) -> Vec<f32> {
             cmnd[tau] = diff[tau] * tau as f32 / running_sum;
         }
     }
-    cmnd
 }
 
-pub fn local_minima(cmnd: &[f32]) -> Vec<usize> {
-    let mut minima = Vec::new();
+/// Write the local minima of `cmnd`, in increasing order, to `minima`.
+pub fn local_minima(cmnd: &[f32], minima: &mut Vec<usize>) {
+    minima.clear();
     if cmnd.len() < 3 {
-        return minima;
+        return;
     }
     for tau in 1..(cmnd.len() - 1) {
         if cmnd[tau] < cmnd[tau - 1] && cmnd[tau] <= cmnd[tau + 1] {
             minima.push(tau);
         }
     }
-    minima
 }
 
 /// Parabolic interpolation around a minimum to refine τ.
@@ -93,14 +136,16 @@ pub fn parabolic_interpolation(cmnd: &[f32], tau: usize) -> f32 {
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_range_loop)]
 mod tests {
     use super::*;
 
     #[test]
     fn cmnd_constant_signal() {
         let frame = vec![1.0_f32; 64];
-        let diff = difference_function(&frame, 32);
-        let cmnd = cumulative_mean_normalized_difference(&diff);
+        let (mut diff, mut cmnd) = (Vec::new(), Vec::new());
+        DifferenceFunction::new(frame.len()).compute(&frame, 32, &mut diff);
+        cumulative_mean_normalized_difference(&diff, &mut cmnd);
         assert!(cmnd.iter().skip(1).all(|v| (*v - 1.0).abs() < 1e-6));
     }
 
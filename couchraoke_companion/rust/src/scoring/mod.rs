@@ -0,0 +1,817 @@
+//! Karaoke scoring of detected pitch against a song's note track.
+//!
+//! Scoring follows UltraStar Deluxe: every beat of a note is hit or missed,
+//! golden notes count twice, and a song is worth 10,000 points, 1,000 of them
+//! a bonus spread over the lines in proportion to how well each was sung.
+//! Pitch is compared octave-agnostically, within a tolerance set by the
+//! difficulty. Freestyle notes are not scored at all.
+//!
+//! Rap notes are scored on rhythm: a beat is hit when the singer is active,
+//! voiced or clearly above the room noise, and a note only counts once a
+//! syllable starts on it (see `rap`). Frames pushed without levels fall back
+//! on the pitch tracker's voicing for both.
+//!
+//! A beat is hit when at least half of the pitch frames falling into it hit
+//! the note, so the result depends only on the frames pushed, not on how the
+//! stream was chunked.
+//!
+//! As notes and lines finish, the session queues `ScoreEvent`s for live
+//! feedback, stamped with song time so they line up with the lyrics whatever
+//! the delay of the microphone.
+//!
+//! Duets and party sessions score each singer separately against the part
+//! they were assigned, or against every part in harmony mode (see `party` and
+//! `pyin::api::Party`).
+
+mod party;
+mod rap;
+
+use crate::pyin::api::PitchEvent;
+use crate::pyin::snapshot::{invalid, StateReader, StateWriter};
+use crate::pyin::{FrameEstimate, PyinError};
+use crate::song::{NoteKind, NoteTrack, Song, SongClock};
+
+pub use party::{PartChoice, SingerResult, SingerScoring};
+pub use rap::{FrameLevel, RapConfig, SyllableDetector, SyllableFrame};
+
+/// Points for a perfectly sung song.
+pub const MAX_SCORE: f64 = 10_000.0;
+/// Part of `MAX_SCORE` awarded as line bonus, when enabled.
+pub const MAX_LINE_BONUS: f64 = 1_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Semitones a sung note may be off and still hit.
+    pub fn tolerance_semitones(self) -> i32 {
+        match self {
+            Self::Easy => 2,
+            Self::Medium => 1,
+            Self::Hard => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoringConfig {
+    pub difficulty: Difficulty,
+    /// Award `MAX_LINE_BONUS` over the lines; otherwise the notes are worth
+    /// the whole `MAX_SCORE`.
+    pub line_bonus: bool,
+    /// Stream time (the clock of `FrameEstimate::time_sec`) at which song time
+    /// 0 is heard. Frame times are the start of their analysis window, so
+    /// subtract half a window to score each frame at its centre.
+    pub song_start_sec: f64,
+    pub rap: RapConfig,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::Medium,
+            line_bonus: true,
+            song_start_sec: 0.0,
+            rap: RapConfig::default(),
+        }
+    }
+}
+
+/// Beats of a note of `kind` count this many times.
+fn score_factor(kind: NoteKind) -> u32 {
+    match kind {
+        NoteKind::Normal | NoteKind::Rap => 1,
+        NoteKind::Golden | NoteKind::RapGolden => 2,
+        NoteKind::Freestyle => 0,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteScore {
+    /// Index of the note's line in the track.
+    pub line: usize,
+    pub kind: NoteKind,
+    pub start_beat: i32,
+    pub length_beats: i32,
+    pub hit_beats: u32,
+    /// A syllable started on this rap note. Always `false` for sung notes.
+    pub onset: bool,
+    pub points: f64,
+    /// Points for hitting every beat; 0 for freestyle notes.
+    pub max_points: f64,
+    /// Set once song time has passed the end of the note.
+    pub finished: bool,
+}
+
+impl NoteScore {
+    pub fn end_beat(&self) -> i32 {
+        self.start_beat + self.length_beats
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LineScore {
+    pub note_points: f64,
+    pub max_note_points: f64,
+    pub bonus: f64,
+    /// Every beat of every scored note of the line was hit.
+    pub perfect: bool,
+    /// Set once every note of the line has finished.
+    pub finished: bool,
+}
+
+impl LineScore {
+    /// Share of the line's note points scored, in [0, 1]. `None` for lines
+    /// without scored notes.
+    pub fn perfection(&self) -> Option<f64> {
+        (self.max_note_points > 0.0).then(|| (self.note_points / self.max_note_points).min(1.0))
+    }
+}
+
+/// Points of a session so far.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScoreTotals {
+    /// Points of normal and rap notes.
+    pub notes: f64,
+    /// Points of golden notes.
+    pub golden: f64,
+    pub line_bonus: f64,
+}
+
+impl ScoreTotals {
+    pub fn total(&self) -> f64 {
+        self.notes + self.golden + self.line_bonus
+    }
+
+    /// The total as UltraStar Deluxe shows it: each part rounded to tens.
+    pub fn rounded(&self) -> u32 {
+        [self.notes, self.golden, self.line_bonus]
+            .iter()
+            .map(|points| (points / 10.0).round() as u32 * 10)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreEventKind {
+    /// A note finished with at least half of its beats hit.
+    NoteHit,
+    NoteMissed,
+    LineFinished,
+    /// A line finished with every beat hit.
+    PerfectLine,
+}
+
+/// Live feedback on a finished note or line. Freestyle notes and lines
+/// without scored notes produce no events.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreEvent {
+    pub kind: ScoreEventKind,
+    /// Song time at which the note or line ends, in seconds of the song audio.
+    pub song_time_sec: f64,
+    /// Index of the scored part in the song's tracks.
+    pub track: u32,
+    /// Index of the line in the track.
+    pub line: u32,
+    /// Index of the note in the track, for note events.
+    pub note: Option<u32>,
+    /// Points of the note, or bonus of the line.
+    pub points: f64,
+    /// Running score after this event, as `ScoreTotals::rounded`.
+    pub total_score: u32,
+    /// Golden notes hit in a row, up to and including this event.
+    pub golden_streak: u32,
+}
+
+/// Frames and hits counted in one beat of a note.
+#[derive(Debug, Clone, Copy, Default)]
+struct BeatTally {
+    frames: u32,
+    hits: u32,
+}
+
+impl BeatTally {
+    fn hit(&self) -> bool {
+        self.frames > 0 && 2 * self.hits >= self.frames
+    }
+}
+
+/// Scores one singer against one note track as pitch frames arrive.
+#[derive(Clone)]
+pub struct ScoringSession {
+    cfg: ScoringConfig,
+    clock: SongClock,
+    /// Index of the scored track in its song, for events.
+    track: usize,
+    /// Target MIDI note of each entry of `notes`.
+    targets: Vec<i32>,
+    /// One tally per beat of each note; empty for freestyle notes.
+    tallies: Vec<Vec<BeatTally>>,
+    syllables: SyllableDetector,
+    notes: Vec<NoteScore>,
+    lines: Vec<LineScore>,
+    /// One past the last note of each line.
+    line_ends: Vec<usize>,
+    totals: ScoreTotals,
+    golden_streak: u32,
+    events: Vec<ScoreEvent>,
+    /// Notes before this one have finished.
+    next_open: usize,
+    next_line: usize,
+}
+
+impl ScoringSession {
+    pub fn new(track: &NoteTrack, clock: &SongClock, cfg: ScoringConfig) -> Self {
+        let total_value: u32 = track
+            .notes()
+            .map(|note| note.length_beats as u32 * score_factor(note.kind))
+            .sum();
+        let note_share = if cfg.line_bonus { MAX_SCORE - MAX_LINE_BONUS } else { MAX_SCORE };
+        let points_per_value = if total_value > 0 { note_share / total_value as f64 } else { 0.0 };
+
+        let mut notes = Vec::new();
+        let mut lines = Vec::new();
+        let mut line_ends = Vec::new();
+        for (index, line) in track.lines.iter().enumerate() {
+            let mut line_score = LineScore::default();
+            for note in &line.notes {
+                let max_points =
+                    note.length_beats as f64 * score_factor(note.kind) as f64 * points_per_value;
+                line_score.max_note_points += max_points;
+                notes.push(NoteScore {
+                    line: index,
+                    kind: note.kind,
+                    start_beat: note.start_beat,
+                    length_beats: note.length_beats,
+                    hit_beats: 0,
+                    onset: false,
+                    points: 0.0,
+                    max_points,
+                    finished: false,
+                });
+            }
+            lines.push(line_score);
+            line_ends.push(notes.len());
+        }
+
+        Self {
+            targets: track.notes().map(|note| note.midi()).collect(),
+            tallies: notes
+                .iter()
+                .map(|note| match note.kind {
+                    NoteKind::Freestyle => Vec::new(),
+                    _ => vec![BeatTally::default(); note.length_beats as usize],
+                })
+                .collect(),
+            syllables: SyllableDetector::new(cfg.rap.clone()),
+            notes,
+            lines,
+            line_ends,
+            totals: ScoreTotals::default(),
+            golden_streak: 0,
+            events: Vec::new(),
+            next_open: 0,
+            next_line: 0,
+            clock: clock.clone(),
+            track: 0,
+            cfg,
+        }
+    }
+
+    /// Score against part `track` of `song`; `None` if there is no such part.
+    pub fn for_part(song: &Song, track: usize, cfg: ScoringConfig) -> Option<Self> {
+        let note_track = song.tracks.get(track)?;
+        Some(Self {
+            track,
+            ..Self::new(note_track, &song.clock, cfg)
+        })
+    }
+
+    /// Index of the scored part in its song's tracks.
+    pub fn track(&self) -> usize {
+        self.track
+    }
+
+    pub fn config(&self) -> &ScoringConfig {
+        &self.cfg
+    }
+
+    /// Move the song on the stream clock (`ScoringConfig::song_start_sec`),
+    /// e.g. after the stream paused while the song played on. Finished notes
+    /// keep their scores.
+    pub fn set_song_start(&mut self, song_start_sec: f64) {
+        self.cfg.song_start_sec = song_start_sec;
+    }
+
+    /// Score a pitch frame at stream time `time_sec`: its MIDI pitch, or
+    /// `None` when unvoiced. Frames must arrive in time order.
+    pub fn push(&mut self, time_sec: f64, midi: Option<f32>) {
+        self.push_with_level(time_sec, midi, None);
+    }
+
+    /// Score a pitch frame along with its level, which lets rap notes be
+    /// scored on unvoiced speech.
+    pub fn push_with_level(&mut self, time_sec: f64, midi: Option<f32>, level: Option<FrameLevel>) {
+        let song_sec = time_sec - self.cfg.song_start_sec;
+        let beat = self.clock.sec_to_beat(song_sec).floor();
+        if !beat.is_finite() {
+            return;
+        }
+        let beat = beat.clamp(i32::MIN as f64, i32::MAX as f64) as i32;
+        self.finish_before(beat);
+
+        let syllable = self.syllables.push(time_sec, midi.is_some(), level);
+        // A syllable starting a little early still belongs to the next note.
+        let onset_beat = if syllable.onset {
+            let early_sec = song_sec + self.cfg.rap.onset_tolerance_sec as f64;
+            let early = self.clock.sec_to_beat(early_sec).floor();
+            early.clamp(beat as f64, i32::MAX as f64) as i32
+        } else {
+            beat
+        };
+
+        let tolerance = self.cfg.difficulty.tolerance_semitones();
+        for index in self.next_open..self.notes.len() {
+            let note = &mut self.notes[index];
+            if note.start_beat > onset_beat {
+                break;
+            }
+            if beat >= note.end_beat() {
+                continue;
+            }
+            let hit = match note.kind {
+                NoteKind::Freestyle => continue,
+                NoteKind::Rap | NoteKind::RapGolden => {
+                    note.onset |= syllable.onset;
+                    syllable.active
+                }
+                NoteKind::Normal | NoteKind::Golden => {
+                    midi.is_some_and(|midi| pitch_matches(midi, self.targets[index], tolerance))
+                }
+            };
+            if note.start_beat > beat {
+                continue;
+            }
+            let tally = &mut self.tallies[index][(beat - note.start_beat) as usize];
+            tally.frames += 1;
+            tally.hits += hit as u32;
+        }
+    }
+
+    pub fn push_estimate(&mut self, frame: &FrameEstimate) {
+        let level = FrameLevel {
+            rms_dbfs: frame.rms_dbfs,
+            noise_floor_dbfs: frame.vad.as_ref().map(|vad| vad.noise_floor_dbfs),
+        };
+        self.push_with_level(frame.time_sec, frame.midi_fractional, Some(level));
+    }
+
+    pub fn push_event(&mut self, event: &PitchEvent) {
+        let level = FrameLevel {
+            rms_dbfs: event.rms_dbfs,
+            noise_floor_dbfs: event.noise_floor_dbfs,
+        };
+        self.push_with_level(event.time_sec, event.midi_fractional, Some(level));
+    }
+
+    /// Finish every note, once the singer is done.
+    pub fn finish(&mut self) {
+        self.finish_before(i32::MAX);
+    }
+
+    pub fn notes(&self) -> &[NoteScore] {
+        &self.notes
+    }
+
+    pub fn lines(&self) -> &[LineScore] {
+        &self.lines
+    }
+
+    pub fn totals(&self) -> ScoreTotals {
+        self.totals
+    }
+
+    /// Golden notes hit in a row so far.
+    pub fn golden_streak(&self) -> u32 {
+        self.golden_streak
+    }
+
+    /// Take the events of the notes and lines finished since the last call,
+    /// in order.
+    pub fn drain_events(&mut self) -> std::vec::Drain<'_, ScoreEvent> {
+        self.events.drain(..)
+    }
+
+    /// Write what the session has scored so far; see
+    /// `AudioAnalyzer::snapshot`.
+    pub(crate) fn save_state(&self, out: &mut StateWriter) {
+        out.usize(self.track);
+        out.usize(self.notes.len());
+        for (note, tallies) in self.notes.iter().zip(&self.tallies) {
+            out.u64(note.hit_beats as u64);
+            out.bool(note.onset);
+            out.f64(note.points);
+            out.bool(note.finished);
+            for tally in tallies {
+                out.u64(tally.frames as u64);
+                out.u64(tally.hits as u64);
+            }
+        }
+        out.usize(self.lines.len());
+        for line in &self.lines {
+            out.f64(line.note_points);
+            out.f64(line.bonus);
+            out.bool(line.perfect);
+            out.bool(line.finished);
+        }
+        out.f64(self.totals.notes);
+        out.f64(self.totals.golden);
+        out.f64(self.totals.line_bonus);
+        out.u64(self.golden_streak as u64);
+        out.usize(self.next_open);
+        out.usize(self.next_line);
+        out.usize(self.events.len());
+        for event in &self.events {
+            save_event(out, event);
+        }
+        self.syllables.save_state(out);
+    }
+
+    /// Continue from `save_state` of a session scoring the same part of the
+    /// same chart.
+    pub(crate) fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.expect_usize("track", self.track)?;
+        input.expect_usize("notes", self.notes.len())?;
+        for (note, tallies) in self.notes.iter_mut().zip(&mut self.tallies) {
+            note.hit_beats = input.u64()? as u32;
+            note.onset = input.bool()?;
+            note.points = input.f64()?;
+            note.finished = input.bool()?;
+            for tally in tallies {
+                tally.frames = input.u64()? as u32;
+                tally.hits = input.u64()? as u32;
+            }
+        }
+        input.expect_usize("lines", self.lines.len())?;
+        for line in &mut self.lines {
+            line.note_points = input.f64()?;
+            line.bonus = input.f64()?;
+            line.perfect = input.bool()?;
+            line.finished = input.bool()?;
+        }
+        self.totals = ScoreTotals {
+            notes: input.f64()?,
+            golden: input.f64()?,
+            line_bonus: input.f64()?,
+        };
+        self.golden_streak = input.u64()? as u32;
+        self.next_open = input.usize()?;
+        self.next_line = input.usize()?;
+        if self.next_open > self.notes.len() || self.next_line > self.lines.len() {
+            return invalid("scoring is past the end of the chart");
+        }
+        self.events.clear();
+        let events = input.usize()?;
+        for _ in 0..events {
+            self.events.push(load_event(input)?);
+        }
+        self.syllables.load_state(input)
+    }
+
+    /// Finish the notes ending at or before `beat`, and the lines they
+    /// complete.
+    fn finish_before(&mut self, beat: i32) {
+        while let Some(note) = self.notes.get(self.next_open) {
+            if note.end_beat() > beat {
+                break;
+            }
+            let note = &mut self.notes[self.next_open];
+            note.hit_beats = self.tallies[self.next_open].iter().filter(|t| t.hit()).count() as u32;
+            if note.kind.is_rap() && !note.onset {
+                note.hit_beats = 0;
+            }
+            note.points = note.max_points * note.hit_beats as f64 / note.length_beats.max(1) as f64;
+            note.finished = true;
+            if note.kind.is_golden() {
+                self.totals.golden += note.points;
+            } else {
+                self.totals.notes += note.points;
+            }
+            self.lines[note.line].note_points += note.points;
+            if note.max_points > 0.0 {
+                let hit = 2 * note.hit_beats as i32 >= note.length_beats;
+                if note.kind.is_golden() {
+                    self.golden_streak = if hit { self.golden_streak + 1 } else { 0 };
+                }
+                let kind = if hit { ScoreEventKind::NoteHit } else { ScoreEventKind::NoteMissed };
+                let event = ScoreEvent {
+                    kind,
+                    song_time_sec: self.clock.beat_to_sec(note.end_beat() as f64),
+                    track: self.track as u32,
+                    line: note.line as u32,
+                    note: Some(self.next_open as u32),
+                    points: note.points,
+                    total_score: self.totals.rounded(),
+                    golden_streak: self.golden_streak,
+                };
+                self.events.push(event);
+            }
+            self.next_open += 1;
+
+            while self.next_line < self.lines.len()
+                && self.line_ends[self.next_line] <= self.next_open
+            {
+                self.finish_line(self.next_line);
+                self.next_line += 1;
+            }
+        }
+    }
+
+    fn finish_line(&mut self, index: usize) {
+        let scored_lines = self.lines.iter().filter(|line| line.max_note_points > 0.0).count();
+        let start = if index == 0 { 0 } else { self.line_ends[index - 1] };
+        let notes = &self.notes[start..self.line_ends[index]];
+        let scored = || notes.iter().filter(|note| note.max_points > 0.0);
+        let line = &mut self.lines[index];
+        line.finished = true;
+        let Some(perfection) = line.perfection() else {
+            return;
+        };
+        line.perfect = scored().all(|note| note.hit_beats as i32 == note.length_beats);
+        if self.cfg.line_bonus {
+            line.bonus = MAX_LINE_BONUS / scored_lines as f64 * perfection;
+            self.totals.line_bonus += line.bonus;
+        }
+        let end_beat = scored().map(NoteScore::end_beat).max().unwrap_or(0);
+        let event = ScoreEvent {
+            kind: if line.perfect {
+                ScoreEventKind::PerfectLine
+            } else {
+                ScoreEventKind::LineFinished
+            },
+            song_time_sec: self.clock.beat_to_sec(end_beat as f64),
+            track: self.track as u32,
+            line: index as u32,
+            note: None,
+            points: line.bonus,
+            total_score: self.totals.rounded(),
+            golden_streak: self.golden_streak,
+        };
+        self.events.push(event);
+    }
+}
+
+fn save_event(out: &mut StateWriter, event: &ScoreEvent) {
+    out.u8(event.kind as u8);
+    out.f64(event.song_time_sec);
+    out.u64(event.track as u64);
+    out.u64(event.line as u64);
+    out.option(event.note.as_ref(), |out, note| out.u64(*note as u64));
+    out.f64(event.points);
+    out.u64(event.total_score as u64);
+    out.u64(event.golden_streak as u64);
+}
+
+fn load_event(input: &mut StateReader) -> Result<ScoreEvent, PyinError> {
+    let kind = match input.u8()? {
+        0 => ScoreEventKind::NoteHit,
+        1 => ScoreEventKind::NoteMissed,
+        2 => ScoreEventKind::LineFinished,
+        3 => ScoreEventKind::PerfectLine,
+        _ => return invalid("unknown score event"),
+    };
+    Ok(ScoreEvent {
+        kind,
+        song_time_sec: input.f64()?,
+        track: input.u64()? as u32,
+        line: input.u64()? as u32,
+        note: input.option(|input| Ok(input.u64()? as u32))?,
+        points: input.f64()?,
+        total_score: input.u64()? as u32,
+        golden_streak: input.u64()? as u32,
+    })
+}
+
+/// Whether the sung `midi`, rounded to a semitone and moved to the octave
+/// nearest `target`, is within `tolerance` semitones of it.
+fn pitch_matches(midi: f32, target: i32, tolerance: i32) -> bool {
+    let offset = (midi.round() as i32 - target).rem_euclid(12);
+    let offset = if offset > 6 { offset - 12 } else { offset };
+    offset.abs() <= tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::song::Song;
+
+    /// Two lines at 300 BPM (20 beats per second): a normal and a golden
+    /// note, then a rap and a freestyle note.
+    const CHART: &str = "#TITLE:Test\n#ARTIST:Test\n#BPM:300\n#GAP:500\n\
+                         : 0 4 0 do\n* 4 4 4 mi\n- 10\nR 10 4 0 yo\nF 14 4 0 la\nE\n";
+
+    /// Sing at 100 frames per second until 0.5 s after the last note, with
+    /// `pitch` giving the sung MIDI note at a song beat.
+    fn sing(cfg: ScoringConfig, pitch: impl Fn(f64) -> Option<f32>) -> ScoringSession {
+        sing_with_levels(CHART, cfg, |beat| (pitch(beat), None))
+    }
+
+    /// As `sing`, with `sound` also giving the level at a song beat.
+    fn sing_with_levels(
+        chart: &str,
+        cfg: ScoringConfig,
+        sound: impl Fn(f64) -> (Option<f32>, Option<f32>),
+    ) -> ScoringSession {
+        let song = Song::parse(chart.as_bytes()).unwrap();
+        let mut session = ScoringSession::new(&song.tracks[0], &song.clock, cfg);
+        for frame in 0..200 {
+            let time_sec = (frame as f64 + 0.5) * 0.01;
+            let (midi, rms_dbfs) = sound(song.clock.sec_to_beat(time_sec));
+            let level = rms_dbfs.map(|rms_dbfs| FrameLevel {
+                rms_dbfs,
+                noise_floor_dbfs: None,
+            });
+            session.push_with_level(time_sec, midi, level);
+        }
+        session.finish();
+        session
+    }
+
+    /// The chart's melody, shifted by `offset` semitones.
+    fn melody(offset: f32) -> impl Fn(f64) -> Option<f32> {
+        move |beat| match beat {
+            b if (0.0..4.0).contains(&b) => Some(60.0 + offset),
+            b if (4.0..8.0).contains(&b) => Some(64.0 + offset),
+            b if (10.0..18.0).contains(&b) => Some(50.0),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn perfect_singing_scores_the_maximum() {
+        let session = sing(ScoringConfig::default(), melody(0.0));
+        let totals = session.totals();
+        // 16 note values: 4 normal, 2 x 4 golden, 4 rap, the freestyle note is free.
+        assert!((totals.notes - 9_000.0 * 8.0 / 16.0).abs() < 1e-6);
+        assert!((totals.golden - 9_000.0 * 8.0 / 16.0).abs() < 1e-6);
+        assert!((totals.line_bonus - 1_000.0).abs() < 1e-6);
+        assert_eq!(totals.rounded(), 10_000);
+        assert!(session.lines().iter().all(|line| line.perfection() == Some(1.0)));
+        let freestyle = &session.notes()[3];
+        assert_eq!((freestyle.kind, freestyle.max_points), (NoteKind::Freestyle, 0.0));
+
+        let cfg = ScoringConfig {
+            line_bonus: false,
+            ..Default::default()
+        };
+        let without_bonus = sing(cfg, melody(0.0));
+        assert_eq!(without_bonus.totals().line_bonus, 0.0);
+        assert_eq!(without_bonus.totals().rounded(), 10_000);
+    }
+
+    #[test]
+    fn pitch_is_octave_agnostic_within_the_difficulty_tolerance() {
+        let score = |difficulty, offset| {
+            let cfg = ScoringConfig { difficulty, ..Default::default() };
+            sing(cfg, melody(offset)).totals().rounded()
+        };
+        assert_eq!(score(Difficulty::Hard, -12.0), 10_000);
+        assert_eq!(score(Difficulty::Hard, 24.3), 10_000);
+        assert_eq!(score(Difficulty::Medium, 13.0), 10_000);
+        assert_eq!(score(Difficulty::Easy, -2.0), 10_000);
+        // Only the rap note is left, worth a quarter of the note points.
+        assert_eq!(score(Difficulty::Hard, 1.0), 2_250 + 500);
+        assert_eq!(score(Difficulty::Medium, 2.0), 2_250 + 500);
+        assert_eq!(score(Difficulty::Easy, 3.0), 2_250 + 500);
+    }
+
+    #[test]
+    fn partial_lines_earn_partial_points_and_bonus() {
+        // The last two of five frames of every beat are out of tune.
+        let session = sing(ScoringConfig::default(), |beat| {
+            if beat < 10.0 && beat.fract() > 0.6 {
+                return Some(70.0);
+            }
+            melody(0.0)(beat)
+        });
+        assert_eq!(session.totals().rounded(), 10_000);
+
+        // Only the first two beats of each sung note.
+        let session = sing(ScoringConfig::default(), |beat| {
+            if beat < 10.0 && beat % 4.0 >= 2.0 {
+                return None;
+            }
+            melody(0.0)(beat)
+        });
+        let first = &session.lines()[0];
+        assert_eq!(session.notes()[0].hit_beats, 2);
+        assert_eq!(first.perfection(), Some(0.5));
+        assert!((first.bonus - 250.0).abs() < 1e-6);
+        let totals = session.totals();
+        assert!((totals.notes - 2_250.0 - 1_125.0).abs() < 1e-6);
+        assert!((totals.golden - 2_250.0).abs() < 1e-6);
+        assert!((totals.line_bonus - 750.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn events_report_notes_lines_and_streaks_in_song_time() {
+        let mut session = sing(ScoringConfig::default(), melody(0.0));
+        let events: Vec<ScoreEvent> = session.drain_events().collect();
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.kind, e.line, e.note, e.total_score, e.golden_streak))
+            .collect();
+        use ScoreEventKind::*;
+        assert_eq!(
+            summary,
+            [
+                (NoteHit, 0, Some(0), 2_250, 0),
+                (NoteHit, 0, Some(1), 6_750, 1),
+                (PerfectLine, 0, None, 7_250, 1),
+                (NoteHit, 1, Some(2), 9_500, 1),
+                (PerfectLine, 1, None, 10_000, 1),
+            ]
+        );
+        // Beat 8 ends the golden note, 0.5 s + 8 beats of 50 ms into the song.
+        assert!((events[1].song_time_sec - 0.9).abs() < 1e-9);
+        assert!((events[2].points - 500.0).abs() < 1e-6);
+        // The freestyle note is not reported and does not delay the line.
+        assert!((events[4].song_time_sec - 1.2).abs() < 1e-9);
+        assert_eq!(session.drain_events().count(), 0);
+
+        let mut missed = sing(ScoringConfig::default(), |beat| {
+            if (4.0..8.0).contains(&beat) {
+                return None;
+            }
+            melody(0.0)(beat)
+        });
+        let kinds: Vec<ScoreEventKind> = missed.drain_events().map(|e| e.kind).collect();
+        assert_eq!(kinds, [NoteHit, NoteMissed, LineFinished, NoteHit, PerfectLine]);
+        assert_eq!(missed.golden_streak(), 0);
+        assert!(!missed.lines()[0].perfect && missed.lines()[1].perfect);
+    }
+
+    #[test]
+    fn rap_notes_need_a_syllable_on_the_beat() {
+        // Holding the golden note on through the rap note starts no syllable.
+        let held = sing(ScoringConfig::default(), |beat| match beat {
+            b if (4.0..18.0).contains(&b) => Some(64.0),
+            b => melody(0.0)(b),
+        });
+        let rap = &held.notes()[2];
+        assert!(!rap.onset);
+        assert_eq!(rap.hit_beats, 0);
+        assert_eq!(held.totals().rounded(), 10_000 - 2_250 - 500);
+
+        // Spoken, unvoiced rap is heard through its level.
+        let spoken = sing_with_levels(CHART, ScoringConfig::default(), |beat| {
+            match melody(0.0)(beat) {
+                _ if (10.0..14.0).contains(&beat) => (None, Some(-20.0)),
+                Some(midi) => (Some(midi), Some(-20.0)),
+                None => (None, Some(-70.0)),
+            }
+        });
+        assert!(spoken.notes()[2].onset);
+        assert_eq!(spoken.totals().rounded(), 10_000);
+        // Background noise is not rap.
+        let noise = sing_with_levels(CHART, ScoringConfig::default(), |_| (None, Some(-70.0)));
+        assert_eq!(noise.totals().rounded(), 0);
+    }
+
+    #[test]
+    fn freestyle_lines_are_left_out_of_the_score() {
+        let chart = CHART.replace("E\n", "- 20\nF 22 4 0 la\nF 26 4 0 la\nE\n");
+        let mut session = sing_with_levels(&chart, ScoringConfig::default(), |beat| {
+            (melody(0.0)(beat).or((22.0..30.0).contains(&beat).then_some(72.0)), None)
+        });
+        assert_eq!(session.totals().rounded(), 10_000);
+        let freestyle = &session.lines()[2];
+        assert!(freestyle.finished && !freestyle.perfect);
+        assert_eq!((freestyle.perfection(), freestyle.bonus), (None, 0.0));
+        let lines: Vec<u32> = session.drain_events().map(|e| e.line).collect();
+        assert_eq!(lines, [0, 0, 0, 1, 1]);
+        assert!(session.notes()[4..].iter().all(|n| n.finished && n.hit_beats == 0));
+    }
+
+    #[test]
+    fn notes_and_lines_finish_as_song_time_passes() {
+        let song = Song::parse(CHART.as_bytes()).unwrap();
+        let mut session = ScoringSession::new(&song.tracks[0], &song.clock, Default::default());
+        // Beat 8 starts 0.9 s into the audio.
+        session.push(0.89, Some(60.0));
+        assert!(!session.notes()[1].finished);
+        session.push(0.91, None);
+        assert!(session.notes()[1].finished);
+        assert!(session.lines()[0].finished);
+        assert!(!session.lines()[1].finished);
+        assert_eq!(session.totals().line_bonus, 0.0);
+        session.finish();
+        assert!(session.lines()[1].finished);
+        assert_eq!(session.totals().rounded(), 0);
+    }
+}
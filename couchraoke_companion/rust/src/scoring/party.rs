@@ -0,0 +1,240 @@
+//! Scoring several singers of one song.
+//!
+//! Each singer has their own detector and stream clock, so each gets a
+//! `SingerScoring` with its own `ScoringConfig` (and so its own
+//! `song_start_sec`), all against the tracks and clock of one song. A singer
+//! is assigned one part of a duet, or in harmony mode is scored against every
+//! part and credited with the best. `pyin::api::Party` runs one analyzer and
+//! `SingerScoring` per singer of a party session.
+
+use super::{FrameLevel, ScoreEvent, ScoreTotals, ScoringConfig, ScoringSession};
+use crate::pyin::api::PitchEvent;
+use crate::pyin::snapshot::{StateReader, StateWriter};
+use crate::pyin::{FrameEstimate, PyinError};
+use crate::song::Song;
+
+/// Part of the song a singer is scored against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartChoice {
+    /// Index into `Song::tracks`.
+    Track(usize),
+    /// Every part; the singer gets the score of the part they sing best.
+    Harmony,
+}
+
+/// Scores one singer against their part, or every part in harmony mode.
+#[derive(Clone)]
+pub struct SingerScoring {
+    part: PartChoice,
+    /// One session per part scored, in track order.
+    sessions: Vec<ScoringSession>,
+}
+
+impl SingerScoring {
+    pub fn new(song: &Song, part: PartChoice, cfg: ScoringConfig) -> Result<Self, PyinError> {
+        let sessions: Vec<_> = match part {
+            PartChoice::Track(track) => ScoringSession::for_part(song, track, cfg)
+                .into_iter()
+                .collect(),
+            PartChoice::Harmony => (0..song.tracks.len())
+                .filter_map(|track| ScoringSession::for_part(song, track, cfg.clone()))
+                .collect(),
+        };
+        if sessions.is_empty() {
+            let message = match part {
+                PartChoice::Track(track) => format!("song chart has no track {}", track),
+                PartChoice::Harmony => "song chart has no tracks".to_string(),
+            };
+            return Err(PyinError::InvalidConfig(message));
+        }
+        Ok(Self { part, sessions })
+    }
+
+    pub fn part(&self) -> PartChoice {
+        self.part
+    }
+
+    pub fn push(&mut self, time_sec: f64, midi: Option<f32>) {
+        self.push_with_level(time_sec, midi, None);
+    }
+
+    pub fn push_with_level(&mut self, time_sec: f64, midi: Option<f32>, level: Option<FrameLevel>) {
+        for session in &mut self.sessions {
+            session.push_with_level(time_sec, midi, level);
+        }
+    }
+
+    pub fn push_estimate(&mut self, frame: &FrameEstimate) {
+        for session in &mut self.sessions {
+            session.push_estimate(frame);
+        }
+    }
+
+    pub fn push_event(&mut self, event: &PitchEvent) {
+        for session in &mut self.sessions {
+            session.push_event(event);
+        }
+    }
+
+    pub fn finish(&mut self) {
+        for session in &mut self.sessions {
+            session.finish();
+        }
+    }
+
+    /// See `ScoringSession::set_song_start`.
+    pub fn set_song_start(&mut self, song_start_sec: f64) {
+        for session in &mut self.sessions {
+            session.set_song_start(song_start_sec);
+        }
+    }
+
+    /// Sessions of the parts scored, in track order.
+    pub fn sessions(&self) -> &[ScoringSession] {
+        &self.sessions
+    }
+
+    /// Session of the part the singer has scored best on so far; the earlier
+    /// part on a tie.
+    pub fn best(&self) -> &ScoringSession {
+        let mut best = &self.sessions[0];
+        for session in &self.sessions[1..] {
+            if session.totals().total() > best.totals().total() {
+                best = session;
+            }
+        }
+        best
+    }
+
+    pub fn totals(&self) -> ScoreTotals {
+        self.best().totals()
+    }
+
+    /// The singer's result so far, on the part they sing best.
+    pub fn result(&self, name: impl Into<String>) -> SingerResult {
+        let best = self.best();
+        SingerResult {
+            name: name.into(),
+            track: best.track(),
+            totals: best.totals(),
+            score: best.totals().rounded(),
+        }
+    }
+
+    /// Take the events of every part scored, in song time order. Events of
+    /// different parts are told apart by `ScoreEvent::track`.
+    pub fn drain_events(&mut self) -> Vec<ScoreEvent> {
+        let mut events: Vec<_> = self
+            .sessions
+            .iter_mut()
+            .flat_map(|session| session.drain_events())
+            .collect();
+        events.sort_by(|a, b| a.song_time_sec.total_cmp(&b.song_time_sec));
+        events
+    }
+
+    pub(crate) fn save_state(&self, out: &mut StateWriter) {
+        out.usize(self.sessions.len());
+        for session in &self.sessions {
+            session.save_state(out);
+        }
+    }
+
+    /// Continue from `save_state` of a singer scored on the same parts of the
+    /// same chart.
+    pub(crate) fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        input.expect_usize("scored parts", self.sessions.len())?;
+        for session in &mut self.sessions {
+            session.load_state(input)?;
+        }
+        Ok(())
+    }
+}
+
+/// Final or running result of one singer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SingerResult {
+    pub name: String,
+    /// Part the result was scored on; the best one in harmony mode.
+    pub track: usize,
+    pub totals: ScoreTotals,
+    /// `totals` as UltraStar Deluxe shows them.
+    pub score: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A duet at 300 BPM (20 beats per second): the second part sings a
+    /// third above the first.
+    const DUET: &str = "#TITLE:Duet\n#ARTIST:Test\n#BPM:300\n#GAP:500\n#P1:Ann\n#P2:Bob\n\
+                        P1\n: 0 4 0 do\n: 4 4 2 re\nP2\n: 0 4 4 mi\n: 4 4 5 fa\nE\n";
+
+    /// Sing `part`'s melody on a stream whose clock starts `offset_sec`
+    /// before the song, at 100 frames per second.
+    fn sing(singer: &mut SingerScoring, part: usize, offset_sec: f64) {
+        let song = Song::parse(DUET.as_bytes()).unwrap();
+        let notes: Vec<_> = song.tracks[part].notes().cloned().collect();
+        for frame in 0..150 {
+            let song_sec = (frame as f64 + 0.5) * 0.01;
+            let beat = song.clock.sec_to_beat(song_sec).floor() as i32;
+            let midi = notes
+                .iter()
+                .find(|note| (note.start_beat..note.end_beat()).contains(&beat))
+                .map(|note| note.midi() as f32);
+            singer.push(song_sec + offset_sec, midi);
+        }
+    }
+
+    fn config(offset_sec: f64) -> ScoringConfig {
+        ScoringConfig {
+            song_start_sec: offset_sec,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn singers_are_scored_on_their_own_parts_and_clocks() {
+        let song = Song::parse(DUET.as_bytes()).unwrap();
+        assert!(song.is_duet());
+        let singer = |part, offset_sec| SingerScoring::new(&song, part, config(offset_sec));
+        let mut ann = singer(PartChoice::Track(0), 2.0).unwrap();
+        let mut bob = singer(PartChoice::Track(1), 7.5).unwrap();
+        let mut wrong = singer(PartChoice::Track(1), 0.0).unwrap();
+        assert!(singer(PartChoice::Track(2), 0.0).is_err());
+
+        sing(&mut ann, 0, 2.0);
+        sing(&mut bob, 1, 7.5);
+        sing(&mut wrong, 0, 0.0);
+        let results: Vec<_> = [(&mut ann, "Ann"), (&mut bob, "Bob"), (&mut wrong, "Cy")]
+            .into_iter()
+            .map(|(singer, name)| {
+                singer.finish();
+                singer.result(name)
+            })
+            .collect();
+        let summary: Vec<_> = results.iter().map(|r| (r.name.as_str(), r.track, r.score)).collect();
+        assert_eq!(summary, [("Ann", 0, 10_000), ("Bob", 1, 10_000), ("Cy", 1, 0)]);
+        let events = bob.drain_events();
+        assert!(events.iter().all(|event| event.track == 1));
+        assert!((events[0].song_time_sec - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn harmony_singers_get_the_part_they_sing_best() {
+        let song = Song::parse(DUET.as_bytes()).unwrap();
+        let mut singer = SingerScoring::new(&song, PartChoice::Harmony, config(0.0)).unwrap();
+        assert_eq!(singer.sessions().len(), 2);
+        sing(&mut singer, 1, 0.0);
+        singer.finish();
+        assert_eq!((singer.best().track(), singer.totals().rounded()), (1, 10_000));
+        assert_eq!(singer.sessions()[0].totals().rounded(), 0);
+
+        // Events of both parts arrive in song time order.
+        let events = singer.drain_events();
+        let tracks: Vec<u32> = events.iter().map(|event| event.track).collect();
+        assert_eq!(tracks, [0, 1, 0, 0, 1, 1]);
+        assert!(events.windows(2).all(|w| w[0].song_time_sec <= w[1].song_time_sec));
+    }
+}
@@ -0,0 +1,244 @@
+//! Syllable detection for rap notes.
+//!
+//! Rap is often spoken or whispered, so the pitch tracker alone misses much of
+//! it. A frame counts as vocal activity when it is voiced or its level stands
+//! clear of the room noise floor, and a syllable starts (an onset) where
+//! activity begins or the level jumps within ongoing activity. The noise floor
+//! is the minimum frame level over a sliding window, as in the VAD, unless the
+//! frames carry the VAD's own estimate.
+
+use std::collections::VecDeque;
+
+use crate::pyin::snapshot::{StateReader, StateWriter};
+use crate::pyin::PyinError;
+
+#[derive(Debug, Clone)]
+pub struct RapConfig {
+    /// Level above the noise floor at which an unvoiced frame counts as
+    /// vocal activity.
+    pub activity_margin_db: f32,
+    /// Frames below this level are never active, whatever the noise floor.
+    pub min_level_dbfs: f32,
+    /// Rise in level over `onset_window_sec` that starts a new syllable while
+    /// the singer is already active.
+    pub onset_rise_db: f32,
+    pub onset_window_sec: f32,
+    /// Onsets closer than this to the previous one belong to the same
+    /// syllable.
+    pub min_onset_interval_sec: f32,
+    /// How early a syllable may start and still count for a rap note.
+    pub onset_tolerance_sec: f32,
+    /// Window over which the noise floor is the minimum frame level.
+    pub noise_window_sec: f32,
+    /// Upper bound on the noise floor until a full window has been seen.
+    pub initial_noise_floor_dbfs: f32,
+}
+
+impl Default for RapConfig {
+    fn default() -> Self {
+        Self {
+            activity_margin_db: 12.0,
+            min_level_dbfs: -60.0,
+            onset_rise_db: 6.0,
+            onset_window_sec: 0.06,
+            min_onset_interval_sec: 0.08,
+            onset_tolerance_sec: 0.1,
+            noise_window_sec: 5.0,
+            initial_noise_floor_dbfs: -70.0,
+        }
+    }
+}
+
+/// Level of one frame, as measured by the level meter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameLevel {
+    pub rms_dbfs: f32,
+    /// Noise floor estimated by the VAD, when enabled.
+    pub noise_floor_dbfs: Option<f32>,
+}
+
+/// Detector output for one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SyllableFrame {
+    /// The singer is voicing something.
+    pub active: bool,
+    /// A syllable starts at this frame.
+    pub onset: bool,
+}
+
+/// Minimum of the values pushed within the last `window_sec`.
+#[derive(Debug, Clone, Default)]
+struct SlidingMin {
+    /// Increasing values with their times; the front is the minimum.
+    values: VecDeque<(f64, f32)>,
+}
+
+impl SlidingMin {
+    fn push(&mut self, time_sec: f64, value: f32, window_sec: f32) -> f32 {
+        while self.values.back().is_some_and(|&(_, v)| v >= value) {
+            self.values.pop_back();
+        }
+        self.values.push_back((time_sec, value));
+        while self.values.front().is_some_and(|&(t, _)| t <= time_sec - window_sec as f64) {
+            self.values.pop_front();
+        }
+        self.values.front().map_or(value, |&(_, v)| v)
+    }
+
+    fn save_state(&self, out: &mut StateWriter) {
+        out.usize(self.values.len());
+        for &(time_sec, value) in &self.values {
+            out.f64(time_sec);
+            out.f32(value);
+        }
+    }
+
+    fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        self.values.clear();
+        let len = input.usize()?;
+        for _ in 0..len {
+            self.values.push_back((input.f64()?, input.f32()?));
+        }
+        Ok(())
+    }
+}
+
+/// Finds vocal activity and syllable onsets in a stream of frames.
+#[derive(Debug, Clone)]
+pub struct SyllableDetector {
+    cfg: RapConfig,
+    noise: SlidingMin,
+    recent: SlidingMin,
+    first_time_sec: Option<f64>,
+    last_onset_sec: f64,
+    was_active: bool,
+}
+
+impl SyllableDetector {
+    pub fn new(cfg: RapConfig) -> Self {
+        Self {
+            cfg,
+            noise: SlidingMin::default(),
+            recent: SlidingMin::default(),
+            first_time_sec: None,
+            last_onset_sec: f64::NEG_INFINITY,
+            was_active: false,
+        }
+    }
+
+    /// Classify the frame at `time_sec`. Without a `level`, activity is the
+    /// pitch tracker's voicing and syllables start where voicing does.
+    /// Frames must arrive in time order.
+    pub fn push(
+        &mut self,
+        time_sec: f64,
+        voiced: bool,
+        level: Option<FrameLevel>,
+    ) -> SyllableFrame {
+        let cfg = &self.cfg;
+        let (active, rise) = match level {
+            Some(level) => {
+                let first = *self.first_time_sec.get_or_insert(time_sec);
+                let mut floor = self.noise.push(time_sec, level.rms_dbfs, cfg.noise_window_sec);
+                if time_sec - first < cfg.noise_window_sec as f64 {
+                    floor = floor.min(cfg.initial_noise_floor_dbfs);
+                }
+                let floor = level.noise_floor_dbfs.unwrap_or(floor);
+                let recent_min = self.recent.push(time_sec, level.rms_dbfs, cfg.onset_window_sec);
+                let audible = level.rms_dbfs >= cfg.min_level_dbfs
+                    && level.rms_dbfs >= floor + cfg.activity_margin_db;
+                (voiced || audible, level.rms_dbfs - recent_min >= cfg.onset_rise_db)
+            }
+            None => (voiced, false),
+        };
+        let onset = active
+            && (!self.was_active || rise)
+            && time_sec - self.last_onset_sec >= cfg.min_onset_interval_sec as f64;
+        if onset {
+            self.last_onset_sec = time_sec;
+        }
+        self.was_active = active;
+        SyllableFrame { active, onset }
+    }
+
+    pub(crate) fn save_state(&self, out: &mut StateWriter) {
+        self.noise.save_state(out);
+        self.recent.save_state(out);
+        out.option(self.first_time_sec.as_ref(), |out, time_sec| out.f64(*time_sec));
+        out.f64(self.last_onset_sec);
+        out.bool(self.was_active);
+    }
+
+    pub(crate) fn load_state(&mut self, input: &mut StateReader) -> Result<(), PyinError> {
+        self.noise.load_state(input)?;
+        self.recent.load_state(input)?;
+        self.first_time_sec = input.option(|input| input.f64())?;
+        self.last_onset_sec = input.f64()?;
+        self.was_active = input.bool()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the detector over levels 10 ms apart, all unvoiced.
+    fn detect(levels: &[f32], floor: Option<f32>) -> Vec<SyllableFrame> {
+        let mut detector = SyllableDetector::new(RapConfig::default());
+        levels
+            .iter()
+            .enumerate()
+            .map(|(i, &rms_dbfs)| {
+                let level = FrameLevel {
+                    rms_dbfs,
+                    noise_floor_dbfs: floor,
+                };
+                detector.push(i as f64 * 0.01, false, Some(level))
+            })
+            .collect()
+    }
+
+    fn onsets(frames: &[SyllableFrame]) -> Vec<usize> {
+        frames.iter().enumerate().filter(|(_, f)| f.onset).map(|(i, _)| i).collect()
+    }
+
+    #[test]
+    fn unvoiced_syllables_above_the_noise_floor_are_detected() {
+        // Room noise at -65 dB, two spoken syllables at -25 dB.
+        let mut levels = vec![-65.0; 50];
+        levels[10..25].fill(-25.0);
+        levels[35..45].fill(-25.0);
+        let frames = detect(&levels, None);
+        assert_eq!(onsets(&frames), [10, 35]);
+        assert!(frames[10..25].iter().all(|f| f.active));
+        assert!(!frames[25].active && !frames[5].active);
+
+        // Too quiet to be the singer, whatever the floor.
+        assert!(onsets(&detect(&[-100.0, -70.0, -70.0, -100.0], None)).is_empty());
+        // The VAD's floor takes precedence.
+        assert!(onsets(&detect(&levels, Some(-30.0))).is_empty());
+    }
+
+    #[test]
+    fn level_jumps_start_syllables_within_continuous_speech() {
+        // Two syllables run together, with a dip between them.
+        let mut levels = vec![-65.0; 40];
+        levels[5..15].fill(-20.0);
+        levels[15..18].fill(-32.0);
+        levels[18..30].fill(-20.0);
+        // A flicker right after an onset is the same syllable.
+        levels[6] = -30.0;
+        assert_eq!(onsets(&detect(&levels, None)), [5, 18]);
+
+        // Held voicing is one syllable; voicing onsets count without levels.
+        let mut detector = SyllableDetector::new(RapConfig::default());
+        let voiced = [false, true, true, true, false, false, false, false, false, false, true];
+        let frames: Vec<_> = voiced
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| detector.push(i as f64 * 0.01, v, None))
+            .collect();
+        assert_eq!(onsets(&frames), [1, 10]);
+    }
+}
@@ -0,0 +1,307 @@
+//! Karaoke charts: the notes a singer is scored against.
+//!
+//! A `Song` holds the metadata of a chart and one `NoteTrack` per part (two
+//! for duets). Notes are placed on beats; `SongClock` maps beats to seconds
+//! of the audio file. Charts are read from UltraStar .txt files with
+//! `Song::parse`.
+
+mod ultrastar;
+
+use crate::pyin::TargetNote;
+
+pub use ultrastar::SongParseError;
+
+/// MIDI note of UltraStar pitch 0 (C4).
+pub const ULTRASTAR_PITCH_OFFSET: i32 = 60;
+
+/// Version of the UltraStar file format, from the `#VERSION` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FormatVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl FormatVersion {
+    /// Files without a `#VERSION` header follow the 0.3 conventions.
+    pub const LEGACY: Self = Self::new(0, 3, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+}
+
+/// Text encoding a chart was read with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Cp1252,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteKind {
+    /// `:` sung note.
+    Normal,
+    /// `*` sung note worth extra points.
+    Golden,
+    /// `F` note that is not scored.
+    Freestyle,
+    /// `R` note scored on rhythm only.
+    Rap,
+    /// `G` rap note worth extra points.
+    RapGolden,
+}
+
+impl NoteKind {
+    pub fn is_golden(self) -> bool {
+        matches!(self, Self::Golden | Self::RapGolden)
+    }
+
+    pub fn is_rap(self) -> bool {
+        matches!(self, Self::Rap | Self::RapGolden)
+    }
+
+    /// Whether the singer's pitch is compared against the note.
+    pub fn is_pitched(self) -> bool {
+        matches!(self, Self::Normal | Self::Golden)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub kind: NoteKind,
+    /// Absolute beat the note starts on, also in `#RELATIVE` files.
+    pub start_beat: i32,
+    pub length_beats: i32,
+    /// UltraStar pitch: semitones above C4.
+    pub pitch: i32,
+    /// Syllable as written, including leading spaces that separate words and
+    /// `~` continuations.
+    pub text: String,
+}
+
+impl Note {
+    pub fn end_beat(&self) -> i32 {
+        self.start_beat + self.length_beats
+    }
+
+    /// Pitch as a MIDI note, on the scale of `midi::midi_from_hz`.
+    pub fn midi(&self) -> i32 {
+        self.pitch + ULTRASTAR_PITCH_OFFSET
+    }
+}
+
+/// A lyric line: the notes between two `-` line breaks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub notes: Vec<Note>,
+    /// Beat of the `-` ending the line; `None` for the last line of a track.
+    pub break_beat: Option<i32>,
+}
+
+impl Line {
+    pub fn start_beat(&self) -> i32 {
+        self.notes.first().map_or(0, |n| n.start_beat)
+    }
+
+    pub fn end_beat(&self) -> i32 {
+        self.notes.iter().map(Note::end_beat).max().unwrap_or(0)
+    }
+}
+
+/// The notes of one singer's part.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NoteTrack {
+    /// Singer name from `#P1`/`#P2` (or `#DUETSINGERP1`/`#DUETSINGERP2`).
+    pub singer: Option<String>,
+    /// In time order, as are the notes of each line.
+    pub lines: Vec<Line>,
+}
+
+impl NoteTrack {
+    pub fn notes(&self) -> impl Iterator<Item = &Note> {
+        self.lines.iter().flat_map(|line| line.notes.iter())
+    }
+}
+
+/// Tempo section starting at `start_beat`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TempoChange {
+    pub start_beat: f64,
+    /// As written in the file; UltraStar beats are quarters of these.
+    pub bpm: f64,
+}
+
+/// Maps chart beats to seconds of the audio file.
+///
+/// UltraStar counts four beats per `#BPM` beat, starting `#GAP` ms into the
+/// audio. Old charts may change tempo mid-song with `B` lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SongClock {
+    pub gap_ms: f64,
+    /// Sorted by `start_beat`; the first section starts at beat 0.
+    pub tempo: Vec<TempoChange>,
+}
+
+impl SongClock {
+    pub fn new(bpm: f64, gap_ms: f64) -> Self {
+        Self {
+            gap_ms,
+            tempo: vec![TempoChange {
+                start_beat: 0.0,
+                bpm,
+            }],
+        }
+    }
+
+    fn beat_sec(bpm: f64) -> f64 {
+        60.0 / (bpm * 4.0)
+    }
+
+    /// Audio time at which `beat` is sung. Beats before the first tempo
+    /// section use its tempo.
+    pub fn beat_to_sec(&self, beat: f64) -> f64 {
+        let mut sec = self.gap_ms / 1000.0;
+        let mut from = 0.0;
+        for (i, section) in self.tempo.iter().enumerate() {
+            let beat_sec = Self::beat_sec(section.bpm);
+            match self.tempo.get(i + 1) {
+                Some(next) if beat > next.start_beat => {
+                    sec += (next.start_beat - from) * beat_sec;
+                    from = next.start_beat;
+                }
+                _ => return sec + (beat - from) * beat_sec,
+            }
+        }
+        sec
+    }
+
+    /// Beat sung at audio time `sec`, fractional.
+    pub fn sec_to_beat(&self, sec: f64) -> f64 {
+        let mut elapsed = sec - self.gap_ms / 1000.0;
+        let mut beat = 0.0;
+        for (i, section) in self.tempo.iter().enumerate() {
+            let beat_sec = Self::beat_sec(section.bpm);
+            match self.tempo.get(i + 1) {
+                Some(next) if elapsed > (next.start_beat - beat) * beat_sec => {
+                    elapsed -= (next.start_beat - beat) * beat_sec;
+                    beat = next.start_beat;
+                }
+                _ => return beat + elapsed / beat_sec,
+            }
+        }
+        beat
+    }
+}
+
+/// Header fields of a chart.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SongInfo {
+    pub title: String,
+    pub artist: String,
+    /// Audio file to play: `#AUDIO`, or `#MP3` in files that lack it.
+    pub audio: Option<String>,
+    /// Isolated vocals (`#VOCALS`), for bleed cancellation.
+    pub vocals: Option<String>,
+    pub video: Option<String>,
+    pub cover: Option<String>,
+    pub background: Option<String>,
+    pub language: Option<String>,
+    pub genre: Option<String>,
+    pub year: Option<String>,
+    pub edition: Option<String>,
+    pub creator: Option<String>,
+    /// Offset of the video against the audio (`#VIDEOGAP`).
+    pub video_gap_sec: f64,
+    /// Where playback starts (`#START`).
+    pub start_sec: f64,
+    /// Where playback ends (`#END`), if before the end of the audio.
+    pub end_ms: Option<f64>,
+    /// Headers not interpreted above, as (upper-case key, value).
+    pub extra: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Song {
+    pub info: SongInfo,
+    pub version: FormatVersion,
+    /// Whether note beats were written relative to their line (`#RELATIVE`).
+    /// The parsed beats are always absolute.
+    pub relative: bool,
+    pub encoding: TextEncoding,
+    pub clock: SongClock,
+    /// One track, or one per part of a duet.
+    pub tracks: Vec<NoteTrack>,
+}
+
+impl Song {
+    /// Parse an UltraStar .txt chart, detecting UTF-8 or CP1252 text.
+    pub fn parse(bytes: &[u8]) -> Result<Self, SongParseError> {
+        ultrastar::parse(bytes)
+    }
+
+    pub fn is_duet(&self) -> bool {
+        self.tracks.len() > 1
+    }
+
+    /// Pitched notes of `track` as the expected melody of the score prior, in
+    /// seconds of the song's audio; pass the stream time playback started at as
+    /// `ScorePriorConfig::song_start_sec`. Chart pitches are relative to the
+    /// tuning of the song, so they match a detector whose `reference_a4_hz` is
+    /// set to that tuning.
+    pub fn target_notes(&self, track: usize) -> Vec<TargetNote> {
+        self.tracks
+            .get(track)
+            .into_iter()
+            .flat_map(NoteTrack::notes)
+            .filter(|note| note.kind.is_pitched())
+            .map(|note| TargetNote {
+                start_sec: self.clock.beat_to_sec(note.start_beat as f64),
+                end_sec: self.clock.beat_to_sec(note.end_beat() as f64),
+                midi: note.midi() as f32,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pyin::midi::midi_from_hz;
+
+    #[test]
+    fn clock_converts_beats_both_ways_across_tempo_changes() {
+        let mut clock = SongClock::new(300.0, 1500.0);
+        // 300 BPM: 20 UltraStar beats per second.
+        assert!((clock.beat_to_sec(0.0) - 1.5).abs() < 1e-9);
+        assert!((clock.beat_to_sec(40.0) - 3.5).abs() < 1e-9);
+        assert!((clock.sec_to_beat(3.5) - 40.0).abs() < 1e-9);
+        assert!((clock.beat_to_sec(-20.0) - 0.5).abs() < 1e-9);
+
+        clock.tempo.push(TempoChange {
+            start_beat: 40.0,
+            bpm: 150.0,
+        });
+        assert!((clock.beat_to_sec(40.0) - 3.5).abs() < 1e-9);
+        assert!((clock.beat_to_sec(50.0) - 4.5).abs() < 1e-9);
+        assert!((clock.beat_to_sec(20.0) - 2.5).abs() < 1e-9);
+        for beat in [-10.0, 0.0, 12.5, 40.0, 41.0, 90.0] {
+            let back = clock.sec_to_beat(clock.beat_to_sec(beat));
+            assert!((back - beat).abs() < 1e-9, "{} came back as {}", beat, back);
+        }
+    }
+
+    #[test]
+    fn note_pitches_match_detected_midi_notes() {
+        let note = Note {
+            kind: NoteKind::Normal,
+            start_beat: 0,
+            length_beats: 4,
+            pitch: 9,
+            text: "la".to_string(),
+        };
+        assert_eq!(note.midi(), midi_from_hz(440.0) as i32);
+        let low = Note { pitch: -17, ..note };
+        assert_eq!(low.midi(), midi_from_hz(110.0) as i32 - 2);
+    }
+}
@@ -0,0 +1,604 @@
+//! UltraStar .txt charts.
+//!
+//! A chart is a block of `#KEY:VALUE` headers followed by one line per note
+//! (`: start length pitch text`, with `*` golden, `F` freestyle, `R` rap and
+//! `G` golden rap notes), `-` line breaks, optional `P1`/`P2` duet markers and
+//! an `E` end marker. Files from format 0.3 up to 2.0 are read: before 1.0 the
+//! text may be CP1252 and beats may be `#RELATIVE` to their line, and old
+//! charts may change tempo with `B` lines.
+
+use std::fmt;
+
+use log::warn;
+
+use super::{
+    FormatVersion, Line, Note, NoteKind, NoteTrack, Song, SongClock, SongInfo, TempoChange,
+    TextEncoding,
+};
+
+/// Newest format version whose files are read.
+const MAX_MAJOR_VERSION: u32 = 2;
+/// Format version from which charts are always UTF-8 and never relative.
+const UTF8_VERSION: FormatVersion = FormatVersion::new(1, 0, 0);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SongParseError {
+    /// 1-based line of the file, or 0 for the file as a whole.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SongParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for SongParseError {}
+
+fn error<T>(line: usize, message: impl Into<String>) -> Result<T, SongParseError> {
+    Err(SongParseError {
+        line,
+        message: message.into(),
+    })
+}
+
+pub(super) fn parse(bytes: &[u8]) -> Result<Song, SongParseError> {
+    let (text, encoding) = decode(bytes);
+    let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line)).peekable();
+
+    let mut headers = Headers::default();
+    while let Some(&(number, line)) = lines.peek() {
+        if let Some(header) = line.strip_prefix('#') {
+            headers.read(number, header)?;
+        } else if !line.trim().is_empty() {
+            break;
+        }
+        lines.next();
+    }
+    let header = headers.finish()?;
+
+    let mut body = Body::new(header.relative);
+    let mut clock = SongClock::new(header.bpm, header.gap_ms);
+    for (number, line) in lines {
+        let line = line.trim_start();
+        let Some(tag) = line.chars().next() else {
+            continue;
+        };
+        let rest = &line[tag.len_utf8()..];
+        match tag {
+            ':' | '*' | 'F' | 'R' | 'G' => {
+                let kind = match tag {
+                    ':' => NoteKind::Normal,
+                    '*' => NoteKind::Golden,
+                    'F' => NoteKind::Freestyle,
+                    'R' => NoteKind::Rap,
+                    _ => NoteKind::RapGolden,
+                };
+                body.note(number, kind, rest)?;
+            }
+            '-' => body.line_break(number, rest)?,
+            'P' => body.part(number, rest)?,
+            'B' => {
+                let mut fields = Fields::new(number, rest);
+                let start_beat = body.beat(number, fields.int()?)? as f64;
+                let bpm = fields.decimal()?;
+                if bpm <= 0.0 {
+                    return error(number, "tempo must be > 0");
+                }
+                if clock.tempo.last().is_some_and(|last| start_beat <= last.start_beat) {
+                    return error(number, "tempo changes must move forward");
+                }
+                clock.tempo.push(TempoChange { start_beat, bpm });
+            }
+            'E' => break,
+            '#' => warn!("ignoring header after the notes on line {}", number),
+            _ => return error(number, format!("unrecognised line {:?}", line)),
+        }
+    }
+
+    let mut tracks = body.finish()?;
+    for (track, singer) in tracks.iter_mut().zip(header.singers) {
+        track.singer = singer;
+    }
+    Ok(Song {
+        info: header.info,
+        version: header.version,
+        relative: header.relative,
+        encoding,
+        clock,
+        tracks,
+    })
+}
+
+/// Decode `bytes` as UTF-8 when they are valid UTF-8 (or start with its byte
+/// order mark) and not declared CP1252 by a pre-1.0 `#ENCODING` header, and
+/// as CP1252 otherwise.
+fn decode(bytes: &[u8]) -> (String, TextEncoding) {
+    if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        return (String::from_utf8_lossy(rest).into_owned(), TextEncoding::Utf8);
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        let declared_cp1252 = declared_encoding(bytes).is_some_and(|encoding| {
+            let encoding = encoding.to_ascii_uppercase();
+            encoding == "CP1252" || encoding == "WINDOWS-1252"
+        });
+        let legacy = declared_version(bytes).is_none_or(|version| version < UTF8_VERSION);
+        if !(declared_cp1252 && legacy) {
+            return (text.to_string(), TextEncoding::Utf8);
+        }
+    }
+    (bytes.iter().map(|&b| cp1252_char(b)).collect(), TextEncoding::Cp1252)
+}
+
+/// Value of the header `key` in the leading header block, read from raw
+/// bytes before the encoding is known.
+fn raw_header<'a>(bytes: &'a [u8], key: &str) -> Option<&'a str> {
+    bytes
+        .split(|&b| b == b'\n')
+        .take_while(|line| line.first().is_none_or(|&b| b == b'#' || b == b'\r'))
+        .filter_map(|line| std::str::from_utf8(line.strip_prefix(b"#")?).ok())
+        .find_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            k.trim().eq_ignore_ascii_case(key).then(|| v.trim())
+        })
+}
+
+fn declared_encoding(bytes: &[u8]) -> Option<&str> {
+    raw_header(bytes, "ENCODING")
+}
+
+fn declared_version(bytes: &[u8]) -> Option<FormatVersion> {
+    raw_header(bytes, "VERSION").and_then(parse_version)
+}
+
+/// Code points of CP1252 bytes 0x80..=0x9F; the five unassigned bytes map to
+/// the C1 control of the same value, as browsers do.
+const CP1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+fn cp1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => CP1252_HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+fn parse_version(value: &str) -> Option<FormatVersion> {
+    let mut parts = value.trim().split('.').map(|part| part.trim().parse::<u32>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some(FormatVersion::new(major, minor, patch))
+}
+
+/// Numbers in headers may use a decimal comma.
+fn parse_decimal(value: &str) -> Option<f64> {
+    value.trim().replace(',', ".").parse().ok().filter(|v: &f64| v.is_finite())
+}
+
+#[derive(Default)]
+struct Headers {
+    info: SongInfo,
+    mp3: Option<String>,
+    bpm: Option<f64>,
+    gap_ms: f64,
+    version: Option<FormatVersion>,
+    relative: bool,
+    singers: [Option<String>; 2],
+}
+
+struct Header {
+    info: SongInfo,
+    bpm: f64,
+    gap_ms: f64,
+    version: FormatVersion,
+    relative: bool,
+    singers: [Option<String>; 2],
+}
+
+impl Headers {
+    fn read(&mut self, number: usize, header: &str) -> Result<(), SongParseError> {
+        let Some((key, value)) = header.split_once(':') else {
+            warn!("ignoring header without a value on line {}", number);
+            return Ok(());
+        };
+        let key = key.trim().to_ascii_uppercase();
+        let value = value.trim();
+        let decimal = || match parse_decimal(value) {
+            Some(v) => Ok(v),
+            None => error(number, format!("#{} is not a number: {:?}", key, value)),
+        };
+        let text = || Some(value.to_string()).filter(|v| !v.is_empty());
+        let info = &mut self.info;
+        match key.as_str() {
+            "TITLE" => info.title = value.to_string(),
+            "ARTIST" => info.artist = value.to_string(),
+            "AUDIO" => info.audio = text(),
+            "MP3" => self.mp3 = text(),
+            "VOCALS" => info.vocals = text(),
+            "VIDEO" => info.video = text(),
+            "COVER" => info.cover = text(),
+            "BACKGROUND" => info.background = text(),
+            "LANGUAGE" => info.language = text(),
+            "GENRE" => info.genre = text(),
+            "YEAR" => info.year = text(),
+            "EDITION" => info.edition = text(),
+            "CREATOR" | "AUTHOR" => info.creator = text(),
+            "BPM" => self.bpm = Some(decimal()?),
+            "GAP" => self.gap_ms = decimal()?,
+            "VIDEOGAP" => info.video_gap_sec = decimal()?,
+            "START" => info.start_sec = decimal()?,
+            "END" => info.end_ms = Some(decimal()?),
+            "VERSION" => match parse_version(value) {
+                Some(version) if version.major <= MAX_MAJOR_VERSION => {
+                    self.version = Some(version)
+                }
+                Some(_) => return error(number, format!("unsupported format version {}", value)),
+                None => return error(number, format!("bad format version {:?}", value)),
+            },
+            "RELATIVE" => {
+                self.relative = ["YES", "TRUE", "1"].iter().any(|v| value.eq_ignore_ascii_case(v))
+            }
+            "P1" | "DUETSINGERP1" => self.singers[0] = text(),
+            "P2" | "DUETSINGERP2" => self.singers[1] = text(),
+            // Already applied while decoding.
+            "ENCODING" => {}
+            _ => info.extra.push((key, value.to_string())),
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Header, SongParseError> {
+        let version = self.version.unwrap_or(FormatVersion::LEGACY);
+        let mut info = self.info;
+        info.audio = info.audio.or(self.mp3);
+        let bpm = match self.bpm {
+            Some(bpm) if bpm > 0.0 => bpm,
+            Some(_) => return error(0, "#BPM must be > 0"),
+            None => return error(0, "missing #BPM header"),
+        };
+        if self.relative && version >= UTF8_VERSION {
+            return error(0, "#RELATIVE is not allowed from format version 1.0");
+        }
+        Ok(Header {
+            info,
+            bpm,
+            gap_ms: self.gap_ms,
+            version,
+            relative: self.relative,
+            singers: self.singers,
+        })
+    }
+}
+
+/// Whitespace-separated fields of a note, line break or tempo line.
+struct Fields<'a> {
+    number: usize,
+    rest: &'a str,
+}
+
+impl<'a> Fields<'a> {
+    fn new(number: usize, rest: &'a str) -> Self {
+        Self { number, rest }
+    }
+
+    fn token(&mut self) -> Option<&'a str> {
+        let rest = self.rest.trim_start();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        self.rest = &rest[end..];
+        Some(&rest[..end]).filter(|token| !token.is_empty())
+    }
+
+    fn int(&mut self) -> Result<i32, SongParseError> {
+        let number = self.number;
+        match self.token() {
+            Some(token) => token
+                .parse()
+                .or_else(|_| error(number, format!("expected a whole number, found {:?}", token))),
+            None => error(number, "missing number"),
+        }
+    }
+
+    fn optional_int(&mut self) -> Result<Option<i32>, SongParseError> {
+        if self.rest.trim().is_empty() {
+            Ok(None)
+        } else {
+            self.int().map(Some)
+        }
+    }
+
+    fn decimal(&mut self) -> Result<f64, SongParseError> {
+        let number = self.number;
+        match self.token() {
+            Some(token) => parse_decimal(token)
+                .map_or_else(|| error(number, format!("expected a number, found {:?}", token)), Ok),
+            None => error(number, "missing number"),
+        }
+    }
+
+    /// The syllable: everything after the single separator that follows the
+    /// last number.
+    fn text(self) -> String {
+        self.rest.strip_prefix([' ', '\t']).unwrap_or(self.rest).to_string()
+    }
+}
+
+/// Notes and line breaks, assigned to the parts selected by the last `P`
+/// marker.
+struct Body {
+    relative: bool,
+    /// Start of the current line in `#RELATIVE` charts.
+    line_base: i32,
+    /// Selected parts, 0-based.
+    parts: Vec<usize>,
+    duet: bool,
+    tracks: Vec<NoteTrack>,
+    /// Notes of the line being read, per track.
+    pending: Vec<Vec<Note>>,
+}
+
+impl Body {
+    fn new(relative: bool) -> Self {
+        Self {
+            relative,
+            line_base: 0,
+            parts: vec![0],
+            duet: false,
+            tracks: vec![NoteTrack::default()],
+            pending: vec![Vec::new()],
+        }
+    }
+
+    /// Absolute beat of `beat` as written.
+    fn beat(&self, number: usize, beat: i32) -> Result<i32, SongParseError> {
+        if !self.relative {
+            return Ok(beat);
+        }
+        match self.line_base.checked_add(beat) {
+            Some(beat) => Ok(beat),
+            None => error(number, "beat out of range"),
+        }
+    }
+
+    fn note(&mut self, number: usize, kind: NoteKind, rest: &str) -> Result<(), SongParseError> {
+        let mut fields = Fields::new(number, rest);
+        let start_beat = self.beat(number, fields.int()?)?;
+        let length_beats = fields.int()?;
+        let pitch = fields.int()?;
+        if length_beats < 0 {
+            return error(number, "note length must be >= 0");
+        }
+        if start_beat.checked_add(length_beats).is_none() {
+            return error(number, "note ends beyond the last beat");
+        }
+        let note = Note {
+            kind,
+            start_beat,
+            length_beats,
+            pitch,
+            text: fields.text(),
+        };
+        for &part in &self.parts {
+            self.pending[part].push(note.clone());
+        }
+        Ok(())
+    }
+
+    fn line_break(&mut self, number: usize, rest: &str) -> Result<(), SongParseError> {
+        let mut fields = Fields::new(number, rest);
+        let written = fields.int()?;
+        let shift = fields.optional_int()?;
+        let break_beat = self.beat(number, written)?;
+        for &part in &self.parts {
+            let notes = std::mem::take(&mut self.pending[part]);
+            if !notes.is_empty() {
+                self.tracks[part].lines.push(Line {
+                    notes,
+                    break_beat: Some(break_beat),
+                });
+            }
+        }
+        if self.relative {
+            self.line_base = self.beat(number, shift.unwrap_or(written))?;
+        }
+        Ok(())
+    }
+
+    fn part(&mut self, number: usize, rest: &str) -> Result<(), SongParseError> {
+        let parts = match rest.trim() {
+            "1" => vec![0],
+            "2" => vec![1],
+            // Sung by both singers.
+            "3" => vec![0, 1],
+            other => return error(number, format!("unknown part P{}", other)),
+        };
+        if !self.duet {
+            if !self.pending[0].is_empty() || !self.tracks[0].lines.is_empty() {
+                return error(number, "notes before the first part marker");
+            }
+            self.duet = true;
+            self.tracks.push(NoteTrack::default());
+            self.pending.push(Vec::new());
+        }
+        self.close_lines();
+        self.parts = parts;
+        self.line_base = 0;
+        Ok(())
+    }
+
+    /// End the lines of the selected parts without a line break.
+    fn close_lines(&mut self) {
+        for &part in &self.parts {
+            let notes = std::mem::take(&mut self.pending[part]);
+            if !notes.is_empty() {
+                self.tracks[part].lines.push(Line {
+                    notes,
+                    break_beat: None,
+                });
+            }
+        }
+    }
+
+    /// The tracks, with their notes in time order: charts may list them out
+    /// of order, but scoring walks them as the song plays.
+    fn finish(mut self) -> Result<Vec<NoteTrack>, SongParseError> {
+        self.close_lines();
+        if self.tracks.iter().all(|track| track.lines.is_empty()) {
+            return error(0, "chart has no notes");
+        }
+        for (index, track) in self.tracks.iter_mut().enumerate() {
+            for line in &mut track.lines {
+                line.notes.sort_by_key(|note| note.start_beat);
+            }
+            track.lines.sort_by_key(Line::start_beat);
+            if !track.notes().is_sorted_by_key(|note| note.start_beat) {
+                return error(0, format!("lines of track {} overlap", index + 1));
+            }
+        }
+        Ok(self.tracks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(song: &Song, track: usize, index: usize) -> &Note {
+        song.tracks[track].notes().nth(index).unwrap()
+    }
+
+    #[test]
+    fn reads_headers_notes_and_line_breaks() {
+        let chart = "#VERSION:1.1.0\r\n#TITLE:Sm\u{f6}rg\u{e5}s\r\n#ARTIST:Band\r\n\
+                     #MP3:old.mp3\r\n#AUDIO:song.ogg\r\n#VOCALS:vocals.ogg\r\n#BPM:300,5\r\n\
+                     #GAP:1500\r\n#MEDLEYSTARTBEAT:12\r\n\r\n\
+                     : 0 4 5 Hel\r\n* 4 4 7 lo\r\nF 8 2 0  world\r\n- 12\r\n\
+                     R 14 2 0 rap\r\nG 16 2 -3 ~\r\nE\r\n: 99 1 1 ignored\r\n";
+        let mut bytes = b"\xEF\xBB\xBF".to_vec();
+        bytes.extend_from_slice(chart.as_bytes());
+        let song = Song::parse(&bytes).unwrap();
+
+        assert_eq!(song.version, FormatVersion::new(1, 1, 0));
+        assert_eq!(song.encoding, TextEncoding::Utf8);
+        assert_eq!(song.info.title, "Sm\u{f6}rg\u{e5}s");
+        assert_eq!(song.info.audio.as_deref(), Some("song.ogg"));
+        assert_eq!(song.info.vocals.as_deref(), Some("vocals.ogg"));
+        assert_eq!(song.info.extra, vec![("MEDLEYSTARTBEAT".to_string(), "12".to_string())]);
+        assert_eq!(song.clock, SongClock::new(300.5, 1500.0));
+        assert!(!song.is_duet());
+
+        let lines = &song.tracks[0].lines;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].break_beat, Some(12));
+        assert_eq!(lines[1].break_beat, None);
+        assert_eq!((lines[0].start_beat(), lines[0].end_beat()), (0, 10));
+        let kinds: Vec<NoteKind> = song.tracks[0].notes().map(|n| n.kind).collect();
+        use NoteKind::*;
+        assert_eq!(kinds, [Normal, Golden, Freestyle, Rap, RapGolden]);
+        assert_eq!(note(&song, 0, 1).midi(), 67);
+        assert_eq!(note(&song, 0, 2).text, " world");
+        assert_eq!(note(&song, 0, 4).pitch, -3);
+        assert_eq!(song.tracks[0].notes().count(), 5);
+        // Only sung notes become score prior targets.
+        let targets = song.target_notes(0);
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].midi, 65.0);
+        assert!((targets[1].start_sec - song.clock.beat_to_sec(4.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn legacy_relative_cp1252_chart_with_tempo_change() {
+        let mut bytes = b"#TITLE:Caf\xE9 \x80\n#ARTIST:X\n#ENCODING:CP1252\n#BPM:200\n\
+                          #RELATIVE:yes\n"
+            .to_vec();
+        bytes.extend_from_slice(b": 0 2 0 Un\n: 2 2 2 d\xE9\n- 4 6\n: 0 3 4 deux\nB 3 100\n");
+        bytes.extend_from_slice(b"- 5\n: 1 1 0 x\n");
+        let song = Song::parse(&bytes).unwrap();
+
+        assert_eq!(song.version, FormatVersion::LEGACY);
+        assert_eq!(song.encoding, TextEncoding::Cp1252);
+        assert!(song.relative);
+        assert_eq!(song.info.title, "Caf\u{e9} \u{20AC}");
+        let starts: Vec<i32> = song.tracks[0].notes().map(|n| n.start_beat).collect();
+        assert_eq!(starts, [0, 2, 6, 12]);
+        let breaks: Vec<Option<i32>> = song.tracks[0].lines.iter().map(|l| l.break_beat).collect();
+        assert_eq!(breaks, [Some(4), Some(11), None]);
+        assert_eq!(note(&song, 0, 1).text, "d\u{e9}");
+        assert_eq!(
+            song.clock.tempo[1],
+            TempoChange {
+                start_beat: 9.0,
+                bpm: 100.0
+            }
+        );
+    }
+
+    #[test]
+    fn valid_utf8_is_not_reinterpreted_without_a_legacy_declaration() {
+        let chart = "#VERSION:1.0.0\n#ENCODING:CP1252\n#TITLE:Caf\u{e9}\n#BPM:100\n: 0 1 0 a\n";
+        let song = Song::parse(chart.as_bytes()).unwrap();
+        assert_eq!(song.encoding, TextEncoding::Utf8);
+        assert_eq!(song.info.title, "Caf\u{e9}");
+        // Undeclared, invalid UTF-8 falls back to CP1252.
+        let song = Song::parse(b"#TITLE:Caf\xE9\n#BPM:100\n: 0 1 0 a\n").unwrap();
+        assert_eq!(song.encoding, TextEncoding::Cp1252);
+        assert_eq!(song.info.title, "Caf\u{e9}");
+    }
+
+    #[test]
+    fn duet_parts_go_to_their_own_tracks() {
+        let chart = "#VERSION:1.0.0\n#BPM:120\n#P1:Ann\n#DUETSINGERP2:Bob\n\
+                     P1\n: 0 2 0 a\n- 4\n: 4 2 0 b\nP2\n: 8 2 5 c\nP3\n: 12 2 7 both\nE\n";
+        let song = Song::parse(chart.as_bytes()).unwrap();
+        assert!(song.is_duet());
+        assert_eq!(song.tracks[0].singer.as_deref(), Some("Ann"));
+        assert_eq!(song.tracks[1].singer.as_deref(), Some("Bob"));
+        let texts = |track: usize| -> Vec<String> {
+            song.tracks[track].notes().map(|n| n.text.clone()).collect()
+        };
+        assert_eq!(texts(0), ["a", "b", "both"]);
+        assert_eq!(texts(1), ["c", "both"]);
+        assert_eq!(song.tracks[0].lines.len(), 3);
+    }
+
+    #[test]
+    fn notes_and_lines_are_sorted_by_start() {
+        let chart = "#BPM:100\n: 6 1 0 d\n: 4 1 0 c\n- 8\n: 2 1 0 b\n: 0 1 0 a\n- 3\n\
+                     : 10 1 0 e\n";
+        let song = Song::parse(chart.as_bytes()).unwrap();
+        let texts: Vec<&str> = song.tracks[0].notes().map(|n| n.text.as_str()).collect();
+        assert_eq!(texts, ["a", "b", "c", "d", "e"]);
+        let breaks: Vec<Option<i32>> = song.tracks[0].lines.iter().map(|l| l.break_beat).collect();
+        assert_eq!(breaks, [Some(3), Some(8), None]);
+
+        let overlapping = "#BPM:100\n: 0 1 0 a\n: 6 1 0 b\n- 8\n: 4 1 0 c\n";
+        let error = Song::parse(overlapping.as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "lines of track 1 overlap");
+    }
+
+    #[test]
+    fn malformed_charts_report_the_line() {
+        let parse_error = |chart: &str| Song::parse(chart.as_bytes()).unwrap_err();
+        assert_eq!(parse_error("#TITLE:x\n: 0 1 0 a\n").message, "missing #BPM header");
+        assert_eq!(parse_error("#BPM:100\n: 0 1 0 a\n: 1 x 0 b\n").line, 3);
+        assert_eq!(parse_error("#BPM:100\n: 0 -1 0 a\n").line, 2);
+        assert_eq!(parse_error("#BPM:100\n: 0 1 0 a\nP1\n: 2 1 0 b\n").line, 3);
+        assert_eq!(parse_error("#BPM:100\n: 0 1 0 a\nX 1\n").line, 3);
+        assert_eq!(parse_error("#VERSION:3.0.0\n#BPM:100\n: 0 1 0 a\n").line, 1);
+        assert_eq!(parse_error("#BPM:100\nE\n").message, "chart has no notes");
+        assert_eq!(parse_error("#BPM:100\n: 0 1 0 a\n: 2147483647 10 0 x\n").line, 3);
+        let relative = "#RELATIVE:yes\n#BPM:100\n: 0 1 0 a\n- 2147483647\n: 1 1 0 b\n";
+        assert_eq!(parse_error(relative).line, 5);
+        let error = parse_error("#VERSION:1.0.0\n#RELATIVE:yes\n#BPM:100\n: 0 1 0 a\n");
+        assert_eq!(error.to_string(), "#RELATIVE is not allowed from format version 1.0");
+    }
+}
@@ -2,4 +2,8 @@
 mod api_integration_test;
 
 #[path = "pyin/pyin_integration.rs"]
+#[allow(clippy::field_reassign_with_default)]
 mod pyin_integration;
+
+#[path = "pyin/analysis_test.rs"]
+mod analysis_test;
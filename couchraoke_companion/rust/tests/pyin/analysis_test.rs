@@ -0,0 +1,84 @@
+use pyin_rs::pyin::{analyze_file, analyze_file_with_progress, BleedReference, FileAnalysisConfig};
+use std::path::Path;
+
+fn fixture(name: &str) -> String {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(name)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn median_midi(frames: &[pyin_rs::pyin::FrameEstimate]) -> f32 {
+    let mut midi: Vec<f32> = frames.iter().filter_map(|f| f.midi_fractional).collect();
+    assert!(!midi.is_empty(), "no voiced frames");
+    midi.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    midi[midi.len() / 2]
+}
+
+#[test]
+fn analyze_file_returns_final_contour_for_whole_file() {
+    let cfg = FileAnalysisConfig::default();
+    let mut progress = Vec::new();
+    let analysis =
+        analyze_file_with_progress(&fixture("E4_329Hz.wav"), &cfg, |p| progress.push(p)).unwrap();
+
+    assert!(analysis.duration_sec > 0.5);
+    assert!(analysis.frames.iter().all(|f| f.is_final));
+    assert_eq!(analysis.source_sample_rate_hz, 44_100);
+    let expected_frames = (analysis.duration_sec * cfg.pyin.sample_rate_hz as f64
+        / cfg.pyin.hop_size as f64) as usize;
+    assert!(
+        analysis.frames.len() + 8 >= expected_frames,
+        "frames {} expected ~{}",
+        analysis.frames.len(),
+        expected_frames
+    );
+    let median = median_midi(&analysis.frames);
+    assert!((median - 64.0).abs() < 0.5, "median midi {}", median);
+
+    assert!(!progress.is_empty());
+    assert!(progress
+        .windows(2)
+        .all(|w| w[1].processed_sec >= w[0].processed_sec));
+    let last = progress.last().unwrap();
+    assert!((last.processed_sec - analysis.duration_sec).abs() < 1e-6);
+}
+
+#[test]
+fn analyze_file_honours_time_range() {
+    let cfg = FileAnalysisConfig {
+        start_sec: 0.25,
+        end_sec: Some(0.75),
+        ..FileAnalysisConfig::default()
+    };
+    let analysis = analyze_file(&fixture("E4_329Hz.wav"), &cfg).unwrap();
+
+    let first = analysis.frames.first().unwrap();
+    let last = analysis.frames.last().unwrap();
+    assert!(
+        (first.time_sec - 0.25).abs() < 1e-3,
+        "first {}",
+        first.time_sec
+    );
+    assert!(last.time_sec < 0.75, "last {}", last.time_sec);
+    let median = median_midi(&analysis.frames);
+    assert!((median - 64.0).abs() < 0.5, "median midi {}", median);
+}
+
+#[test]
+fn analyze_file_rejects_invalid_range() {
+    let cfg = FileAnalysisConfig {
+        start_sec: 1.0,
+        end_sec: Some(0.5),
+        ..FileAnalysisConfig::default()
+    };
+    assert!(analyze_file(&fixture("E4_329Hz.wav"), &cfg).is_err());
+}
+
+#[test]
+fn bleed_reference_decodes_and_resamples_file() {
+    let reference = BleedReference::from_file(&fixture("E4_329Hz.wav"), 16_000).unwrap();
+    assert_eq!(reference.sample_rate_hz(), 16_000);
+    assert!((reference.duration_sec() - 1.0).abs() < 1e-3);
+}
@@ -1,15 +1,34 @@
 use hound::{SampleFormat, WavReader};
-use pyin_rs::pyin::{AudioAnalyzer, PitchConfig};
+use pyin_rs::pyin::{
+    api, AudioAnalyzer, BleedConfig, BleedReference, ChannelMix, FileAnalysisOptions, Party,
+    PcmFormat, PitchAlgorithm, PitchConfig, PitchEvent, PitchStreamOptions, ScoreStreamOptions,
+    SingerOptions,
+};
+use pyin_rs::scoring::{Difficulty, ScoreEventKind};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 
 fn stream_collect(bytes: &[u8], sample_rate_hz: u32, window_ms: u32, hop_ms: u32) -> Vec<u16> {
+    stream_collect_with(bytes, sample_rate_hz, window_ms, hop_ms, false, PitchAlgorithm::Pyin)
+}
+
+fn stream_collect_with(
+    bytes: &[u8],
+    sample_rate_hz: u32,
+    window_ms: u32,
+    hop_ms: u32,
+    octave_correction: bool,
+    algorithm: PitchAlgorithm,
+) -> Vec<u16> {
     let mut analyzer = AudioAnalyzer::new(PitchConfig {
         sample_rate_hz,
         update_interval_ms: hop_ms,
         window_size_ms: window_ms,
+        octave_correction_enabled: octave_correction,
+        algorithm,
+        ..PitchConfig::default()
     })
     .expect("create audio analyzer");
     let mut rng = StdRng::seed_from_u64(7);
@@ -25,6 +44,35 @@ fn stream_collect(bytes: &[u8], sample_rate_hz: u32, window_ms: u32, hop_ms: u32
     mids
 }
 
+fn collect_events(bytes: &[u8], sample_rate_hz: u32) -> Vec<PitchEvent> {
+    let mut analyzer = AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz,
+        update_interval_ms: 5,
+        vad_enabled: true,
+        ..PitchConfig::default()
+    })
+    .expect("create audio analyzer");
+    let mut events = Vec::new();
+    for chunk in bytes.chunks(3001) {
+        events.extend(analyzer.process_chunk_collect_events(chunk).expect("process chunk"));
+    }
+    events
+}
+
+/// The first report of each frame, dropping the final updates that follow
+/// live events.
+fn first_reports(events: &[PitchEvent]) -> Vec<&PitchEvent> {
+    let mut next_position = 0;
+    events
+        .iter()
+        .filter(|event| {
+            let first = event.sample_position >= next_position;
+            next_position = next_position.max(event.sample_position + 1);
+            first
+        })
+        .collect()
+}
+
 fn mode(values: &[u16]) -> Option<u16> {
     let mut counts = std::collections::BTreeMap::<u16, usize>::new();
     for &v in values {
@@ -65,6 +113,69 @@ fn wav_fixtures_expected_modes() {
     }
 }
 
+#[test]
+fn octave_correction_keeps_the_fixture_modes() {
+    let fixtures = [
+        ("fixtures/F2_87Hz.wav", 41),
+        ("fixtures/A#2_116Hz.wav", 46),
+        ("fixtures/B2_123Hz.wav", 47),
+        ("fixtures/C4_261Hz.wav", 60),
+        ("fixtures/C#4_277Hz.wav", 61),
+        ("fixtures/D4_293Hz.wav", 62),
+        ("fixtures/E4_329Hz.wav", 64),
+        ("fixtures/F#4_369Hz.wav", 66),
+        ("fixtures/G#4_415Hz.wav", 68),
+        ("fixtures/B4_493Hz.wav", 71),
+    ];
+    for (path, expected) in fixtures {
+        let (bytes, sample_rate_hz) = read_wav_pcm16le(Path::new(path));
+        let mut voiced =
+            stream_collect_with(&bytes, sample_rate_hz, 43, 5, true, PitchAlgorithm::Pyin);
+        assert!(voiced.len() >= 10, "{} had insufficient voiced outputs", path);
+        voiced.drain(0..voiced.len().min(3));
+        let m = mode(&voiced).expect("mode exists");
+        assert_eq!(m, expected, "fixture {} mode was {} with octave correction", path, m);
+    }
+}
+
+#[test]
+fn every_algorithm_finds_the_fixture_pitches() {
+    let fixtures = [
+        ("fixtures/F2_87Hz.wav", 41),
+        ("fixtures/C4_261Hz.wav", 60),
+        ("fixtures/E4_329Hz.wav", 64),
+        ("fixtures/B4_493Hz.wav", 71),
+    ];
+    for algorithm in [PitchAlgorithm::Yin, PitchAlgorithm::Mpm, PitchAlgorithm::Hps] {
+        for (path, expected) in fixtures {
+            let (bytes, sample_rate_hz) = read_wav_pcm16le(Path::new(path));
+            let notes = stream_collect_with(&bytes, sample_rate_hz, 43, 5, false, algorithm);
+            let voiced: Vec<u16> = notes.into_iter().filter(|&n| n != 255).collect();
+            assert!(voiced.len() >= 10, "{} had too few voiced outputs ({:?})", path, algorithm);
+            let m = mode(&voiced).expect("mode exists");
+            assert_eq!(m, expected, "fixture {} mode was {} ({:?})", path, m, algorithm);
+        }
+    }
+}
+
+/// Bleed cancellation is part of pYIN; other algorithms refuse a backing
+/// track and clearing one is a no-op.
+#[test]
+fn backing_track_requires_the_pyin_algorithm() {
+    let mut analyzer = AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz: 44_100,
+        update_interval_ms: 5,
+        algorithm: PitchAlgorithm::Mpm,
+        ..PitchConfig::default()
+    })
+    .expect("create audio analyzer");
+    let reference = BleedReference::from_samples(vec![0.0; 44_100], 44_100);
+    assert!(analyzer
+        .set_backing_track(reference, BleedConfig::default())
+        .is_err());
+    analyzer.clear_backing_track();
+}
+
 fn read_wav_pcm16le(path: &Path) -> (Vec<u8>, u32) {
     let reader = WavReader::new(BufReader::new(File::open(path).expect("open wav fixture")))
         .expect("read wav header");
@@ -101,3 +212,620 @@ fn read_wav_pcm16le(path: &Path) -> (Vec<u8>, u32) {
     }
     (bytes, sample_rate_hz)
 }
+
+#[test]
+fn pitch_events_carry_every_hop_with_sample_positions() {
+    let (bytes, sample_rate_hz) = read_wav_pcm16le(Path::new("fixtures/E4_329Hz.wav"));
+    let events = collect_events(&bytes, sample_rate_hz);
+    let events = first_reports(&events);
+    let hop = (sample_rate_hz as f64 * 0.005).round() as u64;
+
+    assert!(events.len() > 50);
+    for (idx, event) in events.iter().enumerate() {
+        assert_eq!(event.sample_position, idx as u64 * hop);
+        assert!((event.time_sec - event.sample_position as f64 / sample_rate_hz as f64).abs() < 1e-9);
+    }
+    let voiced: Vec<&PitchEvent> = events.iter().skip(3).copied().filter(|e| e.voiced).collect();
+    assert!(voiced.len() * 10 > events.len() * 8);
+    for event in voiced {
+        let midi = event.midi_fractional.expect("voiced events carry pitch");
+        assert!((midi - 64.0).abs() < 0.5, "midi {}", midi);
+        assert!(event.f0_hz.is_some());
+        assert!(event.rms_dbfs < 0.0 && event.rms_dbfs > -60.0);
+        assert!(event.peak_dbfs > event.rms_dbfs && event.peak_dbfs <= 0.0);
+        assert!(event.loudness_lufs > -70.0 && event.loudness_lufs < 0.0);
+        assert!(event.speech_prob.expect("vad enabled") > 0.5);
+        assert!(event.noise_floor_dbfs.expect("vad enabled") < event.rms_dbfs);
+    }
+}
+
+#[test]
+fn every_frame_is_streamed_final_once_the_stream_ends() {
+    let (bytes, sample_rate_hz) = read_wav_pcm16le(Path::new("fixtures/E4_329Hz.wav"));
+    let run = |final_only| {
+        let mut analyzer = AudioAnalyzer::new(PitchConfig {
+            sample_rate_hz,
+            update_interval_ms: 5,
+            vad_enabled: true,
+            ..PitchConfig::default()
+        })
+        .expect("create audio analyzer");
+        analyzer.set_stream_options(PitchStreamOptions {
+            final_only,
+            ..PitchStreamOptions::default()
+        });
+        let mut events = Vec::new();
+        for chunk in bytes.chunks(3001) {
+            events.extend(analyzer.process_chunk_collect_events(chunk).expect("process chunk"));
+        }
+        let streamed = events.len();
+        events.extend(analyzer.finish_collect_events().expect("finish"));
+        (events, streamed)
+    };
+    let (live, live_streamed) = run(false);
+    let (finals, finals_streamed) = run(true);
+
+    // Final events trail the live ones by the decoder's lag, and `finish`
+    // delivers the rest, so every frame arrives final exactly once.
+    let live_finals: Vec<_> = live.iter().filter(|event| event.is_final).collect();
+    assert_eq!(finals_streamed, first_reports(&live[..live_streamed]).len() - 8);
+    assert_eq!(
+        finals_streamed,
+        live[..live_streamed].iter().filter(|event| event.is_final).count()
+    );
+    assert_eq!(finals.len(), live_finals.len());
+    for (event, live) in finals.iter().zip(&live_finals) {
+        assert!(event.is_final);
+        assert_eq!(event.sample_position, live.sample_position);
+    }
+    assert!(live[..live_streamed].iter().any(|event| !event.is_final));
+    assert!(live[live_streamed..].iter().all(|event| event.is_final));
+}
+
+/// A live stream follows each event the decoder may still revise with its
+/// final update, throttled or not.
+#[test]
+fn live_stream_follows_provisional_events_with_final_updates() {
+    let (bytes, sample_rate_hz) = read_wav_pcm16le(Path::new("fixtures/E4_329Hz.wav"));
+    for options in [
+        PitchStreamOptions::default(),
+        PitchStreamOptions {
+            only_on_change: true,
+            min_change_cents: 10.0,
+            max_events_per_sec: 20,
+            final_only: false,
+        },
+    ] {
+        let mut analyzer = AudioAnalyzer::new(PitchConfig {
+            sample_rate_hz,
+            ..PitchConfig::default()
+        })
+        .expect("create audio analyzer");
+        analyzer.set_stream_options(options);
+        let mut events = Vec::new();
+        for chunk in bytes.chunks(3001) {
+            events.extend(analyzer.process_chunk_collect_events(chunk).expect("process chunk"));
+        }
+        events.extend(analyzer.finish_collect_events().expect("finish"));
+
+        let provisional: Vec<_> = events.iter().filter(|event| !event.is_final).collect();
+        assert!(!provisional.is_empty());
+        for (i, event) in events.iter().enumerate().filter(|(_, event)| !event.is_final) {
+            let updates: Vec<_> = events
+                .iter()
+                .filter(|other| other.sample_position == event.sample_position)
+                .collect();
+            assert_eq!(updates.len(), 2, "frame at {}", event.sample_position);
+            assert!(events[i + 1..]
+                .iter()
+                .any(|other| other.is_final && other.sample_position == event.sample_position));
+        }
+        let finals: Vec<u64> = events
+            .iter()
+            .filter(|event| event.is_final)
+            .map(|event| event.sample_position)
+            .collect();
+        assert!(finals.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
+
+/// The analyzer feeds `Pyin` directly, so its first event arrives with the
+/// last sample of the first window rather than a window later.
+#[test]
+fn first_event_arrives_with_the_last_sample_of_the_first_window() {
+    let sample_rate_hz = 48_000;
+    let mut analyzer = AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz,
+        ..PitchConfig::default()
+    })
+    .expect("create audio analyzer");
+    let window = (sample_rate_hz as usize * 43).div_ceil(1000);
+    let hop = sample_rate_hz as usize / 100;
+    let bytes: Vec<u8> = (0..window + hop)
+        .flat_map(|i| {
+            let t = i as f32 / sample_rate_hz as f32;
+            ((0.5 * (2.0 * std::f32::consts::PI * 220.0 * t).sin() * 32767.0) as i16).to_le_bytes()
+        })
+        .collect();
+
+    let mut events = Vec::new();
+    for (sample, chunk) in bytes.chunks(2).enumerate() {
+        let new = analyzer.process_chunk_collect_events(chunk).expect("process chunk");
+        if sample + 1 < window {
+            assert!(new.is_empty(), "event after {} samples", sample + 1);
+        } else if sample + 1 == window {
+            assert_eq!(new.len(), 1);
+            assert_eq!(new[0].sample_position, 0);
+        }
+        events.extend(new);
+    }
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].sample_position, hop as u64);
+}
+
+#[test]
+fn analyze_file_returns_final_events_in_file_samples() {
+    let contour = api::analyze_file(
+        "fixtures/E4_329Hz.wav".to_string(),
+        FileAnalysisOptions {
+            start_sec: 0.25,
+            ..FileAnalysisOptions::default()
+        },
+    )
+    .expect("analyze file");
+
+    assert_eq!(contour.source_sample_rate_hz, 44_100);
+    assert!(contour.events.len() > 50);
+    assert!(contour.events.iter().all(|e| e.is_final));
+    let first = &contour.events[0];
+    assert!(first.time_sec >= 0.25 - 1e-9, "first frame at {}", first.time_sec);
+    for event in &contour.events {
+        let expected = (event.time_sec * 44_100.0).round() as u64;
+        assert_eq!(event.sample_position, expected);
+    }
+    let mut midi: Vec<f32> = contour.events.iter().filter_map(|e| e.midi_fractional).collect();
+    midi.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = midi[midi.len() / 2];
+    assert!((median - 64.0).abs() < 0.5, "median midi {}", median);
+
+    let missing = api::analyze_file("fixtures/missing.wav".to_string(), Default::default());
+    assert!(missing.is_err());
+}
+
+#[test]
+fn backing_track_is_decoded_from_a_file() {
+    let mut analyzer = AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz: 44_100,
+        update_interval_ms: 10,
+        window_size_ms: 43,
+        reference_a4_hz: 440.0,
+        pcm_format: PcmFormat::I16LE,
+        channels: 1,
+        channel_mix: ChannelMix::Downmix,
+        analysis_sample_rate_hz: None,
+        vad_enabled: false,
+        octave_correction_enabled: false,
+        algorithm: PitchAlgorithm::Pyin,
+        multi_resolution_enabled: false,
+    })
+    .expect("create audio analyzer");
+    analyzer
+        .set_backing_track_file("fixtures/C4_261Hz.wav".to_string(), BleedConfig::default())
+        .expect("load backing track");
+    let (bytes, _) = read_wav_pcm16le(Path::new("fixtures/C4_261Hz.wav"));
+    analyzer.process_chunk_collect(&bytes).expect("process chunk");
+    assert!(analyzer
+        .set_backing_track_file("fixtures/missing.wav".to_string(), BleedConfig::default())
+        .is_err());
+}
+
+#[test]
+fn interleaved_24bit_input_is_picked_and_resampled() {
+    let capture_rate_hz = 96_000;
+    let len = capture_rate_hz as usize;
+    let mut bytes = Vec::with_capacity(len * 6);
+    for i in 0..len {
+        let t = i as f32 / capture_rate_hz as f32;
+        let voice = 0.5 * (2.0 * std::f32::consts::PI * 329.63 * t).sin();
+        let backing = 0.5 * (2.0 * std::f32::consts::PI * 110.0 * t).sin();
+        for sample in [backing, voice] {
+            let value = (sample * 8_388_607.0) as i32;
+            bytes.extend_from_slice(&value.to_be_bytes()[1..]);
+        }
+    }
+
+    let mut analyzer = AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz: capture_rate_hz,
+        pcm_format: PcmFormat::I24BE,
+        channels: 2,
+        channel_mix: ChannelMix::Pick(1),
+        analysis_sample_rate_hz: Some(16_000),
+        ..PitchConfig::default()
+    })
+    .expect("create audio analyzer");
+    let mut events = Vec::new();
+    for chunk in bytes.chunks(1001) {
+        events.extend(analyzer.process_chunk_collect_events(chunk).expect("process chunk"));
+    }
+    let events = first_reports(&events);
+
+    // 1 s of audio at a 10 ms hop, minus the frames still filling the window.
+    assert!(events.len() >= 90, "only {} events", events.len());
+    for (idx, event) in events.iter().enumerate() {
+        assert_eq!(event.sample_position, idx as u64 * 960);
+    }
+    let voiced: Vec<f32> = events
+        .iter()
+        .skip(3)
+        .filter_map(|e| e.midi_fractional)
+        .collect();
+    assert!(voiced.len() * 10 > events.len() * 8);
+    for midi in voiced {
+        assert!((midi - 64.0).abs() < 0.5, "midi {}", midi);
+    }
+}
+
+#[test]
+fn level_meter_reports_chunk_levels_and_clipping() {
+    let sample_rate_hz = 48_000;
+    let mut analyzer = AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz,
+        ..PitchConfig::default()
+    })
+    .expect("create audio analyzer");
+    assert!(analyzer.latest_level().is_none());
+
+    let chunk = |amplitude: f32| -> Vec<u8> {
+        (0..sample_rate_hz / 2)
+            .flat_map(|i| {
+                let t = i as f32 / sample_rate_hz as f32;
+                let sample = amplitude * (2.0 * std::f32::consts::PI * 440.0 * t).sin();
+                ((sample.clamp(-1.0, 1.0) * 32767.0) as i16).to_le_bytes()
+            })
+            .collect()
+    };
+
+    analyzer.process_chunk_collect(&chunk(0.1)).expect("process chunk");
+    let quiet = analyzer.latest_level().expect("level after a chunk");
+    assert!((quiet.peak_dbfs + 20.0).abs() < 0.5, "peak {}", quiet.peak_dbfs);
+    assert!((quiet.rms_dbfs + 23.0).abs() < 0.5, "rms {}", quiet.rms_dbfs);
+    assert_eq!(quiet.clipped_samples, 0);
+
+    // An overdriven mic flattens the waveform at full scale.
+    analyzer.process_chunk_collect(&chunk(2.0)).expect("process chunk");
+    let loud = analyzer.latest_level().expect("level after a chunk");
+    assert!(loud.clipped_samples > 1_000);
+    assert!(loud.momentary_lufs > quiet.momentary_lufs + 20.0);
+    assert!(loud.short_term_lufs < loud.momentary_lufs);
+}
+
+#[test]
+fn clipping_is_counted_on_the_captured_channels() {
+    // Stereo at 44.1 kHz analysed at 16 kHz: the left channel is overdriven,
+    // the right one silent, so the downmix peaks at half scale.
+    let mut analyzer = AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz: 44_100,
+        channels: 2,
+        analysis_sample_rate_hz: Some(16_000),
+        ..PitchConfig::default()
+    })
+    .expect("create audio analyzer");
+    let bytes: Vec<u8> = (0..4_410)
+        .flat_map(|i| {
+            let t = i as f32 / 44_100.0;
+            let left = (2.0 * (2.0 * std::f32::consts::PI * 440.0 * t).sin()).clamp(-1.0, 1.0);
+            let left = (left * 32767.0) as i16;
+            left.to_le_bytes().into_iter().chain(0_i16.to_le_bytes())
+        })
+        .collect();
+    analyzer.process_chunk_collect(&bytes).expect("process chunk");
+    let level = analyzer.latest_level().expect("level after a chunk");
+    assert!(level.peak_dbfs < -5.0, "peak {}", level.peak_dbfs);
+    assert!(level.clipped_samples > 1_000, "clipped {}", level.clipped_samples);
+}
+
+#[test]
+fn restored_analyzer_continues_the_session_exactly() {
+    let config = |algorithm| PitchConfig {
+        sample_rate_hz: 44_100,
+        analysis_sample_rate_hz: Some(16_000),
+        vad_enabled: true,
+        algorithm,
+        ..PitchConfig::default()
+    };
+    let (bytes, _) = read_wav_pcm16le(Path::new("fixtures/E4_329Hz.wav"));
+    let run = |analyzer: &mut AudioAnalyzer, bytes: &[u8]| {
+        let mut events = Vec::new();
+        for chunk in bytes.chunks(2049) {
+            events.extend(analyzer.process_chunk_collect_events(chunk).expect("process chunk"));
+        }
+        format!("{:?} {:?}", events, analyzer.latest_level())
+    };
+
+    let split = bytes.len() / 3 + 1;
+    for algorithm in [PitchAlgorithm::Pyin, PitchAlgorithm::Mpm] {
+        let mut original = AudioAnalyzer::new(config(algorithm)).expect("create audio analyzer");
+        run(&mut original, &bytes[..split]);
+        let blob = original.snapshot();
+
+        let mut restored = AudioAnalyzer::new(config(algorithm)).expect("create audio analyzer");
+        restored.restore(&blob).expect("restore");
+        let continued = run(&mut restored, &bytes[split..]);
+        assert_eq!(continued, run(&mut original, &bytes[split..]), "{:?}", algorithm);
+        assert!(continued.contains("sample_position"));
+    }
+
+    let blob = AudioAnalyzer::new(config(PitchAlgorithm::Pyin))
+        .expect("create audio analyzer")
+        .snapshot();
+    let mut other = AudioAnalyzer::new(config(PitchAlgorithm::Yin)).expect("create audio analyzer");
+    assert!(other.restore(&blob).is_err());
+    assert!(other.restore(b"not a snapshot").is_err());
+}
+
+const SCORED_RATE: u32 = 16_000;
+/// 16 beats per second from the start of the song audio.
+const SCORED_CHART: &[u8] = b"#TITLE:T\n#ARTIST:A\n#BPM:240\n: 0 8 -3 la\n: 8 8 0 do\n- 18\n\
+                               * 18 12 4 mi\nE\n";
+/// The microphone hears the song start 0.4 s into the stream.
+const SCORED_SONG_START_SEC: f64 = 0.4;
+
+/// `SCORED_CHART` sung in tune, as captured by the microphone.
+fn sing_scored_chart() -> Vec<u8> {
+    let notes = [(0.0, 0.5, 220.0), (0.5, 1.0, 261.63), (1.125, 1.875, 329.63)];
+    let mut samples = vec![0.0_f32; (2.5 * SCORED_RATE as f64) as usize];
+    for (start, end, freq) in notes {
+        let from = ((start + SCORED_SONG_START_SEC) * SCORED_RATE as f64) as usize;
+        let to = ((end + SCORED_SONG_START_SEC) * SCORED_RATE as f64) as usize;
+        for (i, sample) in samples[from..to].iter_mut().enumerate() {
+            let t = i as f32 / SCORED_RATE as f32;
+            *sample = 0.5 * (2.0 * std::f32::consts::PI * freq * t).sin();
+        }
+    }
+    samples
+        .iter()
+        .flat_map(|s| ((s * 32767.0) as i16).to_le_bytes())
+        .collect()
+}
+
+fn scored_chart_analyzer() -> AudioAnalyzer {
+    AudioAnalyzer::new(PitchConfig {
+        sample_rate_hz: SCORED_RATE,
+        window_size_ms: 64,
+        ..PitchConfig::default()
+    })
+    .expect("create audio analyzer")
+}
+
+fn scored_chart_options() -> ScoreStreamOptions {
+    ScoreStreamOptions {
+        track: 0,
+        harmony: false,
+        difficulty: Difficulty::Hard,
+        line_bonus: true,
+        song_start_sec: SCORED_SONG_START_SEC,
+    }
+}
+
+#[test]
+fn score_events_are_stamped_in_song_time() {
+    let chart = SCORED_CHART;
+    let bytes = sing_scored_chart();
+    let mut analyzer = scored_chart_analyzer();
+    let options = scored_chart_options();
+    assert!(analyzer.start_scoring(b"#TITLE:x\n: 0 1 0 a\n", options.clone()).is_err());
+    let mut no_duet = options.clone();
+    no_duet.track = 1;
+    assert!(analyzer.start_scoring(chart, no_duet).is_err());
+    analyzer.start_scoring(chart, options).expect("start scoring");
+
+    let mut events = Vec::new();
+    for chunk in bytes.chunks(1500) {
+        analyzer.process_chunk_collect_events(chunk).expect("process chunk");
+        events.extend(analyzer.drain_score_events());
+    }
+    analyzer.finish_scoring().expect("finish scoring");
+    events.extend(analyzer.drain_score_events());
+
+    let kinds: Vec<ScoreEventKind> = events.iter().map(|e| e.kind).collect();
+    use ScoreEventKind::*;
+    assert_eq!(kinds, [NoteHit, NoteHit, PerfectLine, NoteHit, PerfectLine]);
+    // Note and line ends in song time, not in stream time.
+    let times: Vec<f64> = events.iter().map(|e| e.song_time_sec).collect();
+    assert_eq!(times, [0.5, 1.0, 1.0, 1.875, 1.875]);
+    assert_eq!(events[3].golden_streak, 1);
+    let total = events.last().unwrap().total_score;
+    assert_eq!(total, analyzer.scoring().unwrap().totals().rounded());
+    assert_eq!(total, 10_000);
+}
+
+#[test]
+fn restored_analyzer_continues_scoring_the_song() {
+    let bytes = sing_scored_chart();
+    let run = |analyzer: &mut AudioAnalyzer, bytes: &[u8]| {
+        let mut events = Vec::new();
+        for chunk in bytes.chunks(1500) {
+            analyzer.process_chunk_collect_events(chunk).expect("process chunk");
+            events.extend(analyzer.drain_score_events());
+        }
+        events
+    };
+
+    let mut original = scored_chart_analyzer();
+    original.start_scoring(SCORED_CHART, scored_chart_options()).expect("start scoring");
+    // The events of the first half are not drained: they travel with the
+    // snapshot.
+    let split = bytes.len() / 2;
+    original.process_chunk_collect_events(&bytes[..split]).expect("process chunk");
+    let blob = original.snapshot();
+
+    let mut restored = scored_chart_analyzer();
+    assert!(restored.restore(&blob).is_err());
+    restored.start_scoring(SCORED_CHART, scored_chart_options()).expect("start scoring");
+    restored.restore(&blob).expect("restore");
+    let mut events = run(&mut restored, &bytes[split..]);
+    restored.finish_scoring().expect("finish scoring");
+    events.extend(restored.drain_score_events());
+
+    let kinds: Vec<ScoreEventKind> = events.iter().map(|e| e.kind).collect();
+    use ScoreEventKind::*;
+    assert_eq!(kinds, [NoteHit, NoteHit, PerfectLine, NoteHit, PerfectLine]);
+    assert_eq!(restored.scoring().unwrap().totals().rounded(), 10_000);
+
+    // A snapshot without scoring does not restore into a scoring analyzer.
+    let blob = scored_chart_analyzer().snapshot();
+    assert!(restored.restore(&blob).is_err());
+    assert_eq!(restored.scoring().unwrap().totals().rounded(), 10_000);
+}
+
+#[test]
+fn moved_song_start_keeps_scoring_in_step_after_a_pause() {
+    // The mic misses 0.2 s of the room while the song plays on.
+    let bytes = sing_scored_chart();
+    let pause = |sec: f64| (sec * SCORED_RATE as f64) as usize * 2;
+    let (before, after) = (&bytes[..pause(0.1)], &bytes[pause(0.3)..]);
+    let run = |move_song: bool| {
+        let mut analyzer = scored_chart_analyzer();
+        analyzer.start_scoring(SCORED_CHART, scored_chart_options()).expect("start scoring");
+        analyzer.process_chunk_collect_events(before).expect("process chunk");
+        if move_song {
+            analyzer.set_song_start(SCORED_SONG_START_SEC - 0.2).expect("move song");
+        }
+        for chunk in after.chunks(1500) {
+            analyzer.process_chunk_collect_events(chunk).expect("process chunk");
+        }
+        analyzer.finish_scoring().expect("finish scoring");
+        let events = analyzer.drain_score_events();
+        let times: Vec<f64> = events.iter().map(|e| e.song_time_sec).collect();
+        (times, analyzer.scoring().unwrap().totals())
+    };
+
+    let (times, totals) = run(true);
+    assert_eq!(times, [0.5, 1.0, 1.0, 1.875, 1.875]);
+    assert_eq!(totals.rounded(), 10_000);
+    assert!(run(false).1.rounded() < 10_000);
+    assert!(scored_chart_analyzer().set_song_start(f64::NAN).is_err());
+}
+
+/// 16 beats per second; the second part sings a fifth above the first.
+const DUET_CHART: &[u8] = b"#TITLE:T\n#ARTIST:A\n#BPM:240\n#P1:Ann\n#P2:Bob\n\
+                            P1\n: 0 8 -3 la\n: 8 8 0 do\nP2\n: 0 8 4 mi\n: 8 8 7 so\nE\n";
+const DUET_LOW: [f32; 2] = [220.0, 261.63];
+const DUET_HIGH: [f32; 2] = [329.63, 392.0];
+
+/// A phone's stream of one part of `DUET_CHART`, at `SCORED_RATE`. Each phone
+/// hears the song start at its own point of its stream.
+fn sing_duet(notes: [f32; 2], song_start_sec: f64) -> Vec<u8> {
+    const RATE: u32 = SCORED_RATE;
+    let mut samples = vec![0.0_f32; ((song_start_sec + 1.5) * RATE as f64) as usize];
+    for (i, freq) in notes.into_iter().enumerate() {
+        let from = ((i as f64 * 0.5 + song_start_sec) * RATE as f64) as usize;
+        let to = from + RATE as usize / 2;
+        for (n, sample) in samples[from..to].iter_mut().enumerate() {
+            *sample = 0.5 * (2.0 * std::f32::consts::PI * freq * n as f32 / RATE as f32).sin();
+        }
+    }
+    samples
+        .iter()
+        .flat_map(|s| ((s * 32767.0) as i16).to_le_bytes())
+        .collect()
+}
+
+#[test]
+fn duet_singers_score_on_their_own_analyzers() {
+    let chart = DUET_CHART;
+    let sing = sing_duet;
+    let score = |bytes: Vec<u8>, options: ScoreStreamOptions| {
+        let mut analyzer = scored_chart_analyzer();
+        analyzer.start_scoring(chart, options).expect("start scoring");
+        for chunk in bytes.chunks(1500) {
+            analyzer.process_chunk_collect_events(chunk).expect("process chunk");
+        }
+        analyzer.finish_scoring().expect("finish scoring");
+        analyzer
+    };
+    let part = |track, harmony, song_start_sec| ScoreStreamOptions {
+        track,
+        harmony,
+        difficulty: Difficulty::Hard,
+        line_bonus: true,
+        song_start_sec,
+    };
+    let low = DUET_LOW;
+    let high = DUET_HIGH;
+
+    let mut ann = score(sing(low, 0.4), part(0, false, 0.4));
+    let mut bob = score(sing(high, 1.1), part(1, false, 1.1));
+    for (analyzer, track) in [(&mut ann, 0), (&mut bob, 1)] {
+        let scoring = analyzer.scoring().unwrap();
+        assert_eq!((scoring.best().track(), scoring.totals().rounded()), (track, 10_000));
+        let events = analyzer.drain_score_events();
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| e.track == track as u32));
+    }
+
+    // Singing the other part scores nothing, unless either part will do.
+    let swapped = score(sing(high, 0.4), part(0, false, 0.4));
+    assert_eq!(swapped.scoring().unwrap().totals().rounded(), 0);
+    let mut harmony = score(sing(high, 0.4), part(0, true, 0.4));
+    let scoring = harmony.scoring().unwrap();
+    assert_eq!((scoring.best().track(), scoring.totals().rounded()), (1, 10_000));
+    let tracks: Vec<u32> = harmony.drain_score_events().iter().map(|e| e.track).collect();
+    assert_eq!(tracks, [0, 1, 0, 0, 1, 1]);
+}
+
+#[test]
+fn party_scores_every_singer_on_the_song_clock() {
+    // The song starts at 20 s on the party clock; each phone started its
+    // stream at its own time.
+    let mut party = Party::new(DUET_CHART.to_vec(), 20.0).expect("create party");
+    let config = || PitchConfig {
+        sample_rate_hz: SCORED_RATE,
+        window_size_ms: 64,
+        ..PitchConfig::default()
+    };
+    let singer = |name: &str, track, harmony, stream_start_sec| SingerOptions {
+        name: name.to_string(),
+        track,
+        harmony,
+        difficulty: Difficulty::Hard,
+        line_bonus: true,
+        stream_start_sec,
+    };
+    let singers = [
+        (singer("Ann", 0, false, 19.6), sing_duet(DUET_LOW, 0.4)),
+        (singer("Bob", 1, false, 18.9), sing_duet(DUET_HIGH, 1.1)),
+        (singer("Cy", 0, false, 19.2), sing_duet(DUET_HIGH, 0.8)),
+        (singer("Di", 0, true, 19.5), sing_duet(DUET_HIGH, 0.5)),
+    ];
+    for (options, _) in &singers {
+        party.add_singer(config(), options.clone()).expect("add singer");
+    }
+    assert!(party.add_singer(config(), singer("Ed", 2, false, 0.0)).is_err());
+
+    // Interleave the phones' chunks as they would arrive.
+    let longest = singers.iter().map(|(_, bytes)| bytes.len()).max().unwrap();
+    for offset in (0..longest).step_by(1500) {
+        for (index, (_, bytes)) in singers.iter().enumerate() {
+            let chunk = bytes.get(offset..(offset + 1500).min(bytes.len()));
+            if let Some(chunk) = chunk.filter(|chunk| !chunk.is_empty()) {
+                party.process_chunk(index as u32, chunk.to_vec()).expect("process chunk");
+            }
+        }
+    }
+    party.finish().expect("finish party");
+    assert!(party.process_chunk(4, vec![0, 0]).is_err());
+
+    let results = party.results();
+    let summary: Vec<_> = results.iter().map(|r| (r.name.as_str(), r.track, r.score)).collect();
+    assert_eq!(
+        summary,
+        [("Ann", 0, 10_000), ("Bob", 1, 10_000), ("Cy", 0, 0), ("Di", 1, 10_000)]
+    );
+    // Every singer's notes end at the same song times, whatever their stream.
+    for index in [0, 1] {
+        let events = party.drain_score_events(index).expect("drain score events");
+        let times: Vec<f64> = events.iter().map(|e| e.song_time_sec).collect();
+        assert_eq!(times, [0.5, 1.0, 1.0]);
+    }
+}
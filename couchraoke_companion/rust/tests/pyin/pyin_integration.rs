@@ -1,4 +1,8 @@
-use pyin_rs::pyin::{PcmFormat, Pyin, PyinConfig};
+use pyin_rs::pyin::{
+    analyze_vibrato, segment_notes, summarize_vibrato, BleedConfig, BleedReference, NoteConfig,
+    ChannelMix, MultiResolutionConfig, OctaveConfig, PcmFormat, PcmInput, Pyin, PyinConfig,
+    ScorePriorConfig, TailPolicy, TargetNote, VadConfig, VibratoConfig,
+};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
 fn sine_wave(freq_hz: f32, duration_sec: f32, sample_rate: u32) -> Vec<f32> {
@@ -207,3 +211,679 @@ fn regression_configurations() {
     let estimates = run_stream(cfg, &samples);
     assert!(median_cents_error(&estimates, 440.0) < 60.0);
 }
+
+#[test]
+fn fixed_lag_emits_each_frame_once_and_exposes_provisional_tail() {
+    let cfg = PyinConfig {
+        viterbi_lag_frames: 6,
+        ..PyinConfig::default()
+    };
+    let samples = sine_wave(220.0, 1.0, cfg.sample_rate_hz);
+    let mut pyin = Pyin::new(cfg.clone(), PcmFormat::I16LE).unwrap();
+    let bytes = samples_to_i16le(&samples);
+
+    let mut finals = Vec::new();
+    for chunk in bytes.chunks(1000) {
+        finals.extend(pyin.push_bytes(chunk).unwrap());
+    }
+    let provisional = pyin.provisional_estimates();
+
+    let analysed = (samples.len() - cfg.frame_size) / cfg.hop_size + 1;
+    assert_eq!(provisional.len(), cfg.viterbi_lag_frames);
+    assert_eq!(finals.len() + provisional.len(), analysed);
+    assert!(finals.iter().all(|f| f.is_final));
+    assert!(provisional.iter().all(|f| !f.is_final));
+    for (expected, frame) in finals.iter().chain(provisional.iter()).enumerate() {
+        assert_eq!(frame.frame_index, expected as u64);
+    }
+}
+
+/// Input-to-estimate latency of `push_samples`: a frame is analysed, and its
+/// provisional estimate available, as soon as its last sample arrives; the
+/// decoder commits it `viterbi_lag_frames` hops after that.
+#[test]
+fn push_samples_latency_is_one_frame_plus_decoder_lag() {
+    let cfg = PyinConfig::default();
+    let samples = sine_wave(220.0, 0.5, cfg.sample_rate_hz);
+    let mut pyin = Pyin::new(cfg.clone(), PcmFormat::F32LE).unwrap();
+
+    let first_frame = cfg.frame_size;
+    assert!(pyin.push_samples(&samples[..first_frame - 1]).unwrap().is_empty());
+    assert!(pyin.provisional_estimates().is_empty());
+    assert!(pyin.push_samples(&samples[first_frame - 1..first_frame]).unwrap().is_empty());
+    let provisional = pyin.provisional_estimates();
+    assert_eq!(provisional.len(), 1);
+    assert_eq!(provisional[0].frame_index, 0);
+
+    let first_commit = first_frame + cfg.viterbi_lag_frames * cfg.hop_size;
+    assert!(pyin.push_samples(&samples[first_frame..first_commit - 1]).unwrap().is_empty());
+    let committed = pyin.push_samples(&samples[first_commit - 1..first_commit]).unwrap();
+    assert_eq!(committed.len(), 1);
+    assert_eq!(committed[0].frame_index, 0);
+    assert!(committed[0].is_final);
+}
+
+#[test]
+fn finish_emits_the_tail_and_reports_dropped_input() {
+    let cfg = PyinConfig::default();
+    let samples = sine_wave(220.0, 0.5, cfg.sample_rate_hz)
+        .into_iter()
+        .take(24_100)
+        .collect::<Vec<f32>>();
+    let full_frames = (samples.len() - cfg.frame_size) / cfg.hop_size + 1;
+    let covered = (full_frames - 1) * cfg.hop_size + cfg.frame_size;
+    let mut bytes = samples_to_i16le(&samples);
+    bytes.push(0x7f);
+
+    let mut pyin = Pyin::new(cfg.clone(), PcmFormat::I16LE).unwrap();
+    let mut frames = Vec::new();
+    for chunk in bytes.chunks(999) {
+        frames.extend(pyin.push_bytes(chunk).unwrap());
+    }
+    let end = pyin.finish(TailPolicy::ZeroPad).unwrap();
+    assert_eq!(end.dropped_bytes, 1);
+    assert_eq!(end.dropped_samples, 0);
+    assert_eq!(end.padded_samples, covered + cfg.hop_size - samples.len());
+    frames.extend(end.estimates);
+    assert_eq!(frames.len(), full_frames + 1);
+    for (expected, frame) in frames.iter().enumerate() {
+        assert_eq!(frame.frame_index, expected as u64);
+        assert!(frame.is_final);
+    }
+    // The tail of the note is kept, not cut off a lag early.
+    for frame in &frames[frames.len() - cfg.viterbi_lag_frames..] {
+        let f0 = frame.f0_hz.expect("tail frames voiced");
+        assert!((f0 - 220.0).abs() < 2.0, "f0 {}", f0);
+    }
+
+    // The detector starts over after finishing.
+    let restarted = pyin.push_bytes(&bytes[..bytes.len() - 1]).unwrap();
+    assert_eq!(restarted[0].frame_index, 0);
+
+    let mut pyin = Pyin::new(cfg.clone(), PcmFormat::I16LE).unwrap();
+    let mut frames = pyin.push_bytes(&bytes).unwrap();
+    let end = pyin.finish(TailPolicy::Drop).unwrap();
+    assert_eq!(end.padded_samples, 0);
+    assert_eq!(end.dropped_samples, samples.len() - covered);
+    frames.extend(end.estimates);
+    assert_eq!(frames.len(), full_frames);
+    assert!(frames.iter().all(|f| f.is_final));
+}
+
+fn run_samples(cfg: PyinConfig, samples: &[f32]) -> Vec<f32> {
+    let mut pyin = Pyin::new(cfg, PcmFormat::F32LE).unwrap();
+    let mut estimates = pyin.push_samples(samples).unwrap();
+    estimates.extend(pyin.finish(TailPolicy::Drop).unwrap().estimates);
+    estimates.iter().map(|e| e.f0_hz.unwrap_or(0.0)).collect()
+}
+
+#[test]
+fn non_finite_samples_are_analysed_as_silence() {
+    let cfg = PyinConfig::default();
+    let clean = sine_wave(220.0, 0.5, cfg.sample_rate_hz);
+    let mut poisoned = clean.clone();
+    let mut silenced = clean.clone();
+    for i in 8_000..8_400 {
+        poisoned[i] = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY][i % 3];
+        silenced[i] = 0.0;
+    }
+
+    let f0s = run_samples(cfg.clone(), &poisoned);
+    assert_eq!(f0s, run_samples(cfg, &silenced));
+    assert!(f0s.iter().all(|f0| f0.is_finite()));
+    assert!(median_cents_error(&f0s, 220.0) < 10.0);
+}
+
+#[test]
+fn multi_resolution_follows_high_notes_sooner() {
+    let single = PyinConfig::default();
+    let multi = PyinConfig {
+        multi_resolution: Some(MultiResolutionConfig::default()),
+        ..PyinConfig::default()
+    };
+    let rate = single.sample_rate_hz;
+    let mut samples = sine_wave(523.25, 0.5, rate);
+    let step = samples.len();
+    samples.extend_from_slice(&sine_wave(587.33, 0.5, rate));
+
+    // Frames until one ending after the step reports the new note.
+    let frames_to_follow = |cfg: PyinConfig| {
+        let first_after_step = (step - cfg.frame_size) / cfg.hop_size + 1;
+        let estimates = run_samples(cfg, &samples);
+        estimates[first_after_step..]
+            .iter()
+            .position(|&f0| f0 > 0.0 && 1200.0 * (f0 / 587.33).log2().abs() < 50.0)
+            .unwrap()
+    };
+    let single_frames = frames_to_follow(single);
+    let multi_frames = frames_to_follow(multi);
+    // The 512-sample window sees the new note after two hops, the 2048-sample
+    // frame only once it holds more of the new note than the old one.
+    assert!(multi_frames <= 2, "multi-resolution took {} frames", multi_frames);
+    assert!(single_frames >= multi_frames + 2, "single window took {} frames", single_frames);
+}
+
+#[test]
+fn multi_resolution_keeps_low_notes_accurate() {
+    let cfg = PyinConfig {
+        multi_resolution: Some(MultiResolutionConfig::default()),
+        ..PyinConfig::default()
+    };
+    for &freq in &[65.0, 82.4, 440.0, 880.0] {
+        let estimates = run_samples(cfg.clone(), &sine_wave(freq, 1.0, cfg.sample_rate_hz));
+        let voiced = estimates.iter().filter(|v| **v > 0.0).count();
+        assert!(voiced as f32 > 0.9 * estimates.len() as f32, "{} Hz", freq);
+        let error = median_cents_error(&estimates, freq);
+        assert!(error < 10.0, "{} Hz is off by {} cents", freq, error);
+    }
+}
+
+#[test]
+fn multi_resolution_windows_are_validated() {
+    let with_windows = |window_sizes: Vec<usize>, min_periods: f32| PyinConfig {
+        multi_resolution: Some(MultiResolutionConfig {
+            window_sizes,
+            min_periods,
+        }),
+        ..PyinConfig::default()
+    };
+    assert!(Pyin::new(with_windows(vec![1024, 512, 512], 3.0), PcmFormat::F32LE).is_ok());
+    assert!(Pyin::new(with_windows(vec![2048], 3.0), PcmFormat::F32LE).is_err());
+    assert!(Pyin::new(with_windows(vec![0], 3.0), PcmFormat::F32LE).is_err());
+    // Too short to hold three periods of fmax.
+    assert!(Pyin::new(with_windows(vec![64], 3.0), PcmFormat::F32LE).is_err());
+    assert!(Pyin::new(with_windows(vec![512], 0.5), PcmFormat::F32LE).is_err());
+}
+
+#[test]
+fn confidence_is_posterior_voicing_and_pitch_probability() {
+    let cfg = PyinConfig {
+        return_posteriors: true,
+        ..PyinConfig::default()
+    };
+    let mut samples = sine_wave(220.0, 1.0, cfg.sample_rate_hz);
+    samples.extend_from_slice(&silence(1.0, cfg.sample_rate_hz));
+    let mut pyin = Pyin::new(cfg.clone(), PcmFormat::I16LE).unwrap();
+    let mut frames = Vec::new();
+    for chunk in samples_to_i16le(&samples).chunks(1000) {
+        frames.extend(pyin.push_bytes(chunk).unwrap());
+    }
+    frames.extend(pyin.provisional_estimates());
+
+    let num_bins = cfg.pitch_grid().num_bins;
+    for frame in frames.iter() {
+        let posterior = frame.posterior.as_ref().unwrap();
+        assert_eq!(posterior.len(), num_bins);
+        assert!((posterior.iter().sum::<f32>() - frame.voicing_probability).abs() < 1e-3);
+        assert!(frame.confidence >= 0.0 && frame.confidence <= 1.0 + 1e-4);
+    }
+    let frames_per_sec = frames.len() / 2;
+    let tone = &frames[frames_per_sec / 4..frames_per_sec * 3 / 4];
+    let quiet = &frames[frames_per_sec * 5 / 4..frames_per_sec * 7 / 4];
+    assert!(tone.iter().all(|f| f.voiced && f.voicing_probability > 0.95 && f.confidence > 0.9));
+    assert!(quiet.iter().all(|f| !f.voiced && f.voicing_probability < 0.05 && f.confidence > 0.95));
+}
+
+#[test]
+fn note_segmentation_of_sung_melody() {
+    let cfg = PyinConfig::default();
+    let mut samples = Vec::new();
+    samples.extend_from_slice(&silence(0.2, cfg.sample_rate_hz));
+    for &freq in &[220.0, 262.0, 330.0] {
+        samples.extend_from_slice(&sine_wave(freq, 0.4, cfg.sample_rate_hz));
+        samples.extend_from_slice(&silence(0.15, cfg.sample_rate_hz));
+    }
+
+    let mut pyin = Pyin::new(cfg.clone(), PcmFormat::I16LE).unwrap();
+    let mut frames = pyin.push_bytes(&samples_to_i16le(&samples)).unwrap();
+    frames.extend(pyin.provisional_estimates());
+
+    let notes = segment_notes(&frames, &cfg, NoteConfig::default()).unwrap();
+    let midi: Vec<u8> = notes.iter().map(|n| n.midi_note).collect();
+    assert_eq!(midi, vec![57, 60, 64]);
+    for (note, onset) in notes.iter().zip([0.2, 0.75, 1.3]) {
+        assert!((note.onset_sec - onset).abs() < 0.06, "onset {}", note.onset_sec);
+        assert!((note.offset_sec - note.onset_sec - 0.4).abs() < 0.1);
+        assert!(note.stability > 0.9);
+    }
+}
+
+#[test]
+fn fractional_pitch_reports_cents_against_reference() {
+    let sharp_a4 = 440.0 * 2.0_f32.powf(45.0 / 1200.0);
+    for (reference, freq, expected_midi, expected_cents) in [
+        (440.0, 440.0, 69.0, 0.0),
+        (440.0, sharp_a4, 69.45, 45.0),
+        (432.0, 432.0, 69.0, 0.0),
+        (432.0, 440.0, 69.32, 31.8),
+    ] {
+        let cfg = PyinConfig {
+            reference_a4_hz: reference,
+            ..PyinConfig::default()
+        };
+        let samples = sine_wave(freq, 1.0, cfg.sample_rate_hz);
+        let mut pyin = Pyin::new(cfg, PcmFormat::I16LE).unwrap();
+        let frames = pyin.push_bytes(&samples_to_i16le(&samples)).unwrap();
+        let voiced: Vec<_> = frames.iter().skip(10).filter(|f| f.voiced).collect();
+        assert!(voiced.len() > 100);
+        let mut cents: Vec<f32> = voiced.iter().map(|f| f.cents_offset.unwrap()).collect();
+        cents.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median_cents = cents[cents.len() / 2];
+        let median_midi = voiced[voiced.len() / 2].midi_fractional.unwrap();
+        assert!(
+            (median_cents - expected_cents).abs() < 5.0,
+            "ref {} freq {}: cents {}",
+            reference,
+            freq,
+            median_cents
+        );
+        assert!((median_midi - expected_midi).abs() < 0.05);
+        assert!(voiced.iter().all(|f| f.midi_note == Some(69)));
+    }
+}
+
+#[test]
+fn bleed_cancellation_tracks_singer_instead_of_backing_track() {
+    let cfg = PyinConfig {
+        sample_rate_hz: 16_000,
+        frame_size: 1024,
+        hop_size: 160,
+        ..PyinConfig::default()
+    };
+    let rate = cfg.sample_rate_hz;
+    // Song: a loud lead vocal at 440 Hz over broadband backing.
+    let mut rng = StdRng::seed_from_u64(11);
+    let song: Vec<f32> = sine_wave(440.0, 4.0, rate)
+        .iter()
+        .map(|s| 0.5 * s + rng.gen_range(-0.2..0.2))
+        .collect();
+    // The mic hears the song 80 ms late and attenuated, plus the user at 330 Hz.
+    let delay = (0.08 * rate as f32) as usize;
+    let singer = sine_wave(330.0, 4.0, rate);
+    let mic: Vec<f32> = (0..song.len())
+        .map(|n| {
+            let bleed = if n >= delay { 0.8 * song[n - delay] } else { 0.0 };
+            bleed + 0.25 * singer[n]
+        })
+        .collect();
+
+    let track = |with_bleed: bool| -> Vec<f32> {
+        let mut pyin = Pyin::new(cfg.clone(), PcmFormat::F32LE).unwrap();
+        if with_bleed {
+            let bleed_cfg = BleedConfig {
+                delay_sec: 0.08,
+                ..BleedConfig::default()
+            };
+            pyin.set_bleed_reference(BleedReference::from_samples(song.clone(), rate), &bleed_cfg)
+                .unwrap();
+        }
+        let bytes: Vec<u8> = mic.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut f0s = Vec::new();
+        for chunk in bytes.chunks(1234) {
+            for frame in pyin.push_bytes(chunk).unwrap() {
+                f0s.push(frame.f0_hz.unwrap_or(0.0));
+            }
+        }
+        // Judge the second half, once the canceller has converged.
+        f0s.split_off(f0s.len() / 2)
+    };
+
+    let without = track(false);
+    assert!(median_cents_error(&without, 330.0) > 100.0);
+    let with = track(true);
+    assert!(
+        median_cents_error(&with, 330.0) < 50.0,
+        "median error {}",
+        median_cents_error(&with, 330.0)
+    );
+}
+
+#[test]
+fn vad_gates_background_and_reports_noise_floor() {
+    let mut cfg = PyinConfig {
+        sample_rate_hz: 16_000,
+        frame_size: 1024,
+        hop_size: 160,
+        ..PyinConfig::default()
+    };
+    let rate = cfg.sample_rate_hz;
+    // A noisy room with someone talking in the background, then the singer
+    // close to the mic.
+    let mut rng = StdRng::seed_from_u64(21);
+    let background_sec = 4.0;
+    let chatter = sine_wave(196.0, background_sec, rate);
+    let singer = sine_wave(262.0, 1.0, rate);
+    let mut samples: Vec<f32> = chatter.iter().map(|s| 0.1 * s).collect();
+    samples.extend(singer.iter().map(|s| 0.6 * s));
+    for s in samples.iter_mut() {
+        *s += rng.gen_range(-0.03..0.03);
+    }
+
+    let run = |cfg: &PyinConfig| {
+        let mut pyin = Pyin::new(cfg.clone(), PcmFormat::F32LE).unwrap();
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut frames = Vec::new();
+        for chunk in bytes.chunks(4096) {
+            frames.extend(pyin.push_bytes(chunk).unwrap());
+        }
+        frames
+    };
+    let voiced_ratio = |frames: &[pyin_rs::pyin::FrameEstimate]| {
+        frames.iter().filter(|f| f.voiced).count() as f32 / frames.len() as f32
+    };
+    let frames_per_sec = rate as usize / cfg.hop_size;
+    let background = 2 * frames_per_sec..(background_sec as usize - 1) * frames_per_sec;
+    let singing = (background_sec as usize) * frames_per_sec + 10..;
+
+    let without = run(&cfg);
+    assert!(without.iter().all(|f| f.vad.is_none()));
+    assert!(voiced_ratio(&without[background.clone()]) > 0.9);
+
+    cfg.vad = Some(VadConfig {
+        noise_window_sec: 2.0,
+        ..VadConfig::default()
+    });
+    let with = run(&cfg);
+    assert!(voiced_ratio(&with[background.clone()]) < 0.1);
+    assert!(with[background.clone()].iter().all(|f| !f.vad.unwrap().speech));
+    // Chatter at 0.1 plus noise sits around -23 dBFS.
+    let floor = with[background.end].vad.unwrap().noise_floor_dbfs;
+    assert!((floor + 23.0).abs() < 3.0, "noise floor {}", floor);
+    assert!(voiced_ratio(&with[singing.clone()]) > 0.9);
+    assert!(with[singing].iter().all(|f| f.vad.unwrap().speech));
+}
+
+#[test]
+fn score_prior_prefers_expected_note_over_backing() {
+    let cfg = PyinConfig {
+        sample_rate_hz: 16_000,
+        frame_size: 1024,
+        hop_size: 160,
+        ..PyinConfig::default()
+    };
+    let rate = cfg.sample_rate_hz;
+    // The singer on A3 against a louder backing instrument an octave above.
+    let singer = sine_wave(220.0, 2.0, rate);
+    let backing = sine_wave(440.0, 2.0, rate);
+    let bytes: Vec<u8> = singer
+        .iter()
+        .zip(backing.iter())
+        .map(|(s, b)| 0.12 * s + 0.6 * b)
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+
+    let median_cents = |target: Option<f32>, reference_hz: f32| -> f32 {
+        let mut pyin = Pyin::new(cfg.clone(), PcmFormat::F32LE).unwrap();
+        if let Some(midi) = target {
+            let notes = vec![TargetNote {
+                start_sec: 0.0,
+                end_sec: 2.0,
+                midi,
+            }];
+            let prior = ScorePriorConfig {
+                strength: 0.8,
+                ..ScorePriorConfig::default()
+            };
+            pyin.set_score_prior(notes, prior).unwrap();
+        }
+        let mut errors = Vec::new();
+        for chunk in bytes.chunks(2000) {
+            for frame in pyin.push_bytes(chunk).unwrap() {
+                if let Some(f0) = frame.f0_hz {
+                    errors.push((1200.0 * (f0 / reference_hz).log2()).abs());
+                }
+            }
+        }
+        assert!(!errors.is_empty());
+        errors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        errors[errors.len() / 2]
+    };
+
+    // Without a prior the louder backing wins.
+    assert!(median_cents(None, 440.0) < 50.0);
+    // With A3 expected, the singer is tracked instead.
+    assert!(median_cents(Some(57.0), 220.0) < 50.0);
+    // An expected note nobody sings does not pull the contour onto it.
+    assert!(median_cents(Some(62.0), 440.0) < 50.0);
+}
+
+fn harmonic_tone(f0: f32, amplitudes: &[f32], duration_sec: f32, sample_rate: u32) -> Vec<f32> {
+    let len = (duration_sec * sample_rate as f32) as usize;
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            let sum: f32 = amplitudes
+                .iter()
+                .enumerate()
+                .map(|(k, a)| a * (2.0 * std::f32::consts::PI * f0 * (k + 1) as f32 * t).sin())
+                .sum();
+            0.2 * sum
+        })
+        .collect()
+}
+
+#[test]
+fn octave_correction_fixes_weak_odd_harmonics() {
+    let plain = PyinConfig::default();
+    let corrected = PyinConfig {
+        octave_correction: Some(OctaveConfig::default()),
+        ..PyinConfig::default()
+    };
+    // Nasal voices: the second harmonic dominates and odd harmonics are 20 dB
+    // or more down, so YIN locks onto the octave above.
+    for (f0, amplitudes) in [
+        (110.0, [0.1, 1.0, 0.05, 0.6]),
+        (150.0, [0.05, 1.0, 0.1, 0.5]),
+        (300.0, [0.1, 1.0, 0.05, 0.6]),
+    ] {
+        let samples = harmonic_tone(f0, &amplitudes, 1.5, plain.sample_rate_hz);
+        let before = run_stream(plain.clone(), &samples);
+        assert!(median_cents_error(&before, 2.0 * f0) < 25.0);
+        let after = run_stream(corrected.clone(), &samples);
+        assert!(median_cents_error(&after, f0) < 25.0, "{} Hz", f0);
+    }
+
+    // Rich tones are left alone.
+    for &f0 in &[110.0, 220.0, 440.0] {
+        let samples = harmonic_tone(f0, &[1.0, 0.5, 0.3, 0.2], 1.5, plain.sample_rate_hz);
+        let after = run_stream(corrected.clone(), &samples);
+        assert!(median_cents_error(&after, f0) < 25.0, "{} Hz", f0);
+    }
+}
+
+#[test]
+fn octave_jumps_keep_an_octave_leap_voiced() {
+    let cfg = PyinConfig::default();
+    let mut samples = harmonic_tone(220.0, &[1.0, 0.5, 0.3], 0.5, cfg.sample_rate_hz);
+    samples.extend(harmonic_tone(440.0, &[1.0, 0.5, 0.3], 0.5, cfg.sample_rate_hz));
+
+    let unvoiced_around_leap = |cfg: PyinConfig| {
+        let estimates = run_stream(cfg, &samples);
+        let middle = estimates.len() / 2;
+        estimates[middle - 10..middle + 20]
+            .iter()
+            .filter(|f0| **f0 == 0.0)
+            .count()
+    };
+    // Without octave jumps the path can only glide, so it drops out of voicing
+    // to cross the octave.
+    assert!(unvoiced_around_leap(cfg.clone()) > 3);
+    let corrected = PyinConfig {
+        octave_correction: Some(OctaveConfig::default()),
+        ..cfg
+    };
+    assert_eq!(unvoiced_around_leap(corrected), 0);
+}
+
+/// Sine whose frequency swings `extent_cents` around `freq_hz` at `rate_hz`.
+fn vibrato_tone(
+    freq_hz: f32,
+    rate_hz: f32,
+    extent_cents: f32,
+    duration_sec: f32,
+    sample_rate: u32,
+) -> Vec<f32> {
+    let len = (duration_sec * sample_rate as f32) as usize;
+    let mut phase = 0.0_f32;
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            let cents = extent_cents * (2.0 * std::f32::consts::PI * rate_hz * t).sin();
+            phase += 2.0 * std::f32::consts::PI * freq_hz * 2.0_f32.powf(cents / 1200.0)
+                / sample_rate as f32;
+            phase = phase.rem_euclid(2.0 * std::f32::consts::PI);
+            0.5 * phase.sin()
+        })
+        .collect()
+}
+
+#[test]
+fn vibrato_is_measured_per_note_and_centred() {
+    let cfg = PyinConfig::default();
+    let mut samples = silence(0.2, cfg.sample_rate_hz);
+    samples.extend(vibrato_tone(220.0, 5.5, 60.0, 1.5, cfg.sample_rate_hz));
+    samples.extend(silence(0.3, cfg.sample_rate_hz));
+    samples.extend(sine_wave(330.0, 1.0, cfg.sample_rate_hz));
+    samples.extend(silence(0.2, cfg.sample_rate_hz));
+
+    let mut pyin = Pyin::new(cfg.clone(), PcmFormat::I16LE).unwrap();
+    let mut frames = pyin.push_bytes(&samples_to_i16le(&samples)).unwrap();
+    frames.extend(pyin.provisional_estimates());
+    let vibrato = analyze_vibrato(&frames, &cfg, VibratoConfig::default()).unwrap();
+    let notes = segment_notes(&frames, &cfg, NoteConfig::default()).unwrap();
+    let midi: Vec<u8> = notes.iter().map(|n| n.midi_note).collect();
+    assert_eq!(midi, vec![57, 64]);
+
+    let note_frames = |n: usize| {
+        let (first, last) = (notes[n].first_frame as usize, notes[n].last_frame as usize);
+        &vibrato[first..=last]
+    };
+    let sung = summarize_vibrato(note_frames(0)).expect("first note has vibrato");
+    assert!((sung.rate_hz - 5.5).abs() < 0.3, "rate {}", sung.rate_hz);
+    assert!((sung.extent_cents - 60.0).abs() < 10.0, "extent {}", sung.extent_cents);
+    assert!(sung.regularity > 0.7);
+    assert!(summarize_vibrato(note_frames(1)).is_none());
+
+    // The centre pitch stays on the note while the raw pitch swings.
+    for frame in note_frames(0).iter().filter(|f| f.vibrato) {
+        let centre_cents = (frame.centre_midi.unwrap() - 57.0) * 100.0;
+        assert!(centre_cents.abs() < 10.0, "centre {} cents", centre_cents);
+    }
+}
+
+#[test]
+fn restored_detector_continues_exactly_like_the_original() {
+    let cfg = PyinConfig {
+        vad: Some(VadConfig::default()),
+        octave_correction: Some(OctaveConfig::default()),
+        ..PyinConfig::default()
+    };
+    // Stereo 16-bit capture at 44.1 kHz, resampled to the 48 kHz analysis rate.
+    let input = PcmInput {
+        format: PcmFormat::I16LE,
+        channels: 2,
+        channel_mix: ChannelMix::Downmix,
+        sample_rate_hz: 44_100,
+    };
+    let mut mono = silence(0.2, 44_100);
+    for freq in [220.0, 247.0, 196.0] {
+        mono.extend(sine_wave(freq, 0.3, 44_100).iter().map(|s| 0.5 * s));
+    }
+    let mut rng = StdRng::seed_from_u64(5);
+    let stereo: Vec<f32> = mono
+        .iter()
+        .flat_map(|&s| [s + rng.gen_range(-0.01..0.01), s])
+        .collect();
+    let bytes = samples_to_i16le(&stereo);
+    let backing: Vec<f32> = (0..cfg.sample_rate_hz as usize * 2)
+        .map(|i| 0.1 * (i as f32 * 0.02).sin())
+        .collect();
+    let notes: Vec<TargetNote> = [(0.2, 57.0), (0.5, 59.0), (0.8, 55.0)]
+        .iter()
+        .map(|&(start_sec, midi)| TargetNote {
+            start_sec,
+            end_sec: start_sec + 0.3,
+            midi,
+        })
+        .collect();
+    let detector = || {
+        let mut pyin = Pyin::with_input(cfg.clone(), input).unwrap();
+        let reference = BleedReference::from_samples(backing.clone(), cfg.sample_rate_hz);
+        pyin.set_bleed_reference(reference, &BleedConfig::default())
+            .unwrap();
+        pyin.set_score_prior(notes.clone(), ScorePriorConfig::default())
+            .unwrap();
+        pyin
+    };
+    let run = |pyin: &mut Pyin, bytes: &[u8]| {
+        let mut frames = Vec::new();
+        for chunk in bytes.chunks(1001) {
+            frames.extend(pyin.push_bytes(chunk).unwrap());
+        }
+        frames.extend(pyin.provisional_estimates());
+        frames.extend(pyin.finish(TailPolicy::ZeroPad).unwrap().estimates);
+        format!("{:?}", frames)
+    };
+
+    // Split mid-frame and mid-sample, with the decoder lag partly filled.
+    let split = bytes.len() / 2 + 3;
+    let mut original = detector();
+    original.push_bytes(&bytes[..split]).unwrap();
+    let blob = original.snapshot();
+    let mut restored = detector();
+    restored.restore(&blob).unwrap();
+    assert_eq!(run(&mut restored, &bytes[split..]), run(&mut original, &bytes[split..]));
+
+    let mut unset = Pyin::with_input(cfg.clone(), input).unwrap();
+    assert!(unset.restore(&blob).is_err());
+    let other = PyinConfig {
+        hop_size: 128,
+        ..cfg.clone()
+    };
+    assert!(Pyin::with_input(other, input).unwrap().restore(&blob).is_err());
+    assert!(detector().restore(&blob[..blob.len() / 2]).is_err());
+}
+
+#[test]
+fn snapshot_is_rejected_by_a_detector_with_other_input() {
+    let input = PcmInput {
+        format: PcmFormat::I16LE,
+        channels: 2,
+        channel_mix: ChannelMix::Pick(1),
+        sample_rate_hz: 44_100,
+    };
+    let stereo: Vec<f32> = sine_wave(220.0, 0.2, 44_100)
+        .iter()
+        .flat_map(|&s| [0.0, 0.5 * s])
+        .collect();
+    let mut original = Pyin::with_input(PyinConfig::default(), input).unwrap();
+    original.push_bytes(&samples_to_i16le(&stereo)).unwrap();
+    let blob = original.snapshot();
+
+    let restore = |input| Pyin::with_input(PyinConfig::default(), input).unwrap().restore(&blob);
+    assert!(restore(input).is_ok());
+    for other in [
+        PcmInput {
+            sample_rate_hz: 48_000,
+            ..input
+        },
+        PcmInput {
+            format: PcmFormat::I16BE,
+            ..input
+        },
+        PcmInput {
+            channels: 3,
+            ..input
+        },
+        PcmInput {
+            channel_mix: ChannelMix::Pick(0),
+            ..input
+        },
+        PcmInput {
+            channel_mix: ChannelMix::Downmix,
+            ..input
+        },
+    ] {
+        assert!(restore(other).is_err(), "{:?}", other);
+    }
+}
@@ -0,0 +1,145 @@
+//! Steady-state streaming through `Pyin` must not touch the heap, whether or
+//! not the input is resampled.
+//!
+//! The guarantee stops at `Pyin::push_bytes_into`: `AudioAnalyzer` builds a
+//! `PitchEvent` per frame and hands them to Dart, which allocates.
+//!
+//! A global allocator applies to the whole test binary, so this test has a
+//! binary of its own, and only allocations made by the thread that turned
+//! counting on are counted.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use pyin_rs::pyin::{
+    BleedConfig, BleedReference, OctaveConfig, PcmFormat, PcmInput, Pyin, PyinConfig,
+    ScorePriorConfig, TargetNote, VadConfig,
+};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn count() {
+    if COUNTING.try_with(Cell::get).unwrap_or(false) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    COUNTING.with(|counting| counting.set(true));
+    f();
+    COUNTING.with(|counting| counting.set(false));
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+const RATE: u32 = 48_000;
+
+/// Phrases of a harmonic voice moving between notes, separated by quiet
+/// noise, as F32LE bytes.
+fn singing(seconds: f32) -> Vec<u8> {
+    let mut state = 7_u32;
+    let mut phase = 0.0_f32;
+    (0..(seconds * RATE as f32) as usize)
+        .flat_map(|i| {
+            let t = i as f32 / RATE as f32;
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let noise = 0.01 * ((state >> 8) as f32 / (1 << 24) as f32 - 0.5);
+            let sample = if t % 1.5 < 1.0 {
+                let midi = [57.0, 60.0, 64.0, 62.0][(t / 0.4) as usize % 4];
+                let f0 = 440.0 * 2.0_f32.powf((midi - 69.0) / 12.0);
+                phase += 2.0 * std::f32::consts::PI * f0 / RATE as f32;
+                (1..=4).map(|k| 0.3 / k as f32 * (k as f32 * phase).sin()).sum::<f32>() + noise
+            } else {
+                noise
+            };
+            sample.to_le_bytes()
+        })
+        .collect()
+}
+
+#[test]
+fn steady_state_streaming_does_not_allocate() {
+    let audio = singing(6.0);
+    let chunk_bytes = 4 * 1024;
+    let warm_up_bytes = 2 * 4 * RATE as usize;
+
+    let plain = PyinConfig::default();
+    let full = PyinConfig {
+        vad: Some(VadConfig::default()),
+        octave_correction: Some(OctaveConfig::default()),
+        ..PyinConfig::default()
+    };
+    let cases = [
+        ("plain", plain.clone(), false, RATE),
+        ("full", full, true, RATE),
+        ("resampled", plain, false, 44_100),
+    ];
+    for (name, cfg, extras, capture_rate_hz) in cases {
+        let input = PcmInput::mono(PcmFormat::F32LE, capture_rate_hz);
+        let mut pyin = Pyin::with_input(cfg, input).expect("create pyin");
+        if extras {
+            let reference = (0..10 * RATE).map(|i| 0.1 * (i as f32 * 0.01).sin()).collect();
+            pyin.set_bleed_reference(
+                BleedReference::from_samples(reference, RATE),
+                &BleedConfig::default(),
+            )
+            .expect("bleed reference");
+            let notes = (0..12)
+                .map(|i| TargetNote {
+                    start_sec: i as f64 * 0.5,
+                    end_sec: i as f64 * 0.5 + 0.4,
+                    midi: [57.0, 60.0, 64.0][i % 3],
+                })
+                .collect();
+            pyin.set_score_prior(notes, ScorePriorConfig::default())
+                .expect("score prior");
+        }
+
+        let mut output = Vec::with_capacity(64);
+        for chunk in audio[..warm_up_bytes].chunks(chunk_bytes) {
+            output.clear();
+            pyin.push_bytes_into(chunk, &mut output).expect("push");
+        }
+        let mut committed = 0;
+        let allocations = allocations_during(|| {
+            for chunk in audio[warm_up_bytes..].chunks(chunk_bytes) {
+                output.clear();
+                pyin.push_bytes_into(chunk, &mut output).expect("push");
+                committed += output.len();
+            }
+        });
+        assert!(committed > 600, "{}: only {} frames", name, committed);
+        assert_eq!(allocations, 0, "{}: {} allocations", name, allocations);
+    }
+}
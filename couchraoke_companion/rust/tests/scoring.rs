@@ -0,0 +1,113 @@
+use pyin_rs::pyin::{PcmFormat, Pyin, PyinConfig, TailPolicy};
+use pyin_rs::scoring::{Difficulty, ScoringConfig, ScoringSession};
+use pyin_rs::song::Song;
+
+const RATE: u32 = 16_000;
+
+/// Two lines at 240 BPM (16 beats per second), starting 0.3 s into the audio.
+const CHART: &str = "#TITLE:Scale\n#ARTIST:Test\n#BPM:240\n#GAP:300\n\
+                     : 0 6 -3 la\n: 8 6 -1 ti\n* 16 8 0 do\n- 26\n\
+                     : 28 6 2 re\n: 36 6 4 mi\n: 44 10 -3 la\nE\n";
+
+/// A sine at each note of the chart, `offset` semitones away, with silence
+/// between notes.
+fn sing(song: &Song, offset: f32) -> Vec<f32> {
+    let end_sec = song.clock.beat_to_sec(60.0);
+    let mut samples = vec![0.0; (end_sec * RATE as f64) as usize];
+    for note in song.tracks[0].notes() {
+        let freq = 440.0 * 2.0_f32.powf((note.midi() as f32 + offset - 69.0) / 12.0);
+        let start = (song.clock.beat_to_sec(note.start_beat as f64) * RATE as f64) as usize;
+        let end = (song.clock.beat_to_sec(note.end_beat() as f64) * RATE as f64) as usize;
+        for (i, sample) in samples[start..end].iter_mut().enumerate() {
+            *sample = 0.5 * (2.0 * std::f32::consts::PI * freq * i as f32 / RATE as f32).sin();
+        }
+    }
+    samples
+}
+
+/// Noise bursts (spoken syllables) at each note of the chart, starting
+/// `delay_beats` late, over quiet room noise.
+fn rap(song: &Song, delay_beats: i32) -> Vec<f32> {
+    let end_sec = song.clock.beat_to_sec(60.0);
+    let mut seed = 1_u32;
+    let mut noise = move || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (seed >> 8) as f32 / (1 << 23) as f32 - 1.0
+    };
+    let mut samples: Vec<f32> =
+        (0..(end_sec * RATE as f64) as usize).map(|_| 0.001 * noise()).collect();
+    for note in song.tracks[0].notes() {
+        let beat_sec = |beat: i32| song.clock.beat_to_sec((beat + delay_beats) as f64);
+        let start = (beat_sec(note.start_beat) * RATE as f64) as usize;
+        let end = (beat_sec(note.end_beat()) * RATE as f64) as usize;
+        for sample in &mut samples[start..end] {
+            *sample = 0.3 * noise();
+        }
+    }
+    samples
+}
+
+fn score(song: &Song, samples: &[f32], difficulty: Difficulty) -> ScoringSession {
+    let cfg = PyinConfig {
+        sample_rate_hz: RATE,
+        frame_size: 1024,
+        hop_size: 160,
+        ..PyinConfig::default()
+    };
+    let scoring = ScoringConfig {
+        difficulty,
+        // Score each frame at the centre of its window.
+        song_start_sec: -(cfg.frame_size as f64 / 2.0) / RATE as f64,
+        ..ScoringConfig::default()
+    };
+    let mut session = ScoringSession::new(&song.tracks[0], &song.clock, scoring);
+    let mut pyin = Pyin::new(cfg, PcmFormat::F32LE).unwrap();
+    for chunk in samples.chunks(700) {
+        for frame in pyin.push_samples(chunk).unwrap() {
+            session.push_estimate(&frame);
+        }
+    }
+    for frame in pyin.finish(TailPolicy::ZeroPad).unwrap().estimates {
+        session.push_estimate(&frame);
+    }
+    session.finish();
+    session
+}
+
+#[test]
+fn detected_singing_scores_against_the_chart() {
+    let song = Song::parse(CHART.as_bytes()).unwrap();
+
+    let in_tune = score(&song, &sing(&song, 0.0), Difficulty::Hard);
+    let total = in_tune.totals().rounded();
+    assert!(total >= 9_000, "in tune scored {}", total);
+    assert!(in_tune.lines().iter().all(|line| line.finished));
+
+    // An octave down is as good on any difficulty.
+    let octave = score(&song, &sing(&song, -12.0), Difficulty::Hard).totals().rounded();
+    assert!(octave >= 9_000, "octave down scored {}", octave);
+
+    // A semitone sharp passes on medium only.
+    let sharp = sing(&song, 1.0);
+    let medium = score(&song, &sharp, Difficulty::Medium).totals().rounded();
+    assert!(medium >= 9_000, "sharp on medium scored {}", medium);
+    assert_eq!(score(&song, &sharp, Difficulty::Hard).totals().rounded(), 0);
+
+    assert_eq!(score(&song, &vec![0.0; sharp.len()], Difficulty::Easy).totals().rounded(), 0);
+}
+
+#[test]
+fn rapped_syllables_score_on_rhythm() {
+    let chart = "#TITLE:Rap\n#ARTIST:Test\n#BPM:240\n#GAP:300\n\
+                 R 0 4 0 yo\nR 12 4 0 yo\n- 18\nG 24 4 0 yo\nR 36 4 0 yo\nE\n";
+    let song = Song::parse(chart.as_bytes()).unwrap();
+
+    let on_beat = score(&song, &rap(&song, 0), Difficulty::Hard);
+    let total = on_beat.totals().rounded();
+    assert!(total >= 9_000, "rap on the beat scored {}", total);
+    assert!(on_beat.notes().iter().all(|note| note.onset));
+
+    // Syllables in the gaps between notes miss every one.
+    let off_beat = score(&song, &rap(&song, 6), Difficulty::Hard).totals().rounded();
+    assert_eq!(off_beat, 0);
+}